@@ -68,6 +68,8 @@ enum TWBlockchain {
     TWBlockchainBitcoinCash = 55,
     TWBlockchainPactus = 56,
     TWBlockchainKomodo = 57,
+    TWBlockchainDogecoin = 58, // Bitcoin
+    TWBlockchainLitecoin = 59, // Bitcoin
 };
 
 TW_EXTERN_C_END
@@ -49,6 +49,14 @@ TWData* _Nonnull TWEthereumAbiDecodeValue(enum TWCoinType coin, TWData* _Nonnull
 TW_EXPORT_STATIC_METHOD
 TWData* _Nonnull TWEthereumAbiEncodeFunction(enum TWCoinType coin, TWData* _Nonnull input);
 
+/// Decode an event log according to the given contract ABI json.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input The serialized data of `TW.EthereumAbi.Proto.EventLogDecodingInput`.
+/// \return The serialized data of a `TW.EthereumAbi.Proto.EventLogDecodingOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumAbiDecodeEventLog(enum TWCoinType coin, TWData* _Nonnull input);
+
 /// Encode function to Eth ABI binary
 ///
 /// \param fn Non-null Eth abi function
@@ -116,6 +124,7 @@ TWData* _Nonnull TWEthereumAbiEncodeTyped(TWString* _Nonnull messageJson);
 /// Get function signature from Ethereum ABI json
 ///
 /// \param abi The function ABI json string, for example: {"inputs":[{"internalType":"bool","name":"arg1","type":"bool"}],"name":"fun1","outputs":[],"stateMutability":"nonpayable","type":"function"}
+///            or a human-readable function ABI, for example: function fun1(bool arg1)
 /// \return the function type signature, of the form "baz(int32,uint256)", null if the abi is invalid.
 TW_EXPORT_STATIC_METHOD
 TWString* _Nullable TWEthereumAbiGetFunctionSignature(TWString* _Nonnull abi);
@@ -5,6 +5,7 @@
 #pragma once
 
 #include "TWBase.h"
+#include "TWCoinType.h"
 #include "TWData.h"
 #include "TWString.h"
 #include "TWPrivateKey.h"
@@ -70,4 +71,21 @@ TWString* _Nonnull TWEthereumMessageSignerSignMessageEip155(const struct TWPriva
 TW_EXPORT_STATIC_METHOD
 bool TWEthereumMessageSignerVerifyMessage(const struct TWPublicKey* _Nonnull pubKey, TWString* _Nonnull message, TWString* _Nonnull signature);
 
+/// Prepare the call to verify a smart contract account signature (ERC-1271).
+/// The signature can be wrapped according to ERC-6492.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input The serialized data of `TW.Ethereum.Proto.ContractSignatureVerifyingInput`.
+/// \return The serialized data of a `TW.Ethereum.Proto.ContractSignatureVerifyingOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumMessageSignerPrepareContractSignatureVerification(enum TWCoinType coin, TWData* _Nonnull input);
+
+/// Decode the result of the smart contract account signature verification call.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input The serialized data of `TW.Ethereum.Proto.ContractSignatureResultInput`.
+/// \return The serialized data of a `TW.Ethereum.Proto.ContractSignatureResultOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumMessageSignerDecodeContractSignatureResult(enum TWCoinType coin, TWData* _Nonnull input);
+
 TW_EXTERN_C_END
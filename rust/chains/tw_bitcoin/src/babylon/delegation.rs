@@ -0,0 +1,269 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::babylon::tx_builder::output::BabylonOutputBuilder;
+use crate::babylon::tx_builder::utxo::BabylonUtxoBuilder;
+use crate::babylon::tx_builder::{BabylonStakingParams, BabylonUnbondingParams};
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_keypair::schnorr;
+use tw_misc::traits::ToBytesVec;
+use tw_utxo::modules::keys_manager::KeysManager;
+use tw_utxo::modules::sighash_computer::SighashComputer;
+use tw_utxo::modules::sighash_verifier::SighashVerifier;
+use tw_utxo::modules::tx_compiler::TxCompiler;
+use tw_utxo::modules::tx_signer::TxSigner;
+use tw_utxo::script::Script;
+use tw_utxo::sighash::{SighashBase, SighashType};
+use tw_utxo::signature::BitcoinSchnorrSignature;
+use tw_utxo::transaction::standard_transaction::builder::{
+    OutputBuilder, TransactionBuilder, UtxoBuilder,
+};
+use tw_utxo::transaction::standard_transaction::{Transaction, TransactionInput};
+use tw_utxo::transaction::transaction_interface::TransactionInterface;
+use tw_utxo::transaction::transaction_parts::Amount;
+use tw_utxo::transaction::unsigned_transaction::UnsignedTransaction;
+use tw_utxo::transaction::UtxoToSign;
+
+/// Babylon transactions are version 2 with no locktime.
+const BABYLON_TX_VERSION: u32 = 2;
+const BABYLON_TX_LOCKTIME: u32 = 0;
+/// Unbonding transaction spends the only Staking output.
+const UNBONDING_OUTPUT_INDEX: u32 = 0;
+const BASIS_POINTS: i64 = 10_000;
+
+/// Parameters of a Babylon BTC delegation required to build its Unbonding and Slashing transactions.
+pub struct BabylonDelegationParams {
+    /// Staking Output parameters where `staking_locktime` is the staking time.
+    pub staking: BabylonStakingParams,
+    /// `global_parameters.unbonding_time`.
+    pub unbonding_locktime: u16,
+    /// Staking transaction hash as it's stored in the outpoint, i.e. reversed relative to the displayed txid.
+    pub staking_txid: H256,
+    pub staking_output_index: u32,
+    pub staking_value: Amount,
+    /// `global_parameters.unbonding_fee`.
+    pub unbonding_fee: Amount,
+    /// `global_parameters.slashing_pk_script`.
+    pub slashing_script_pubkey: Script,
+    /// `global_parameters.slashing_rate` in basis points, e.g. `1000` means 10%.
+    pub slashing_rate_bps: u32,
+    /// `global_parameters.min_slashing_tx_fee`.
+    pub slashing_fee: Amount,
+}
+
+/// Staker's signatures required by Babylon PoS chain to register a delegation,
+/// and to unbond it later.
+pub struct BabylonStakerSignatures {
+    /// Staker's signature of the Unbonding transaction.
+    pub unbonding: schnorr::Signature,
+    /// Staker's signature of the Slashing transaction spending the Staking output.
+    pub staking_slashing: schnorr::Signature,
+    /// Staker's signature of the Slashing transaction spending the Unbonding output.
+    pub unbonding_slashing: schnorr::Signature,
+}
+
+/// Builds the Unbonding transaction and the pair of pre-signed Slashing transactions of a Babylon BTC delegation.
+///
+/// https://github.com/babylonlabs-io/babylon/blob/main/docs/transaction-impl-spec.md
+pub struct BabylonDelegation {
+    params: BabylonDelegationParams,
+}
+
+impl BabylonDelegation {
+    pub fn new(params: BabylonDelegationParams) -> SigningResult<BabylonDelegation> {
+        let delegation = BabylonDelegation { params };
+
+        if delegation.unbonding_value()? <= 0 {
+            return SigningError::err(SigningErrorType::Error_not_enough_utxos)
+                .context("Staking value is not enough to cover the unbonding fee");
+        }
+        if delegation.params.slashing_rate_bps as i64 > BASIS_POINTS {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Slashing rate cannot be greater than 100%");
+        }
+        delegation.slashing_amounts(delegation.params.staking_value)?;
+        delegation.slashing_amounts(delegation.unbonding_value()?)?;
+
+        Ok(delegation)
+    }
+
+    /// Builds an unsigned Unbonding transaction that spends the Staking output via unbonding path.
+    pub fn unbonding_tx(&self) -> SigningResult<UnsignedTransaction<Transaction>> {
+        let (input, utxo) = self
+            .staking_utxo_builder()
+            .babylon_staking_unbonding_path_presign(self.params.staking.clone())?;
+        self.unbonding_tx_with_input(input, utxo)
+    }
+
+    /// Builds an unsigned Slashing transaction that spends the Staking output via slashing path.
+    pub fn staking_slashing_tx(&self) -> SigningResult<UnsignedTransaction<Transaction>> {
+        let (input, utxo) = self.staking_utxo_builder().babylon_staking_slashing_path(
+            self.params.staking.clone(),
+            &[],
+            &[],
+        )?;
+        self.slashing_tx(input, utxo, self.params.staking_value)
+    }
+
+    /// Builds an unsigned Slashing transaction that spends the Unbonding output via slashing path.
+    pub fn unbonding_slashing_tx(&self) -> SigningResult<UnsignedTransaction<Transaction>> {
+        // `TransactionInterface::txid` is reversed, but the outpoint requires the original byte order.
+        let unbonding_txid = H256::try_from(self.unbonding_tx()?.transaction().txid().as_slice())
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Invalid Unbonding transaction hash")?
+            .rev();
+        let unbonding_value = self.unbonding_value()?;
+
+        let (input, utxo) = UtxoBuilder::new()
+            .prev_txid(unbonding_txid)
+            .prev_index(UNBONDING_OUTPUT_INDEX)
+            .amount(unbonding_value)
+            .sighash_type(babylon_sighash_type())
+            .babylon_unbonding_slashing_path(self.unbonding_params(), &[], &[])?;
+        self.slashing_tx(input, utxo, unbonding_value)
+    }
+
+    /// Signs the Unbonding transaction and both Slashing transactions with the staker's private key.
+    pub fn sign(&self, staker: &schnorr::PrivateKey) -> SigningResult<BabylonStakerSignatures> {
+        let mut keys_manager = KeysManager::default();
+        keys_manager.add_schnorr_private(staker.clone());
+
+        Ok(BabylonStakerSignatures {
+            unbonding: sign_single_input(&keys_manager, &self.unbonding_tx()?)
+                .context("Error signing the Unbonding transaction")?,
+            staking_slashing: sign_single_input(&keys_manager, &self.staking_slashing_tx()?)
+                .context("Error signing the Staking Slashing transaction")?,
+            unbonding_slashing: sign_single_input(&keys_manager, &self.unbonding_slashing_tx()?)
+                .context("Error signing the Unbonding Slashing transaction")?,
+        })
+    }
+
+    /// Compiles the Unbonding transaction with the staker's and covenant committee signatures.
+    /// There can be less covenant signatures than covenant public keys, but not less than the covenant quorum.
+    pub fn compile_unbonding(
+        &self,
+        staker_signature: &schnorr::Signature,
+        covenant_committee_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
+    ) -> SigningResult<Transaction> {
+        let (input, utxo) = self.staking_utxo_builder().babylon_staking_unbonding_path(
+            self.params.staking.clone(),
+            covenant_committee_signatures,
+        )?;
+        let unsigned_tx = self.unbonding_tx_with_input(input, utxo)?;
+
+        let signatures = vec![staker_signature.to_vec()];
+        SighashVerifier::verify_signatures(&unsigned_tx, &signatures)
+            .context("Invalid staker signature")?;
+        TxCompiler::compile(unsigned_tx, &signatures)
+    }
+
+    /// Returns the Unbonding output value, i.e. the Staking value minus the unbonding fee.
+    pub fn unbonding_value(&self) -> SigningResult<Amount> {
+        self.params
+            .staking_value
+            .checked_sub(self.params.unbonding_fee)
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("Invalid unbonding fee")
+    }
+
+    fn staking_utxo_builder(&self) -> UtxoBuilder {
+        UtxoBuilder::new()
+            .prev_txid(self.params.staking_txid)
+            .prev_index(self.params.staking_output_index)
+            .amount(self.params.staking_value)
+            .sighash_type(babylon_sighash_type())
+    }
+
+    fn unbonding_params(&self) -> BabylonUnbondingParams {
+        BabylonUnbondingParams {
+            staking_locktime: self.params.unbonding_locktime,
+            ..self.params.staking.clone()
+        }
+    }
+
+    fn unbonding_tx_with_input(
+        &self,
+        input: TransactionInput,
+        utxo: UtxoToSign,
+    ) -> SigningResult<UnsignedTransaction<Transaction>> {
+        let unbonding_output = OutputBuilder::new(self.unbonding_value()?)
+            .babylon_unbonding(self.unbonding_params())?;
+
+        let mut builder = TransactionBuilder::new();
+        builder
+            .version(BABYLON_TX_VERSION)
+            .lock_time(BABYLON_TX_LOCKTIME)
+            .push_input(input, utxo)
+            .push_output(unbonding_output);
+        builder.build()
+    }
+
+    /// Slashing transaction sends `slashing_rate` of the input value to `slashing_pk_script`,
+    /// and the rest (minus the slashing fee) to the staker's timelocked change output.
+    fn slashing_tx(
+        &self,
+        input: TransactionInput,
+        utxo: UtxoToSign,
+        input_value: Amount,
+    ) -> SigningResult<UnsignedTransaction<Transaction>> {
+        let (slashing_value, change_value) = self.slashing_amounts(input_value)?;
+
+        let slashing_output = OutputBuilder::new(slashing_value)
+            .custom_script_pubkey(self.params.slashing_script_pubkey.clone());
+        let change_output = OutputBuilder::new(change_value).babylon_slashing_change(
+            &self.params.staking.staker.x_only(),
+            self.params.unbonding_locktime,
+        )?;
+
+        let mut builder = TransactionBuilder::new();
+        builder
+            .version(BABYLON_TX_VERSION)
+            .lock_time(BABYLON_TX_LOCKTIME)
+            .push_input(input, utxo)
+            .push_output(slashing_output)
+            .push_output(change_output);
+        builder.build()
+    }
+
+    /// Returns `(slashing_value, change_value)`.
+    /// The slashed value is rounded to the nearest satoshi.
+    fn slashing_amounts(&self, input_value: Amount) -> SigningResult<(Amount, Amount)> {
+        let slashing_value = input_value
+            .checked_mul(self.params.slashing_rate_bps as Amount)
+            .map(|value| (value + BASIS_POINTS / 2) / BASIS_POINTS)
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("Invalid slashing rate")?;
+
+        let change_value = input_value - slashing_value - self.params.slashing_fee;
+        if slashing_value <= 0 || change_value <= 0 {
+            return SigningError::err(SigningErrorType::Error_not_enough_utxos).context(
+                "Slashing transaction outputs must be positive, consider increasing the staking value",
+            );
+        }
+        Ok((slashing_value, change_value))
+    }
+}
+
+fn babylon_sighash_type() -> SighashType {
+    // Serialized as Taproot `SIGHASH_DEFAULT`.
+    SighashType::from_base(SighashBase::All)
+}
+
+fn sign_single_input(
+    keys_manager: &KeysManager,
+    unsigned_tx: &UnsignedTransaction<Transaction>,
+) -> SigningResult<schnorr::Signature> {
+    let preimage = SighashComputer::preimage_tx(unsigned_tx)?;
+    let sighash = preimage
+        .sighashes
+        .first()
+        .or_tw_err(SigningErrorType::Error_internal)
+        .context("Babylon transaction must have exactly one input")?;
+
+    let signature = TxSigner::<Transaction>::sign_taproot_sighash(keys_manager, sighash)?;
+    schnorr::Signature::try_from(signature.as_slice())
+        .tw_err(|_| SigningErrorType::Error_internal)
+        .context("Invalid schnorr signature")
+}
@@ -3,6 +3,7 @@
 // Copyright © 2017 Trust Wallet.
 
 pub mod conditions;
+pub mod delegation;
 pub mod multi_sig_ordered;
 pub mod proto_builder;
 pub mod spending_data;
@@ -13,6 +13,7 @@ use tw_utxo::signature::BitcoinSchnorrSignature;
 type OptionalSignature = Option<BitcoinSchnorrSignature>;
 type PubkeySigMap = BTreeMap<schnorr::XOnlyPublicKey, OptionalSignature>;
 
+#[derive(Clone)]
 pub struct MultiSigOrderedKeys {
     pks: Vec<schnorr::XOnlyPublicKey>,
     quorum: u32,
@@ -59,6 +60,20 @@ impl MultiSigOrderedKeys {
         pk_sig_map.check_quorum()?;
         Ok(pk_sig_map)
     }
+
+    /// Same as [`MultiSigOrderedKeys::with_partial_signatures`], but leaves all signatures empty
+    /// if no signatures are given.
+    /// Used to pre-sign a transaction by the staker before other parties provide their signatures.
+    pub fn with_optional_signatures<'a, I>(self, sigs: I) -> SigningResult<MultiSigOrdered>
+    where
+        I: IntoIterator<Item = &'a (schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)>,
+    {
+        let mut sigs = sigs.into_iter().peekable();
+        if sigs.peek().is_none() {
+            return Ok(MultiSigOrdered::checked(self.pks, self.quorum));
+        }
+        self.with_partial_signatures(sigs)
+    }
 }
 
 #[derive(Clone, Debug)]
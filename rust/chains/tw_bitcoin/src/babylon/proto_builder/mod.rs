@@ -76,3 +76,16 @@ pub fn parse_schnorr_pubkey_sig(
     let btc_sign = BitcoinSchnorrSignature::new(sig, sighash_ty)?;
     Ok((pk, btc_sign))
 }
+
+pub fn parse_schnorr_pubkey_sigs<'a, 'b: 'a, I>(
+    pubkey_sigs: I,
+    sighash_ty: SighashType,
+) -> SigningResult<Vec<(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)>>
+where
+    I: IntoIterator<Item = &'a Proto::PublicKeySignature<'b>>,
+{
+    pubkey_sigs
+        .into_iter()
+        .map(|pk_sig| parse_schnorr_pubkey_sig(pk_sig, sighash_ty))
+        .collect()
+}
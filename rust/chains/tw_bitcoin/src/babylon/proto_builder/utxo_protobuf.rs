@@ -2,7 +2,7 @@
 //
 // Copyright © 2017 Trust Wallet.
 
-use crate::babylon::proto_builder::{parse_schnorr_pubkey_sig, staking_params_from_proto};
+use crate::babylon::proto_builder::{parse_schnorr_pubkey_sigs, staking_params_from_proto};
 use crate::babylon::tx_builder::utxo::BabylonUtxoBuilder;
 use crate::modules::tx_builder::utxo_protobuf::UtxoProtobuf;
 use tw_coin_entry::error::prelude::*;
@@ -55,21 +55,30 @@ impl<Context: UtxoContext> BabylonUtxoProtobuf for UtxoProtobuf<'_, Context> {
         let params = staking_params_from_proto(&unbonding.params)?;
         let sighash_ty = self.sighash_ty()?;
 
-        let covenant_signatures = unbonding
-            .covenant_committee_signatures
-            .iter()
-            .map(|pk_sig| parse_schnorr_pubkey_sig(pk_sig, sighash_ty))
-            .collect::<SigningResult<Vec<_>>>()?;
+        let covenant_signatures =
+            parse_schnorr_pubkey_sigs(&unbonding.covenant_committee_signatures, sighash_ty)?;
         self.prepare_builder()?
             .babylon_staking_unbonding_path(params, &covenant_signatures)
     }
 
     fn babylon_staking_slashing(
         &self,
-        _slashing: &Proto::mod_InputBuilder::StakingSlashingPath,
+        slashing: &Proto::mod_InputBuilder::StakingSlashingPath,
     ) -> SigningResult<(TransactionInput, UtxoToSign)> {
-        SigningError::err(SigningErrorType::Error_not_supported)
-            .context("'babylonStakingSlashing' is not supported at the moment")
+        let params = staking_params_from_proto(&slashing.params)?;
+        let sighash_ty = self.sighash_ty()?;
+
+        let fp_signatures =
+            parse_schnorr_pubkey_sigs(slashing.finality_provider_signature.iter(), sighash_ty)
+                .context("Invalid finalityProviderSignature")?;
+        let covenant_signatures =
+            parse_schnorr_pubkey_sigs(&slashing.covenant_committee_signatures, sighash_ty)
+                .context("Invalid covenantCommitteeSignatures")?;
+        self.prepare_builder()?.babylon_staking_slashing_path(
+            params,
+            &fp_signatures,
+            &covenant_signatures,
+        )
     }
 
     fn babylon_unbonding_timelock(
@@ -83,9 +92,21 @@ impl<Context: UtxoContext> BabylonUtxoProtobuf for UtxoProtobuf<'_, Context> {
 
     fn babylon_unbonding_slashing(
         &self,
-        _slashing: &Proto::mod_InputBuilder::UnbondingSlashingPath,
+        slashing: &Proto::mod_InputBuilder::UnbondingSlashingPath,
     ) -> SigningResult<(TransactionInput, UtxoToSign)> {
-        SigningError::err(SigningErrorType::Error_not_supported)
-            .context("'babylonUnbondingSlashing' is not supported at the moment")
+        let params = staking_params_from_proto(&slashing.params)?;
+        let sighash_ty = self.sighash_ty()?;
+
+        let fp_signatures =
+            parse_schnorr_pubkey_sigs(slashing.finality_provider_signature.iter(), sighash_ty)
+                .context("Invalid finalityProviderSignature")?;
+        let covenant_signatures =
+            parse_schnorr_pubkey_sigs(&slashing.covenant_committee_signatures, sighash_ty)
+                .context("Invalid covenantCommitteeSignatures")?;
+        self.prepare_builder()?.babylon_unbonding_slashing_path(
+            params,
+            &fp_signatures,
+            &covenant_signatures,
+        )
     }
 }
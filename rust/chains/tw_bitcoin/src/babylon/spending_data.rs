@@ -53,3 +53,56 @@ impl SchnorrSpendingDataConstructor for BabylonUnbondingPath {
         }
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct BabylonSlashingPath {
+    slashing_script: Script,
+    control_block: Data,
+    /// Signatures signed by finality providers.
+    /// Sorted by finality providers public keys in reverse order.
+    finality_provider_signatures: MultiSigOrdered,
+    /// Signatures signed by covenant committees.
+    /// Sorted by covenant committees public keys in reverse order.
+    covenant_committee_signatures: MultiSigOrdered,
+}
+
+impl BabylonSlashingPath {
+    pub fn new(
+        slashing_script: Script,
+        control_block: Data,
+        finality_provider_signatures: MultiSigOrdered,
+        covenant_committee_signatures: MultiSigOrdered,
+    ) -> Self {
+        BabylonSlashingPath {
+            slashing_script,
+            control_block,
+            finality_provider_signatures,
+            covenant_committee_signatures,
+        }
+    }
+}
+
+impl SchnorrSpendingDataConstructor for BabylonSlashingPath {
+    fn get_spending_data(&self, sig: &BitcoinSchnorrSignature) -> SpendingData {
+        // The slashing script checks the staker's signature first, then finality providers' and then covenants'.
+        // Since the left-most witness item is popped last, covenant signatures go first.
+        let mut slashing_sigs = self
+            .covenant_committee_signatures
+            .get_signatures_reverse_order();
+        slashing_sigs.extend(
+            self.finality_provider_signatures
+                .get_signatures_reverse_order(),
+        );
+        // User's signature is always last.
+        slashing_sigs.push(Some(sig.clone()));
+
+        SpendingData {
+            script_sig: Script::default(),
+            witness: claims::new_p2tr_script_path(
+                &slashing_sigs,
+                self.slashing_script.clone(),
+                self.control_block.clone(),
+            ),
+        }
+    }
+}
@@ -147,6 +147,45 @@ impl UnbondingSpendInfo {
     }
 }
 
+/// Change output of a Slashing transaction.
+/// It can be spent by the staker via timelock path only once the unbonding time expires.
+pub struct SlashingChangeSpendInfo {
+    timelock_script: Script,
+    spend_info: bitcoin::taproot::TaprootSpendInfo,
+}
+
+impl SlashingChangeSpendInfo {
+    pub fn new(
+        staker: &schnorr::XOnlyPublicKey,
+        unbonding_locktime: u16,
+    ) -> SigningResult<SlashingChangeSpendInfo> {
+        let timelock_script = conditions::new_timelock_script(staker, unbonding_locktime);
+
+        let spend_info = bitcoin::taproot::TaprootBuilder::new()
+            .add_leaf(0, timelock_script.clone().into())
+            .expect("Leaf added at a valid depth")
+            .finalize(secp256k1::SECP256K1, *UNSPENDABLE_KEY_PATH_XONLY)
+            .expect("Expected a valid Taproot tree");
+
+        Ok(SlashingChangeSpendInfo {
+            timelock_script,
+            spend_info,
+        })
+    }
+
+    pub fn merkle_root(&self) -> SigningResult<H256> {
+        merkle_root(&self.spend_info)
+    }
+
+    pub fn timelock_script(&self) -> &Script {
+        &self.timelock_script
+    }
+
+    pub fn timelock_control_block(&self) -> SigningResult<bitcoin::taproot::ControlBlock> {
+        control_block(&self.spend_info, &self.timelock_script)
+    }
+}
+
 fn control_block(
     spend_info: &bitcoin::taproot::TaprootSpendInfo,
     script: &Script,
@@ -11,6 +11,7 @@ pub mod utxo;
 /// Unbonding parameters are the same as Staking except `staking_locktime` means an unbonding timelock.
 pub type BabylonUnbondingParams = BabylonStakingParams;
 
+#[derive(Clone)]
 pub struct BabylonStakingParams {
     pub staker: schnorr::PublicKey,
     pub staking_locktime: u16,
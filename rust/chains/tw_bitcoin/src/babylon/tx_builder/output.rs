@@ -28,6 +28,14 @@ pub trait BabylonOutputBuilder: Sized {
     ) -> TransactionOutput;
 
     fn babylon_unbonding(self, params: BabylonUnbondingParams) -> SigningResult<TransactionOutput>;
+
+    /// Create a Slashing transaction change output that returns the unslashed funds to the staker
+    /// after the unbonding time expires.
+    fn babylon_slashing_change(
+        self,
+        staker: &schnorr::XOnlyPublicKey,
+        unbonding_locktime: u16,
+    ) -> SigningResult<TransactionOutput>;
 }
 
 impl BabylonOutputBuilder for OutputBuilder {
@@ -78,4 +86,23 @@ impl BabylonOutputBuilder for OutputBuilder {
             ),
         })
     }
+
+    fn babylon_slashing_change(
+        self,
+        staker: &schnorr::XOnlyPublicKey,
+        unbonding_locktime: u16,
+    ) -> SigningResult<TransactionOutput> {
+        let spend_info =
+            babylon::spending_info::SlashingChangeSpendInfo::new(staker, unbonding_locktime)?;
+        let merkle_root = spend_info.merkle_root()?;
+
+        Ok(TransactionOutput {
+            value: self.get_amount(),
+            script_pubkey: conditions::new_p2tr_script_path(
+                // Using an unspendable key as a P2TR internal public key effectively disables taproot key spends.
+                &babylon::spending_info::UNSPENDABLE_KEY_PATH.compressed(),
+                &merkle_root,
+            ),
+        })
+    }
 }
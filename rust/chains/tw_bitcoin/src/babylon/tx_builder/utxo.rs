@@ -3,11 +3,15 @@
 // Copyright © 2017 Trust Wallet.
 
 use crate::babylon;
+use crate::babylon::multi_sig_ordered::MultiSigOrdered;
 use crate::babylon::spending_data;
 use crate::babylon::spending_info::UNSPENDABLE_KEY_PATH;
 use crate::babylon::tx_builder::{BabylonStakingParams, BabylonUnbondingParams};
 use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
 use tw_keypair::schnorr;
+use tw_memory::Data;
+use tw_utxo::script::Script;
 use tw_utxo::signature::BitcoinSchnorrSignature;
 use tw_utxo::spending_data::SpendingDataConstructor;
 use tw_utxo::transaction::standard_transaction::builder::UtxoBuilder;
@@ -31,10 +35,41 @@ pub trait BabylonUtxoBuilder: Sized {
         covenant_committee_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
     ) -> SigningResult<(TransactionInput, UtxoToSign)>;
 
+    /// Spend a Staking Output via unbonding path without covenant committee signatures.
+    /// Used to pre-sign an Unbonding transaction by the staker before covenant signatures are collected.
+    fn babylon_staking_unbonding_path_presign(
+        self,
+        params: BabylonStakingParams,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)>;
+
+    /// Spend a Staking Output via slashing path.
+    /// In other words, create a Slashing transaction.
+    ///
+    /// Finality provider and covenant committee signatures can be empty
+    /// in order to pre-sign the Slashing transaction by the staker.
+    fn babylon_staking_slashing_path(
+        self,
+        params: BabylonStakingParams,
+        finality_provider_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
+        covenant_committee_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
+    ) -> SigningResult<(TransactionInput, UtxoToSign)>;
+
     fn babylon_unbonding_timelock_path(
         self,
         params: BabylonUnbondingParams,
     ) -> SigningResult<(TransactionInput, UtxoToSign)>;
+
+    /// Spend an Unbonding Output via slashing path.
+    /// In other words, create a Slashing transaction spending an Unbonding transaction.
+    ///
+    /// Finality provider and covenant committee signatures can be empty
+    /// in order to pre-sign the Slashing transaction by the staker.
+    fn babylon_unbonding_slashing_path(
+        self,
+        params: BabylonUnbondingParams,
+        finality_provider_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
+        covenant_committee_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
+    ) -> SigningResult<(TransactionInput, UtxoToSign)>;
 }
 
 impl BabylonUtxoBuilder for UtxoBuilder {
@@ -64,33 +99,46 @@ impl BabylonUtxoBuilder for UtxoBuilder {
         params: BabylonStakingParams,
         covenant_committee_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
     ) -> SigningResult<(TransactionInput, UtxoToSign)> {
-        let spend_info = babylon::spending_info::StakingSpendInfo::new(&params)?;
         let signatures = params
             .covenants
+            .clone()
             .with_partial_signatures(covenant_committee_signatures)?;
-        let unbonding_script = spend_info.unbonding_script();
+        staking_unbonding_path(self, &params, signatures)
+    }
 
-        let unbonding_control_block = spend_info.unbonding_control_block()?.serialize();
-        let spending_data_ctor =
-            SpendingDataConstructor::schnorr(spending_data::BabylonUnbondingPath::new(
-                unbonding_script.clone(),
-                unbonding_control_block.clone(),
-                signatures,
-            ));
+    fn babylon_staking_unbonding_path_presign(
+        self,
+        params: BabylonStakingParams,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let no_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)] = &[];
+        let signatures = params
+            .covenants
+            .clone()
+            .with_optional_signatures(no_signatures)?;
+        staking_unbonding_path(self, &params, signatures)
+    }
+
+    fn babylon_staking_slashing_path(
+        self,
+        params: BabylonStakingParams,
+        finality_provider_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
+        covenant_committee_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let spend_info = babylon::spending_info::StakingSpendInfo::new(&params)?;
 
+        let slashing_script = spend_info.slashing_script().clone();
+        let control_block = spend_info.slashing_control_block()?.serialize();
         let merkle_root = spend_info.merkle_root()?;
 
-        self.p2tr_script_path()
-            .reveal_script_pubkey(unbonding_script.clone())
-            // Staker is responsible to sign the UTXO.
-            .spender_public_key(&params.staker)
-            // Babylon Staking or Unbonding output was created using an unspendable internal public key,
-            // that means taproot key spends is disabled.
-            .restore_prevout_script_pubkey(&UNSPENDABLE_KEY_PATH, &merkle_root)
-            .control_block(unbonding_control_block)
-            // For Babylon Unbonding path we use a custom spending data constructor.
-            .custom_spending_data_ctor(spending_data_ctor)
-            .build()
+        slashing_path(
+            self,
+            params,
+            slashing_script,
+            control_block,
+            merkle_root,
+            finality_provider_signatures,
+            covenant_committee_signatures,
+        )
     }
 
     fn babylon_unbonding_timelock_path(
@@ -113,4 +161,99 @@ impl BabylonUtxoBuilder for UtxoBuilder {
             .control_block(control_block)
             .build()
     }
+
+    fn babylon_unbonding_slashing_path(
+        self,
+        params: BabylonUnbondingParams,
+        finality_provider_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
+        covenant_committee_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let spend_info = babylon::spending_info::UnbondingSpendInfo::new(&params)?;
+
+        let slashing_script = spend_info.slashing_script().clone();
+        let control_block = spend_info.slashing_control_block()?.serialize();
+        let merkle_root = spend_info.merkle_root()?;
+
+        slashing_path(
+            self,
+            params,
+            slashing_script,
+            control_block,
+            merkle_root,
+            finality_provider_signatures,
+            covenant_committee_signatures,
+        )
+    }
+}
+
+fn staking_unbonding_path(
+    builder: UtxoBuilder,
+    params: &BabylonStakingParams,
+    covenant_committee_signatures: MultiSigOrdered,
+) -> SigningResult<(TransactionInput, UtxoToSign)> {
+    let spend_info = babylon::spending_info::StakingSpendInfo::new(params)?;
+    let unbonding_script = spend_info.unbonding_script();
+
+    let unbonding_control_block = spend_info.unbonding_control_block()?.serialize();
+    let spending_data_ctor =
+        SpendingDataConstructor::schnorr(spending_data::BabylonUnbondingPath::new(
+            unbonding_script.clone(),
+            unbonding_control_block.clone(),
+            covenant_committee_signatures,
+        ));
+
+    let merkle_root = spend_info.merkle_root()?;
+
+    builder
+        .p2tr_script_path()
+        .reveal_script_pubkey(unbonding_script.clone())
+        // Staker is responsible to sign the UTXO.
+        .spender_public_key(&params.staker)
+        // Babylon Staking or Unbonding output was created using an unspendable internal public key,
+        // that means taproot key spends is disabled.
+        .restore_prevout_script_pubkey(&UNSPENDABLE_KEY_PATH, &merkle_root)
+        .control_block(unbonding_control_block)
+        // For Babylon Unbonding path we use a custom spending data constructor.
+        .custom_spending_data_ctor(spending_data_ctor)
+        .build()
+}
+
+fn slashing_path(
+    builder: UtxoBuilder,
+    params: BabylonStakingParams,
+    slashing_script: Script,
+    control_block: Data,
+    merkle_root: H256,
+    finality_provider_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
+    covenant_committee_signatures: &[(schnorr::XOnlyPublicKey, BitcoinSchnorrSignature)],
+) -> SigningResult<(TransactionInput, UtxoToSign)> {
+    let fp_signatures = params
+        .finality_providers
+        .with_optional_signatures(finality_provider_signatures)
+        .context("Invalid finality provider signatures")?;
+    let covenant_signatures = params
+        .covenants
+        .with_optional_signatures(covenant_committee_signatures)
+        .context("Invalid covenant committee signatures")?;
+
+    let spending_data_ctor =
+        SpendingDataConstructor::schnorr(spending_data::BabylonSlashingPath::new(
+            slashing_script.clone(),
+            control_block.clone(),
+            fp_signatures,
+            covenant_signatures,
+        ));
+
+    builder
+        .p2tr_script_path()
+        .reveal_script_pubkey(slashing_script)
+        // Staker is responsible to sign the UTXO.
+        .spender_public_key(&params.staker)
+        // Babylon Staking or Unbonding output was created using an unspendable internal public key,
+        // that means taproot key spends is disabled.
+        .restore_prevout_script_pubkey(&UNSPENDABLE_KEY_PATH, &merkle_root)
+        .control_block(control_block)
+        // For Babylon Slashing path we use a custom spending data constructor.
+        .custom_spending_data_ctor(spending_data_ctor)
+        .build()
 }
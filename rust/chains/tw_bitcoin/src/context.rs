@@ -6,9 +6,11 @@ use crate::modules::protobuf_builder::standard_protobuf_builder::StandardProtobu
 use crate::modules::protobuf_builder::ProtobufBuilder;
 use crate::modules::psbt_request::standard_psbt_request_builder::StandardPsbtRequestBuilder;
 use crate::modules::psbt_request::PsbtRequestBuilder;
+use crate::modules::signing_request::dogecoin_signing_request::DogecoinSigningRequestBuilder;
+use crate::modules::signing_request::litecoin_signing_request::LitecoinSigningRequestBuilder;
 use crate::modules::signing_request::standard_signing_request::StandardSigningRequestBuilder;
 use crate::modules::signing_request::SigningRequestBuilder;
-use tw_coin_entry::error::prelude::SigningResult;
+use tw_coin_entry::error::prelude::*;
 use tw_utxo::address::standard_bitcoin::StandardBitcoinAddress;
 use tw_utxo::context::{AddressPrefixes, UtxoContext};
 use tw_utxo::fee::fee_estimator::StandardFeeEstimator;
@@ -51,3 +53,59 @@ impl BitcoinSigningContext for StandardBitcoinContext {
     type ProtobufBuilder = StandardProtobufBuilder;
     type PsbtRequestBuilder = StandardPsbtRequestBuilder;
 }
+
+/// Dogecoin has the standard transaction format and address types,
+/// but Segwit and Taproot are not activated, and it has its own dust and fee policies.
+#[derive(Default)]
+pub struct DogecoinContext;
+
+impl UtxoContext for DogecoinContext {
+    type Address = StandardBitcoinAddress;
+    type Transaction = Transaction;
+    type FeeEstimator = StandardFeeEstimator<Self::Transaction>;
+
+    fn addr_to_script_pubkey(
+        addr: &Self::Address,
+        prefixes: AddressPrefixes,
+    ) -> SigningResult<Script> {
+        match addr {
+            StandardBitcoinAddress::Legacy(legacy) => {
+                legacy.to_script_pubkey(prefixes.p2pkh_prefix, prefixes.p2sh_prefix)
+            },
+            StandardBitcoinAddress::Segwit(_) | StandardBitcoinAddress::Taproot(_) => {
+                SigningError::err(SigningErrorType::Error_invalid_address)
+                    .context("Dogecoin supports legacy addresses only")
+            },
+        }
+    }
+}
+
+impl BitcoinSigningContext for DogecoinContext {
+    type SigningRequestBuilder = DogecoinSigningRequestBuilder;
+    type ProtobufBuilder = StandardProtobufBuilder;
+    type PsbtRequestBuilder = StandardPsbtRequestBuilder;
+}
+
+/// Litecoin has the standard transaction format and address types,
+/// but it has its own dust policy and MWEB peg-in outputs are not supported.
+#[derive(Default)]
+pub struct LitecoinContext;
+
+impl UtxoContext for LitecoinContext {
+    type Address = StandardBitcoinAddress;
+    type Transaction = Transaction;
+    type FeeEstimator = StandardFeeEstimator<Self::Transaction>;
+
+    fn addr_to_script_pubkey(
+        addr: &Self::Address,
+        prefixes: AddressPrefixes,
+    ) -> SigningResult<Script> {
+        StandardBitcoinContext::addr_to_script_pubkey(addr, prefixes)
+    }
+}
+
+impl BitcoinSigningContext for LitecoinContext {
+    type SigningRequestBuilder = LitecoinSigningRequestBuilder;
+    type ProtobufBuilder = StandardProtobufBuilder;
+    type PsbtRequestBuilder = StandardPsbtRequestBuilder;
+}
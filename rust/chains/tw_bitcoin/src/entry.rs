@@ -1,8 +1,11 @@
-use crate::context::StandardBitcoinContext;
+use crate::context::{
+    BitcoinSigningContext, DogecoinContext, LitecoinContext, StandardBitcoinContext,
+};
 use crate::modules::compiler::BitcoinCompiler;
 use crate::modules::planner::BitcoinPlanner;
 use crate::modules::signer::BitcoinSigner;
 use crate::modules::transaction_util::BitcoinTransactionUtil;
+use std::marker::PhantomData;
 use std::str::FromStr;
 use tw_coin_entry::coin_context::CoinContext;
 use tw_coin_entry::coin_entry::{CoinEntry, PublicKeyBytes, SignatureBytes};
@@ -16,9 +19,24 @@ use tw_keypair::tw::PublicKey;
 use tw_proto::BitcoinV2::Proto;
 use tw_utxo::address::standard_bitcoin::{StandardBitcoinAddress, StandardBitcoinPrefix};
 
-pub struct BitcoinEntry;
+/// Bitcoin and the forks that share the standard transaction format and address types,
+/// but may have different signing policies specified by the `Context`.
+pub struct BitcoinEntry<Context: BitcoinSigningContext = StandardBitcoinContext> {
+    _phantom: PhantomData<Context>,
+}
+
+pub type DogecoinEntry = BitcoinEntry<DogecoinContext>;
+pub type LitecoinEntry = BitcoinEntry<LitecoinContext>;
+
+impl<Context: BitcoinSigningContext> BitcoinEntry<Context> {
+    pub const fn new() -> Self {
+        BitcoinEntry {
+            _phantom: PhantomData,
+        }
+    }
+}
 
-impl CoinEntry for BitcoinEntry {
+impl<Context: BitcoinSigningContext> CoinEntry for BitcoinEntry<Context> {
     type AddressPrefix = StandardBitcoinPrefix;
     type Address = StandardBitcoinAddress;
     type SigningInput<'a> = Proto::SigningInput<'a>;
@@ -27,7 +45,7 @@ impl CoinEntry for BitcoinEntry {
 
     // Optional modules:
     type JsonSigner = NoJsonSigner;
-    type PlanBuilder = BitcoinPlanner<StandardBitcoinContext>;
+    type PlanBuilder = BitcoinPlanner<Context>;
     type MessageSigner = NoMessageSigner;
     type WalletConnector = NoWalletConnector;
     type TransactionDecoder = NoTransactionDecoder;
@@ -61,7 +79,7 @@ impl CoinEntry for BitcoinEntry {
 
     #[inline]
     fn sign(&self, coin: &dyn CoinContext, proto: Self::SigningInput<'_>) -> Self::SigningOutput {
-        BitcoinSigner::<StandardBitcoinContext>::sign(coin, &proto)
+        BitcoinSigner::<Context>::sign(coin, &proto)
     }
 
     #[inline]
@@ -70,7 +88,7 @@ impl CoinEntry for BitcoinEntry {
         coin: &dyn CoinContext,
         proto: Proto::SigningInput<'_>,
     ) -> Self::PreSigningOutput {
-        BitcoinCompiler::<StandardBitcoinContext>::preimage_hashes(coin, proto)
+        BitcoinCompiler::<Context>::preimage_hashes(coin, proto)
     }
 
     #[inline]
@@ -81,12 +99,12 @@ impl CoinEntry for BitcoinEntry {
         signatures: Vec<SignatureBytes>,
         public_keys: Vec<PublicKeyBytes>,
     ) -> Self::SigningOutput {
-        BitcoinCompiler::<StandardBitcoinContext>::compile(coin, proto, signatures, public_keys)
+        BitcoinCompiler::<Context>::compile(coin, proto, signatures, public_keys)
     }
 
     #[inline]
     fn plan_builder(&self) -> Option<Self::PlanBuilder> {
-        Some(BitcoinPlanner::<StandardBitcoinContext>::default())
+        Some(BitcoinPlanner::<Context>::default())
     }
 
     #[inline]
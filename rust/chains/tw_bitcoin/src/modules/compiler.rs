@@ -3,9 +3,12 @@
 // Copyright © 2017 Trust Wallet.
 
 use crate::context::BitcoinSigningContext;
+use crate::modules::planner::fee_bump_planner::FeeBumpPlanner;
 use crate::modules::protobuf_builder::ProtobufBuilder;
 use crate::modules::psbt_request::{PsbtRequest, PsbtRequestBuilder};
+use crate::modules::signing_request::standard_signing_request::chain_info;
 use crate::modules::signing_request::SigningRequestBuilder;
+use crate::modules::silent_payments::SilentPaymentOutputs;
 use std::borrow::Cow;
 use std::marker::PhantomData;
 use tw_coin_entry::coin_context::CoinContext;
@@ -49,12 +52,19 @@ impl<Context: BitcoinSigningContext> BitcoinCompiler<Context> {
     ) -> SigningResult<Proto::PreSigningOutput<'static>> {
         let unsigned_tx = match input.transaction {
             TransactionType::builder(ref tx_builder) => {
+                Self::ensure_no_silent_payments(coin, &input, tx_builder)?;
                 let request = Context::SigningRequestBuilder::build(coin, &input, tx_builder)?;
                 TxPlanner::plan(request)?.unsigned_tx
             },
             TransactionType::psbt(ref psbt) => {
                 Context::PsbtRequestBuilder::build(&input, psbt)?.unsigned_tx
             },
+            TransactionType::fee_bump(ref fee_bump) => {
+                let tx_builder = FeeBumpPlanner::<Context>::tx_builder(coin, &input, fee_bump)?;
+                Self::ensure_no_silent_payments(coin, &input, &tx_builder)?;
+                let request = Context::SigningRequestBuilder::build(coin, &input, &tx_builder)?;
+                TxPlanner::plan(request)?.unsigned_tx
+            },
             TransactionType::None => {
                 return SigningError::err(SigningErrorType::Error_invalid_params)
                     .context("Either `TransactionBuilder` or `Psbt` should be set")
@@ -101,6 +111,10 @@ impl<Context: BitcoinSigningContext> BitcoinCompiler<Context> {
                 Self::compile_with_tx_builder(coin, &input, tx, signatures)
             },
             TransactionType::psbt(ref psbt) => Self::compile_psbt(coin, &input, psbt, signatures),
+            TransactionType::fee_bump(ref fee_bump) => {
+                let tx = FeeBumpPlanner::<Context>::tx_builder(coin, &input, fee_bump)?;
+                Self::compile_with_tx_builder(coin, &input, &tx, signatures)
+            },
             TransactionType::None => SigningError::err(SigningErrorType::Error_invalid_params)
                 .context("No transaction type specified"),
         }
@@ -112,6 +126,7 @@ impl<Context: BitcoinSigningContext> BitcoinCompiler<Context> {
         tx_builder_input: &Proto::TransactionBuilder,
         signatures: Vec<SignatureBytes>,
     ) -> SigningResult<Proto::SigningOutput<'static>> {
+        Self::ensure_no_silent_payments(coin, input, tx_builder_input)?;
         let request = Context::SigningRequestBuilder::build(coin, input, tx_builder_input)?;
         let SelectResult { unsigned_tx, plan } = TxPlanner::plan(request)?;
 
@@ -154,6 +169,23 @@ impl<Context: BitcoinSigningContext> BitcoinCompiler<Context> {
             ..Proto::SigningOutput::default()
         })
     }
+
+    /// Silent payment outputs are derived from the input private keys,
+    /// so the transaction cannot be signed externally.
+    fn ensure_no_silent_payments(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput,
+        tx_builder_input: &Proto::TransactionBuilder,
+    ) -> SigningResult<()> {
+        let chain_info = chain_info(coin, &input.chain_info)?;
+        if SilentPaymentOutputs::<Context>::from_tx_builder(&chain_info, tx_builder_input)?
+            .is_empty()
+        {
+            return Ok(());
+        }
+        SigningError::err(SigningErrorType::Error_not_supported)
+            .context("Silent payment outputs require private keys and cannot be compiled")
+    }
 }
 
 pub fn signing_method(s: SigningMethod) -> ProtoSigningMethod {
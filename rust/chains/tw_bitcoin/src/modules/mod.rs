@@ -9,5 +9,6 @@ pub mod psbt;
 pub mod psbt_request;
 pub mod signer;
 pub mod signing_request;
+pub mod silent_payments;
 pub mod transaction_util;
 pub mod tx_builder;
@@ -0,0 +1,341 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::context::BitcoinSigningContext;
+use crate::modules::planner::BitcoinPlanner;
+use crate::modules::tx_builder::utxo_protobuf::parse_out_point;
+use bitcoin::consensus::deserialize;
+use bitcoin::hashes::Hash;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::transaction::transaction_parts::{Amount, OutPoint};
+use Proto::mod_FeeBump::mod_ChildPaysForParent::OneOfdust_policy as CpfpDustPolicy;
+use Proto::mod_FeeBump::mod_ReplaceByFee::{
+    OneOfchange as RbfChange, OneOfdust_policy as RbfDustPolicy,
+};
+use Proto::mod_FeeBump::OneOfvariant as FeeBumpType;
+use Proto::mod_TransactionBuilder::OneOfdust_policy as ProtoDustPolicy;
+
+/// The minimum fee rate the replacement transaction must pay for its own bandwidth
+/// on top of the original fee, as per BIP-125 rule #4.
+pub const INCREMENTAL_RELAY_FEE_PER_VB: Amount = 1;
+
+/// Fee bumping planner of stuck transactions.
+///
+/// The planner converts a [`Proto::FeeBump`] request into a regular [`Proto::TransactionBuilder`]
+/// with the fee rate adjusted to satisfy either
+/// [BIP-125](https://github.com/bitcoin/bips/blob/master/bip-0125.mediawiki) Replace-By-Fee rules,
+/// or the target package fee rate of a Child-Pays-For-Parent transaction.
+/// The result can be planned, signed or compiled as any other transaction builder.
+pub struct FeeBumpPlanner<Context: BitcoinSigningContext> {
+    _phantom: PhantomData<Context>,
+}
+
+impl<Context: BitcoinSigningContext> FeeBumpPlanner<Context> {
+    pub fn tx_builder<'a>(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput<'a>,
+        fee_bump: &Proto::FeeBump<'a>,
+    ) -> SigningResult<Proto::TransactionBuilder<'a>> {
+        match fee_bump.variant {
+            FeeBumpType::replace_by_fee(ref rbf) => Self::replace_by_fee(coin, input, rbf),
+            FeeBumpType::child_pays_for_parent(ref cpfp) => {
+                Self::child_pays_for_parent(coin, input, cpfp)
+            },
+            FeeBumpType::None => SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Either `ReplaceByFee` or `ChildPaysForParent` should be set"),
+        }
+    }
+
+    fn replace_by_fee<'a>(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput<'a>,
+        rbf: &Proto::mod_FeeBump::ReplaceByFee<'a>,
+    ) -> SigningResult<Proto::TransactionBuilder<'a>> {
+        let original_tx =
+            decode_tx(&rbf.original_tx).context("Error decoding the original transaction")?;
+
+        // BIP-125 rule #1: the original transaction must signal replaceability.
+        let signals_rbf = original_tx.input.iter().any(|txin| txin.sequence.is_rbf());
+        if !signals_rbf && !rbf.allow_full_rbf {
+            return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                "The original transaction does not signal replaceability. Consider setting 'ReplaceByFee.allow_full_rbf'",
+            );
+        }
+
+        // Prepare a map of source Inputs Proto `{ OutPoint -> Input }`.
+        let mut inputs_map = HashMap::with_capacity(rbf.inputs.len());
+        for utxo in rbf.inputs.iter() {
+            inputs_map.insert(parse_out_point(&utxo.out_point)?, utxo);
+        }
+
+        // Restore the original inputs in the same order and with the same sequence numbers.
+        let mut inputs = Vec::with_capacity(original_tx.input.len());
+        let mut original_total_in: Amount = 0;
+        for txin in original_tx.input.iter() {
+            let out_point = OutPoint {
+                hash: H256::from(txin.previous_output.txid.to_byte_array()),
+                index: txin.previous_output.vout,
+            };
+            let utxo = inputs_map
+                .get(&out_point)
+                .or_tw_err(SigningErrorType::Error_missing_input_utxos)
+                .with_context(|| {
+                    format!(
+                        "No UTXO provided for the original input '{}'",
+                        txin.previous_output
+                    )
+                })?;
+
+            original_total_in = original_total_in
+                .checked_add(utxo.value)
+                .or_tw_err(SigningErrorType::Error_invalid_utxo_amount)
+                .context("Sum of the original input amounts is too large")?;
+
+            let mut utxo = (*utxo).clone();
+            utxo.sequence = Some(Proto::mod_Input::Sequence {
+                sequence: txin.sequence.to_consensus_u32(),
+            });
+            inputs.push(utxo);
+        }
+
+        let change_output_index = match rbf.change {
+            RbfChange::change_output_index(index) => Some(index as usize),
+            RbfChange::None => None,
+        };
+
+        // Keep the original outputs, but the change one.
+        let mut outputs = Vec::with_capacity(original_tx.output.len());
+        let mut change_output = None;
+        let mut original_total_out: Amount = 0;
+        for (index, txout) in original_tx.output.iter().enumerate() {
+            let value = output_value(txout)?;
+            original_total_out = original_total_out
+                .checked_add(value)
+                .or_tw_err(SigningErrorType::Error_invalid_utxo_amount)
+                .context("Sum of the original output amounts is too large")?;
+
+            let output = Proto::Output {
+                value,
+                to_recipient: Proto::mod_Output::OneOfto_recipient::custom_script_pubkey(
+                    Cow::from(txout.script_pubkey.to_bytes()),
+                ),
+                cash_token: None,
+            };
+            if change_output_index == Some(index) {
+                // The change output value will be recalculated by the planner.
+                change_output = Some(output);
+            } else {
+                outputs.push(output);
+            }
+        }
+
+        if change_output_index.is_some() && change_output.is_none() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("'ReplaceByFee.change_output_index' is out of bounds");
+        }
+
+        let original_fee = original_total_in
+            .checked_sub(original_total_out)
+            .filter(|fee| *fee >= 0)
+            .or_tw_err(SigningErrorType::Error_invalid_utxo_amount)
+            .context("Original transaction outputs exceed the provided UTXO amounts")?;
+        let original_vsize = original_tx.vsize() as Amount;
+
+        // BIP-125 rule #6: the replacement must pay a higher fee rate than the original transaction.
+        let min_original_fee = original_vsize
+            .checked_mul(rbf.fee_per_vb)
+            .or_tw_err(SigningErrorType::Error_wrong_fee)
+            .context("'ReplaceByFee.fee_per_vb' is too large")?;
+        if min_original_fee <= original_fee {
+            return SigningError::err(SigningErrorType::Error_wrong_fee).context(format!(
+                "'ReplaceByFee.fee_per_vb' must be higher than the original fee rate: '{original_fee}' fee, '{original_vsize}' vsize"
+            ));
+        }
+
+        let dust_policy = match rbf.dust_policy {
+            RbfDustPolicy::fixed_dust_threshold(threshold) => {
+                ProtoDustPolicy::fixed_dust_threshold(threshold)
+            },
+            RbfDustPolicy::None => ProtoDustPolicy::None,
+        };
+
+        let mut tx_builder = Proto::TransactionBuilder {
+            version: transaction_version(original_tx.version)?,
+            lock_time: original_tx.lock_time.to_consensus_u32(),
+            inputs,
+            outputs,
+            // All the original inputs must be spent by the replacement transaction.
+            input_selector: Proto::InputSelector::UseAll,
+            fee_per_vb: rbf.fee_per_vb,
+            dust_policy,
+            ..Proto::TransactionBuilder::default()
+        };
+        // If the change is the only output, the whole remaining amount should be sent to it.
+        if tx_builder.outputs.is_empty() {
+            tx_builder.max_amount_output = change_output;
+        } else {
+            tx_builder.change_output = change_output;
+        }
+
+        // BIP-125 rules #3 and #4: the replacement must pay at least the original fee
+        // plus its own bandwidth at the incremental relay fee rate.
+        Self::adjust_fee(coin, input, tx_builder, &rbf.additional_inputs, |vsize| {
+            vsize
+                .checked_mul(INCREMENTAL_RELAY_FEE_PER_VB)
+                .and_then(|relay_fee| relay_fee.checked_add(original_fee))
+                .or_tw_err(SigningErrorType::Error_wrong_fee)
+                .context("Replacement transaction fee is too large")
+        })
+    }
+
+    fn child_pays_for_parent<'a>(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput<'a>,
+        cpfp: &Proto::mod_FeeBump::ChildPaysForParent<'a>,
+    ) -> SigningResult<Proto::TransactionBuilder<'a>> {
+        let parent_tx =
+            decode_tx(&cpfp.parent_tx).context("Error decoding the parent transaction")?;
+
+        let parent_output = cpfp
+            .parent_output
+            .as_ref()
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("No 'ChildPaysForParent.parent_output' provided")?;
+        let out_point = parse_out_point(&parent_output.out_point)?;
+
+        let parent_txid = H256::from(parent_tx.txid().to_byte_array());
+        if out_point.hash != parent_txid {
+            return SigningError::err(SigningErrorType::Error_invalid_utxo).context(
+                "'ChildPaysForParent.parent_output' must reference the parent transaction",
+            );
+        }
+
+        let parent_txout = parent_tx
+            .output
+            .get(out_point.index as usize)
+            .or_tw_err(SigningErrorType::Error_invalid_utxo)
+            .with_context(|| {
+                format!(
+                    "Parent transaction does not contain '{}' output",
+                    out_point.index
+                )
+            })?;
+        if output_value(parent_txout)? != parent_output.value {
+            return SigningError::err(SigningErrorType::Error_invalid_utxo_amount)
+                .context("'ChildPaysForParent.parent_output.value' does not match the parent transaction output");
+        }
+
+        let child_output = cpfp
+            .child_output
+            .clone()
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("No 'ChildPaysForParent.child_output' provided")?;
+
+        let dust_policy = match cpfp.dust_policy {
+            CpfpDustPolicy::fixed_dust_threshold(threshold) => {
+                ProtoDustPolicy::fixed_dust_threshold(threshold)
+            },
+            CpfpDustPolicy::None => ProtoDustPolicy::None,
+        };
+
+        let tx_builder = Proto::TransactionBuilder {
+            version: Proto::TransactionVersion::V2,
+            inputs: vec![parent_output.clone()],
+            input_selector: Proto::InputSelector::UseAll,
+            fee_per_vb: cpfp.fee_per_vb,
+            max_amount_output: Some(child_output),
+            dust_policy,
+            ..Proto::TransactionBuilder::default()
+        };
+
+        let parent_vsize = parent_tx.vsize() as Amount;
+        let parent_fee = cpfp.parent_fee;
+        let package_fee_per_vb = cpfp.fee_per_vb;
+
+        // The child transaction must pay the rest of the package fee:
+        // `(parent_vsize + child_vsize) * fee_per_vb - parent_fee`.
+        Self::adjust_fee(
+            coin,
+            input,
+            tx_builder,
+            &cpfp.additional_inputs,
+            |child_vsize| {
+                child_vsize
+                    .checked_add(parent_vsize)
+                    .and_then(|package_vsize| package_vsize.checked_mul(package_fee_per_vb))
+                    .and_then(|package_fee| package_fee.checked_sub(parent_fee))
+                    .or_tw_err(SigningErrorType::Error_wrong_fee)
+                    .context("Package fee is too large")
+            },
+        )
+    }
+
+    /// Plans the transaction and raises its fee rate until it pays at least `required_fee(vsize)`.
+    /// The `additional_inputs` are added one by one if the transaction inputs do not cover the fee.
+    fn adjust_fee<'a, F>(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput<'a>,
+        mut tx_builder: Proto::TransactionBuilder<'a>,
+        additional_inputs: &[Proto::Input<'a>],
+        required_fee: F,
+    ) -> SigningResult<Proto::TransactionBuilder<'a>>
+    where
+        F: Fn(Amount) -> SigningResult<Amount>,
+    {
+        let mut additional_inputs = additional_inputs.iter();
+
+        loop {
+            let plan =
+                match BitcoinPlanner::<Context>::plan_with_tx_builder(coin, input, &tx_builder) {
+                    Ok(plan) => plan,
+                    Err(e) if *e.error_type() == SigningErrorType::Error_not_enough_utxos => {
+                        match additional_inputs.next() {
+                            Some(utxo) => {
+                                tx_builder.inputs.push(utxo.clone());
+                                continue;
+                            },
+                            None => return Err(e),
+                        }
+                    },
+                    Err(e) => return Err(e),
+                };
+
+            let vsize = plan.vsize_estimate as Amount;
+            let required_fee = required_fee(vsize)?;
+            if plan.fee_estimate >= required_fee {
+                return Ok(tx_builder);
+            }
+
+            // Raise the fee rate, so the transaction pays at least the required fee.
+            // Please note the fee rate is strictly increased to guarantee the loop ends.
+            let required_fee_per_vb = (required_fee + vsize - 1) / vsize;
+            tx_builder.fee_per_vb = required_fee_per_vb.max(tx_builder.fee_per_vb + 1);
+        }
+    }
+}
+
+fn decode_tx(encoded: &[u8]) -> SigningResult<bitcoin::Transaction> {
+    deserialize(encoded).tw_err(|_| SigningErrorType::Error_input_parse)
+}
+
+fn output_value(txout: &bitcoin::TxOut) -> SigningResult<Amount> {
+    Amount::try_from(txout.value)
+        .tw_err(|_| SigningErrorType::Error_invalid_utxo_amount)
+        .context("Transaction output amount is too large")
+}
+
+fn transaction_version(version: i32) -> SigningResult<Proto::TransactionVersion> {
+    match version {
+        1 => Ok(Proto::TransactionVersion::V1),
+        2 => Ok(Proto::TransactionVersion::V2),
+        _ => SigningError::err(SigningErrorType::Error_not_supported)
+            .with_context(|| format!("Unsupported transaction version: {version}")),
+    }
+}
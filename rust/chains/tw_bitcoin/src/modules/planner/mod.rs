@@ -20,6 +20,7 @@ use tw_utxo::transaction::transaction_interface::{
     TransactionInterface, TxInputInterface, TxOutputInterface,
 };
 
+pub mod fee_bump_planner;
 pub mod psbt_planner;
 
 #[derive(Default)]
@@ -39,6 +40,11 @@ impl<Context: BitcoinSigningContext> BitcoinPlanner<Context> {
             TransactionType::psbt(ref psbt) => {
                 psbt_planner::PsbtPlanner::<Context>::plan_psbt(coin, input, psbt)
             },
+            TransactionType::fee_bump(ref fee_bump) => {
+                let tx =
+                    fee_bump_planner::FeeBumpPlanner::<Context>::tx_builder(coin, input, fee_bump)?;
+                Self::plan_with_tx_builder(coin, input, &tx)
+            },
             TransactionType::None => SigningError::err(SigningErrorType::Error_invalid_params)
                 .context("Either `TransactionBuilder` or `Psbt` should be set"),
         }
@@ -86,6 +92,7 @@ impl<Context: BitcoinSigningContext> BitcoinPlanner<Context> {
             outputs_proto.push(Proto::Output {
                 value: selected_output.value(),
                 to_recipient,
+                cash_token: None,
             })
         }
 
@@ -97,6 +104,8 @@ impl<Context: BitcoinSigningContext> BitcoinPlanner<Context> {
             vsize_estimate: plan.vsize_estimate as u64,
             fee_estimate: plan.fee_estimate,
             change: plan.change,
+            min_fee_estimate: plan.fee_bounds.min,
+            max_fee_estimate: plan.fee_bounds.max,
             ..Proto::TransactionPlan::default()
         })
     }
@@ -91,6 +91,8 @@ impl<Context: BitcoinSigningContext> PsbtPlanner<Context> {
             sighash_type: unsigned_txin.sighash_ty.raw_sighash(),
             sequence: Some(sequence),
             claiming_script: ClaimingScriptProto::receiver_address(from_address.into()),
+            cash_token: None,
+            satisfaction_weight: None,
         })
     }
 
@@ -111,6 +113,7 @@ impl<Context: BitcoinSigningContext> PsbtPlanner<Context> {
         Ok(Proto::Output {
             value: output.value(),
             to_recipient,
+            cash_token: None,
         })
     }
 }
@@ -3,10 +3,13 @@
 // Copyright © 2017 Trust Wallet.
 
 use crate::context::BitcoinSigningContext;
+use crate::modules::planner::fee_bump_planner::FeeBumpPlanner;
 use crate::modules::protobuf_builder::ProtobufBuilder;
 use crate::modules::psbt::update_psbt_signed;
 use crate::modules::psbt_request::{PsbtRequest, PsbtRequestBuilder};
+use crate::modules::signing_request::standard_signing_request::chain_info;
 use crate::modules::signing_request::SigningRequestBuilder;
+use crate::modules::silent_payments::SilentPaymentOutputs;
 use std::borrow::Cow;
 use std::marker::PhantomData;
 use tw_coin_entry::coin_context::CoinContext;
@@ -45,6 +48,10 @@ impl<Context: BitcoinSigningContext> BitcoinSigner<Context> {
         match input.transaction {
             TransactionType::builder(ref tx) => Self::sign_with_tx_builder(coin, input, tx),
             TransactionType::psbt(ref psbt) => Self::sign_psbt(coin, input, psbt),
+            TransactionType::fee_bump(ref fee_bump) => {
+                let tx = FeeBumpPlanner::<Context>::tx_builder(coin, input, fee_bump)?;
+                Self::sign_with_tx_builder(coin, input, &tx)
+            },
             TransactionType::None => SigningError::err(SigningErrorType::Error_invalid_params)
                 .context("Either `TransactionBuilder` or `Psbt` should be set"),
         }
@@ -56,7 +63,10 @@ impl<Context: BitcoinSigningContext> BitcoinSigner<Context> {
         tx_builder_input: &Proto::TransactionBuilder,
     ) -> SigningResult<Proto::SigningOutput<'static>> {
         let request = Context::SigningRequestBuilder::build(coin, input, tx_builder_input)?;
-        let SelectResult { unsigned_tx, plan } = TxPlanner::plan(request)?;
+        let SelectResult {
+            mut unsigned_tx,
+            plan,
+        } = TxPlanner::plan(request)?;
 
         let keys_manager = Self::keys_manager_for_tx(
             &input.private_keys,
@@ -64,6 +74,11 @@ impl<Context: BitcoinSigningContext> BitcoinSigner<Context> {
             input.dangerous_use_fixed_schnorr_rng,
         )?;
 
+        // Silent payment outputs depend on the selected inputs, so they can be derived after planning only.
+        let chain_info = chain_info(coin, &input.chain_info)?;
+        SilentPaymentOutputs::<Context>::from_tx_builder(&chain_info, tx_builder_input)?
+            .set_output_keys(&mut unsigned_tx, &keys_manager)?;
+
         let signed_tx =
             TxSigner::sign_tx(unsigned_tx, &keys_manager).context("Error signing transaction")?;
 
@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::signing_request::standard_signing_request::StandardSigningRequestBuilder;
+use crate::modules::signing_request::SigningRequestBuilder;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::context::UtxoContext;
+use tw_utxo::dust::DustPolicy;
+use tw_utxo::fee::fee_estimator::StandardFeeEstimator;
+use tw_utxo::modules::tx_planner::{PlanRequest, RequestType};
+use tw_utxo::script::standard_script::conditions;
+use tw_utxo::script::Script;
+use tw_utxo::signing_mode::SigningMethod;
+use tw_utxo::transaction::standard_transaction::Transaction;
+use tw_utxo::transaction::transaction_interface::TxOutputInterface;
+use tw_utxo::transaction::unsigned_transaction::UnsignedTransaction;
+
+/// Dogecoin Core soft dust limit is 0.01 DOGE.
+/// Outputs below the limit require an extra fee, so they are not created at all.
+pub const DOGECOIN_DUST_THRESHOLD: i64 = 1_000_000;
+/// Dogecoin Core min relay fee is 0.001 DOGE per kB, i.e. 100 koinu per vbyte.
+pub const DOGECOIN_MIN_FEE_PER_VB: i64 = 100;
+
+/// Builds a standard signing request and applies Dogecoin policies:
+/// * The dust threshold is at least [`DOGECOIN_DUST_THRESHOLD`].
+/// * The fee rate is at least [`DOGECOIN_MIN_FEE_PER_VB`].
+/// * There are no Segwit or Taproot UTXOs and outputs.
+pub struct DogecoinSigningRequestBuilder;
+
+impl<Context> SigningRequestBuilder<Context> for DogecoinSigningRequestBuilder
+where
+    Context:
+        UtxoContext<Transaction = Transaction, FeeEstimator = StandardFeeEstimator<Transaction>>,
+{
+    fn build(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput,
+        transaction_builder: &Proto::TransactionBuilder,
+    ) -> SigningResult<PlanRequest<Context>> {
+        if transaction_builder.fee_per_vb < DOGECOIN_MIN_FEE_PER_VB {
+            return SigningError::err(SigningErrorType::Error_wrong_fee).context(format!(
+                "Dogecoin fee rate must be at least {DOGECOIN_MIN_FEE_PER_VB} koinu per vbyte (0.001 DOGE per kB)"
+            ));
+        }
+
+        let mut request = <StandardSigningRequestBuilder as SigningRequestBuilder<Context>>::build(
+            coin,
+            input,
+            transaction_builder,
+        )?;
+
+        let dust_threshold = request.dust_policy.dust_threshold();
+        request.dust_policy = DustPolicy::FixedAmount(dust_threshold.max(DOGECOIN_DUST_THRESHOLD));
+
+        match request.ty {
+            RequestType::SendMax { ref unsigned_tx } => Self::check_legacy_only(unsigned_tx)?,
+            RequestType::SendExact {
+                ref unsigned_tx,
+                ref change_output,
+                ..
+            } => {
+                Self::check_legacy_only(unsigned_tx)?;
+                if let Some(change_output) = change_output {
+                    Self::check_legacy_output(change_output.script_pubkey())
+                        .context("Invalid change output")?;
+                }
+            },
+        }
+
+        Ok(request)
+    }
+}
+
+impl DogecoinSigningRequestBuilder {
+    /// Dogecoin hasn't activated Segwit and Taproot.
+    fn check_legacy_only(unsigned_tx: &UnsignedTransaction<Transaction>) -> SigningResult<()> {
+        for (i, utxo_args) in unsigned_tx.input_args().iter().enumerate() {
+            if utxo_args.signing_method != SigningMethod::Legacy {
+                return SigningError::err(SigningErrorType::Error_invalid_utxo).context(format!(
+                    "Input #{i}: Dogecoin supports neither Segwit nor Taproot UTXOs"
+                ));
+            }
+        }
+        for (i, output) in unsigned_tx.outputs().iter().enumerate() {
+            Self::check_legacy_output(output.script_pubkey())
+                .with_context(|| format!("Invalid output #{i}"))?;
+        }
+        Ok(())
+    }
+
+    fn check_legacy_output(script_pubkey: &Script) -> SigningResult<()> {
+        if conditions::match_witness_program(script_pubkey).is_some() {
+            return SigningError::err(SigningErrorType::Error_script_output)
+                .context("Dogecoin supports neither Segwit nor Taproot outputs");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::signing_request::standard_signing_request::StandardSigningRequestBuilder;
+use crate::modules::signing_request::SigningRequestBuilder;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::context::UtxoContext;
+use tw_utxo::dust::DustPolicy;
+use tw_utxo::fee::fee_estimator::StandardFeeEstimator;
+use tw_utxo::modules::tx_planner::{PlanRequest, RequestType};
+use tw_utxo::script::standard_script::conditions;
+use tw_utxo::script::Script;
+use tw_utxo::transaction::standard_transaction::Transaction;
+use tw_utxo::transaction::transaction_interface::TxOutputInterface;
+
+/// Litecoin Core dust relay fee is 30000 litoshi per kB, ten times larger than in Bitcoin Core.
+/// That gives 5460 litoshi dust threshold for a P2PKH output.
+pub const LITECOIN_DUST_THRESHOLD: i64 = 5_460;
+/// MWEB peg-in outputs are witness programs of version 9.
+pub const MWEB_PEGIN_WITNESS_VERSION: u8 = 9;
+
+/// Builds a standard signing request and applies Litecoin policies:
+/// * The dust threshold is at least [`LITECOIN_DUST_THRESHOLD`].
+/// * There are no MWEB peg-in outputs as MimbleWimble extension blocks are not supported.
+pub struct LitecoinSigningRequestBuilder;
+
+impl<Context> SigningRequestBuilder<Context> for LitecoinSigningRequestBuilder
+where
+    Context:
+        UtxoContext<Transaction = Transaction, FeeEstimator = StandardFeeEstimator<Transaction>>,
+{
+    fn build(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput,
+        transaction_builder: &Proto::TransactionBuilder,
+    ) -> SigningResult<PlanRequest<Context>> {
+        let mut request = <StandardSigningRequestBuilder as SigningRequestBuilder<Context>>::build(
+            coin,
+            input,
+            transaction_builder,
+        )?;
+
+        let dust_threshold = request.dust_policy.dust_threshold();
+        request.dust_policy = DustPolicy::FixedAmount(dust_threshold.max(LITECOIN_DUST_THRESHOLD));
+
+        let (outputs, change_output) = match request.ty {
+            RequestType::SendMax { ref unsigned_tx } => (unsigned_tx.outputs(), None),
+            RequestType::SendExact {
+                ref unsigned_tx,
+                ref change_output,
+                ..
+            } => (unsigned_tx.outputs(), change_output.as_ref()),
+        };
+        for (i, output) in outputs.iter().enumerate() {
+            check_not_mweb_pegin(output.script_pubkey())
+                .with_context(|| format!("Invalid output #{i}"))?;
+        }
+        if let Some(change_output) = change_output {
+            check_not_mweb_pegin(change_output.script_pubkey()).context("Invalid change output")?;
+        }
+
+        Ok(request)
+    }
+}
+
+fn check_not_mweb_pegin(script_pubkey: &Script) -> SigningResult<()> {
+    match conditions::match_witness_program(script_pubkey) {
+        Some((MWEB_PEGIN_WITNESS_VERSION, _)) => {
+            SigningError::err(SigningErrorType::Error_script_output)
+                .context("MWEB peg-in outputs are not supported")
+        },
+        _ => Ok(()),
+    }
+}
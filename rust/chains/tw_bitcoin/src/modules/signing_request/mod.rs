@@ -8,6 +8,8 @@ use tw_proto::BitcoinV2::Proto;
 use tw_utxo::context::UtxoContext;
 use tw_utxo::modules::tx_planner::PlanRequest;
 
+pub mod dogecoin_signing_request;
+pub mod litecoin_signing_request;
 pub mod standard_signing_request;
 
 pub trait SigningRequestBuilder<Context: UtxoContext> {
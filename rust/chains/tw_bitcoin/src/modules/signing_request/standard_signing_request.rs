@@ -18,6 +18,7 @@ use tw_utxo::fee::fee_estimator::StandardFeeEstimator;
 use tw_utxo::fee::FeePolicy;
 use tw_utxo::modules::tx_planner::{PlanRequest, RequestType};
 use tw_utxo::modules::utxo_selector::InputSelector;
+use tw_utxo::script::timelock::{Timelock, RELATIVE_TIMELOCK_MIN_TX_VERSION};
 use tw_utxo::transaction::standard_transaction::builder::TransactionBuilder;
 use tw_utxo::transaction::standard_transaction::Transaction;
 use Proto::mod_TransactionBuilder::OneOfdust_policy as ProtoDustPolicy;
@@ -51,15 +52,26 @@ where
             .lock_time(transaction_builder.lock_time);
 
         // Parse all UTXOs.
+        let mut has_relative_timelocks = false;
         for utxo_proto in transaction_builder.inputs.iter() {
             let utxo_builder = UtxoProtobuf::<Context>::new(&chain_info, utxo_proto, &public_keys);
 
             let (utxo, utxo_args) = utxo_builder
                 .utxo_from_proto()
                 .context("Error creating UTXO from Protobuf")?;
+            if let Some(Timelock::Relative(_)) = utxo_args.timelock {
+                has_relative_timelocks = true;
+            }
             builder.push_input(utxo, utxo_args);
         }
 
+        // Relative timelocks are enabled since version 2 transactions only.
+        let use_default_version =
+            transaction_builder.version == Proto::TransactionVersion::UseDefault;
+        if has_relative_timelocks && use_default_version {
+            builder.version(RELATIVE_TIMELOCK_MIN_TX_VERSION as u32);
+        }
+
         // If `max_amount_output` is set, construct a transaction with only one output.
         if let Some(max_output_proto) = transaction_builder.max_amount_output.as_ref() {
             let output_builder = OutputProtobuf::<Context>::new(&chain_info, max_output_proto);
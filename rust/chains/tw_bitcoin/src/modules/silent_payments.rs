@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::tx_builder::output_protobuf::OutputProtobuf;
+use crate::modules::tx_builder::BitcoinChainInfo;
+use std::marker::PhantomData;
+use tw_coin_entry::error::prelude::*;
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::address::silent_payment::SilentPaymentAddress;
+use tw_utxo::context::UtxoContext;
+use tw_utxo::modules::keys_manager::KeysManager;
+use tw_utxo::modules::silent_payments::SilentPaymentSender;
+use tw_utxo::transaction::unsigned_transaction::UnsignedTransaction;
+
+/// Outputs of a [`Proto::TransactionBuilder`] that pay to silent payment addresses.
+///
+/// Such outputs are built with a placeholder P2TR `scriptPubkey` (see [`OutputProtobuf::silent_payment`]),
+/// as the actual output keys depend on the selected inputs and their private keys.
+pub struct SilentPaymentOutputs<Context: UtxoContext> {
+    /// Silent payment addresses with the corresponding output indexes.
+    recipients: Vec<(usize, SilentPaymentAddress)>,
+    _phantom: PhantomData<Context>,
+}
+
+impl<Context: UtxoContext> SilentPaymentOutputs<Context> {
+    pub fn from_tx_builder(
+        chain_info: &BitcoinChainInfo,
+        tx_builder: &Proto::TransactionBuilder,
+    ) -> SigningResult<Self> {
+        use Proto::mod_Output::OneOfto_recipient as RecipientType;
+
+        // Outputs are pushed to the transaction in the same order,
+        // and the change output (if not dust) is always the last one.
+        let outputs: Vec<_> = match tx_builder.max_amount_output {
+            Some(ref max_output) => vec![max_output],
+            None => tx_builder
+                .outputs
+                .iter()
+                .chain(tx_builder.change_output.as_ref())
+                .collect(),
+        };
+
+        let mut recipients = Vec::default();
+        for (output_idx, output) in outputs.into_iter().enumerate() {
+            let RecipientType::to_address(ref addr_str) = output.to_recipient else {
+                continue;
+            };
+            if let Some(address) = OutputProtobuf::<Context>::new(chain_info, output)
+                .silent_payment_address(addr_str)?
+            {
+                recipients.push((output_idx, address));
+            }
+        }
+
+        Ok(SilentPaymentOutputs {
+            recipients,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recipients.is_empty()
+    }
+
+    /// Derives the silent payment output keys from the selected inputs,
+    /// and replaces the placeholder `scriptPubkey`s with them.
+    pub fn set_output_keys(
+        &self,
+        unsigned_tx: &mut UnsignedTransaction<Context::Transaction>,
+        keys_manager: &KeysManager,
+    ) -> SigningResult<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        // The change output could have been omitted if its amount is dust.
+        let outputs_len = unsigned_tx.outputs().len();
+        let recipients: Vec<_> = self
+            .recipients
+            .iter()
+            .filter(|(output_idx, _)| *output_idx < outputs_len)
+            .cloned()
+            .collect();
+
+        SilentPaymentSender::from_unsigned_tx(unsigned_tx, keys_manager)?
+            .set_outputs(unsigned_tx, &recipients)
+            .context("Error deriving silent payment outputs")
+    }
+}
@@ -2,11 +2,17 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use tw_utxo::address::silent_payment::{
+    SILENT_PAYMENT_MAINNET_HRP, SILENT_PAYMENT_REGTEST_HRP, SILENT_PAYMENT_TESTNET_HRP,
+};
 use tw_utxo::context::AddressPrefixes;
 
 pub mod output_protobuf;
 pub mod public_keys;
+pub mod runes_protobuf;
 pub mod script_parser;
+pub mod tap_tree_protobuf;
+pub mod timelock_protobuf;
 pub mod utxo_protobuf;
 
 pub struct BitcoinChainInfo {
@@ -23,4 +29,14 @@ impl BitcoinChainInfo {
             p2sh_prefix: self.p2sh_prefix,
         }
     }
+
+    /// Returns a silent payment address HRP if supported by the chain.
+    pub fn silent_payment_hrp(&self) -> Option<&'static str> {
+        match self.hrp.as_deref() {
+            Some("bc") => Some(SILENT_PAYMENT_MAINNET_HRP),
+            Some("tb") => Some(SILENT_PAYMENT_TESTNET_HRP),
+            Some("bcrt") => Some(SILENT_PAYMENT_REGTEST_HRP),
+            _ => None,
+        }
+    }
 }
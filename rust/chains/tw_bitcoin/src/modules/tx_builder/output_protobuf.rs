@@ -3,6 +3,11 @@
 // Copyright © 2017 Trust Wallet.
 
 use crate::babylon::proto_builder::output_protobuf::BabylonOutputProtobuf;
+use crate::modules::tx_builder::runes_protobuf::{rune_from_proto, runestone_from_proto};
+use crate::modules::tx_builder::tap_tree_protobuf::tap_tree_from_proto;
+use crate::modules::tx_builder::timelock_protobuf::{
+    timelock_ecdsa_pubkey_from_proto, timelock_from_proto, timelock_taproot_keys_from_proto,
+};
 use crate::modules::tx_builder::BitcoinChainInfo;
 use std::marker::PhantomData;
 use std::str::FromStr;
@@ -13,7 +18,9 @@ use tw_hash::{Hash, H256};
 use tw_keypair::{ecdsa, schnorr};
 use tw_memory::Data;
 use tw_proto::BitcoinV2::Proto;
+use tw_utxo::address::silent_payment::SilentPaymentAddress;
 use tw_utxo::context::UtxoContext;
+use tw_utxo::descriptor::Descriptor;
 use tw_utxo::script::Script;
 use tw_utxo::transaction::standard_transaction::builder::OutputBuilder;
 use tw_utxo::transaction::standard_transaction::TransactionOutput;
@@ -50,17 +57,27 @@ impl<'a, Context: UtxoContext> OutputProtobuf<'a, Context> {
                     self.p2tr_dangerous_assume_tweaked(pubkey)
                 },
                 BuilderType::brc20_inscribe(ref inscription) => self.brc20_inscribe(inscription),
+                BuilderType::p2tr_script_tree(ref script_tree) => {
+                    self.p2tr_script_tree(script_tree)
+                },
                 BuilderType::op_return(ref data) => self.op_return(data),
+                BuilderType::runestone(ref runestone) => self.runestone(runestone),
+                BuilderType::rune_etching_commit(ref commitment) => {
+                    self.rune_etching_commit(commitment)
+                },
                 BuilderType::babylon_staking(ref staking) => self.babylon_staking(staking),
                 BuilderType::babylon_staking_op_return(ref op_return) => {
                     self.babylon_staking_op_return(op_return)
                 },
                 BuilderType::babylon_unbonding(ref unbonding) => self.babylon_unbonding(unbonding),
+                BuilderType::p2wsh_timelock(ref script) => self.p2wsh_timelock(script),
+                BuilderType::p2tr_timelock(ref script) => self.p2tr_timelock(script),
                 BuilderType::None => SigningError::err(SigningErrorType::Error_invalid_params)
                     .context("No Output Builder type provided"),
             },
             RecipientType::custom_script_pubkey(ref script) => self.custom_script(script.to_vec()),
             RecipientType::to_address(ref address) => self.recipient_address(address),
+            RecipientType::descriptor(ref descriptor) => self.descriptor(descriptor),
             RecipientType::None => SigningError::err(SigningErrorType::Error_invalid_params)
                 .context("No Output recipient type provided"),
         }
@@ -144,6 +161,38 @@ impl<'a, Context: UtxoContext> OutputProtobuf<'a, Context> {
             .p2tr_script_path(&public_key, merkle_root))
     }
 
+    pub fn p2tr_script_tree(
+        &self,
+        script_tree: &Proto::mod_Output::OutputTaprootScriptTree,
+    ) -> SigningResult<TransactionOutput> {
+        let tree_proto = script_tree
+            .tree
+            .as_ref()
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("No Taproot script tree provided")?;
+        let tree = tap_tree_from_proto(&script_tree.internal_key, tree_proto)?;
+        Ok(self.prepare_builder()?.p2tr_script_tree(&tree))
+    }
+
+    pub fn p2wsh_timelock(
+        &self,
+        script: &Proto::TimelockScript,
+    ) -> SigningResult<TransactionOutput> {
+        let pubkey = timelock_ecdsa_pubkey_from_proto(script)?;
+        let timelock = timelock_from_proto(&script.timelock)?;
+        Ok(self.prepare_builder()?.p2wsh_timelock(&pubkey, timelock))
+    }
+
+    pub fn p2tr_timelock(
+        &self,
+        script: &Proto::TimelockScript,
+    ) -> SigningResult<TransactionOutput> {
+        let (internal_key, spender) = timelock_taproot_keys_from_proto(script)?;
+        let timelock = timelock_from_proto(&script.timelock)?;
+        self.prepare_builder()?
+            .p2tr_timelock(&internal_key, &spender, timelock)
+    }
+
     pub fn brc20_inscribe(
         &self,
         inscription: &Proto::mod_Output::OutputBrc20Inscription,
@@ -162,6 +211,10 @@ impl<'a, Context: UtxoContext> OutputProtobuf<'a, Context> {
     }
 
     pub fn recipient_address(&self, addr_str: &str) -> SigningResult<TransactionOutput> {
+        if self.silent_payment_address(addr_str)?.is_some() {
+            return self.silent_payment();
+        }
+
         let addr = Context::Address::from_str(addr_str)
             .into_tw()
             .context("Invalid recipient address")?;
@@ -171,10 +224,64 @@ impl<'a, Context: UtxoContext> OutputProtobuf<'a, Context> {
             .with_context(|| format!("Error handling {addr_str} output address"))
     }
 
+    /// Parses a silent payment address if the given `addr_str` starts with the chain's silent payment HRP.
+    /// Returns `Ok(None)` if `addr_str` is not supposed to be a silent payment address.
+    pub fn silent_payment_address(
+        &self,
+        addr_str: &str,
+    ) -> SigningResult<Option<SilentPaymentAddress>> {
+        let Some(hrp) = self.chain_info.silent_payment_hrp() else {
+            return Ok(None);
+        };
+
+        let separator = format!("{hrp}1");
+        if !addr_str.to_lowercase().starts_with(&separator) {
+            return Ok(None);
+        }
+
+        SilentPaymentAddress::from_str_checked(addr_str, hrp)
+            .map(Some)
+            .into_tw()
+            .context("Invalid silent payment address")
+    }
+
+    /// Silent payment output keys can only be derived from the private keys of the selected inputs.
+    /// That's why a placeholder P2TR output is built here, which has the same size as the final one,
+    /// and is replaced by [`crate::modules::silent_payments::SilentPaymentOutputs::set_output_keys`] before the transaction is signed.
+    pub fn silent_payment(&self) -> SigningResult<TransactionOutput> {
+        Ok(self
+            .prepare_builder()?
+            .p2tr_dangerous_assume_tweaked(&H256::default()))
+    }
+
+    pub fn descriptor(&self, descriptor: &Proto::Descriptor) -> SigningResult<TransactionOutput> {
+        let script_pubkey = Descriptor::from_str(&descriptor.descriptor)
+            .and_then(|desc| desc.script_pubkey_at(descriptor.index))
+            .context("Invalid output descriptor")?;
+        self.custom_script(script_pubkey.into())
+    }
+
     pub fn op_return(&self, op_return_data: &[u8]) -> SigningResult<TransactionOutput> {
         self.prepare_builder()?.op_return(op_return_data)
     }
 
+    pub fn runestone(&self, runestone: &Proto::Runestone) -> SigningResult<TransactionOutput> {
+        let runestone = runestone_from_proto(runestone).context("Invalid Runestone")?;
+        self.prepare_builder()?.runestone(&runestone)
+    }
+
+    pub fn rune_etching_commit(
+        &self,
+        commitment: &Proto::mod_Output::OutputRuneCommitment,
+    ) -> SigningResult<TransactionOutput> {
+        let public_key = schnorr::PublicKey::try_from(commitment.public_key.as_ref())
+            .into_tw()
+            .context("Invalid rune commitment public key")?;
+        let rune = rune_from_proto(&commitment.rune)?;
+        self.prepare_builder()?
+            .rune_etching_commit(&public_key, &rune)
+    }
+
     /// Tries to convert [`Proto::RedeemScriptOrHash`] to [`Hash<N>`] using a specific `hasher` function.
     /// Please note `P2SH` and `P2WSH` use different hashing functions.
     pub fn redeem_hash_from_proto<const N: usize, F>(
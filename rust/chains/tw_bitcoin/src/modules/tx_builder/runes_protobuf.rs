@@ -0,0 +1,130 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::*;
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::transaction::asset::runes::{
+    Edict, Etching, Rune, RuneId, Runestone, SpacedRune, Terms, MAX_DIVISIBILITY,
+};
+
+pub fn runestone_from_proto(runestone: &Proto::Runestone) -> SigningResult<Runestone> {
+    use Proto::mod_Runestone::OneOfpointer as PointerType;
+
+    let edicts = runestone
+        .edicts
+        .iter()
+        .map(|edict| {
+            Ok(Edict {
+                id: rune_id_from_proto(edict.id.as_ref())?,
+                amount: parse_u128(&edict.amount).context("Invalid rune edict amount")?,
+                output: edict.output,
+            })
+        })
+        .collect::<SigningResult<Vec<_>>>()?;
+
+    let etching = runestone
+        .etching
+        .as_ref()
+        .map(etching_from_proto)
+        .transpose()?;
+
+    let mint = runestone
+        .mint
+        .as_ref()
+        .map(|id| rune_id_from_proto(Some(id)))
+        .transpose()?;
+
+    let pointer = match runestone.pointer {
+        PointerType::pointer_output(output) => Some(output),
+        PointerType::None => None,
+    };
+
+    Ok(Runestone {
+        edicts,
+        etching,
+        mint,
+        pointer,
+    })
+}
+
+pub fn rune_from_proto(rune: &str) -> SigningResult<Rune> {
+    SpacedRune::from_str(rune)
+        .map(|spaced| spaced.rune)
+        .context("Invalid rune name")
+}
+
+fn etching_from_proto(etching: &Proto::RuneEtching) -> SigningResult<Etching> {
+    let (rune, spacers) = if etching.rune.is_empty() {
+        (None, None)
+    } else {
+        let spaced = SpacedRune::from_str(&etching.rune).context("Invalid rune name")?;
+        let spacers = Some(spaced.spacers).filter(|spacers| *spacers != 0);
+        (Some(spaced.rune), spacers)
+    };
+
+    let divisibility = u8::try_from(etching.divisibility)
+        .ok()
+        .filter(|divisibility| *divisibility <= MAX_DIVISIBILITY)
+        .or_tw_err(SigningErrorType::Error_invalid_params)
+        .with_context(|| format!("Rune divisibility can be up to {MAX_DIVISIBILITY}"))?;
+
+    let mut symbol_chars = etching.symbol.chars();
+    let symbol = symbol_chars.next();
+    if symbol_chars.next().is_some() {
+        return SigningError::err(SigningErrorType::Error_invalid_params)
+            .context("Rune symbol must be a single character");
+    }
+
+    let terms = etching
+        .terms
+        .as_ref()
+        .map(|terms| -> SigningResult<Terms> {
+            Ok(Terms {
+                amount: parse_optional_u128(&terms.amount).context("Invalid rune terms amount")?,
+                cap: parse_optional_u128(&terms.cap).context("Invalid rune terms cap")?,
+                height: (non_zero(terms.height_start), non_zero(terms.height_end)),
+                offset: (non_zero(terms.offset_start), non_zero(terms.offset_end)),
+            })
+        })
+        .transpose()?;
+
+    let etching = Etching {
+        divisibility: Some(divisibility).filter(|divisibility| *divisibility != 0),
+        premine: parse_optional_u128(&etching.premine).context("Invalid rune premine")?,
+        rune,
+        spacers,
+        symbol,
+        terms,
+        turbo: etching.turbo,
+    };
+
+    if etching.supply().is_none() {
+        return SigningError::err(SigningErrorType::Error_invalid_params)
+            .context("Rune supply overflows u128");
+    }
+    Ok(etching)
+}
+
+fn rune_id_from_proto(id: Option<&Proto::RuneId>) -> SigningResult<RuneId> {
+    let id = id
+        .or_tw_err(SigningErrorType::Error_invalid_params)
+        .context("No rune ID provided")?;
+    RuneId::new(id.block, id.tx)
+}
+
+fn parse_u128(amount: &str) -> SigningResult<u128> {
+    u128::from_str(amount).tw_err(|_| SigningErrorType::Error_invalid_params)
+}
+
+fn parse_optional_u128(amount: &str) -> SigningResult<Option<u128>> {
+    if amount.is_empty() {
+        return Ok(None);
+    }
+    parse_u128(amount).map(Some)
+}
+
+fn non_zero(value: u64) -> Option<u64> {
+    Some(value).filter(|value| *value != 0)
+}
@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use tw_coin_entry::error::prelude::*;
+use tw_keypair::schnorr;
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::script::tap_tree::{TapLeaf, TapTree, TapTreeBuilder, TAPSCRIPT_LEAF_VERSION};
+use tw_utxo::script::Script;
+
+pub fn tap_tree_from_proto(
+    internal_key: &[u8],
+    tree: &Proto::TaprootScriptTree,
+) -> SigningResult<TapTree> {
+    let internal_key = schnorr::PublicKey::try_from(internal_key)
+        .into_tw()
+        .context("Invalid Taproot internal key")?;
+
+    let builder = if tree.huffman {
+        let weighted_leaves = tree
+            .leaves
+            .iter()
+            .map(|leaf| Ok((leaf.weight, tap_leaf_from_proto(leaf)?)))
+            .collect::<SigningResult<Vec<_>>>()?;
+        TapTreeBuilder::with_huffman(weighted_leaves)?
+    } else {
+        let mut builder = TapTreeBuilder::new();
+        for leaf in tree.leaves.iter() {
+            let depth = u8::try_from(leaf.depth)
+                .tw_err(|_| SigningErrorType::Error_invalid_params)
+                .context("Taproot leaf depth is too large")?;
+            builder = builder.add_leaf(depth, tap_leaf_from_proto(leaf)?);
+        }
+        builder
+    };
+
+    builder.finalize(&internal_key)
+}
+
+pub fn tap_leaf_from_proto(leaf: &Proto::TaprootLeaf) -> SigningResult<TapLeaf> {
+    let script = Script::from(leaf.script.to_vec());
+    let leaf_version = match leaf.leaf_version {
+        0 => TAPSCRIPT_LEAF_VERSION,
+        version => u8::try_from(version)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Taproot leaf version must be a single byte")?,
+    };
+    TapLeaf::new(script, leaf_version)
+}
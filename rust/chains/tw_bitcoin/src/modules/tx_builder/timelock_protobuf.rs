@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::babylon::spending_info::UNSPENDABLE_KEY_PATH;
+use tw_coin_entry::error::prelude::*;
+use tw_keypair::{ecdsa, schnorr};
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::script::timelock::Timelock;
+
+pub fn timelock_from_proto(timelock: &Option<Proto::Timelock>) -> SigningResult<Timelock> {
+    use Proto::mod_Timelock::OneOfvariant as TimelockType;
+
+    let timelock = timelock
+        .as_ref()
+        .or_tw_err(SigningErrorType::Error_invalid_params)
+        .context("No timelock provided")?;
+    match timelock.variant {
+        TimelockType::absolute(locktime) => Timelock::absolute(locktime),
+        TimelockType::relative(sequence) => Timelock::relative(sequence),
+        TimelockType::None => SigningError::err(SigningErrorType::Error_invalid_params)
+            .context("Expected either an absolute or relative timelock"),
+    }
+}
+
+pub fn timelock_ecdsa_pubkey_from_proto(
+    script: &Proto::TimelockScript,
+) -> SigningResult<ecdsa::secp256k1::PublicKey> {
+    ecdsa::secp256k1::PublicKey::try_from(script.public_key.as_ref())
+        .into_tw()
+        .context("Invalid timelock script public key")
+}
+
+/// Returns the Taproot internal key and the timelock script spender.
+/// An unspendable NUMS internal key is used if not specified.
+pub fn timelock_taproot_keys_from_proto(
+    script: &Proto::TimelockScript,
+) -> SigningResult<(schnorr::PublicKey, schnorr::PublicKey)> {
+    let internal_key = if script.internal_key.is_empty() {
+        UNSPENDABLE_KEY_PATH.clone()
+    } else {
+        schnorr::PublicKey::try_from(script.internal_key.as_ref())
+            .into_tw()
+            .context("Invalid Taproot internal key")?
+    };
+    let spender = schnorr::PublicKey::try_from(script.public_key.as_ref())
+        .into_tw()
+        .context("Invalid timelock script public key")?;
+    Ok((internal_key, spender))
+}
@@ -4,7 +4,12 @@
 
 use crate::babylon::proto_builder::utxo_protobuf::BabylonUtxoProtobuf;
 use crate::modules::tx_builder::public_keys::PublicKeys;
+use crate::modules::tx_builder::runes_protobuf::rune_from_proto;
 use crate::modules::tx_builder::script_parser::{StandardScript, StandardScriptParser};
+use crate::modules::tx_builder::tap_tree_protobuf::{tap_leaf_from_proto, tap_tree_from_proto};
+use crate::modules::tx_builder::timelock_protobuf::{
+    timelock_ecdsa_pubkey_from_proto, timelock_from_proto, timelock_taproot_keys_from_proto,
+};
 use crate::modules::tx_builder::BitcoinChainInfo;
 use std::marker::PhantomData;
 use std::str::FromStr;
@@ -16,8 +21,11 @@ use tw_misc::traits::ToBytesVec;
 use tw_proto::BitcoinV2::Proto;
 use tw_proto::Utxo::Proto as UtxoProto;
 use tw_utxo::context::UtxoContext;
+use tw_utxo::descriptor::{Descriptor, DescriptorType};
+use tw_utxo::fee::satisfaction_weight::SatisfactionWeight;
 use tw_utxo::script::Script;
 use tw_utxo::sighash::SighashType;
+use tw_utxo::spending_data::standard_constructor::TaprootWitnessItem;
 use tw_utxo::transaction::standard_transaction::builder::UtxoBuilder;
 use tw_utxo::transaction::standard_transaction::TransactionInput;
 use tw_utxo::transaction::transaction_parts::OutPoint;
@@ -58,6 +66,12 @@ impl<'a, Context: UtxoContext> UtxoProtobuf<'a, Context> {
                 BuilderType::p2tr_key_path(ref key_path) => self.p2tr_key_path(key_path),
                 // BuilderType::p2tr_script_path(ref script) => self.p2tr_script_path(script),
                 BuilderType::brc20_inscribe(ref inscription) => self.brc20_inscribe(inscription),
+                BuilderType::p2tr_script_tree(ref script_tree) => {
+                    self.p2tr_script_tree(script_tree)
+                },
+                BuilderType::rune_etching_reveal(ref commitment) => {
+                    self.rune_etching_reveal(commitment)
+                },
                 BuilderType::babylon_staking_timelock_path(ref timelock) => {
                     self.babylon_staking_timelock(timelock)
                 },
@@ -73,11 +87,14 @@ impl<'a, Context: UtxoContext> UtxoProtobuf<'a, Context> {
                 BuilderType::babylon_unbonding_slashing_path(ref slashing) => {
                     self.babylon_unbonding_slashing(slashing)
                 },
+                BuilderType::p2wsh_timelock(ref script) => self.p2wsh_timelock(script),
+                BuilderType::p2tr_timelock(ref script) => self.p2tr_timelock(script),
                 BuilderType::None => SigningError::err(SigningErrorType::Error_invalid_params)
                     .context("No Input Builder type provided"),
             },
             ScriptType::script_data(ref script) => self.custom_script(script.to_vec()),
             ScriptType::receiver_address(ref address) => self.recipient_address(address),
+            ScriptType::descriptor(ref descriptor) => self.descriptor(descriptor),
             ScriptType::None => SigningError::err(SigningErrorType::Error_invalid_params)
                 .context("No Input claiming script provided"),
         }
@@ -147,14 +164,92 @@ impl<'a, Context: UtxoContext> UtxoProtobuf<'a, Context> {
         )
     }
 
+    pub fn rune_etching_reveal(
+        &self,
+        commitment: &Proto::mod_Input::InputRuneCommitment,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let public_key = schnorr::PublicKey::try_from(commitment.public_key.as_ref())
+            .into_tw()
+            .context("Invalid rune commitment public key")?;
+        let rune = rune_from_proto(&commitment.rune)?;
+        self.prepare_builder()?
+            .rune_etching_reveal(&public_key, &rune)
+    }
+
+    pub fn p2tr_script_tree(
+        &self,
+        script_tree: &Proto::mod_Input::InputTaprootScriptTree,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        use Proto::mod_Input::mod_TaprootWitnessItem::OneOfvariant as WitnessItemType;
+
+        let tree_proto = script_tree
+            .tree
+            .as_ref()
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("No Taproot script tree provided")?;
+        let tree = tap_tree_from_proto(&script_tree.internal_key, tree_proto)?;
+
+        let leaf_proto = tree_proto
+            .leaves
+            .get(script_tree.leaf_index as usize)
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("'InputTaprootScriptTree.leaf_index' is out of bounds")?;
+        let leaf = tap_leaf_from_proto(leaf_proto)?;
+
+        let spender = schnorr::PublicKey::try_from(script_tree.spender_public_key.as_ref())
+            .into_tw()
+            .context("Invalid Taproot spender public key")?;
+
+        let witness_items = script_tree
+            .witness_items
+            .iter()
+            .map(|item| match item.variant {
+                WitnessItemType::data(ref data) => Ok(TaprootWitnessItem::Data(data.to_vec())),
+                WitnessItemType::signature(_) => Ok(TaprootWitnessItem::Signature),
+                WitnessItemType::None => SigningError::err(SigningErrorType::Error_invalid_params)
+                    .context("No Taproot witness item provided"),
+            })
+            .collect::<SigningResult<Vec<_>>>()?;
+
+        self.prepare_builder()?
+            .p2tr_script_tree_leaf(&tree, &leaf, &spender, witness_items)
+    }
+
+    pub fn p2wsh_timelock(
+        &self,
+        script: &Proto::TimelockScript,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let pubkey = timelock_ecdsa_pubkey_from_proto(script)?;
+        let timelock = timelock_from_proto(&script.timelock)?;
+        self.prepare_builder()?.p2wsh_timelock(&pubkey, timelock)
+    }
+
+    pub fn p2tr_timelock(
+        &self,
+        script: &Proto::TimelockScript,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let (internal_key, spender) = timelock_taproot_keys_from_proto(script)?;
+        let timelock = timelock_from_proto(&script.timelock)?;
+        self.prepare_builder()?
+            .p2tr_timelock(&internal_key, &spender, timelock)
+    }
+
     pub fn custom_script(
         &self,
         script_data: Data,
     ) -> SigningResult<(TransactionInput, UtxoToSign)> {
         let script = Script::from(script_data);
         let builder = self.prepare_builder()?;
+        let has_satisfaction_weight = self.input.satisfaction_weight.is_some();
+
+        let standard_script = match StandardScriptParser.parse(&script) {
+            Ok(standard_script) => standard_script,
+            // A non-standard UTXO can be planned if its satisfaction weight is known.
+            Err(_) if has_satisfaction_weight => return builder.foreign(script),
+            Err(e) => return Err(e),
+        };
 
-        match StandardScriptParser.parse(&script)? {
+        match standard_script {
             StandardScript::P2PK(pk) => builder.p2pk(&pk),
             StandardScript::P2PKH(pubkey_hash) => {
                 let pubkey = self.public_keys.get_ecdsa_public_key(&pubkey_hash)?;
@@ -167,9 +262,13 @@ impl<'a, Context: UtxoContext> UtxoProtobuf<'a, Context> {
             StandardScript::P2TR(tweaked_pubkey) => {
                 builder.p2tr_key_path_with_tweaked_pubkey(&tweaked_pubkey)
             },
+            StandardScript::P2SH(_) | StandardScript::P2WSH(_) if has_satisfaction_weight => {
+                builder.foreign(script)
+            },
             StandardScript::P2SH(_) | StandardScript::P2WSH(_) => {
-                SigningError::err(SigningErrorType::Error_not_supported)
-                    .context("P2SH and P2WSH scriptPubkey's are not supported yet")
+                SigningError::err(SigningErrorType::Error_not_supported).context(
+                    "P2SH and P2WSH scriptPubkey's are not supported yet. Consider specifying 'Input.satisfactionWeight' to plan the transaction",
+                )
             },
             StandardScript::OpReturn(_) => SigningError::err(SigningErrorType::Error_invalid_utxo)
                 .context("Cannot spend an OP_RETURN output"),
@@ -189,6 +288,60 @@ impl<'a, Context: UtxoContext> UtxoProtobuf<'a, Context> {
             .with_context(|| format!("Error handling {addr_str} input recipient"))
     }
 
+    pub fn descriptor(
+        &self,
+        descriptor: &Proto::Descriptor,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let desc =
+            Descriptor::from_str(&descriptor.descriptor).context("Invalid input descriptor")?;
+        let index = descriptor.index;
+        let builder = self.prepare_builder()?;
+
+        match desc.descriptor_type() {
+            DescriptorType::Pkh(key) => {
+                // `pkh()` may contain an uncompressed public key.
+                let pubkey_bytes = key.derive_public_key(index)?;
+                let pubkey = ecdsa::secp256k1::PublicKey::try_from(pubkey_bytes.as_slice())?;
+                if pubkey_bytes.len() == ecdsa::secp256k1::PublicKey::UNCOMPRESSED {
+                    builder.p2pkh_uncompressed(&pubkey)
+                } else {
+                    builder.p2pkh(&pubkey)
+                }
+            },
+            DescriptorType::Wpkh(key) => {
+                let pubkey = ecdsa::secp256k1::PublicKey::try_from(
+                    key.derive_compressed(index)?.as_slice(),
+                )?;
+                builder.p2wpkh(&pubkey)
+            },
+            DescriptorType::ShWpkh(key) => {
+                let pubkey = ecdsa::secp256k1::PublicKey::try_from(
+                    key.derive_compressed(index)?.as_slice(),
+                )?;
+                builder.p2sh_p2wpkh(&pubkey)
+            },
+            // Multisig requires signatures of several parties, so the UTXO can be used to plan a transaction only.
+            DescriptorType::WshMulti { .. } if self.input.satisfaction_weight.is_some() => {
+                builder.foreign(desc.script_pubkey_at(index)?)
+            },
+            DescriptorType::WshMulti { .. } => {
+                SigningError::err(SigningErrorType::Error_not_supported).context(
+                    "'wsh(multi())' input descriptors cannot be signed. Consider specifying 'Input.satisfactionWeight' to plan the transaction",
+                )
+            },
+            // The UTXO is spent via the key path, while the script tree only tweaks the output key.
+            DescriptorType::Tr { internal_key, .. } => {
+                let pubkey = schnorr::PublicKey::try_from(
+                    internal_key.derive_compressed(index)?.as_slice(),
+                )?;
+                match desc.tap_tree_merkle_root_at(index)? {
+                    Some(merkle_root) => builder.p2tr_key_path_with_merkle_root(&pubkey, merkle_root),
+                    None => builder.p2tr_key_path(&pubkey),
+                }
+            },
+        }
+    }
+
     pub fn prepare_builder(&self) -> SigningResult<UtxoBuilder> {
         let OutPoint { hash, index } = parse_out_point(&self.input.out_point)?;
         let sighash_ty = self.sighash_ty()?;
@@ -206,12 +359,19 @@ impl<'a, Context: UtxoContext> UtxoProtobuf<'a, Context> {
             // Use the default 0xFFFFFFFF sequence value if not specified.
             .unwrap_or(u32::MAX);
 
-        Ok(UtxoBuilder::default()
+        let builder = UtxoBuilder::default()
             .prev_txid(hash)
             .prev_index(index)
             .sequence(sequence)
             .amount(self.input.value)
-            .sighash_type(sighash_ty))
+            .sighash_type(sighash_ty);
+
+        match self.input.satisfaction_weight {
+            Some(ref weight) => {
+                Ok(builder.satisfaction_weight(satisfaction_weight_from_proto(weight)))
+            },
+            None => Ok(builder),
+        }
     }
 
     pub fn sighash_ty(&self) -> SigningResult<SighashType> {
@@ -261,3 +421,12 @@ pub fn parse_out_point(maybe_out_point: &Option<UtxoProto::OutPoint>) -> Signing
         index: out_point.vout,
     })
 }
+
+pub fn satisfaction_weight_from_proto(
+    proto: &Proto::mod_Input::SatisfactionWeight,
+) -> SatisfactionWeight {
+    SatisfactionWeight {
+        script_sig_size: proto.script_sig_size as usize,
+        max_witness_size: proto.max_witness_size as usize,
+    }
+}
@@ -7,9 +7,17 @@ use bitcoin::taproot::TaprootBuilder;
 use bitcoin::ScriptBuf;
 use secp256k1::{PublicKey, SECP256K1};
 use tw_bitcoin::babylon::conditions;
+use tw_bitcoin::babylon::delegation::{BabylonDelegation, BabylonDelegationParams};
 use tw_bitcoin::babylon::multi_sig_ordered::MultiSigOrderedKeys;
+use tw_bitcoin::babylon::tx_builder::BabylonStakingParams;
 use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_hash::H256;
 use tw_keypair::schnorr;
+use tw_utxo::encode::encode;
+use tw_utxo::script::Script;
+use tw_utxo::sighash::{SighashBase, SighashType};
+use tw_utxo::signature::BitcoinSchnorrSignature;
+use tw_utxo::transaction::transaction_interface::TransactionInterface;
 
 const BABYLON_MERKLE_ROOTS: &str = include_str!("data/babylon_staking_merkle_roots.json");
 const BABYLON_TRANSACTIONS: &str = include_str!("data/babylon_staking_transactions.json");
@@ -25,6 +33,11 @@ fn parse_pks(value: &serde_json::Value) -> Vec<schnorr::XOnlyPublicKey> {
     value.as_array().unwrap().iter().map(parse_pk).collect()
 }
 
+/// `OP_RETURN "babylon"`
+const SLASHING_SCRIPT_PUBKEY: &str = "6a07626162796c6f6e";
+const SLASHING_RATE_BPS: u32 = 1000;
+const SLASHING_FEE: i64 = 1000;
+
 #[test]
 fn test_babylon_scripts() {
     let input: serde_json::Value = serde_json::from_str(BABYLON_TRANSACTIONS).unwrap();
@@ -116,3 +129,163 @@ fn test_babylon_staking_taproot_tree() {
         assert_eq!(actual_root.to_hex(), root_hash);
     }
 }
+
+#[test]
+fn test_babylon_delegation_unbonding_transaction() {
+    let input: serde_json::Value = serde_json::from_str(BABYLON_TRANSACTIONS).unwrap();
+
+    for test in input.as_array().unwrap() {
+        let name = test["name"].as_str().unwrap();
+        let params = &test["parameters"];
+        let expected = &test["expected"];
+
+        let covenants = MultiSigOrderedKeys::new(
+            parse_pks(&params["covenant_public_keys"]),
+            params["covenant_quorum"].as_u64().unwrap() as u32,
+        )
+        .unwrap();
+        let finality_providers =
+            MultiSigOrderedKeys::new(parse_pks(&params["finality_provider_public_keys"]), 1)
+                .unwrap();
+        let staker = params["staker_public_key"]
+            .as_str()
+            .unwrap()
+            .decode_hex()
+            .unwrap();
+        let staking_txid = params["staking_tx_hash"]
+            .as_str()
+            .unwrap()
+            .decode_hex()
+            .unwrap();
+
+        let delegation = BabylonDelegation::new(BabylonDelegationParams {
+            staking: BabylonStakingParams {
+                staker: schnorr::PublicKey::try_from(staker.as_slice()).unwrap(),
+                staking_locktime: params["staking_time"].as_u64().unwrap() as u16,
+                finality_providers,
+                covenants,
+            },
+            unbonding_locktime: params["unbonding_time"].as_u64().unwrap() as u16,
+            staking_txid: H256::try_from(staking_txid.as_slice()).unwrap().rev(),
+            staking_output_index: params["staking_output_index"].as_u64().unwrap() as u32,
+            staking_value: params["staking_value"].as_i64().unwrap(),
+            unbonding_fee: params["unbonding_fee"].as_i64().unwrap(),
+            slashing_script_pubkey: Script::from(SLASHING_SCRIPT_PUBKEY.decode_hex().unwrap()),
+            slashing_rate_bps: SLASHING_RATE_BPS,
+            slashing_fee: SLASHING_FEE,
+        })
+        .unwrap();
+
+        let unbonding_tx = delegation.unbonding_tx().unwrap();
+        assert_eq!(
+            encode(unbonding_tx.transaction()).to_hex(),
+            expected["unbonding_transaction_hex"].as_str().unwrap(),
+            "Test '{}' Invalid unbonding transaction",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_babylon_delegation_sign_and_compile() {
+    const PRIVATE_KEY: &str = "43fe106cf05afdd8c39476651ad3729fc71bfe2ff4dfbc3e8654b851677a19b2";
+    const FINALITY_PROVIDER: &str =
+        "03d5a0bb72d71993e435d6c5a70e2aa4db500a62cfaae33c56050deefee64ec0";
+    const COVENANT_COMMITTEES: [&str; 3] = [
+        "0249766ccd9e3cd94343e2040474a77fb37cdfd30530d05f9f1e96ae1e2102c86e",
+        "0276d1ae01f8fb6bf30108731c884cddcf57ef6eef2d9d9559e130894e0e40c62c",
+        "0217921cf156ccb4e73d428f996ed11b245313e37e27c978ac4d2cc21eca4672e4",
+    ];
+    const COVENANT_1_SIGNATURE: &str = "f5c08530d9f48ab1b3bc2e3f5fed043a82cbcdf1f10af4b1774ac943392af108f6fd071157474505dea939ede9f7b07157e7274fb53e5b3d6e88f6fb1d23e790";
+    const COVENANT_2_SIGNATURE: &str = "57f09c746aa784e4aebe8002d9068755f7d22262af71fdb75a840793fab6871e5ab387081dd6e31dd0e1ddfdfa11e298469321171eba722e79efaf2dbdb89905";
+
+    let staker_private = schnorr::PrivateKey::try_from(PRIVATE_KEY)
+        .unwrap()
+        .no_aux_rand();
+    let covenants = MultiSigOrderedKeys::new(
+        COVENANT_COMMITTEES
+            .iter()
+            .map(|pk| parse_pk(&(*pk).into()))
+            .collect(),
+        2,
+    )
+    .unwrap();
+    let finality_providers =
+        MultiSigOrderedKeys::new(vec![parse_pk(&FINALITY_PROVIDER.into())], 1).unwrap();
+    let staking_txid =
+        H256::from("0942545a1e324c713933350bd234e708b4f6092406dc45a97a61ce627a095e1b").rev();
+
+    let delegation = BabylonDelegation::new(BabylonDelegationParams {
+        staking: BabylonStakingParams {
+            staker: staker_private.public(),
+            staking_locktime: 1000,
+            finality_providers,
+            covenants,
+        },
+        unbonding_locktime: 5,
+        staking_txid,
+        staking_output_index: 0,
+        staking_value: 30_000,
+        unbonding_fee: 3000,
+        slashing_script_pubkey: Script::from(SLASHING_SCRIPT_PUBKEY.decode_hex().unwrap()),
+        slashing_rate_bps: SLASHING_RATE_BPS,
+        slashing_fee: SLASHING_FEE,
+    })
+    .unwrap();
+
+    let staking_slashing_tx = delegation.staking_slashing_tx().unwrap();
+    assert_eq!(
+        encode(staking_slashing_tx.transaction()).to_hex(),
+        "02000000011b5e097a62ce617aa945dc062409f6b408e734d20b353339714c321e5a5442090000000000ffffffff02b80b000000000000096a07626162796c6f6e90650000000000002251208d0ba49897cee799fb10906ac9e15ad4ee4a552ace0e6dec8db67e7b1928eee200000000"
+    );
+
+    let signatures = delegation.sign(&staker_private).unwrap();
+    // Same signature as in `test_bitcoin_babylon_unbond`.
+    assert_eq!(
+        signatures.unbonding.bytes().to_hex(),
+        "f3f6ff2a8b1aaf43cc7f6bb40b3ccf1aca14dd190a70f06528f594d26281e5c1afb792c454b5e9eda5e8f1a2723ebe463c5cf559710a9dbde6f4c329f1a64ec9"
+    );
+    assert_eq!(
+        signatures.staking_slashing.bytes().to_hex(),
+        "ad82796e76c866ec660425967343c29e51fee70c1bd46043a87661c54661094bf59dc6bff0fa50ffaf6a6564d7cd244fdba45456f52fe4cfee05e19f10589c58"
+    );
+    assert_eq!(
+        signatures.unbonding_slashing.bytes().to_hex(),
+        "d417f627ebab23671f77fb99bc525cf012826e912d6474c064f618c60433ff6c2b9f3bff1a8174bc1f8d2eb632e1e92d6df18f74139c9dfdb76cf39149acca7e"
+    );
+
+    let sighash_ty = SighashType::from_base(SighashBase::All);
+    let covenant_signature = |pk: &str, sig: &str| {
+        let sig = schnorr::Signature::try_from(sig.decode_hex().unwrap().as_slice()).unwrap();
+        (
+            parse_pk(&pk.into()),
+            BitcoinSchnorrSignature::new(sig, sighash_ty).unwrap(),
+        )
+    };
+    let covenant_signatures = [
+        covenant_signature(COVENANT_COMMITTEES[1], COVENANT_1_SIGNATURE),
+        covenant_signature(COVENANT_COMMITTEES[2], COVENANT_2_SIGNATURE),
+    ];
+
+    let unbonding_tx = delegation
+        .compile_unbonding(&signatures.unbonding, &covenant_signatures)
+        .unwrap();
+    // Same transaction as in `test_bitcoin_babylon_unbond`.
+    assert_eq!(
+        unbonding_tx.txid().to_hex(),
+        "d7f7e34c4c3996f45f8a3ec291a27a9d5a3a636699d89c3ab22485295263fe77"
+    );
+    assert_eq!(
+        encode(&unbonding_tx).to_hex(),
+        "020000000001011b5e097a62ce617aa945dc062409f6b408e734d20b353339714c321e5a5442090000000000ffffffff0178690000000000002251206a19e05bf781849787564097f8986b84270b35f6dead370b0846b0c6ff4b0a5f0640f5c08530d9f48ab1b3bc2e3f5fed043a82cbcdf1f10af4b1774ac943392af108f6fd071157474505dea939ede9f7b07157e7274fb53e5b3d6e88f6fb1d23e790004057f09c746aa784e4aebe8002d9068755f7d22262af71fdb75a840793fab6871e5ab387081dd6e31dd0e1ddfdfa11e298469321171eba722e79efaf2dbdb8990540f3f6ff2a8b1aaf43cc7f6bb40b3ccf1aca14dd190a70f06528f594d26281e5c1afb792c454b5e9eda5e8f1a2723ebe463c5cf559710a9dbde6f4c329f1a64ec98a209789cdd12bc90bbd73445718f8a709956eb3cce362716a3425610abb75ea1132ad2017921cf156ccb4e73d428f996ed11b245313e37e27c978ac4d2cc21eca4672e4ac2049766ccd9e3cd94343e2040474a77fb37cdfd30530d05f9f1e96ae1e2102c86eba2076d1ae01f8fb6bf30108731c884cddcf57ef6eef2d9d9559e130894e0e40c62cba529c61c050929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac01a868c60f4ea18d9593a742735d726a3c63569a36fd50fe15d291fd1e3538429e494fdcafb7ee38a6636ab068f83d359e89af3b37ad3ed15b32354917f72254900000000"
+    );
+
+    // Not enough covenant signatures.
+    delegation
+        .compile_unbonding(&signatures.unbonding, &covenant_signatures[..1])
+        .unwrap_err();
+    // Staker signature of a different transaction.
+    delegation
+        .compile_unbonding(&signatures.staking_slashing, &covenant_signatures)
+        .unwrap_err();
+}
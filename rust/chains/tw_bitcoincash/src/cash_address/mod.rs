@@ -23,6 +23,37 @@ mod unchecked;
 pub enum CashAddressType {
     P2PKH,
     P2SH,
+    /// Token-aware P2PKH address, i.e. the receiver's wallet supports CashTokens.
+    P2PKHWithTokens,
+    /// Token-aware P2SH address, i.e. the receiver's wallet supports CashTokens.
+    P2SHWithTokens,
+}
+
+impl CashAddressType {
+    pub fn is_token_aware(self) -> bool {
+        matches!(
+            self,
+            CashAddressType::P2PKHWithTokens | CashAddressType::P2SHWithTokens
+        )
+    }
+
+    /// Returns the same address type that signals whether the receiver supports CashTokens.
+    pub fn with_tokens(self, token_aware: bool) -> CashAddressType {
+        match (self, token_aware) {
+            (CashAddressType::P2PKH | CashAddressType::P2PKHWithTokens, false) => {
+                CashAddressType::P2PKH
+            },
+            (CashAddressType::P2PKH | CashAddressType::P2PKHWithTokens, true) => {
+                CashAddressType::P2PKHWithTokens
+            },
+            (CashAddressType::P2SH | CashAddressType::P2SHWithTokens, false) => {
+                CashAddressType::P2SH
+            },
+            (CashAddressType::P2SH | CashAddressType::P2SHWithTokens, true) => {
+                CashAddressType::P2SHWithTokens
+            },
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -81,10 +112,36 @@ impl CashAddress {
         unchecked::UncheckedCashAddress::from_str(address_str)?.partly_checked()
     }
 
+    pub fn ty(&self) -> CashAddressType {
+        self.ty
+    }
+
+    /// Whether the address signals that the receiver's wallet supports CashTokens.
+    pub fn is_token_aware(&self) -> bool {
+        self.ty.is_token_aware()
+    }
+
+    /// Converts the address to a token-aware (`z`/`r` payload) or a regular (`q`/`p` payload) address.
+    pub fn with_tokens(&self, token_aware: bool) -> AddressResult<CashAddress> {
+        let ty = self.ty.with_tokens(token_aware);
+        let address_str = Self::encode(&self.hrp, ty, &self.key_hash)?;
+        Ok(CashAddress {
+            hrp: self.hrp.clone(),
+            ty,
+            key_hash: self.key_hash,
+            address_str,
+        })
+    }
+
+    /// Please note that token-aware addresses are converted to the same legacy addresses as regular ones.
     pub fn to_legacy(&self, p2pkh_prefix: u8, p2sh_prefix: u8) -> AddressResult<LegacyAddress> {
         match self.ty {
-            CashAddressType::P2PKH => LegacyAddress::new(p2pkh_prefix, self.key_hash.as_slice()),
-            CashAddressType::P2SH => LegacyAddress::new(p2sh_prefix, self.key_hash.as_slice()),
+            CashAddressType::P2PKH | CashAddressType::P2PKHWithTokens => {
+                LegacyAddress::new(p2pkh_prefix, self.key_hash.as_slice())
+            },
+            CashAddressType::P2SH | CashAddressType::P2SHWithTokens => {
+                LegacyAddress::new(p2sh_prefix, self.key_hash.as_slice())
+            },
         }
     }
 
@@ -110,6 +167,9 @@ impl CashAddress {
         let en_address_type: u8 = match ty {
             CashAddressType::P2PKH => 0,
             CashAddressType::P2SH => 1,
+            // https://github.com/bitjson/cashtokens#cashaddress-token-support
+            CashAddressType::P2PKHWithTokens => 2,
+            CashAddressType::P2SHWithTokens => 3,
         };
 
         // Please note we always serialize the key hash length as 0 here,
@@ -189,6 +249,44 @@ mod tests {
                 address_str: "bchtest:qqjr7yu573z4faxw8ltgvjwpntwys08fysk07zmvce".to_string(),
             },
         );
+
+        test_address_from_str_impl(
+            "bitcoincash:zrplwyx7kueqkrh6dmd3fclta6u32hafp5vejgk3ee",
+            CashAddress {
+                hrp: "bitcoincash".to_string(),
+                ty: CashAddressType::P2PKHWithTokens,
+                key_hash: H160::from("c3f710deb7320b0efa6edb14e3ebeeb9155fa90d"),
+                address_str: "bitcoincash:zrplwyx7kueqkrh6dmd3fclta6u32hafp5vejgk3ee".to_string(),
+            },
+        );
+
+        test_address_from_str_impl(
+            "bitcoincash:rq4ql3ph6738xuv2cycduvkpu4rdwqge5qdk4n8w96",
+            CashAddress {
+                hrp: "bitcoincash".to_string(),
+                ty: CashAddressType::P2SHWithTokens,
+                key_hash: H160::from("2a0fc437d7a273718ac130de32c1e546d70119a0"),
+                address_str: "bitcoincash:rq4ql3ph6738xuv2cycduvkpu4rdwqge5qdk4n8w96".to_string(),
+            },
+        );
+    }
+
+    #[test]
+    fn test_cash_address_with_tokens() {
+        let regular = CashAddress::from_str_with_hrp(
+            "bchtest:qqjr7yu573z4faxw8ltgvjwpntwys08fysk07zmvce",
+            "bchtest".to_string(),
+        )
+        .unwrap();
+        assert!(!regular.is_token_aware());
+
+        let token_aware = regular.with_tokens(true).unwrap();
+        assert!(token_aware.is_token_aware());
+        assert_eq!(
+            token_aware.to_string(),
+            "bchtest:zqjr7yu573z4faxw8ltgvjwpntwys08fys39du4282"
+        );
+        assert_eq!(token_aware.with_tokens(false).unwrap(), regular);
     }
 
     struct AddressToLegacyTest<'a> {
@@ -237,6 +335,15 @@ mod tests {
             legacy: "31nwvkZwyPdgzjBJZXfDmSWsC4ZLKpYyUw",
         });
 
+        // Token-aware P2PKH
+        test_address_to_legacy_impl(AddressToLegacyTest {
+            p2pkh_prefix: 0,
+            p2sh_prefixes: 5,
+            hrp: "bitcoincash",
+            address: "bitcoincash:zrplwyx7kueqkrh6dmd3fclta6u32hafp5vejgk3ee",
+            legacy: "1JsAjr6d21j9T8EMsYnQ6GXf1mM523JAv1",
+        });
+
         // P2PKH testnet
         test_address_to_legacy_impl(AddressToLegacyTest {
             p2pkh_prefix: 111,
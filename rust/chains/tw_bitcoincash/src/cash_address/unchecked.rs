@@ -139,9 +139,12 @@ impl FromStr for UncheckedCashAddress {
 }
 
 fn get_address_type(version_byte: u8) -> AddressResult<CashAddressType> {
-    match version_byte & 8 {
+    // The type bits are followed by 3 bits of the hash size.
+    match (version_byte >> 3) & 0x0f {
         0 => Ok(CashAddressType::P2PKH),
-        8 => Ok(CashAddressType::P2SH),
+        1 => Ok(CashAddressType::P2SH),
+        2 => Ok(CashAddressType::P2PKHWithTokens),
+        3 => Ok(CashAddressType::P2SHWithTokens),
         _ => Err(AddressError::UnexpectedAddressPrefix),
     }
 }
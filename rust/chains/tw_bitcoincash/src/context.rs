@@ -3,10 +3,10 @@
 // Copyright © 2017 Trust Wallet.
 
 use crate::address::Address;
+use crate::modules::signing_request::BitcoinCashSigningRequestBuilder;
 use tw_bitcoin::context::BitcoinSigningContext;
 use tw_bitcoin::modules::protobuf_builder::standard_protobuf_builder::StandardProtobufBuilder;
 use tw_bitcoin::modules::psbt_request::standard_psbt_request_builder::StandardPsbtRequestBuilder;
-use tw_bitcoin::modules::signing_request::standard_signing_request::StandardSigningRequestBuilder;
 use tw_coin_entry::error::prelude::*;
 use tw_utxo::context::{AddressPrefixes, UtxoContext};
 use tw_utxo::fee::fee_estimator::StandardFeeEstimator;
@@ -39,7 +39,7 @@ impl UtxoContext for BitcoinCashContext {
 }
 
 impl BitcoinSigningContext for BitcoinCashContext {
-    type SigningRequestBuilder = StandardSigningRequestBuilder;
+    type SigningRequestBuilder = BitcoinCashSigningRequestBuilder;
     type ProtobufBuilder = StandardProtobufBuilder;
     type PsbtRequestBuilder = StandardPsbtRequestBuilder;
 }
@@ -6,3 +6,4 @@ pub mod address;
 pub mod cash_address;
 pub mod context;
 pub mod entry;
+pub mod modules;
@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use tw_bitcoin::modules::signing_request::standard_signing_request::StandardSigningRequestBuilder;
+use tw_bitcoin::modules::signing_request::SigningRequestBuilder;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::context::UtxoContext;
+use tw_utxo::fee::fee_estimator::StandardFeeEstimator;
+use tw_utxo::modules::tx_planner::{PlanRequest, RequestType};
+use tw_utxo::transaction::asset::cash_token::{CashToken, NftCapability, NonFungibleToken};
+use tw_utxo::transaction::standard_transaction::Transaction;
+use tw_utxo::transaction::transaction_interface::TxOutputInterface;
+use tw_utxo::transaction::unsigned_transaction::UnsignedTransaction;
+
+/// Builds a standard signing request and attaches CashTokens to the UTXOs and outputs.
+pub struct BitcoinCashSigningRequestBuilder;
+
+impl<Context> SigningRequestBuilder<Context> for BitcoinCashSigningRequestBuilder
+where
+    Context:
+        UtxoContext<Transaction = Transaction, FeeEstimator = StandardFeeEstimator<Transaction>>,
+{
+    fn build(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput,
+        transaction_builder: &Proto::TransactionBuilder,
+    ) -> SigningResult<PlanRequest<Context>> {
+        let mut request = <StandardSigningRequestBuilder as SigningRequestBuilder<Context>>::build(
+            coin,
+            input,
+            transaction_builder,
+        )?;
+
+        if let Some(ref change_output) = transaction_builder.change_output {
+            if change_output.cash_token.is_some() {
+                return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                    "Remaining tokens are sent to the change output automatically, 'changeOutput.cashToken' must not be set",
+                );
+            }
+        }
+
+        match request.ty {
+            RequestType::SendMax {
+                ref mut unsigned_tx,
+            } => {
+                let max_output = match transaction_builder.max_amount_output {
+                    Some(ref max_output) => std::slice::from_ref(max_output),
+                    None => &[],
+                };
+                Self::attach_tokens(unsigned_tx, &transaction_builder.inputs, max_output)?;
+            },
+            RequestType::SendExact {
+                ref mut unsigned_tx,
+                ..
+            } => {
+                check_token_aware_change(transaction_builder)?;
+                Self::attach_tokens(
+                    unsigned_tx,
+                    &transaction_builder.inputs,
+                    &transaction_builder.outputs,
+                )?;
+            },
+        }
+
+        Ok(request)
+    }
+}
+
+impl BitcoinCashSigningRequestBuilder {
+    /// Prepends token prefixes to `scriptPubkey`s of the UTXOs and outputs holding CashTokens.
+    /// UTXOs and outputs are expected to be in the same order as in the Protobuf.
+    fn attach_tokens(
+        unsigned_tx: &mut UnsignedTransaction<Transaction>,
+        inputs: &[Proto::Input],
+        outputs: &[Proto::Output],
+    ) -> SigningResult<()> {
+        let mut utxo_args = unsigned_tx.input_args().to_vec();
+        for (utxo_arg, utxo_proto) in utxo_args.iter_mut().zip(inputs) {
+            let Some(ref token_proto) = utxo_proto.cash_token else {
+                continue;
+            };
+            let token = cash_token_from_proto(token_proto).context("Invalid 'Input.cashToken'")?;
+            utxo_arg.prevout_script_pubkey = token.prepend_to(&utxo_arg.prevout_script_pubkey)?;
+        }
+        let utxos = unsigned_tx.inputs().to_vec();
+        unsigned_tx.set_inputs(utxos, utxo_args)?;
+
+        for (output, output_proto) in unsigned_tx.outputs_mut().iter_mut().zip(outputs) {
+            let Some(ref token_proto) = output_proto.cash_token else {
+                continue;
+            };
+            check_token_aware_recipient(output_proto)?;
+
+            let token = cash_token_from_proto(token_proto).context("Invalid 'Output.cashToken'")?;
+            let script_pubkey = token.prepend_to(output.script_pubkey())?;
+            output.set_script_pubkey(script_pubkey);
+        }
+
+        Ok(())
+    }
+}
+
+pub fn cash_token_from_proto(proto: &Proto::CashToken) -> SigningResult<CashToken> {
+    let category = H256::try_from(proto.category.as_ref())
+        .tw_err(|_| SigningErrorType::Error_invalid_params)
+        .context("Invalid token category, expected 32 bytes")?;
+
+    let nft = proto.nft.as_ref().map(|nft| NonFungibleToken {
+        capability: match nft.capability {
+            Proto::CashTokenCapability::Immutable => NftCapability::Immutable,
+            Proto::CashTokenCapability::Mutable => NftCapability::Mutable,
+            Proto::CashTokenCapability::Minting => NftCapability::Minting,
+        },
+        commitment: nft.commitment.to_vec(),
+    });
+
+    let token = CashToken {
+        category,
+        nft,
+        amount: proto.amount,
+    };
+    token.validate()?;
+    Ok(token)
+}
+
+/// Remaining fungible tokens are sent to the change output,
+/// so it must not be a CashAddress of a wallet that doesn't support CashTokens.
+fn check_token_aware_change(transaction_builder: &Proto::TransactionBuilder) -> SigningResult<()> {
+    let Some(ref change_output) = transaction_builder.change_output else {
+        return Ok(());
+    };
+
+    let mut sent: BTreeMap<&[u8], u128> = BTreeMap::new();
+    for token in transaction_builder
+        .outputs
+        .iter()
+        .filter_map(|output| output.cash_token.as_ref())
+    {
+        *sent.entry(token.category.as_ref()).or_default() += token.amount as u128;
+    }
+
+    let mut available: BTreeMap<&[u8], u128> = BTreeMap::new();
+    for token in transaction_builder
+        .inputs
+        .iter()
+        .filter_map(|utxo| utxo.cash_token.as_ref())
+        // UTXOs holding tokens of categories that are not sent are not spent.
+        .filter(|token| sent.contains_key(token.category.as_ref()))
+    {
+        *available.entry(token.category.as_ref()).or_default() += token.amount as u128;
+    }
+
+    let has_remaining_tokens = available
+        .iter()
+        .any(|(category, available)| sent.get(category).map_or(false, |sent| available > sent));
+    if !has_remaining_tokens {
+        return Ok(());
+    }
+
+    check_token_aware_recipient(change_output)
+        .context("Remaining fungible tokens are sent to the change output")
+}
+
+/// Tokens must not be sent to a CashAddress of a wallet that doesn't support CashTokens.
+fn check_token_aware_recipient(output: &Proto::Output) -> SigningResult<()> {
+    use Proto::mod_Output::OneOfto_recipient as RecipientType;
+
+    let RecipientType::to_address(ref address) = output.to_recipient else {
+        return Ok(());
+    };
+    match Address::from_str(address) {
+        Ok(Address::Cash(cash)) if !cash.is_token_aware() => {
+            SigningError::err(SigningErrorType::Error_invalid_address)
+                .context("CashTokens can be sent to a token-aware address only")
+        },
+        _ => Ok(()),
+    }
+}
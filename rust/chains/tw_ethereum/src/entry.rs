@@ -9,7 +9,6 @@ use tw_coin_entry::derivation::Derivation;
 use tw_coin_entry::error::prelude::*;
 use tw_coin_entry::modules::json_signer::NoJsonSigner;
 use tw_coin_entry::modules::plan_builder::NoPlanBuilder;
-use tw_coin_entry::modules::transaction_decoder::NoTransactionDecoder;
 use tw_coin_entry::modules::wallet_connector::NoWalletConnector;
 use tw_coin_entry::prefix::NoPrefix;
 use tw_evm::address::Address;
@@ -18,6 +17,7 @@ use tw_evm::evm_entry::EvmEntry;
 use tw_evm::modules::compiler::Compiler;
 use tw_evm::modules::message_signer::EthMessageSigner;
 use tw_evm::modules::signer::Signer;
+use tw_evm::modules::transaction_decoder::EvmTransactionDecoder;
 use tw_evm::modules::transaction_util::EvmTransactionUtil;
 use tw_keypair::tw::PublicKey;
 use tw_proto::Ethereum::Proto;
@@ -37,7 +37,7 @@ impl CoinEntry for EthereumEntry {
     type PlanBuilder = NoPlanBuilder;
     type MessageSigner = EthMessageSigner;
     type WalletConnector = NoWalletConnector;
-    type TransactionDecoder = NoTransactionDecoder;
+    type TransactionDecoder = EvmTransactionDecoder;
     type TransactionUtil = EvmTransactionUtil;
 
     #[inline]
@@ -99,6 +99,11 @@ impl CoinEntry for EthereumEntry {
         Some(EthMessageSigner)
     }
 
+    #[inline]
+    fn transaction_decoder(&self) -> Option<Self::TransactionDecoder> {
+        Some(EvmTransactionDecoder)
+    }
+
     #[inline]
     fn transaction_util(&self) -> Option<Self::TransactionUtil> {
         Some(EvmTransactionUtil)
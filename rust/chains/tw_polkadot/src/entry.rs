@@ -7,9 +7,9 @@ use tw_coin_entry::coin_context::CoinContext;
 use tw_coin_entry::error::prelude::*;
 use tw_coin_entry::signing_output_error;
 use tw_keypair::ed25519::sha512::{KeyPair, PublicKey};
+use tw_keypair::traits::KeyPairTrait;
 use tw_number::U256;
 use tw_proto::Polkadot::Proto;
-use tw_proto::TxCompiler::Proto as CompilerProto;
 use tw_scale::{RawOwned, ToScale};
 use tw_ss58_address::SS58Address;
 use tw_substrate::*;
@@ -28,6 +28,31 @@ impl PolkadotEntry {
         Ok(KeyPair::try_from(input.private_key.as_ref())?)
     }
 
+    #[inline]
+    fn get_public_key_impl(
+        &self,
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput<'_>,
+    ) -> SigningResult<Option<PublicKey>> {
+        let public_key = match (input.private_key.is_empty(), input.public_key.is_empty()) {
+            (true, true) => return Ok(None),
+            (true, false) => PublicKey::try_from(input.public_key.as_ref())
+                .tw_err(|_| SigningErrorType::Error_invalid_params)
+                .context("Invalid 'SigningInput.public_key'")?,
+            (false, true) => self.get_keypair_impl(coin, input)?.public().clone(),
+            (false, false) => {
+                let public_key = self.get_keypair_impl(coin, input)?.public().clone();
+                if public_key.as_slice() != input.public_key.as_ref() {
+                    return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                        "'SigningInput.public_key' doesn't match 'SigningInput.private_key'",
+                    );
+                }
+                public_key
+            },
+        };
+        Ok(Some(public_key))
+    }
+
     fn build_transaction_impl(
         &self,
         _coin: &dyn CoinContext,
@@ -91,13 +116,23 @@ impl PolkadotEntry {
     fn presigning_output_impl(
         &self,
         _coin: &dyn CoinContext,
-        result: SigningResult<RawOwned>,
-    ) -> SigningResult<CompilerProto::PreSigningOutput<'static>> {
-        let pre_image = result?.to_scale();
-        Ok(CompilerProto::PreSigningOutput {
-            // `pre_image` is already hashed if it is larger then 256 bytes.
-            data_hash: pre_image.clone().into(),
-            data: pre_image.into(),
+        result: SigningResult<SubstratePreImage>,
+    ) -> SigningResult<Proto::PreSigningOutput<'static>> {
+        let pre_image = result?;
+        let signature_scheme = match pre_image.signature_scheme {
+            SignatureScheme::Ed25519 => Proto::SignatureScheme::ED25519,
+            SignatureScheme::Sr25519 => Proto::SignatureScheme::SR25519,
+            SignatureScheme::Ecdsa => Proto::SignatureScheme::ECDSA,
+        };
+        Ok(Proto::PreSigningOutput {
+            data_hash: pre_image.data_to_sign.into(),
+            data: pre_image.payload.into(),
+            is_hashed: pre_image.is_hashed,
+            signature_scheme,
+            signer_address: pre_image
+                .signer
+                .map(|signer| signer.to_string().into())
+                .unwrap_or_default(),
             ..Default::default()
         })
     }
@@ -106,7 +141,7 @@ impl PolkadotEntry {
 impl SubstrateCoinEntry for PolkadotEntry {
     type SigningInput<'a> = Proto::SigningInput<'a>;
     type SigningOutput = Proto::SigningOutput<'static>;
-    type PreSigningOutput = CompilerProto::PreSigningOutput<'static>;
+    type PreSigningOutput = Proto::PreSigningOutput<'static>;
 
     #[inline]
     fn get_keypair(
@@ -117,6 +152,15 @@ impl SubstrateCoinEntry for PolkadotEntry {
         self.get_keypair_impl(coin, input)
     }
 
+    #[inline]
+    fn get_public_key(
+        &self,
+        coin: &dyn CoinContext,
+        input: &Self::SigningInput<'_>,
+    ) -> SigningResult<Option<PublicKey>> {
+        self.get_public_key_impl(coin, input)
+    }
+
     #[inline]
     fn build_transaction(
         &self,
@@ -142,9 +186,9 @@ impl SubstrateCoinEntry for PolkadotEntry {
     fn presigning_output(
         &self,
         coin: &dyn CoinContext,
-        result: SigningResult<RawOwned>,
+        result: SigningResult<SubstratePreImage>,
     ) -> Self::PreSigningOutput {
         self.presigning_output_impl(coin, result)
-            .unwrap_or_else(|e| signing_output_error!(CompilerProto::PreSigningOutput, e))
+            .unwrap_or_else(|e| signing_output_error!(Proto::PreSigningOutput, e))
     }
 }
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::t_address::TAddress;
+use crate::tex_address::{TexAddress, TEX_MAINNET_HRP};
+use std::fmt;
+use std::str::FromStr;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::coin_entry::CoinAddress;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::prefix::BitcoinBase58Prefix;
+use tw_memory::Data;
+use tw_utxo::script::Script;
+
+/// A set of Zcash transparent address types.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ZcashAddress {
+    Transparent(TAddress),
+    Tex(TexAddress),
+}
+
+impl ZcashAddress {
+    /// Tries to parse one of the `ZcashAddress` variants
+    /// and validates if the result address matches the given `prefix` address or belongs to the `coin` network.
+    pub fn from_str_with_coin_and_prefix(
+        coin: &dyn CoinContext,
+        s: &str,
+        prefix: Option<BitcoinBase58Prefix>,
+    ) -> AddressResult<ZcashAddress> {
+        // Base58 prefix is not applicable to TEX addresses.
+        if prefix.is_none() {
+            if let Ok(tex) = TexAddress::from_str_checked(s, TEX_MAINNET_HRP) {
+                return Ok(ZcashAddress::Tex(tex));
+            }
+        }
+
+        TAddress::from_str_with_coin_and_prefix(coin, s, prefix).map(ZcashAddress::Transparent)
+    }
+
+    pub fn to_script_pubkey(&self, p2pkh_prefix: u8, p2sh_prefix: u8) -> SigningResult<Script> {
+        match self {
+            ZcashAddress::Transparent(t) => t.to_script_pubkey(p2pkh_prefix, p2sh_prefix),
+            ZcashAddress::Tex(tex) => Ok(tex.to_script_pubkey()),
+        }
+    }
+
+    pub fn is_tex(&self) -> bool {
+        matches!(self, ZcashAddress::Tex(_))
+    }
+}
+
+impl FromStr for ZcashAddress {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(t) = TAddress::from_str(s) {
+            return Ok(ZcashAddress::Transparent(t));
+        }
+        if let Ok(tex) = TexAddress::from_str(s) {
+            return Ok(ZcashAddress::Tex(tex));
+        }
+        Err(AddressError::InvalidInput)
+    }
+}
+
+impl fmt::Display for ZcashAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZcashAddress::Transparent(t) => write!(f, "{t}"),
+            ZcashAddress::Tex(tex) => write!(f, "{tex}"),
+        }
+    }
+}
+
+impl CoinAddress for ZcashAddress {
+    fn data(&self) -> Data {
+        match self {
+            ZcashAddress::Transparent(t) => t.data(),
+            ZcashAddress::Tex(tex) => tex.data(),
+        }
+    }
+}
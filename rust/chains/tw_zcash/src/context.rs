@@ -2,10 +2,10 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::address::ZcashAddress;
 use crate::modules::protobuf_builder::ZcashProtobufBuilder;
 use crate::modules::signing_request::ZcashSigningRequestBuilder;
 use crate::modules::zcash_fee_estimator::ZcashFeeEstimator;
-use crate::t_address::TAddress;
 use crate::transaction::ZcashTransaction;
 use tw_bitcoin::context::BitcoinSigningContext;
 use tw_bitcoin::modules::psbt_request::NoPsbtRequestBuilder;
@@ -17,7 +17,7 @@ use tw_utxo::script::Script;
 pub struct ZcashContext;
 
 impl UtxoContext for ZcashContext {
-    type Address = TAddress;
+    type Address = ZcashAddress;
     type Transaction = ZcashTransaction;
     type FeeEstimator = ZcashFeeEstimator;
 
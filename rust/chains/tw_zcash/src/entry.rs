@@ -2,6 +2,7 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::address::ZcashAddress;
 use crate::context::ZcashContext;
 use crate::t_address::TAddress;
 use std::str::FromStr;
@@ -25,7 +26,7 @@ pub struct ZcashEntry;
 
 impl CoinEntry for ZcashEntry {
     type AddressPrefix = BitcoinBase58Prefix;
-    type Address = TAddress;
+    type Address = ZcashAddress;
     type SigningInput<'a> = BitcoinV2Proto::SigningInput<'a>;
     type SigningOutput = BitcoinV2Proto::SigningOutput<'static>;
     type PreSigningOutput = BitcoinV2Proto::PreSigningOutput<'static>;
@@ -45,12 +46,12 @@ impl CoinEntry for ZcashEntry {
         address: &str,
         prefix: Option<Self::AddressPrefix>,
     ) -> AddressResult<Self::Address> {
-        TAddress::from_str_with_coin_and_prefix(coin, address, prefix)
+        ZcashAddress::from_str_with_coin_and_prefix(coin, address, prefix)
     }
 
     #[inline]
     fn parse_address_unchecked(&self, address: &str) -> AddressResult<Self::Address> {
-        TAddress::from_str(address)
+        ZcashAddress::from_str(address)
     }
 
     #[inline]
@@ -62,6 +63,7 @@ impl CoinEntry for ZcashEntry {
         prefix: Option<Self::AddressPrefix>,
     ) -> AddressResult<Self::Address> {
         TAddress::p2pkh_with_coin_and_prefix(coin, &public_key, prefix)
+            .map(ZcashAddress::Transparent)
     }
 
     #[inline]
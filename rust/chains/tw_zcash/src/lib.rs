@@ -13,9 +13,13 @@
 //!
 //! - **Transaction Signer**: Utilizes Bitcoin's transaction signing mechanisms.
 //! - **Pre-image hashing**: Utilizes Bitcoin's hashing algorithms in a specific way for Zcash transaction.
+//!   Version 4 transactions are signed according to [ZIP-243](https://zips.z.cash/zip-0243),
+//!   version 5 transactions according to [ZIP-244](https://zips.z.cash/zip-0244).
 //! - **Compiler APIs**: Utilizes Bitcoin's compiler APIs to compile a transaction with externally signed signatures.
 //! - **Zcash-Specific Transaction Structure**: Implements a (transparent) transaction [`ZcashTransaction`] tailored
 //!   to the requirements of the Zcash blockchain.
+//! - **Addresses**: Supports transparent Base58 addresses and [ZIP-320](https://zips.z.cash/zip-0320)
+//!   TEX (transparent-source-only) addresses. A transaction paying to a TEX address can spend P2PKH inputs only.
 //! - **Protobuf-Based Transaction Builder**: Provides a transaction builder based on Protobuf
 //!   definitions, facilitating the creation and manipulation of Zcash transactions.
 //! - **Fee calculation**: Utilizes Bitcoin's transaction fee estimation and UTXO selection.
@@ -126,8 +130,10 @@
 //! assert.equal(output.signingResultV2!.error, TW.Common.Proto.SigningError.OK);
 //! ```
 
+pub mod address;
 pub mod context;
 pub mod entry;
 pub mod modules;
 pub mod t_address;
+pub mod tex_address;
 pub mod transaction;
@@ -7,3 +7,4 @@ pub mod signing_request;
 pub mod transaction_builder;
 pub mod zcash_fee_estimator;
 pub mod zcash_sighash;
+pub mod zcash_v5_sighash;
@@ -3,7 +3,9 @@
 // Copyright © 2017 Trust Wallet.
 
 use crate::modules::zcash_fee_estimator::ZcashFeeEstimator;
-use crate::transaction::{ZcashTransaction, TRANSACTION_VERSION_4};
+use crate::tex_address::TexAddress;
+use crate::transaction::{transaction_version_for_branch, ZcashTransaction};
+use std::str::FromStr;
 use tw_bitcoin::modules::signing_request::SigningRequestBuilder;
 use tw_bitcoin::modules::tx_builder::output_protobuf::OutputProtobuf;
 use tw_bitcoin::modules::tx_builder::utxo_protobuf::UtxoProtobuf;
@@ -15,6 +17,7 @@ use tw_proto::BitcoinV2::Proto;
 use tw_utxo::fee::fee_estimator::StandardFeeEstimator;
 use tw_utxo::fee::FeePolicy;
 use tw_utxo::modules::tx_planner::{PlanRequest, RequestType};
+use tw_utxo::script::standard_script::conditions;
 
 use crate::modules::transaction_builder::ZcashTransactionBuilder;
 use tw_bitcoin::modules::signing_request::standard_signing_request::{
@@ -46,13 +49,16 @@ where
         let dust_policy =
             StandardSigningRequestBuilder::dust_policy(&transaction_builder.dust_policy)?;
         let fee_estimator = Self::fee_estimator(transaction_builder, &extra_data)?;
-        let version = Self::transaction_version(&transaction_builder.version)?;
+        let (version, version_group_id) =
+            Self::transaction_version(&transaction_builder.version, &extra_data.branch_id)?;
 
         let public_keys = StandardSigningRequestBuilder::get_public_keys(input)?;
+        let pays_to_tex = Self::pays_to_tex(transaction_builder);
 
         let mut builder = ZcashTransactionBuilder::default();
         builder
             .version(version)
+            .version_group_id(version_group_id)
             .lock_time(transaction_builder.lock_time)
             .expiry_height(extra_data.expiry_height)
             .branch_id(extra_data.branch_id);
@@ -64,6 +70,13 @@ where
             let (utxo, utxo_args) = utxo_builder
                 .utxo_from_proto()
                 .context("Error creating UTXO from Protobuf")?;
+
+            // ZIP-320: a transaction paying to a TEX address must spend transparent P2PKH inputs only.
+            if pays_to_tex && !conditions::is_p2pkh(&utxo_args.prevout_script_pubkey) {
+                return SigningError::err(SigningErrorType::Error_invalid_utxo).context(
+                    "Only P2PKH inputs can be spent in a transaction paying to a TEX address",
+                );
+            }
             builder.push_input(utxo, utxo_args);
         }
 
@@ -120,14 +133,34 @@ where
 }
 
 impl ZcashSigningRequestBuilder {
-    pub fn transaction_version(proto: &Proto::TransactionVersion) -> SigningResult<i32> {
+    /// Returns the transaction version and version group ID.
+    /// The version 5 format is used if the consensus branch is NU5 or later.
+    pub fn transaction_version(
+        proto: &Proto::TransactionVersion,
+        branch_id: &H32,
+    ) -> SigningResult<(i32, u32)> {
         match proto {
-            Proto::TransactionVersion::UseDefault => Ok(TRANSACTION_VERSION_4),
+            Proto::TransactionVersion::UseDefault => transaction_version_for_branch(branch_id),
             _ => SigningError::err(SigningErrorType::Error_invalid_params)
                 .context("ZCash currently supports `UseDefault` transaction version only"),
         }
     }
 
+    /// Checks whether any of the outputs pays to a TEX (transparent-source-only) address.
+    pub fn pays_to_tex(proto: &Proto::TransactionBuilder) -> bool {
+        use Proto::mod_Output::OneOfto_recipient as RecipientType;
+
+        proto
+            .outputs
+            .iter()
+            .chain(proto.change_output.as_ref())
+            .chain(proto.max_amount_output.as_ref())
+            .any(|output| match output.to_recipient {
+                RecipientType::to_address(ref addr) => TexAddress::from_str(addr).is_ok(),
+                _ => false,
+            })
+    }
+
     pub fn fee_estimator(
         proto: &Proto::TransactionBuilder,
         extra_data: &ZcashExtraData,
@@ -0,0 +1,210 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::transaction::ZcashTransaction;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::hasher::{Blake2bPersonalHasher, HasherOps};
+use tw_hash::{H128, H256};
+use tw_utxo::encode::stream::Stream;
+use tw_utxo::sighash::SighashBase;
+use tw_utxo::transaction::UtxoPreimageArgs;
+
+const TX_HASH_PERSONALISATION_PREFIX: &[u8] = b"ZcashTxHash_";
+const HEADERS_PERSONALISATION: &[u8] = b"ZTxIdHeadersHash";
+const TRANSPARENT_PERSONALISATION: &[u8] = b"ZTxIdTranspaHash";
+const PREVOUT_PERSONALISATION: &[u8] = b"ZTxIdPrevoutHash";
+const SEQUENCE_PERSONALISATION: &[u8] = b"ZTxIdSequencHash";
+const OUTPUTS_PERSONALISATION: &[u8] = b"ZTxIdOutputsHash";
+const AMOUNTS_PERSONALISATION: &[u8] = b"ZTxTrAmountsHash";
+const SCRIPTS_PERSONALISATION: &[u8] = b"ZTxTrScriptsHash";
+const TX_IN_PERSONALISATION: &[u8] = b"Zcash___TxInHash";
+const SAPLING_PERSONALISATION: &[u8] = b"ZTxIdSaplingHash";
+const ORCHARD_PERSONALISATION: &[u8] = b"ZTxIdOrchardHash";
+
+/// `SIGHASH_ALL`, `SIGHASH_NONE`, `SIGHASH_SINGLE` optionally combined with `SIGHASH_ANYONECANPAY`.
+const VALID_HASH_TYPES: [u8; 6] = [0x01, 0x02, 0x03, 0x81, 0x82, 0x83];
+
+/// Transaction identifier and signature digests of a version 5 transaction as specified in ZIP-244:
+/// https://zips.z.cash/zip-0244
+///
+/// Only transparent inputs and outputs are supported, Sapling and Orchard bundles are always empty.
+pub struct ZcashV5Sighash;
+
+impl ZcashV5Sighash {
+    /// Computes `txid_digest`. Please note the result is not reversed.
+    pub fn txid_digest(tx: &ZcashTransaction) -> H256 {
+        let transparent_digest = Self::transparent_txid_digest(tx);
+        Self::root_digest(tx, &transparent_digest)
+    }
+
+    /// Computes `signature_digest` for the transparent input at [`UtxoPreimageArgs::input_index`].
+    pub fn sighash_tx(tx: &ZcashTransaction, args: &UtxoPreimageArgs) -> SigningResult<H256> {
+        let transparent_digest = Self::transparent_sig_digest(tx, args)?;
+        Ok(Self::root_digest(tx, &transparent_digest))
+    }
+
+    /// T.1 / S.1: header digest, T.2 / S.2: transparent digest, T.3: sapling digest, T.4: orchard digest.
+    fn root_digest(tx: &ZcashTransaction, transparent_digest: &H256) -> H256 {
+        let mut personalisation = H128::new();
+        personalisation[..TX_HASH_PERSONALISATION_PREFIX.len()]
+            .copy_from_slice(TX_HASH_PERSONALISATION_PREFIX);
+        personalisation[TX_HASH_PERSONALISATION_PREFIX.len()..]
+            .copy_from_slice(tx.branch_id.as_slice());
+
+        let mut stream = Stream::default();
+        stream
+            .append_raw_slice(Self::header_digest(tx).as_slice())
+            .append_raw_slice(transparent_digest.as_slice())
+            // Sapling and Orchard bundles are empty.
+            .append_raw_slice(blake2b(SAPLING_PERSONALISATION, &[]).as_slice())
+            .append_raw_slice(blake2b(ORCHARD_PERSONALISATION, &[]).as_slice());
+
+        blake2b(personalisation.as_slice(), &stream.out())
+    }
+
+    fn header_digest(tx: &ZcashTransaction) -> H256 {
+        let mut stream = Stream::default();
+        stream
+            .append(&tx.version)
+            .append(&tx.version_group_id)
+            .append_raw_slice(tx.branch_id.as_slice())
+            .append(&tx.locktime)
+            .append(&tx.expiry_height);
+        blake2b(HEADERS_PERSONALISATION, &stream.out())
+    }
+
+    fn transparent_txid_digest(tx: &ZcashTransaction) -> H256 {
+        if tx.transparent_inputs.is_empty() && tx.transparent_outputs.is_empty() {
+            return blake2b(TRANSPARENT_PERSONALISATION, &[]);
+        }
+
+        let mut stream = Stream::default();
+        stream
+            .append_raw_slice(Self::prevouts_digest(tx).as_slice())
+            .append_raw_slice(Self::sequence_digest(tx).as_slice())
+            .append_raw_slice(Self::outputs_digest(tx).as_slice());
+        blake2b(TRANSPARENT_PERSONALISATION, &stream.out())
+    }
+
+    fn transparent_sig_digest(
+        tx: &ZcashTransaction,
+        args: &UtxoPreimageArgs,
+    ) -> SigningResult<H256> {
+        let utxo = tx
+            .transparent_inputs
+            .get(args.input_index)
+            .or_tw_err(SigningErrorType::Error_internal)
+            .context("Zcash sighash error: input_index is out of bounds")?;
+        if args.spent_amounts.len() != tx.transparent_inputs.len()
+            || args.spent_script_pubkeys.len() != tx.transparent_inputs.len()
+        {
+            return SigningError::err(SigningErrorType::Error_internal)
+                .context("Zcash sighash error: expected amounts and scripts of all spent UTXOs");
+        }
+        let script_pubkey = &args.spent_script_pubkeys[args.input_index];
+
+        let hash_type = args.sighash_ty.serialize()?;
+        if !VALID_HASH_TYPES.contains(&hash_type) {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context(format!("Invalid ZIP-244 sighash type: {hash_type:#04x}"));
+        }
+        let anyone_can_pay = args.sighash_ty.anyone_can_pay();
+
+        let (prevouts_digest, amounts_digest, scripts_digest, sequence_digest) = if anyone_can_pay {
+            (
+                blake2b(PREVOUT_PERSONALISATION, &[]),
+                blake2b(AMOUNTS_PERSONALISATION, &[]),
+                blake2b(SCRIPTS_PERSONALISATION, &[]),
+                blake2b(SEQUENCE_PERSONALISATION, &[]),
+            )
+        } else {
+            (
+                Self::prevouts_digest(tx),
+                Self::amounts_digest(args),
+                Self::scripts_digest(args),
+                Self::sequence_digest(tx),
+            )
+        };
+
+        let outputs_digest = match args.sighash_ty.base_type() {
+            SighashBase::All => Self::outputs_digest(tx),
+            SighashBase::Single => match tx.transparent_outputs.get(args.input_index) {
+                Some(output) => {
+                    let mut stream = Stream::default();
+                    stream.append(output);
+                    blake2b(OUTPUTS_PERSONALISATION, &stream.out())
+                },
+                None => blake2b(OUTPUTS_PERSONALISATION, &[]),
+            },
+            SighashBase::None => blake2b(OUTPUTS_PERSONALISATION, &[]),
+        };
+
+        // The input being signed.
+        let mut stream = Stream::default();
+        stream
+            .append(&utxo.previous_output)
+            .append(&args.amount)
+            .append(script_pubkey)
+            .append(&utxo.sequence);
+        let txin_digest = blake2b(TX_IN_PERSONALISATION, &stream.out());
+
+        let mut stream = Stream::default();
+        stream
+            .append(&hash_type)
+            .append_raw_slice(prevouts_digest.as_slice())
+            .append_raw_slice(amounts_digest.as_slice())
+            .append_raw_slice(scripts_digest.as_slice())
+            .append_raw_slice(sequence_digest.as_slice())
+            .append_raw_slice(outputs_digest.as_slice())
+            .append_raw_slice(txin_digest.as_slice());
+        Ok(blake2b(TRANSPARENT_PERSONALISATION, &stream.out()))
+    }
+
+    fn prevouts_digest(tx: &ZcashTransaction) -> H256 {
+        let mut stream = Stream::default();
+        for input in tx.transparent_inputs.iter() {
+            stream.append(&input.previous_output);
+        }
+        blake2b(PREVOUT_PERSONALISATION, &stream.out())
+    }
+
+    fn sequence_digest(tx: &ZcashTransaction) -> H256 {
+        let mut stream = Stream::default();
+        for input in tx.transparent_inputs.iter() {
+            stream.append(&input.sequence);
+        }
+        blake2b(SEQUENCE_PERSONALISATION, &stream.out())
+    }
+
+    fn outputs_digest(tx: &ZcashTransaction) -> H256 {
+        let mut stream = Stream::default();
+        for output in tx.transparent_outputs.iter() {
+            stream.append(output);
+        }
+        blake2b(OUTPUTS_PERSONALISATION, &stream.out())
+    }
+
+    fn amounts_digest(args: &UtxoPreimageArgs) -> H256 {
+        let mut stream = Stream::default();
+        for amount in args.spent_amounts.iter() {
+            stream.append(amount);
+        }
+        blake2b(AMOUNTS_PERSONALISATION, &stream.out())
+    }
+
+    fn scripts_digest(args: &UtxoPreimageArgs) -> H256 {
+        let mut stream = Stream::default();
+        for script in args.spent_script_pubkeys.iter() {
+            stream.append(script);
+        }
+        blake2b(SCRIPTS_PERSONALISATION, &stream.out())
+    }
+}
+
+fn blake2b(personalisation: &[u8], data: &[u8]) -> H256 {
+    let hash = Blake2bPersonalHasher::new(H256::LEN, personalisation)
+        .expect("Expected a valid Blake2b hash and personalisation lengths")
+        .hash(data);
+    H256::try_from(hash.as_slice()).expect("Expected a 32-byte Blake2b hash")
+}
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::t_address::TAddress;
+use bech32::{FromBase32, ToBase32, Variant};
+use std::fmt;
+use std::str::FromStr;
+use tw_coin_entry::coin_entry::CoinAddress;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::hasher::sha256_ripemd;
+use tw_hash::H160;
+use tw_keypair::ecdsa;
+use tw_memory::Data;
+use tw_utxo::script::standard_script::conditions;
+use tw_utxo::script::Script;
+
+/// TEX address HRP on Zcash mainnet.
+pub const TEX_MAINNET_HRP: &str = "tex";
+/// TEX address HRP on Zcash testnet.
+pub const TEX_TESTNET_HRP: &str = "textest";
+
+/// Transparent-source-only (TEX) address as specified in ZIP-320:
+/// https://zips.z.cash/zip-0320
+///
+/// It's a Bech32m encoding of a P2PKH public key hash.
+/// A sender must only spend transparent P2PKH inputs in a transaction that pays to a TEX address.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TexAddress {
+    hrp: String,
+    key_hash: H160,
+}
+
+impl TexAddress {
+    pub fn new(hrp: String, key_hash: H160) -> AddressResult<TexAddress> {
+        if hrp != TEX_MAINNET_HRP && hrp != TEX_TESTNET_HRP {
+            return Err(AddressError::InvalidHrp);
+        }
+        Ok(TexAddress { hrp, key_hash })
+    }
+
+    pub fn p2pkh_with_public_key(
+        hrp: String,
+        public_key: &ecdsa::secp256k1::PublicKey,
+    ) -> AddressResult<TexAddress> {
+        let public_key_hash = sha256_ripemd(public_key.compressed().as_slice());
+        let key_hash =
+            H160::try_from(public_key_hash.as_slice()).expect("sha256ripemd must return 20 bytes");
+        TexAddress::new(hrp, key_hash)
+    }
+
+    /// Converts a P2PKH transparent address to a TEX address.
+    pub fn from_t_address(
+        t_address: &TAddress,
+        p2pkh_prefix: u8,
+        hrp: String,
+    ) -> AddressResult<TexAddress> {
+        if t_address.prefix() != p2pkh_prefix {
+            // Only P2PKH addresses can be converted.
+            return Err(AddressError::UnexpectedAddressPrefix);
+        }
+        TexAddress::new(hrp, t_address.payload())
+    }
+
+    /// Converts the TEX address to a P2PKH transparent address.
+    pub fn to_t_address(&self, p2pkh_prefix: u8) -> AddressResult<TAddress> {
+        TAddress::new(p2pkh_prefix, self.key_hash.as_slice())
+    }
+
+    pub fn from_str_checked(s: &str, expected_hrp: &str) -> AddressResult<TexAddress> {
+        let address = TexAddress::from_str(s)?;
+        if address.hrp != expected_hrp {
+            return Err(AddressError::InvalidHrp);
+        }
+        Ok(address)
+    }
+
+    pub fn hrp(&self) -> &str {
+        &self.hrp
+    }
+
+    pub fn key_hash(&self) -> &H160 {
+        &self.key_hash
+    }
+
+    pub fn to_script_pubkey(&self) -> Script {
+        conditions::new_p2pkh(&self.key_hash)
+    }
+}
+
+impl FromStr for TexAddress {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hrp, payload_u5, checksum_variant) =
+            bech32::decode(s).map_err(|_| AddressError::FromBech32Error)?;
+
+        if checksum_variant != Variant::Bech32m {
+            return Err(AddressError::InvalidInput);
+        }
+
+        let payload = Data::from_base32(&payload_u5).map_err(|_| AddressError::FromBech32Error)?;
+        let key_hash =
+            H160::try_from(payload.as_slice()).map_err(|_| AddressError::InvalidInput)?;
+        TexAddress::new(hrp, key_hash)
+    }
+}
+
+impl fmt::Display for TexAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoded = bech32::encode(&self.hrp, self.key_hash.to_base32(), Variant::Bech32m)
+            .map_err(|_| fmt::Error)?;
+        write!(f, "{encoded}")
+    }
+}
+
+impl CoinAddress for TexAddress {
+    fn data(&self) -> Data {
+        self.key_hash.to_vec()
+    }
+}
@@ -3,6 +3,7 @@
 // Copyright © 2017 Trust Wallet.
 
 use crate::modules::zcash_sighash::ZcashSighash;
+use crate::modules::zcash_v5_sighash::ZcashV5Sighash;
 use tw_coin_entry::error::prelude::{ResultContext, SigningError, SigningErrorType, SigningResult};
 use tw_hash::hasher::sha256_d;
 use tw_hash::{H256, H32};
@@ -21,23 +22,66 @@ use tw_utxo::transaction::{TransactionPreimage, UtxoPreimageArgs, UtxoTaprootPre
 /// However, the value will be serialized correctly.
 pub const TRANSACTION_VERSION_4: i32 = 0x80000004_u32 as i32;
 pub const TRANSACTION_VERSION_GROUP_ID: u32 = 0x892F2085;
+/// See https://zips.z.cash/zip-0205#sapling-deployment BRANCH_ID section
+pub const SAPLING_BRANCH_ID: H32 = H32::from_array([0xbb, 0x09, 0xb8, 0x76]);
+/// See https://zips.z.cash/zip-0206#blossom-deployment BRANCH_ID section
+pub const BLOSSOM_BRANCH_ID: H32 = H32::from_array([0x60, 0x0e, 0xb4, 0x2b]);
+/// See https://zips.z.cash/zip-0250#heartwood-deployment BRANCH_ID section
+pub const HEARTWOOD_BRANCH_ID: H32 = H32::from_array([0x0b, 0x23, 0xb9, 0xf5]);
+/// See https://zips.z.cash/zip-0251#canopy-deployment BRANCH_ID section
+pub const CANOPY_BRANCH_ID: H32 = H32::from_array([0xa6, 0x75, 0xff, 0xe9]);
+/// Version 5 transaction format introduced in NU5.
+/// See https://zips.z.cash/zip-0225
+pub const TRANSACTION_VERSION_5: i32 = 0x80000005_u32 as i32;
+pub const TRANSACTION_VERSION_5_GROUP_ID: u32 = 0x26A7270A;
+/// See https://zips.z.cash/zip-0252#nu5-deployment CONSENSUS_BRANCH_ID section
+pub const NU5_BRANCH_ID: H32 = H32::from_array([0xb4, 0xd0, 0xd6, 0xc2]);
 /// See https://github.com/zcash/zips/blob/main/zips/zip-0253.md#nu6-deployment CONSENSUS_BRANCH_ID section
 pub const NU6_BRANCH_ID: H32 = H32::from_array([0x55, 0x10, 0xe7, 0xc8]);
+/// See https://zips.z.cash/zip-0255#nu6-1-deployment CONSENSUS_BRANCH_ID section
+pub const NU6_1_BRANCH_ID: H32 = H32::from_array([0xf0, 0x4d, 0xec, 0x4d]);
+
+/// Consensus branches before NU5 that support the version 4 transaction format only.
+const V4_BRANCH_IDS: [H32; 4] = [
+    SAPLING_BRANCH_ID,
+    BLOSSOM_BRANCH_ID,
+    HEARTWOOD_BRANCH_ID,
+    CANOPY_BRANCH_ID,
+];
+/// Consensus branches that support the version 5 transaction format.
+const V5_BRANCH_IDS: [H32; 3] = [NU5_BRANCH_ID, NU6_BRANCH_ID, NU6_1_BRANCH_ID];
 
 const SEGWIT_NOT_SUPPORTED: bool = false;
 const SAPLING_SPENDING_LEN: usize = 0;
 const SAPLING_OUTPUTS_LEN: usize = 0;
 const JOIN_SPLITS_LEN: usize = 0;
+const ORCHARD_ACTIONS_LEN: usize = 0;
+
+/// Returns the transaction version and version group ID to be used in the given consensus branch.
+/// Version 5 is used since NU5, version 4 since Sapling.
+/// Returns an error if the consensus branch is unknown, as the transaction format cannot be chosen safely.
+pub fn transaction_version_for_branch(branch_id: &H32) -> SigningResult<(i32, u32)> {
+    if V5_BRANCH_IDS.contains(branch_id) {
+        Ok((TRANSACTION_VERSION_5, TRANSACTION_VERSION_5_GROUP_ID))
+    } else if V4_BRANCH_IDS.contains(branch_id) {
+        Ok((TRANSACTION_VERSION_4, TRANSACTION_VERSION_GROUP_ID))
+    } else {
+        SigningError::err(SigningErrorType::Error_invalid_params)
+            .with_context(|| format!("Unknown ZCash consensus branch ID: {branch_id}"))
+    }
+}
 
 /// Transparent ZCash transaction (transparent).
 /// https://github.com/zcash/zips/blob/998a97f2a1e5686e0d5c57f399a08b4daf100f8e/zips/zip-0243.rst
+/// https://zips.z.cash/zip-0225
 /// https://github.com/zcash/zcash/blob/a3435336b0c561799ac6805a27993eca3f9656df/src/primitives/transaction.h#L454
 #[derive(Clone, Debug)]
 pub struct ZcashTransaction {
     /// Transaction version.
-    /// Currently, version 4 (0x80000004) is supported only.
+    /// Currently, version 4 (0x80000004) and version 5 (0x80000005) are supported only.
     pub version: i32,
     // If transaction version is 4 (0x80000004), version group ID is 0x892F2085.
+    // If transaction version is 5 (0x80000005), version group ID is 0x26A7270A.
     pub version_group_id: u32,
     /// Unsigned transaction inputs.
     pub transparent_inputs: Vec<TransactionInput>,
@@ -58,14 +102,23 @@ pub struct ZcashTransaction {
     pub expiry_height: u32,
     /// Sapling value balance for the transaction.
     /// Always 0 for a transparent transaction.
+    /// Note it's not used in the version 5 transaction encoding, as the Sapling bundle is empty.
     pub sapling_value_balance: Amount,
     /// Consensus branch ID for the epoch of the block containing the transaction.
-    /// Note it's not used in the final transaction encoding, but in the sighash computing.
+    /// Note it's used in the sighash computing, and also in the final transaction encoding since version 5.
     pub branch_id: H32,
 }
 
 impl ZcashTransaction {
+    pub fn is_v5(&self) -> bool {
+        self.version == TRANSACTION_VERSION_5
+    }
+
     fn total_size(&self) -> usize {
+        if self.is_v5() {
+            return self.total_size_v5();
+        }
+
         let ins = &self.transparent_inputs;
         let outs = &self.transparent_outputs;
 
@@ -86,6 +139,47 @@ impl ZcashTransaction {
         s += CompactInteger::from(SAPLING_OUTPUTS_LEN).encoded_size();
         s + CompactInteger::from(JOIN_SPLITS_LEN).encoded_size()
     }
+
+    fn total_size_v5(&self) -> usize {
+        let ins = &self.transparent_inputs;
+        let outs = &self.transparent_outputs;
+
+        let mut s = self.version.encoded_size();
+        s += self.version_group_id.encoded_size();
+        s += H32::LEN;
+        s += self.locktime.encoded_size();
+        s += self.expiry_height.encoded_size();
+
+        s += CompactInteger::from(ins.len()).encoded_size();
+        s += ins.iter().map(|i| i.base_size()).sum::<usize>();
+
+        s += CompactInteger::from(outs.len()).encoded_size();
+        s += outs.iter().map(|o| o.encoded_size()).sum::<usize>();
+
+        s += CompactInteger::from(SAPLING_SPENDING_LEN).encoded_size();
+        s += CompactInteger::from(SAPLING_OUTPUTS_LEN).encoded_size();
+        s + CompactInteger::from(ORCHARD_ACTIONS_LEN).encoded_size()
+    }
+
+    /// Encodes the transaction according to the version 5 format:
+    /// https://zips.z.cash/zip-0225#transaction-format
+    fn encode_v5(&self, stream: &mut Stream) {
+        stream.append(&self.version);
+        stream.append(&self.version_group_id);
+        stream.append_raw_slice(self.branch_id.as_slice());
+        stream.append(&self.locktime);
+        stream.append(&self.expiry_height);
+
+        stream
+            .append_list(&self.transparent_inputs)
+            .append_list(&self.transparent_outputs);
+
+        // Sapling and Orchard bundles are empty,
+        // so `valueBalanceSapling`, `anchorSapling` and other bundle fields are omitted.
+        CompactInteger::from(SAPLING_SPENDING_LEN).encode(stream);
+        CompactInteger::from(SAPLING_OUTPUTS_LEN).encode(stream);
+        CompactInteger::from(ORCHARD_ACTIONS_LEN).encode(stream);
+    }
 }
 
 impl TransactionInterface for ZcashTransaction {
@@ -141,6 +235,13 @@ impl TransactionInterface for ZcashTransaction {
     }
 
     fn txid(&self) -> Vec<u8> {
+        if self.is_v5() {
+            // Since version 5, transaction ID is a ZIP-244 digest that doesn't commit to the signatures.
+            let mut tx_hash = ZcashV5Sighash::txid_digest(self).to_vec();
+            tx_hash.reverse();
+            return tx_hash;
+        }
+
         let encoded = self.encode_out();
         let mut tx_hash = sha256_d(&encoded);
         tx_hash.reverse();
@@ -150,6 +251,11 @@ impl TransactionInterface for ZcashTransaction {
 
 impl Encodable for ZcashTransaction {
     fn encode(&self, stream: &mut Stream) {
+        if self.is_v5() {
+            self.encode_v5(stream);
+            return;
+        }
+
         stream.append(&self.version);
         stream.append(&self.version_group_id);
 
@@ -174,6 +280,7 @@ impl Encodable for ZcashTransaction {
 impl TransactionPreimage for ZcashTransaction {
     fn preimage_tx(&self, args: &UtxoPreimageArgs) -> SigningResult<H256> {
         match args.signing_method {
+            SigningMethod::Legacy if self.is_v5() => ZcashV5Sighash::sighash_tx(self, args),
             SigningMethod::Legacy => ZcashSighash::sighash_tx(self, args),
             SigningMethod::Segwit | SigningMethod::Taproot => {
                 SigningError::err(SigningErrorType::Error_internal)
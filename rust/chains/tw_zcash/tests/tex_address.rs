@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::AddressError;
+use tw_keypair::ecdsa::secp256k1;
+use tw_zcash::address::ZcashAddress;
+use tw_zcash::t_address::TAddress;
+use tw_zcash::tex_address::{TexAddress, TEX_MAINNET_HRP, TEX_TESTNET_HRP};
+
+const ZCASH_P2PKH_PREFIX: u8 = 184;
+
+/// ZIP-320 test vector:
+/// https://zips.z.cash/zip-0320#reference-implementation
+#[test]
+fn test_tex_address_to_from_t_address() {
+    let t_address = TAddress::from_str("t1VmmGiyjVNeCjxDZzg7vZmd99WyzVby9yC").unwrap();
+
+    let tex =
+        TexAddress::from_t_address(&t_address, ZCASH_P2PKH_PREFIX, TEX_MAINNET_HRP.to_string())
+            .unwrap();
+    assert_eq!(
+        tex.to_string(),
+        "tex1s2rt77ggv6q989lr49rkgzmh5slsksa9khdgte"
+    );
+
+    let tex = TexAddress::from_str("tex1s2rt77ggv6q989lr49rkgzmh5slsksa9khdgte").unwrap();
+    assert_eq!(tex.hrp(), TEX_MAINNET_HRP);
+    assert_eq!(tex.to_t_address(ZCASH_P2PKH_PREFIX).unwrap(), t_address);
+}
+
+#[test]
+fn test_tex_address_from_public_key() {
+    let private_key = secp256k1::PrivateKey::try_from(
+        "2d8f68944bdbfbc0769542fba8fc2d2a3de67393334471624364c7006da2aa54",
+    )
+    .unwrap();
+
+    let tex = TexAddress::p2pkh_with_public_key(TEX_MAINNET_HRP.to_string(), &private_key.public())
+        .unwrap();
+    assert_eq!(
+        tex.to_string(),
+        "tex133h52v2h397w9ek7gyln98vetlsd37gzcddkaa"
+    );
+    assert_eq!(
+        tex.to_t_address(ZCASH_P2PKH_PREFIX).unwrap().to_string(),
+        "t1Wg9uPPAfwhBWeRjtDPa5ZHNzyBx9rJVKY"
+    );
+
+    let tex = TexAddress::p2pkh_with_public_key(TEX_TESTNET_HRP.to_string(), &private_key.public())
+        .unwrap();
+    assert_eq!(
+        tex.to_string(),
+        "textest133h52v2h397w9ek7gyln98vetlsd37gzprjqcx"
+    );
+}
+
+#[test]
+fn test_tex_address_invalid() {
+    // P2SH address cannot be converted to TEX.
+    let p2sh = TAddress::from_str("t3RD6RFKhWSotNbPEY4Vw7Ku9QCfKkzrbBL").unwrap();
+    assert_eq!(
+        TexAddress::from_t_address(&p2sh, ZCASH_P2PKH_PREFIX, TEX_MAINNET_HRP.to_string()),
+        Err(AddressError::UnexpectedAddressPrefix)
+    );
+
+    // Bech32 checksum instead of Bech32m.
+    TexAddress::from_str("tex133h52v2h397w9ek7gyln98vetlsd37gzd3a6cl").unwrap_err();
+    // Unexpected HRP.
+    assert_eq!(
+        TexAddress::from_str_checked(
+            "textest133h52v2h397w9ek7gyln98vetlsd37gzprjqcx",
+            TEX_MAINNET_HRP
+        ),
+        Err(AddressError::InvalidHrp)
+    );
+}
+
+#[test]
+fn test_zcash_address_from_str() {
+    let addr = ZcashAddress::from_str("tex1s2rt77ggv6q989lr49rkgzmh5slsksa9khdgte").unwrap();
+    assert!(addr.is_tex());
+
+    let addr = ZcashAddress::from_str("t1VmmGiyjVNeCjxDZzg7vZmd99WyzVby9yC").unwrap();
+    assert!(!addr.is_tex());
+}
@@ -95,6 +95,10 @@ impl MultiAddress {
     pub fn new(account: AccountId, multi: bool) -> Self {
         Self { account, multi }
     }
+
+    pub fn account(&self) -> &AccountId {
+        &self.account
+    }
 }
 
 impl ToScale for MultiAddress {
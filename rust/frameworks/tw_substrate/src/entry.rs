@@ -3,8 +3,9 @@
 // Copyright © 2017 Trust Wallet.
 
 use crate::address::{SubstrateAddress, SubstratePrefix};
+use crate::extrinsic::hash_payload_if_needed;
 use crate::modules::transaction_util::SubstrateTransactionUtil;
-use crate::substrate_coin_entry::SubstrateCoinEntry;
+use crate::substrate_coin_entry::{SignatureScheme, SubstrateCoinEntry, SubstratePreImage};
 use std::str::FromStr;
 use tw_coin_entry::coin_context::CoinContext;
 use tw_coin_entry::coin_entry::{CoinEntry, PublicKeyBytes, SignatureBytes};
@@ -16,7 +17,8 @@ use tw_coin_entry::modules::message_signer::NoMessageSigner;
 use tw_coin_entry::modules::plan_builder::NoPlanBuilder;
 use tw_coin_entry::modules::transaction_decoder::NoTransactionDecoder;
 use tw_coin_entry::modules::wallet_connector::NoWalletConnector;
-use tw_keypair::{ed25519, traits::KeyPairTrait, tw::PublicKey};
+use tw_keypair::traits::{KeyPairTrait, VerifyingKeyTrait};
+use tw_keypair::{ed25519, tw::PublicKey};
 use tw_scale::RawOwned;
 use tw_ss58_address::{NetworkId, SS58Address};
 
@@ -41,12 +43,19 @@ impl<T: SubstrateCoinEntry> SubstrateEntry<T> {
         &self,
         coin: &dyn CoinContext,
         input: T::SigningInput<'_>,
-    ) -> SigningResult<RawOwned> {
-        let keypair = self.0.get_keypair(coin, &input).ok();
-        let public_key = keypair.map(|p| p.public().clone());
+    ) -> SigningResult<SubstratePreImage> {
+        let public_key = self.0.get_public_key(coin, &input)?;
         let unsigned_tx = self.0.build_transaction(coin, public_key, &input)?;
-        let pre_image = unsigned_tx.encode_payload()?;
-        Ok(RawOwned(pre_image))
+
+        let payload = unsigned_tx.encode_raw_payload();
+        let data_to_sign = hash_payload_if_needed(payload.clone())?;
+        Ok(SubstratePreImage {
+            is_hashed: data_to_sign != payload,
+            payload,
+            data_to_sign,
+            signature_scheme: self.0.signature_scheme(),
+            signer: unsigned_tx.account().cloned(),
+        })
     }
 
     #[inline]
@@ -61,10 +70,32 @@ impl<T: SubstrateCoinEntry> SubstrateEntry<T> {
             signature,
             public_key,
         } = SingleSignaturePubkey::from_sign_pubkey_list(signatures, public_keys)?;
+
+        if self.0.signature_scheme() != SignatureScheme::Ed25519 {
+            return SigningError::err(SigningErrorType::Error_not_supported)
+                .context("Only Ed25519 signatures can be compiled");
+        }
         let signature = ed25519::Signature::try_from(signature.as_slice())?;
         let public_key = ed25519::sha512::PublicKey::try_from(public_key.as_slice())?;
 
-        let unsigned_tx = self.0.build_transaction(coin, Some(public_key), &input)?;
+        if let Some(expected_public_key) = self.0.get_public_key(coin, &input)? {
+            if expected_public_key != public_key {
+                return SigningError::err(SigningErrorType::Error_invalid_params)
+                    .context("The given public key doesn't match the signer's public key");
+            }
+        }
+
+        let unsigned_tx = self
+            .0
+            .build_transaction(coin, Some(public_key.clone()), &input)?;
+
+        // Make sure the signature is valid before producing the extrinsic.
+        let data_to_sign = unsigned_tx.encode_payload()?;
+        if !public_key.verify(signature.clone(), data_to_sign) {
+            return SigningError::err(SigningErrorType::Error_signing)
+                .context("The signature doesn't match the transaction pre-image");
+        }
+
         let signed_tx = unsigned_tx.into_signed(signature)?;
         Ok(RawOwned::new(signed_tx))
     }
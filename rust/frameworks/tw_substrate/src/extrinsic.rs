@@ -130,7 +130,7 @@ pub struct TransactionBuilder {
     /// Additional data attached to the transaction.
     extensions: TxExtensionData,
     /// The sender's address.
-    account: MultiAddress,
+    account: Option<MultiAddress>,
 }
 
 impl TransactionBuilder {
@@ -148,7 +148,12 @@ impl TransactionBuilder {
 
     /// Sets the sender's account.
     pub fn set_account(&mut self, account: AccountId) {
-        self.account = MultiAddress::new(account, self.multi_address);
+        self.account = Some(MultiAddress::new(account, self.multi_address));
+    }
+
+    /// Returns the sender's account if it has been set.
+    pub fn account(&self) -> Option<&AccountId> {
+        self.account.as_ref().map(MultiAddress::account)
     }
 
     /// Adds an extension to the transaction.
@@ -156,20 +161,19 @@ impl TransactionBuilder {
         extension.encode(&mut self.extensions);
     }
 
-    /// Encodes the payload that needs to be signed.
-    /// Returns the encoded bytes or a hash of the payload if it exceeds MAX_PAYLOAD_SIZE.
-    pub fn encode_payload(&self) -> Result<Vec<u8>, KeyPairError> {
+    /// Encodes the full payload without hashing it.
+    pub fn encode_raw_payload(&self) -> Vec<u8> {
         // SCALE encode the payload that needs to be signed: (call, extensions.data, extensions.signed).
         let mut payload = self.call.to_scale();
         self.extensions.data.to_scale_into(&mut payload);
         self.extensions.signed.to_scale_into(&mut payload);
+        payload
+    }
 
-        // if the payload is large then we sign a hash of the payload.
-        if payload.len() > MAX_PAYLOAD_SIZE {
-            Ok(blake2_b(&payload, PAYLOAD_HASH_SIZE).map_err(|_| KeyPairError::InternalError)?)
-        } else {
-            Ok(payload)
-        }
+    /// Encodes the payload that needs to be signed.
+    /// Returns the encoded bytes or a hash of the payload if it exceeds MAX_PAYLOAD_SIZE.
+    pub fn encode_payload(&self) -> Result<Vec<u8>, KeyPairError> {
+        hash_payload_if_needed(self.encode_raw_payload())
     }
 
     /// Signs the transaction with the given keypair.
@@ -182,7 +186,7 @@ impl TransactionBuilder {
     /// Creates a signed extrinsic using a pre-computed signature.
     pub fn into_signed(self, signature: Signature) -> Result<ExtrinsicV4, KeyPairError> {
         Ok(ExtrinsicV4::signed(
-            self.account,
+            self.account.unwrap_or_default(),
             signature.into(),
             self.extensions.data,
             self.call,
@@ -190,6 +194,17 @@ impl TransactionBuilder {
     }
 }
 
+/// Returns a Blake2b-256 hash of the payload if it exceeds MAX_PAYLOAD_SIZE,
+/// or the payload itself otherwise.
+pub fn hash_payload_if_needed(payload: Vec<u8>) -> Result<Vec<u8>, KeyPairError> {
+    // if the payload is large then we sign a hash of the payload.
+    if payload.len() > MAX_PAYLOAD_SIZE {
+        blake2_b(&payload, PAYLOAD_HASH_SIZE).map_err(|_| KeyPairError::InternalError)
+    } else {
+        Ok(payload)
+    }
+}
+
 impl_struct_scale!(
     #[derive(Clone, Debug)]
     pub struct ExtrinsicSignature {
@@ -13,7 +13,7 @@
 //!
 //! ```rust,ignore
 //! use tw_proto::MyChain::Proto;
-//! use tw_substrate::substrate_coin_entry::SubstrateCoinEntry;
+//! use tw_substrate::substrate_coin_entry::{SubstrateCoinEntry, SubstratePreImage};
 //! use tw_substrate::TransactionBuilder;
 //! use tw_keypair::ed25519::sha512::{KeyPair, PublicKey};
 //! use tw_coin_entry::{coin_context::CoinContext, error::SigningResult};
@@ -68,12 +68,14 @@
 //!     fn presigning_output(
 //!         &self,
 //!         _coin: &dyn CoinContext,
-//!         result: SigningResult<RawOwned>,
+//!         result: SigningResult<SubstratePreImage>,
 //!     ) -> Self::PreSigningOutput {
 //!         let mut output = Proto::PreSigningOutput::new();
 //!         match result {
-//!             Ok(encoded) => {
-//!                 output.data = encoded.to_vec();
+//!             Ok(pre_image) => {
+//!                 output.data_hash = pre_image.data_to_sign;
+//!                 output.data = pre_image.payload;
+//!                 output.is_hashed = pre_image.is_hashed;
 //!             }
 //!             Err(e) => {
 //!                 output.error = true;
@@ -88,18 +90,45 @@
 //! The example above demonstrates:
 //! * Implementing the required associated types for protocol buffer messages
 //! * Converting private keys to keypairs
+//! * Exposing the pre-image to external signers
 //! * Building transactions using chain-specific context and call encoding
 //! * Handling success and error cases in output generation
 //!
 //! Once implemented, the entry can be wrapped with `SubstrateEntry<T>` to provide
 //! the complete `CoinEntry` implementation.
 
-use crate::TransactionBuilder;
+use crate::{SubstrateAddress, TransactionBuilder};
 use tw_coin_entry::{coin_context::CoinContext, error::prelude::*};
 use tw_keypair::ed25519::sha512::{KeyPair, PublicKey};
+use tw_keypair::traits::KeyPairTrait;
 use tw_proto::{MessageRead, MessageWrite};
 use tw_scale::RawOwned;
 
+/// Signature scheme expected by a Substrate-based chain.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SignatureScheme {
+    #[default]
+    Ed25519,
+    Sr25519,
+    Ecdsa,
+}
+
+/// Pre-image of an unsigned transaction that needs to be signed by an external signer.
+#[derive(Clone, Debug)]
+pub struct SubstratePreImage {
+    /// SCALE-encoded signing payload, never hashed.
+    pub payload: Vec<u8>,
+    /// Data that must be signed: Blake2b-256 hash of the `payload`
+    /// if it exceeds [`crate::MAX_PAYLOAD_SIZE`], or the `payload` itself otherwise.
+    pub data_to_sign: Vec<u8>,
+    /// Whether `data_to_sign` is a hash of the `payload`.
+    pub is_hashed: bool,
+    /// Signature scheme expected for the `data_to_sign`.
+    pub signature_scheme: SignatureScheme,
+    /// Signer address if the public key is known.
+    pub signer: Option<SubstrateAddress>,
+}
+
 /// Trait for implementing a Substrate-based chain entry.
 ///
 /// This trait provides the core functionality needed for Substrate-based chains,
@@ -131,6 +160,33 @@ pub trait SubstrateCoinEntry {
         input: &Self::SigningInput<'_>,
     ) -> SigningResult<KeyPair>;
 
+    /// Retrieves the signer's public key from the signing input if it is known.
+    ///
+    /// By default, the public key is derived from the private key if present.
+    /// Override this method to support public-key-only (external signing) flows.
+    ///
+    /// # Parameters
+    /// * `coin`: The coin context
+    /// * `input`: The signing input containing private or public key data
+    ///
+    /// # Returns
+    /// Returns a `SigningResult` containing the Ed25519 public key if known.
+    fn get_public_key(
+        &self,
+        coin: &dyn CoinContext,
+        input: &Self::SigningInput<'_>,
+    ) -> SigningResult<Option<PublicKey>> {
+        Ok(self
+            .get_keypair(coin, input)
+            .ok()
+            .map(|keypair| keypair.public().clone()))
+    }
+
+    /// Returns the signature scheme expected by the chain.
+    fn signature_scheme(&self) -> SignatureScheme {
+        SignatureScheme::Ed25519
+    }
+
     /// Builds an unsigned transaction from the input data.
     ///
     /// The returned transaction builder can be used to sign the transaction.
@@ -167,13 +223,13 @@ pub trait SubstrateCoinEntry {
     ///
     /// # Parameters
     /// * `coin`: The coin context
-    /// * `result`: The result from building the transaction.
+    /// * `result`: The pre-image of the unsigned transaction.
     ///
     /// # Returns
     /// Returns the protobuf pre-signing output message.
     fn presigning_output(
         &self,
         coin: &dyn CoinContext,
-        result: SigningResult<RawOwned>,
+        result: SigningResult<SubstratePreImage>,
     ) -> Self::PreSigningOutput;
 }
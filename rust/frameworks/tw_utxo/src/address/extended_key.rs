@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! BIP-32 extended public keys with
+//! [SLIP-132](https://github.com/satoshilabs/slips/blob/master/slip-0132.md) version bytes.
+//!
+//! Allows watch-only services to enumerate receive and change addresses of an account
+//! without having access to the private key.
+
+use crate::address::legacy::LegacyAddress;
+use crate::address::segwit::SegwitAddress;
+use crate::address::standard_bitcoin::StandardBitcoinAddress;
+use crate::address::taproot::TaprootAddress;
+use crate::script::standard_script::conditions;
+use bitcoin::bip32::{ChildNumber, ExtendedPubKey};
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::hasher::sha256_ripemd;
+use tw_hash::{H160, H264};
+use tw_keypair::ecdsa::secp256k1;
+
+const EXTENDED_KEY_LEN: usize = 78;
+const VERSION_LEN: usize = 4;
+
+/// Script type of the addresses derived from an extended public key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AddressType {
+    /// BIP-44 P2PKH.
+    P2PKH,
+    /// BIP-49 P2WPKH nested in P2SH.
+    P2SHP2WPKH,
+    /// BIP-84 P2WPKH.
+    P2WPKH,
+    /// BIP-86 P2TR key-path.
+    P2TR,
+}
+
+/// SLIP-132 extended public key version bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExtendedKeyVersion {
+    Xpub,
+    Ypub,
+    Zpub,
+    Tpub,
+    Upub,
+    Vpub,
+}
+
+impl ExtendedKeyVersion {
+    pub fn from_bytes(version: u32) -> Option<ExtendedKeyVersion> {
+        match version {
+            0x0488_B21E => Some(ExtendedKeyVersion::Xpub),
+            0x049D_7CB2 => Some(ExtendedKeyVersion::Ypub),
+            0x04B2_4746 => Some(ExtendedKeyVersion::Zpub),
+            0x0435_87CF => Some(ExtendedKeyVersion::Tpub),
+            0x044A_5262 => Some(ExtendedKeyVersion::Upub),
+            0x045F_1CF6 => Some(ExtendedKeyVersion::Vpub),
+            _ => None,
+        }
+    }
+
+    pub fn to_bytes(self) -> u32 {
+        match self {
+            ExtendedKeyVersion::Xpub => 0x0488_B21E,
+            ExtendedKeyVersion::Ypub => 0x049D_7CB2,
+            ExtendedKeyVersion::Zpub => 0x04B2_4746,
+            ExtendedKeyVersion::Tpub => 0x0435_87CF,
+            ExtendedKeyVersion::Upub => 0x044A_5262,
+            ExtendedKeyVersion::Vpub => 0x045F_1CF6,
+        }
+    }
+
+    /// Returns the version for the given address type and network.
+    /// There is no dedicated SLIP-132 version for Taproot, so `xpub`/`tpub` is used.
+    pub fn with_address_type(address_type: AddressType, testnet: bool) -> ExtendedKeyVersion {
+        match (address_type, testnet) {
+            (AddressType::P2PKH | AddressType::P2TR, false) => ExtendedKeyVersion::Xpub,
+            (AddressType::P2SHP2WPKH, false) => ExtendedKeyVersion::Ypub,
+            (AddressType::P2WPKH, false) => ExtendedKeyVersion::Zpub,
+            (AddressType::P2PKH | AddressType::P2TR, true) => ExtendedKeyVersion::Tpub,
+            (AddressType::P2SHP2WPKH, true) => ExtendedKeyVersion::Upub,
+            (AddressType::P2WPKH, true) => ExtendedKeyVersion::Vpub,
+        }
+    }
+
+    pub fn is_testnet(self) -> bool {
+        matches!(
+            self,
+            ExtendedKeyVersion::Tpub | ExtendedKeyVersion::Upub | ExtendedKeyVersion::Vpub
+        )
+    }
+
+    /// Address type implied by the version bytes.
+    pub fn address_type(self) -> AddressType {
+        match self {
+            ExtendedKeyVersion::Xpub | ExtendedKeyVersion::Tpub => AddressType::P2PKH,
+            ExtendedKeyVersion::Ypub | ExtendedKeyVersion::Upub => AddressType::P2SHP2WPKH,
+            ExtendedKeyVersion::Zpub | ExtendedKeyVersion::Vpub => AddressType::P2WPKH,
+        }
+    }
+
+    /// BIP-32 version bytes of the same network that the `bitcoin` crate understands.
+    fn bip32_version(self) -> ExtendedKeyVersion {
+        if self.is_testnet() {
+            ExtendedKeyVersion::Tpub
+        } else {
+            ExtendedKeyVersion::Xpub
+        }
+    }
+}
+
+/// BIP-44 address chain, i.e. the fourth derivation path element.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AddressChain {
+    Receive = 0,
+    Change = 1,
+}
+
+/// Extended public key, usually of an account level (e.g. `m/84'/0'/0'`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtendedPublicKey {
+    version: ExtendedKeyVersion,
+    xpub: ExtendedPubKey,
+}
+
+impl ExtendedPublicKey {
+    pub fn version(&self) -> ExtendedKeyVersion {
+        self.version
+    }
+
+    /// Address type implied by the SLIP-132 version bytes.
+    pub fn address_type(&self) -> AddressType {
+        self.version.address_type()
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.xpub.depth
+    }
+
+    pub fn public_key(&self) -> H264 {
+        H264::from(self.xpub.public_key.serialize())
+    }
+
+    /// Derives a child extended public key at the given non-hardened `path`.
+    pub fn derive(&self, path: &[u32]) -> SigningResult<ExtendedPublicKey> {
+        let path = path
+            .iter()
+            .map(|index| {
+                ChildNumber::from_normal_idx(*index)
+                    .tw_err(|_| SigningErrorType::Error_not_supported)
+                    .context("Hardened derivation requires a private key")
+            })
+            .collect::<SigningResult<Vec<_>>>()?;
+
+        let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+        let xpub = self
+            .xpub
+            .derive_pub(&secp, &path)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Error deriving an extended public key")?;
+
+        Ok(ExtendedPublicKey {
+            version: self.version,
+            xpub,
+        })
+    }
+
+    /// Derives a public key at `<chain>/<index>` relative to this key.
+    pub fn derive_public_key(
+        &self,
+        chain: AddressChain,
+        index: u32,
+    ) -> SigningResult<secp256k1::PublicKey> {
+        self.derive(&[chain as u32, index])?.ecdsa_public_key()
+    }
+
+    /// Derives an address at `<chain>/<index>` relative to this key.
+    /// Address prefixes are taken from the `coin` context.
+    pub fn derive_address(
+        &self,
+        coin: &dyn CoinContext,
+        address_type: AddressType,
+        chain: AddressChain,
+        index: u32,
+    ) -> SigningResult<StandardBitcoinAddress> {
+        let public_key = self.derive_public_key(chain, index)?;
+        address_with_public_key(coin, address_type, &public_key)
+            .into_tw()
+            .with_context(|| format!("Error generating {address_type:?} address"))
+    }
+
+    /// Derives addresses at `<chain>/<index>` for each index of the given range.
+    pub fn derive_addresses(
+        &self,
+        coin: &dyn CoinContext,
+        address_type: AddressType,
+        chain: AddressChain,
+        indexes: Range<u32>,
+    ) -> SigningResult<Vec<StandardBitcoinAddress>> {
+        let chain_key = self.derive(&[chain as u32])?;
+        indexes
+            .map(|index| {
+                let public_key = chain_key.derive(&[index])?.ecdsa_public_key()?;
+                address_with_public_key(coin, address_type, &public_key)
+                    .into_tw()
+                    .with_context(|| format!("Error generating {address_type:?} address"))
+            })
+            .collect()
+    }
+
+    /// Serializes the key with other SLIP-132 version bytes, e.g. converts `zpub` to `xpub`.
+    /// The network of the `version` must match the network of the key.
+    pub fn to_string_with_version(&self, version: ExtendedKeyVersion) -> SigningResult<String> {
+        if version.is_testnet() != self.version.is_testnet() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Cannot change the network of an extended public key");
+        }
+        Ok(self.encode_with_version(version))
+    }
+
+    fn ecdsa_public_key(&self) -> SigningResult<secp256k1::PublicKey> {
+        secp256k1::PublicKey::try_from(self.public_key().as_slice())
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Derived an invalid public key")
+    }
+
+    fn encode_with_version(&self, version: ExtendedKeyVersion) -> String {
+        let mut data = self.xpub.encode();
+        data[..VERSION_LEN].copy_from_slice(&version.to_bytes().to_be_bytes());
+        bitcoin::base58::encode_check(&data)
+    }
+}
+
+impl FromStr for ExtendedPublicKey {
+    type Err = SigningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut data = bitcoin::base58::decode_check(s)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Invalid base58check extended public key")?;
+        if data.len() != EXTENDED_KEY_LEN {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Invalid extended public key length");
+        }
+
+        let mut version_bytes = [0; VERSION_LEN];
+        version_bytes.copy_from_slice(&data[..VERSION_LEN]);
+        let version = ExtendedKeyVersion::from_bytes(u32::from_be_bytes(version_bytes))
+            .or_tw_err(SigningErrorType::Error_not_supported)
+            .context("Unknown extended public key version")?;
+
+        // Replace SLIP-132 version bytes with the standard BIP-32 ones.
+        let bip32_version = version.bip32_version().to_bytes().to_be_bytes();
+        data[..VERSION_LEN].copy_from_slice(&bip32_version);
+
+        let xpub = ExtendedPubKey::decode(&data)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Invalid extended public key")?;
+        Ok(ExtendedPublicKey { version, xpub })
+    }
+}
+
+impl fmt::Display for ExtendedPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.encode_with_version(self.version))
+    }
+}
+
+fn address_with_public_key(
+    coin: &dyn CoinContext,
+    address_type: AddressType,
+    public_key: &secp256k1::PublicKey,
+) -> AddressResult<StandardBitcoinAddress> {
+    match address_type {
+        AddressType::P2PKH => {
+            let p2pkh_prefix = coin.p2pkh_prefix().ok_or(AddressError::InvalidRegistry)?;
+            LegacyAddress::p2pkh_with_public_key(p2pkh_prefix, public_key)
+                .map(StandardBitcoinAddress::Legacy)
+        },
+        AddressType::P2SHP2WPKH => {
+            let p2sh_prefix = coin.p2sh_prefix().ok_or(AddressError::InvalidRegistry)?;
+            let redeem_script = conditions::new_p2wpkh(&public_key_hash(public_key));
+            LegacyAddress::p2sh_with_prefix_byte(&redeem_script, p2sh_prefix)
+                .map(StandardBitcoinAddress::Legacy)
+        },
+        AddressType::P2WPKH => {
+            let hrp = coin.hrp().ok_or(AddressError::InvalidRegistry)?;
+            SegwitAddress::new(hrp, public_key_hash(public_key).to_vec())
+                .map(StandardBitcoinAddress::Segwit)
+        },
+        AddressType::P2TR => {
+            let hrp = coin.hrp().ok_or(AddressError::InvalidRegistry)?;
+            let no_merkle_root = None;
+            TaprootAddress::p2tr_with_public_key(hrp, &public_key.compressed(), no_merkle_root)
+                .map(StandardBitcoinAddress::Taproot)
+        },
+    }
+}
+
+fn public_key_hash(public_key: &secp256k1::PublicKey) -> H160 {
+    H160::try_from(sha256_ripemd(public_key.compressed().as_slice()).as_slice())
+        .expect("sha256_ripemd returns 20 bytes")
+}
@@ -3,8 +3,10 @@
 // Copyright © 2017 Trust Wallet.
 
 pub mod derivation;
+pub mod extended_key;
 pub mod legacy;
 pub mod segwit;
+pub mod silent_payment;
 pub mod standard_bitcoin;
 pub mod taproot;
 pub mod witness_program;
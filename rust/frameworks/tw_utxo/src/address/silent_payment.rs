@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use bech32::FromBase32;
+use std::fmt;
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H264;
+use tw_keypair::ecdsa;
+use tw_memory::Data;
+
+/// Silent payment address HRP on Bitcoin mainnet.
+/// cbindgen:ignore
+pub const SILENT_PAYMENT_MAINNET_HRP: &str = "sp";
+/// Silent payment address HRP on Bitcoin testnet and signet.
+/// cbindgen:ignore
+pub const SILENT_PAYMENT_TESTNET_HRP: &str = "tsp";
+/// Silent payment address HRP on Bitcoin regtest.
+/// cbindgen:ignore
+pub const SILENT_PAYMENT_REGTEST_HRP: &str = "sprt";
+
+/// cbindgen:ignore
+pub const SILENT_PAYMENT_V0: u8 = 0;
+/// Version 31 is reserved for a backward incompatible change.
+/// cbindgen:ignore
+pub const SILENT_PAYMENT_INVALID_VERSION: u8 = 31;
+
+/// `B_scan || B_spend` compressed public keys.
+const SILENT_PAYMENT_PAYLOAD_SIZE: usize = H264::LEN * 2;
+
+/// A BIP-352 silent payment address that consists of the receiver's scan and spend public keys:
+/// https://github.com/bitcoin/bips/blob/master/bip-0352.mediawiki#address-encoding
+///
+/// Unlike other Bitcoin addresses, it doesn't correspond to a `scriptPubkey`.
+/// Instead, a unique P2TR output key is derived from the transaction inputs for every payment.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SilentPaymentAddress {
+    hrp: String,
+    version: u8,
+    scan_pubkey: H264,
+    spend_pubkey: H264,
+}
+
+impl SilentPaymentAddress {
+    pub fn new(
+        hrp: String,
+        version: u8,
+        scan_pubkey: H264,
+        spend_pubkey: H264,
+    ) -> AddressResult<SilentPaymentAddress> {
+        if version >= SILENT_PAYMENT_INVALID_VERSION {
+            return Err(AddressError::Unsupported);
+        }
+
+        // Validate the public keys.
+        for pubkey in [&scan_pubkey, &spend_pubkey] {
+            ecdsa::secp256k1::PublicKey::try_from(pubkey.as_slice())
+                .map_err(|_| AddressError::InvalidInput)?;
+        }
+
+        Ok(SilentPaymentAddress {
+            hrp,
+            version,
+            scan_pubkey,
+            spend_pubkey,
+        })
+    }
+
+    pub fn from_str_checked(s: &str, expected_hrp: &str) -> AddressResult<SilentPaymentAddress> {
+        let address = Self::from_str(s)?;
+        if address.hrp != expected_hrp {
+            return Err(AddressError::InvalidHrp);
+        }
+        Ok(address)
+    }
+
+    pub fn hrp(&self) -> &str {
+        &self.hrp
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the `B_scan` public key.
+    pub fn scan_pubkey(&self) -> &H264 {
+        &self.scan_pubkey
+    }
+
+    /// Returns the `B_spend` public key.
+    pub fn spend_pubkey(&self) -> &H264 {
+        &self.spend_pubkey
+    }
+}
+
+impl FromStr for SilentPaymentAddress {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hrp, payload_u5, checksum_variant) =
+            bech32::decode(s).map_err(|_| AddressError::FromBech32Error)?;
+
+        if checksum_variant != bech32::Variant::Bech32m {
+            return Err(AddressError::InvalidInput);
+        }
+
+        if payload_u5.is_empty() {
+            return Err(AddressError::InvalidInput);
+        }
+
+        let (version, payload) = payload_u5.split_at(1);
+        let version = version[0].to_u8();
+        let payload = Data::from_base32(payload).map_err(|_| AddressError::FromBech32Error)?;
+
+        // V0 must contain the scan and spend public keys only,
+        // while newer versions are allowed to append data that should be ignored by V0 senders.
+        let is_valid_size = match version {
+            SILENT_PAYMENT_V0 => payload.len() == SILENT_PAYMENT_PAYLOAD_SIZE,
+            _ => payload.len() >= SILENT_PAYMENT_PAYLOAD_SIZE,
+        };
+        if !is_valid_size {
+            return Err(AddressError::InvalidInput);
+        }
+
+        let (scan_pubkey, rest) = payload.split_at(H264::LEN);
+        let scan_pubkey =
+            H264::try_from(scan_pubkey).expect("Payload size has been checked already");
+        let spend_pubkey =
+            H264::try_from(&rest[..H264::LEN]).expect("Payload size has been checked already");
+
+        SilentPaymentAddress::new(hrp, version, scan_pubkey, spend_pubkey)
+    }
+}
+
+impl fmt::Display for SilentPaymentAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let version_u5 = bech32::u5::try_from_u8(self.version).map_err(|_| fmt::Error)?;
+
+        let mut bech32_writer = bech32::Bech32Writer::new(&self.hrp, bech32::Variant::Bech32m, f)?;
+        bech32::WriteBase32::write_u5(&mut bech32_writer, version_u5)?;
+        bech32::ToBase32::write_base32(&self.scan_pubkey.as_slice(), &mut bech32_writer)?;
+        bech32::ToBase32::write_base32(&self.spend_pubkey.as_slice(), &mut bech32_writer)?;
+        bech32_writer.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::DecodeHex;
+
+    const ADDRESS: &str = "sp1qqgste7k9hx0qftg6qmwlkqtwuy6cycyavzmzj85c6qdfhjdpdjtdgqjuexzk6murw56suy3e0rd2cgqvycxttddwsvgxe2usfpxumr70xc9pkqwv";
+    const SCAN_PUBKEY: &str = "0220bcfac5b99e04ad1a06ddfb016ee13582609d60b6291e98d01a9bc9a16c96d4";
+    const SPEND_PUBKEY: &str = "025cc9856d6f8375350e123978daac200c260cb5b5ae83106cab90484dcd8fcf36";
+
+    #[test]
+    fn test_silent_payment_address_to_from_str() {
+        let address = SilentPaymentAddress::from_str(ADDRESS).unwrap();
+        assert_eq!(address.hrp(), SILENT_PAYMENT_MAINNET_HRP);
+        assert_eq!(address.version(), SILENT_PAYMENT_V0);
+        assert_eq!(
+            address.scan_pubkey().as_slice(),
+            SCAN_PUBKEY.decode_hex().unwrap()
+        );
+        assert_eq!(
+            address.spend_pubkey().as_slice(),
+            SPEND_PUBKEY.decode_hex().unwrap()
+        );
+        assert_eq!(address.to_string(), ADDRESS);
+
+        // Uppercase addresses are valid too.
+        let address = SilentPaymentAddress::from_str(&ADDRESS.to_uppercase()).unwrap();
+        assert_eq!(address.to_string(), ADDRESS);
+
+        SilentPaymentAddress::from_str_checked(ADDRESS, SILENT_PAYMENT_MAINNET_HRP).unwrap();
+        assert_eq!(
+            SilentPaymentAddress::from_str_checked(ADDRESS, SILENT_PAYMENT_TESTNET_HRP),
+            Err(AddressError::InvalidHrp)
+        );
+    }
+
+    #[test]
+    fn test_silent_payment_address_new() {
+        let scan_pubkey = H264::try_from(SCAN_PUBKEY.decode_hex().unwrap().as_slice()).unwrap();
+        let spend_pubkey = H264::try_from(SPEND_PUBKEY.decode_hex().unwrap().as_slice()).unwrap();
+
+        let address = SilentPaymentAddress::new(
+            SILENT_PAYMENT_MAINNET_HRP.to_string(),
+            SILENT_PAYMENT_V0,
+            scan_pubkey,
+            spend_pubkey,
+        )
+        .unwrap();
+        assert_eq!(address.to_string(), ADDRESS);
+
+        // Version 31 is not allowed.
+        SilentPaymentAddress::new(
+            SILENT_PAYMENT_MAINNET_HRP.to_string(),
+            SILENT_PAYMENT_INVALID_VERSION,
+            scan_pubkey,
+            spend_pubkey,
+        )
+        .unwrap_err();
+
+        // Invalid public key.
+        SilentPaymentAddress::new(
+            SILENT_PAYMENT_MAINNET_HRP.to_string(),
+            SILENT_PAYMENT_V0,
+            H264::default(),
+            spend_pubkey,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_silent_payment_address_from_str_invalid() {
+        // Bech32 checksum instead of Bech32m.
+        SilentPaymentAddress::from_str("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").unwrap_err();
+        // Taproot address.
+        SilentPaymentAddress::from_str(
+            "bc1ptmsk7c2yut2xah4pgflpygh2s7fh0cpfkrza9cjj29awapv53mrslgd5cf",
+        )
+        .unwrap_err();
+        // Invalid checksum.
+        let mut invalid = ADDRESS.to_string();
+        invalid.pop();
+        invalid.push('q');
+        SilentPaymentAddress::from_str(&invalid).unwrap_err();
+    }
+}
@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Descriptor checksum as specified in
+//! [BIP-380](https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki#checksum).
+
+use tw_coin_entry::error::prelude::*;
+
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+pub const CHECKSUM_LENGTH: usize = 8;
+pub const CHECKSUM_SEPARATOR: char = '#';
+
+fn poly_mod(mut c: u64, val: u64) -> u64 {
+    let c0 = c >> 35;
+    c = ((c & 0x7ffffffff) << 5) ^ val;
+    if c0 & 1 != 0 {
+        c ^= 0xf5dee51989;
+    }
+    if c0 & 2 != 0 {
+        c ^= 0xa9fdca3312;
+    }
+    if c0 & 4 != 0 {
+        c ^= 0x1bab10e32d;
+    }
+    if c0 & 8 != 0 {
+        c ^= 0x3706b1677a;
+    }
+    if c0 & 16 != 0 {
+        c ^= 0x644d626ffd;
+    }
+    c
+}
+
+/// Computes an 8-character checksum of the given descriptor (without the `#` suffix).
+pub fn descriptor_checksum(desc: &str) -> SigningResult<String> {
+    let mut c = 1_u64;
+    let mut cls = 0_u64;
+    let mut cls_count = 0;
+
+    for ch in desc.chars() {
+        let pos = INPUT_CHARSET
+            .find(ch)
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .with_context(|| format!("Invalid descriptor character: '{ch}'"))?
+            as u64;
+
+        // Emit a symbol for the position inside the group, for every character.
+        c = poly_mod(c, pos & 31);
+        // Accumulate the group numbers.
+        cls = cls * 3 + (pos >> 5);
+        cls_count += 1;
+        if cls_count == 3 {
+            // Emit an extra symbol representing the group numbers, for every 3 characters.
+            c = poly_mod(c, cls);
+            cls = 0;
+            cls_count = 0;
+        }
+    }
+    if cls_count > 0 {
+        c = poly_mod(c, cls);
+    }
+    // Shift further to determine the checksum.
+    for _ in 0..CHECKSUM_LENGTH {
+        c = poly_mod(c, 0);
+    }
+    // Prevent appending zeroes from not affecting the checksum.
+    c ^= 1;
+
+    let checksum = (0..CHECKSUM_LENGTH)
+        .map(|j| CHECKSUM_CHARSET[((c >> (5 * (7 - j))) & 31) as usize] as char)
+        .collect();
+    Ok(checksum)
+}
+
+/// Splits the given `desc#checksum` string, verifies the checksum if present,
+/// and returns the descriptor without the checksum.
+pub fn split_and_verify_checksum(s: &str) -> SigningResult<&str> {
+    let Some((desc, checksum)) = s.split_once(CHECKSUM_SEPARATOR) else {
+        // Checksum is optional.
+        return Ok(s);
+    };
+
+    if checksum.len() != CHECKSUM_LENGTH {
+        return SigningError::err(SigningErrorType::Error_invalid_params)
+            .context("Descriptor checksum must be 8 characters long");
+    }
+    let expected = descriptor_checksum(desc)?;
+    if checksum != expected {
+        return SigningError::err(SigningErrorType::Error_invalid_params).context(format!(
+            "Invalid descriptor checksum: expected '{expected}', found '{checksum}'"
+        ));
+    }
+    Ok(desc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descriptor_checksum() {
+        assert_eq!(descriptor_checksum("raw(deadbeef)").unwrap(), "89f8spxm");
+        assert_eq!(
+            split_and_verify_checksum("raw(deadbeef)#89f8spxm").unwrap(),
+            "raw(deadbeef)"
+        );
+        assert_eq!(
+            split_and_verify_checksum("raw(deadbeef)").unwrap(),
+            "raw(deadbeef)"
+        );
+        split_and_verify_checksum("raw(deadbeef)#89f8spxn").unwrap_err();
+        split_and_verify_checksum("raw(deadbeef)#89f8spx").unwrap_err();
+    }
+}
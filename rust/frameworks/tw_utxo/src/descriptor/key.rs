@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Key expressions as specified in
+//! [BIP-380](https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki#key-expressions).
+
+use bitcoin::bip32::{ChildNumber, ExtendedPubKey};
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::*;
+use tw_encoding::hex::DecodeHex;
+use tw_hash::{H256, H264, H32};
+use tw_keypair::ecdsa::secp256k1;
+use tw_memory::Data;
+
+const HARDENED_MARKERS: [char; 3] = ['\'', 'h', 'H'];
+const WILDCARD: &str = "*";
+const COMPRESSED_PUBLIC_KEY_LEN: usize = H264::LEN;
+const UNCOMPRESSED_PUBLIC_KEY_LEN: usize = 65;
+const XONLY_PUBLIC_KEY_LEN: usize = H256::LEN;
+const COMPRESSED_EVEN_PREFIX: u8 = 0x02;
+const HARDENED_FLAG: u32 = 1 << 31;
+
+/// Script context the key expression is used in.
+/// Determines what public key encodings are allowed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyContext {
+    /// `pkh()`, `sh()` - compressed and uncompressed keys are allowed.
+    Legacy,
+    /// `wpkh()`, `wsh()` - compressed keys only.
+    Segwit,
+    /// `tr()` - compressed and x-only keys are allowed.
+    Taproot,
+}
+
+/// Key origin information: `[fingerprint/path]`.
+/// It doesn't affect the derived script, but is kept to be reported back to the caller.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyOrigin {
+    pub fingerprint: H32,
+    /// Derivation path elements. Hardened elements have the highest bit set.
+    pub path: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DescriptorKeyType {
+    /// Hex-encoded public key.
+    Single(Data),
+    /// Extended public key with an optional non-hardened derivation path.
+    Extended {
+        xpub: ExtendedPubKey,
+        path: Vec<ChildNumber>,
+        wildcard: bool,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DescriptorPublicKey {
+    pub origin: Option<KeyOrigin>,
+    pub key: DescriptorKeyType,
+}
+
+impl DescriptorPublicKey {
+    /// Parses a key expression allowed within the given `ctx`.
+    pub fn parse(s: &str, ctx: KeyContext) -> SigningResult<DescriptorPublicKey> {
+        let (origin, key_str) = match s.strip_prefix('[') {
+            Some(rest) => {
+                let (origin_str, key_str) = rest
+                    .split_once(']')
+                    .or_tw_err(SigningErrorType::Error_invalid_params)
+                    .context("Key origin is not closed with ']'")?;
+                (Some(parse_key_origin(origin_str)?), key_str)
+            },
+            None => (None, s),
+        };
+
+        let key = if is_hex_key(key_str) {
+            DescriptorKeyType::Single(parse_single_key(key_str, ctx)?)
+        } else {
+            parse_extended_key(key_str)?
+        };
+        Ok(DescriptorPublicKey { origin, key })
+    }
+
+    /// Whether the key expression ends with `/*`.
+    pub fn has_wildcard(&self) -> bool {
+        matches!(self.key, DescriptorKeyType::Extended { wildcard: true, .. })
+    }
+
+    /// Derives a public key at the given `index`.
+    /// The `index` is ignored if the key expression has no wildcard.
+    ///
+    /// Returns the public key bytes as they should be used in a script:
+    /// 33 bytes (or 65 for uncompressed single keys), or 32 bytes for x-only single keys.
+    pub fn derive_public_key(&self, index: u32) -> SigningResult<Data> {
+        match self.key {
+            DescriptorKeyType::Single(ref pubkey) => Ok(pubkey.clone()),
+            DescriptorKeyType::Extended {
+                ref xpub,
+                ref path,
+                wildcard,
+            } => {
+                let mut path = path.clone();
+                if wildcard {
+                    let child = ChildNumber::from_normal_idx(index)
+                        .tw_err(|_| SigningErrorType::Error_invalid_params)
+                        .context("Derivation index must be non-hardened")?;
+                    path.push(child);
+                }
+
+                let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+                let derived = xpub
+                    .derive_pub(&secp, &path)
+                    .tw_err(|_| SigningErrorType::Error_invalid_params)
+                    .context("Error deriving an extended public key")?;
+                Ok(derived.public_key.serialize().to_vec())
+            },
+        }
+    }
+
+    /// Derives a compressed public key at the given `index`.
+    /// X-only keys are extended with an even `0x02` prefix.
+    pub fn derive_compressed(&self, index: u32) -> SigningResult<H264> {
+        let pubkey = self.derive_public_key(index)?;
+        match pubkey.len() {
+            COMPRESSED_PUBLIC_KEY_LEN => {
+                Ok(H264::try_from(pubkey.as_slice()).expect("Expected exactly 33 bytes"))
+            },
+            XONLY_PUBLIC_KEY_LEN => {
+                let mut compressed = H264::default();
+                compressed[0] = COMPRESSED_EVEN_PREFIX;
+                compressed[1..].copy_from_slice(&pubkey);
+                Ok(compressed)
+            },
+            _ => SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Expected a compressed public key"),
+        }
+    }
+
+    /// Derives an x-only public key at the given `index`.
+    pub fn derive_xonly(&self, index: u32) -> SigningResult<H256> {
+        let compressed = self.derive_compressed(index)?;
+        Ok(H256::try_from(&compressed[1..]).expect("Expected exactly 32 bytes"))
+    }
+
+    /// Derives an ECDSA public key at the given `index`.
+    pub fn derive_ecdsa(&self, index: u32) -> SigningResult<secp256k1::PublicKey> {
+        let pubkey = self.derive_public_key(index)?;
+        secp256k1::PublicKey::try_from(pubkey.as_slice())
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Invalid ECDSA public key")
+    }
+}
+
+fn is_hex_key(s: &str) -> bool {
+    s.chars().all(|ch| ch.is_ascii_hexdigit())
+}
+
+fn parse_single_key(s: &str, ctx: KeyContext) -> SigningResult<Data> {
+    let pubkey = s
+        .decode_hex()
+        .tw_err(|_| SigningErrorType::Error_invalid_params)
+        .context("Invalid hex public key")?;
+
+    let allowed = match (ctx, pubkey.len()) {
+        (_, COMPRESSED_PUBLIC_KEY_LEN) => true,
+        (KeyContext::Legacy, UNCOMPRESSED_PUBLIC_KEY_LEN) => true,
+        (KeyContext::Taproot, XONLY_PUBLIC_KEY_LEN) => true,
+        _ => false,
+    };
+    if !allowed {
+        return SigningError::err(SigningErrorType::Error_invalid_params).context(format!(
+            "Public key of {} bytes is not allowed in {ctx:?} context",
+            pubkey.len()
+        ));
+    }
+
+    // Validate the public key is a valid point.
+    let to_validate = if pubkey.len() == XONLY_PUBLIC_KEY_LEN {
+        let mut compressed = vec![COMPRESSED_EVEN_PREFIX];
+        compressed.extend_from_slice(&pubkey);
+        compressed
+    } else {
+        pubkey.clone()
+    };
+    secp256k1::PublicKey::try_from(to_validate.as_slice())
+        .tw_err(|_| SigningErrorType::Error_invalid_params)
+        .context("Invalid public key")?;
+
+    Ok(pubkey)
+}
+
+fn parse_extended_key(s: &str) -> SigningResult<DescriptorKeyType> {
+    let mut elements = s.split('/');
+    let xpub_str = elements.next().unwrap_or_default();
+    let xpub = ExtendedPubKey::from_str(xpub_str)
+        .tw_err(|_| SigningErrorType::Error_invalid_params)
+        .with_context(|| format!("Invalid extended public key: '{xpub_str}'"))?;
+
+    let mut path = Vec::new();
+    let mut wildcard = false;
+    for element in elements {
+        if wildcard {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Wildcard must be the last derivation path element");
+        }
+        if element == WILDCARD {
+            wildcard = true;
+            continue;
+        }
+
+        let (index, hardened) = parse_path_element(element)?;
+        if hardened {
+            return SigningError::err(SigningErrorType::Error_not_supported)
+                .context("Hardened derivation requires a private key");
+        }
+        let child = ChildNumber::from_normal_idx(index)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .with_context(|| format!("Invalid derivation path element: '{element}'"))?;
+        path.push(child);
+    }
+
+    Ok(DescriptorKeyType::Extended {
+        xpub,
+        path,
+        wildcard,
+    })
+}
+
+fn parse_key_origin(s: &str) -> SigningResult<KeyOrigin> {
+    let mut elements = s.split('/');
+
+    let fingerprint_str = elements.next().unwrap_or_default();
+    let fingerprint = H32::from_str(fingerprint_str)
+        .tw_err(|_| SigningErrorType::Error_invalid_params)
+        .with_context(|| format!("Invalid key origin fingerprint: '{fingerprint_str}'"))?;
+
+    let path = elements
+        .map(|element| {
+            let (index, hardened) = parse_path_element(element)?;
+            if index >= HARDENED_FLAG {
+                return SigningError::err(SigningErrorType::Error_invalid_params)
+                    .with_context(|| format!("Invalid derivation path element: '{element}'"));
+            }
+            Ok(if hardened {
+                index | HARDENED_FLAG
+            } else {
+                index
+            })
+        })
+        .collect::<SigningResult<Vec<_>>>()?;
+
+    Ok(KeyOrigin { fingerprint, path })
+}
+
+/// Returns a derivation index and whether it's hardened.
+fn parse_path_element(element: &str) -> SigningResult<(u32, bool)> {
+    let (index_str, hardened) = match element.strip_suffix(HARDENED_MARKERS) {
+        Some(index_str) => (index_str, true),
+        None => (element, false),
+    };
+    let index = u32::from_str(index_str)
+        .tw_err(|_| SigningErrorType::Error_invalid_params)
+        .with_context(|| format!("Invalid derivation path element: '{element}'"))?;
+    Ok((index, hardened))
+}
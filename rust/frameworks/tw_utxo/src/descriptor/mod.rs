@@ -0,0 +1,326 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Output script descriptors as specified in
+//! [BIP-380..386](https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki).
+//!
+//! Supported expressions:
+//! * `pkh(KEY)`
+//! * `wpkh(KEY)`
+//! * `sh(wpkh(KEY))`
+//! * `wsh(multi(k,KEY_1,...,KEY_n))` and `wsh(sortedmulti(k,KEY_1,...,KEY_n))`
+//! * `tr(KEY)` and `tr(KEY,TREE)` where `TREE` consists of `pk()`, `multi_a()` and `sortedmulti_a()` leaves.
+
+use crate::address::legacy::LegacyAddress;
+use crate::address::segwit::SegwitAddress;
+use crate::address::standard_bitcoin::StandardBitcoinAddress;
+use crate::address::taproot::TaprootAddress;
+use crate::descriptor::checksum::{descriptor_checksum, split_and_verify_checksum};
+use crate::descriptor::key::{DescriptorPublicKey, KeyContext};
+use crate::descriptor::parse_utils::{parse_multi_args, split_args, strip_function};
+use crate::descriptor::tap_tree::TapTreeDescriptor;
+use crate::script::standard_script::conditions;
+use crate::script::tap_tree::{TapLeaf, TapTreeBuilder};
+use crate::script::Script;
+use std::str::FromStr;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::hasher::sha256_ripemd;
+use tw_hash::sha2::sha256;
+use tw_hash::{H160, H256, H264};
+use tw_keypair::schnorr;
+
+pub mod checksum;
+pub mod key;
+pub mod parse_utils;
+pub mod tap_tree;
+
+/// Maximum number of keys within `multi()` and `sortedmulti()` under `wsh()`.
+pub const MAX_PUBKEYS_PER_MULTISIG: usize = 20;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DescriptorType {
+    /// `pkh(KEY)`
+    Pkh(DescriptorPublicKey),
+    /// `wpkh(KEY)`
+    Wpkh(DescriptorPublicKey),
+    /// `sh(wpkh(KEY))`
+    ShWpkh(DescriptorPublicKey),
+    /// `wsh(multi(k,KEY_1,...,KEY_n))` or `wsh(sortedmulti(k,KEY_1,...,KEY_n))`
+    WshMulti {
+        threshold: usize,
+        keys: Vec<DescriptorPublicKey>,
+        sorted: bool,
+    },
+    /// `tr(KEY)` or `tr(KEY,TREE)`
+    Tr {
+        internal_key: DescriptorPublicKey,
+        tree: Option<TapTreeDescriptor>,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Descriptor {
+    /// Descriptor string without a checksum.
+    desc: String,
+    ty: DescriptorType,
+}
+
+impl Descriptor {
+    pub fn descriptor_type(&self) -> &DescriptorType {
+        &self.ty
+    }
+
+    /// Returns the descriptor checksum.
+    pub fn checksum(&self) -> String {
+        descriptor_checksum(&self.desc).expect("Descriptor has been validated already")
+    }
+
+    /// Returns the descriptor string with a `#checksum` suffix.
+    pub fn to_string_with_checksum(&self) -> String {
+        format!("{}#{}", self.desc, self.checksum())
+    }
+
+    /// Whether any of the key expressions ends with `/*`,
+    /// i.e. whether the descriptor describes a range of scripts.
+    pub fn has_wildcard(&self) -> bool {
+        match self.ty {
+            DescriptorType::Pkh(ref key)
+            | DescriptorType::Wpkh(ref key)
+            | DescriptorType::ShWpkh(ref key) => key.has_wildcard(),
+            DescriptorType::WshMulti { ref keys, .. } => keys.iter().any(|key| key.has_wildcard()),
+            DescriptorType::Tr {
+                ref internal_key,
+                ref tree,
+            } => {
+                internal_key.has_wildcard()
+                    || tree.as_ref().map_or(false, TapTreeDescriptor::has_wildcard)
+            },
+        }
+    }
+
+    /// Derives a `scriptPubKey` at the given `index`.
+    /// The `index` is ignored if the descriptor has no wildcard.
+    pub fn script_pubkey_at(&self, index: u32) -> SigningResult<Script> {
+        match self.ty {
+            DescriptorType::Pkh(ref key) => {
+                let pubkey_hash = pubkey_hash(&key.derive_public_key(index)?);
+                Ok(conditions::new_p2pkh(&pubkey_hash))
+            },
+            DescriptorType::Wpkh(ref key) => {
+                let pubkey_hash = pubkey_hash(key.derive_compressed(index)?.as_slice());
+                Ok(conditions::new_p2wpkh(&pubkey_hash))
+            },
+            DescriptorType::ShWpkh(ref key) => {
+                let pubkey_hash = pubkey_hash(key.derive_compressed(index)?.as_slice());
+                let redeem_script = conditions::new_p2wpkh(&pubkey_hash);
+                Ok(conditions::new_p2sh(&script_hash(&redeem_script)))
+            },
+            DescriptorType::WshMulti {
+                threshold,
+                ref keys,
+                sorted,
+            } => {
+                let witness_script = multi_witness_script_at(threshold, keys, sorted, index)?;
+                let script_hash = H256::try_from(sha256(witness_script.as_slice()).as_slice())
+                    .expect("sha256 must return exactly 32 bytes");
+                Ok(conditions::new_p2wsh(&script_hash))
+            },
+            DescriptorType::Tr {
+                ref internal_key,
+                ref tree,
+            } => {
+                let internal_key = internal_key.derive_compressed(index)?;
+                match tree {
+                    Some(tree) => {
+                        let merkle_root = tap_tree_merkle_root(&internal_key, tree, index)?;
+                        Ok(conditions::new_p2tr_script_path(
+                            &internal_key,
+                            &merkle_root,
+                        ))
+                    },
+                    None => Ok(conditions::new_p2tr_key_path(&internal_key)),
+                }
+            },
+        }
+    }
+
+    /// Computes the merkle root of the `tr(KEY,TREE)` script tree at the given `index`.
+    /// Returns `None` if the descriptor is not `tr()` or has no script tree.
+    pub fn tap_tree_merkle_root_at(&self, index: u32) -> SigningResult<Option<H256>> {
+        match self.ty {
+            DescriptorType::Tr {
+                ref internal_key,
+                tree: Some(ref tree),
+            } => {
+                let internal_key = internal_key.derive_compressed(index)?;
+                tap_tree_merkle_root(&internal_key, tree, index).map(Some)
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Derives an address at the given `index`.
+    /// The `index` is ignored if the descriptor has no wildcard.
+    pub fn address_at(
+        &self,
+        coin: &dyn CoinContext,
+        index: u32,
+    ) -> SigningResult<StandardBitcoinAddress> {
+        let script_pubkey = self.script_pubkey_at(index)?;
+
+        if let Some(pubkey_hash) = conditions::match_p2pkh(&script_pubkey) {
+            let prefix = coin
+                .p2pkh_prefix()
+                .or_tw_err(SigningErrorType::Error_internal)?;
+            let address = LegacyAddress::new(prefix, pubkey_hash.as_slice())?;
+            return Ok(StandardBitcoinAddress::Legacy(address));
+        }
+        if let Some(script_hash) = conditions::match_p2sh(&script_pubkey) {
+            let prefix = coin
+                .p2sh_prefix()
+                .or_tw_err(SigningErrorType::Error_internal)?;
+            let address = LegacyAddress::new(prefix, script_hash.as_slice())?;
+            return Ok(StandardBitcoinAddress::Legacy(address));
+        }
+
+        let hrp = coin
+            .hrp()
+            .or_tw_err(SigningErrorType::Error_not_supported)
+            .context("The chain doesn't support segwit addresses")?;
+        if let Some(pubkey_hash) = conditions::match_p2wpkh(&script_pubkey) {
+            let address = SegwitAddress::new(hrp, pubkey_hash.to_vec())?;
+            return Ok(StandardBitcoinAddress::Segwit(address));
+        }
+        if let Some(script_hash) = conditions::match_p2wsh(&script_pubkey) {
+            let address = SegwitAddress::new(hrp, script_hash.to_vec())?;
+            return Ok(StandardBitcoinAddress::Segwit(address));
+        }
+        if let Some(output_key) = conditions::match_p2tr(&script_pubkey) {
+            let address = TaprootAddress::new(hrp, output_key.to_vec())?;
+            return Ok(StandardBitcoinAddress::Taproot(address));
+        }
+
+        SigningError::err(SigningErrorType::Error_internal)
+            .context("Descriptor produced an unexpected scriptPubKey")
+    }
+
+    fn parse_type(s: &str) -> SigningResult<DescriptorType> {
+        if let Some(key) = strip_function(s, "pkh") {
+            return DescriptorPublicKey::parse(key, KeyContext::Legacy).map(DescriptorType::Pkh);
+        }
+        if let Some(key) = strip_function(s, "wpkh") {
+            return DescriptorPublicKey::parse(key, KeyContext::Segwit).map(DescriptorType::Wpkh);
+        }
+        if let Some(key) = strip_function(s, "sh").and_then(|s| strip_function(s, "wpkh")) {
+            return DescriptorPublicKey::parse(key, KeyContext::Segwit).map(DescriptorType::ShWpkh);
+        }
+        if let Some(inner) = strip_function(s, "wsh") {
+            let (args, sorted) = match (
+                strip_function(inner, "multi"),
+                strip_function(inner, "sortedmulti"),
+            ) {
+                (Some(args), _) => (args, false),
+                (_, Some(args)) => (args, true),
+                _ => {
+                    return SigningError::err(SigningErrorType::Error_not_supported)
+                        .with_context(|| format!("Unsupported 'wsh()' expression: '{inner}'"))
+                },
+            };
+
+            let (threshold, keys) = parse_multi_args(args, KeyContext::Segwit)?;
+            if keys.len() > MAX_PUBKEYS_PER_MULTISIG {
+                return SigningError::err(SigningErrorType::Error_invalid_params)
+                    .context("Too many keys within 'multi()'");
+            }
+            return Ok(DescriptorType::WshMulti {
+                threshold,
+                keys,
+                sorted,
+            });
+        }
+        if let Some(args) = strip_function(s, "tr") {
+            let args = split_args(args)?;
+            let (internal_key, tree) = match args.as_slice() {
+                [key] => (key, None),
+                [key, tree] => (key, Some(TapTreeDescriptor::parse(tree)?)),
+                _ => {
+                    return SigningError::err(SigningErrorType::Error_invalid_params)
+                        .context("'tr()' expects one or two arguments")
+                },
+            };
+            let internal_key = DescriptorPublicKey::parse(internal_key, KeyContext::Taproot)?;
+            return Ok(DescriptorType::Tr { internal_key, tree });
+        }
+
+        SigningError::err(SigningErrorType::Error_not_supported)
+            .with_context(|| format!("Unsupported descriptor: '{s}'"))
+    }
+}
+
+impl FromStr for Descriptor {
+    type Err = SigningError;
+
+    /// Parses a descriptor with an optional `#checksum` suffix.
+    /// The checksum is verified if present.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let desc = split_and_verify_checksum(s)?;
+        // Check if the descriptor consists of valid characters only.
+        descriptor_checksum(desc)?;
+
+        let ty = Self::parse_type(desc)?;
+        Ok(Descriptor {
+            desc: desc.to_string(),
+            ty,
+        })
+    }
+}
+
+/// Builds a `multi()` witness script:
+///
+/// ```txt
+/// <k> <KEY_1> ... <KEY_n> <n> OP_CHECKMULTISIG
+/// ```
+fn multi_witness_script_at(
+    threshold: usize,
+    keys: &[DescriptorPublicKey],
+    sorted: bool,
+    index: u32,
+) -> SigningResult<Script> {
+    let mut pubkeys = keys
+        .iter()
+        .map(|key| key.derive_compressed(index))
+        .collect::<SigningResult<Vec<H264>>>()?;
+    if sorted {
+        pubkeys.sort();
+    }
+    Ok(conditions::new_multisig(threshold as u8, &pubkeys))
+}
+
+fn pubkey_hash(pubkey: &[u8]) -> H160 {
+    H160::try_from(sha256_ripemd(pubkey).as_slice()).expect("sha256_ripemd returns 20 bytes")
+}
+
+fn script_hash(script: &Script) -> H160 {
+    H160::try_from(sha256_ripemd(script.as_slice()).as_slice())
+        .expect("sha256_ripemd returns 20 bytes")
+}
+
+fn tap_tree_merkle_root(
+    internal_key: &H264,
+    tree: &TapTreeDescriptor,
+    index: u32,
+) -> SigningResult<H256> {
+    let internal_key = schnorr::PublicKey::try_from(internal_key.as_slice())
+        .tw_err(|_| SigningErrorType::Error_invalid_params)
+        .context("Invalid Taproot internal key")?;
+
+    let builder = tree
+        .leaves_at(index)?
+        .into_iter()
+        .fold(TapTreeBuilder::new(), |builder, (depth, script)| {
+            builder.add_leaf(depth, TapLeaf::tapscript(script))
+        });
+    Ok(builder.finalize(&internal_key)?.merkle_root())
+}
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::descriptor::key::{DescriptorPublicKey, KeyContext};
+use tw_coin_entry::error::prelude::*;
+
+/// Returns the function arguments if the given `s` is `name(ARGS)`.
+pub fn strip_function<'a>(s: &'a str, name: &str) -> Option<&'a str> {
+    s.strip_prefix(name)?.strip_prefix('(')?.strip_suffix(')')
+}
+
+/// Splits the given arguments by top-level commas,
+/// i.e. commas that are not enclosed in `()`, `{}` or `[]`.
+pub fn split_args(s: &str) -> SigningResult<Vec<&str>> {
+    let mut args = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '(' | '{' | '[' => depth += 1,
+            ')' | '}' | ']' => {
+                depth = depth
+                    .checked_sub(1)
+                    .or_tw_err(SigningErrorType::Error_invalid_params)
+                    .context("Unbalanced descriptor brackets")?;
+            },
+            ',' if depth == 0 => {
+                args.push(&s[start..i]);
+                start = i + 1;
+            },
+            _ => (),
+        }
+    }
+
+    if depth != 0 {
+        return SigningError::err(SigningErrorType::Error_invalid_params)
+            .context("Unbalanced descriptor brackets");
+    }
+    args.push(&s[start..]);
+    Ok(args)
+}
+
+/// Parses `k,KEY_1,...,KEY_n` arguments of a multisig expression.
+pub fn parse_multi_args(
+    args: &str,
+    ctx: KeyContext,
+) -> SigningResult<(usize, Vec<DescriptorPublicKey>)> {
+    let args = split_args(args)?;
+    let Some((threshold, keys)) = args.split_first() else {
+        return SigningError::err(SigningErrorType::Error_invalid_params)
+            .context("Multisig threshold is not specified");
+    };
+
+    let threshold: usize = threshold
+        .parse()
+        .tw_err(|_| SigningErrorType::Error_invalid_params)
+        .with_context(|| format!("Invalid multisig threshold: '{threshold}'"))?;
+    if threshold == 0 || threshold > keys.len() {
+        return SigningError::err(SigningErrorType::Error_invalid_params).context(format!(
+            "Multisig threshold must be within [1, {}]",
+            keys.len()
+        ));
+    }
+
+    let keys = keys
+        .iter()
+        .map(|key| DescriptorPublicKey::parse(key, ctx))
+        .collect::<SigningResult<Vec<_>>>()?;
+    Ok((threshold, keys))
+}
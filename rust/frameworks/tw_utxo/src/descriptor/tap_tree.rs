@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Taproot script tree expressions as specified in
+//! [BIP-386](https://github.com/bitcoin/bips/blob/master/bip-0386.mediawiki).
+
+use crate::descriptor::key::{DescriptorPublicKey, KeyContext};
+use crate::descriptor::parse_utils::{parse_multi_args, split_args, strip_function};
+use crate::script::standard_script::opcodes::*;
+use crate::script::Script;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+
+/// Maximum depth of a Taproot script tree.
+/// https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#constructing-and-spending-taproot-outputs
+pub const TAPROOT_CONTROL_MAX_NODE_COUNT: u8 = 128;
+/// Maximum number of keys within `multi_a()` and `sortedmulti_a()`.
+pub const MAX_PUBKEYS_PER_MULTI_A: usize = 999;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TapLeafDescriptor {
+    /// `pk(KEY)`
+    Pk(DescriptorPublicKey),
+    /// `multi_a(k,KEY_1,...,KEY_n)` or `sortedmulti_a(k,KEY_1,...,KEY_n)`
+    MultiA {
+        threshold: usize,
+        keys: Vec<DescriptorPublicKey>,
+        sorted: bool,
+    },
+}
+
+impl TapLeafDescriptor {
+    fn parse(s: &str) -> SigningResult<TapLeafDescriptor> {
+        if let Some(key) = strip_function(s, "pk") {
+            return DescriptorPublicKey::parse(key, KeyContext::Taproot).map(TapLeafDescriptor::Pk);
+        }
+
+        let (args, sorted) = match (
+            strip_function(s, "multi_a"),
+            strip_function(s, "sortedmulti_a"),
+        ) {
+            (Some(args), _) => (args, false),
+            (_, Some(args)) => (args, true),
+            _ => {
+                return SigningError::err(SigningErrorType::Error_not_supported)
+                    .with_context(|| format!("Unsupported tapscript expression: '{s}'"))
+            },
+        };
+
+        let (threshold, keys) = parse_multi_args(args, KeyContext::Taproot)?;
+        if keys.len() > MAX_PUBKEYS_PER_MULTI_A {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Too many keys within 'multi_a()'");
+        }
+        Ok(TapLeafDescriptor::MultiA {
+            threshold,
+            keys,
+            sorted,
+        })
+    }
+
+    fn has_wildcard(&self) -> bool {
+        match self {
+            TapLeafDescriptor::Pk(key) => key.has_wildcard(),
+            TapLeafDescriptor::MultiA { keys, .. } => keys.iter().any(|key| key.has_wildcard()),
+        }
+    }
+
+    /// Builds a tapscript at the given derivation `index`.
+    ///
+    /// ```txt
+    /// pk(KEY): <KEY> OP_CHECKSIG
+    /// multi_a(k,KEY_1,...,KEY_n): <KEY_1> OP_CHECKSIG <KEY_2> OP_CHECKSIGADD ... <KEY_n> OP_CHECKSIGADD <k> OP_NUMEQUAL
+    /// ```
+    pub fn script_at(&self, index: u32) -> SigningResult<Script> {
+        match self {
+            TapLeafDescriptor::Pk(key) => {
+                let mut s = Script::with_capacity(34);
+                s.push_slice(key.derive_xonly(index)?.as_slice());
+                s.push(OP_CHECKSIG);
+                Ok(s)
+            },
+            TapLeafDescriptor::MultiA {
+                threshold,
+                keys,
+                sorted,
+            } => {
+                let mut xonly_keys = keys
+                    .iter()
+                    .map(|key| key.derive_xonly(index))
+                    .collect::<SigningResult<Vec<H256>>>()?;
+                if *sorted {
+                    xonly_keys.sort();
+                }
+
+                let mut s = Script::new();
+                for (i, key) in xonly_keys.iter().enumerate() {
+                    s.push_slice(key.as_slice());
+                    if i == 0 {
+                        s.push(OP_CHECKSIG);
+                    } else {
+                        s.push(OP_CHECKSIGADD);
+                    }
+                }
+                s.push_int(*threshold as i64);
+                s.push(OP_NUMEQUAL);
+                Ok(s)
+            },
+        }
+    }
+}
+
+/// Taproot script tree: `{TREE,TREE}` or a leaf script expression.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TapTreeDescriptor {
+    Leaf(TapLeafDescriptor),
+    Branch(Box<TapTreeDescriptor>, Box<TapTreeDescriptor>),
+}
+
+impl TapTreeDescriptor {
+    pub fn parse(s: &str) -> SigningResult<TapTreeDescriptor> {
+        Self::parse_with_depth(s, 0)
+    }
+
+    fn parse_with_depth(s: &str, depth: u8) -> SigningResult<TapTreeDescriptor> {
+        if depth > TAPROOT_CONTROL_MAX_NODE_COUNT {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Taproot script tree is too deep");
+        }
+
+        let Some(inner) = s.strip_prefix('{').and_then(|s| s.strip_suffix('}')) else {
+            return TapLeafDescriptor::parse(s).map(TapTreeDescriptor::Leaf);
+        };
+
+        let branches = split_args(inner)?;
+        let [left, right] = branches.as_slice() else {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Taproot script tree branch must contain exactly two elements");
+        };
+        Ok(TapTreeDescriptor::Branch(
+            Box::new(Self::parse_with_depth(left, depth + 1)?),
+            Box::new(Self::parse_with_depth(right, depth + 1)?),
+        ))
+    }
+
+    pub fn has_wildcard(&self) -> bool {
+        match self {
+            TapTreeDescriptor::Leaf(leaf) => leaf.has_wildcard(),
+            TapTreeDescriptor::Branch(left, right) => left.has_wildcard() || right.has_wildcard(),
+        }
+    }
+
+    /// Returns tapscripts with their depths in the depth-first order.
+    pub fn leaves_at(&self, index: u32) -> SigningResult<Vec<(u8, Script)>> {
+        let mut leaves = Vec::new();
+        self.collect_leaves(index, 0, &mut leaves)?;
+        Ok(leaves)
+    }
+
+    fn collect_leaves(
+        &self,
+        index: u32,
+        depth: u8,
+        leaves: &mut Vec<(u8, Script)>,
+    ) -> SigningResult<()> {
+        match self {
+            TapTreeDescriptor::Leaf(leaf) => leaves.push((depth, leaf.script_at(index)?)),
+            TapTreeDescriptor::Branch(left, right) => {
+                left.collect_leaves(index, depth + 1, leaves)?;
+                right.collect_leaves(index, depth + 1, leaves)?;
+            },
+        }
+        Ok(())
+    }
+}
@@ -36,13 +36,15 @@ impl<Transaction: TransactionInterface> DustFilter<Transaction> {
     }
 
     /// Checks if all transaction output amounts are greater or equal to a dust threshold.
+    /// Returns an error pointing to the first dust output.
     pub fn check_outputs(
         &self,
         transaction: &UnsignedTransaction<Transaction>,
     ) -> SigningResult<()> {
         let dust_threshold = self.dust_policy.dust_threshold();
 
-        let has_dust_output = transaction.transaction().outputs().iter().any(|output| {
+        let outputs = transaction.transaction().outputs();
+        let dust_output_index = outputs.iter().position(|output| {
             if conditions::is_op_return(output.script_pubkey()) {
                 // Ignore the OP_RETURN output value. It can (or even should) be 0.
                 return false;
@@ -50,8 +52,11 @@ impl<Transaction: TransactionInterface> DustFilter<Transaction> {
             output.value() < dust_threshold
         });
 
-        if has_dust_output {
-            return SigningError::err(SigningErrorType::Error_dust_amount_requested);
+        if let Some(output_index) = dust_output_index {
+            let amount = outputs[output_index].value();
+            return SigningError::err(SigningErrorType::Error_dust_amount_requested).context(
+                format!("Output #{output_index} amount '{amount}' is less than the dust threshold '{dust_threshold}'"),
+            );
         }
         Ok(())
     }
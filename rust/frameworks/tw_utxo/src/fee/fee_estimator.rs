@@ -5,6 +5,7 @@
 use crate::fee::FeePolicy;
 use crate::transaction::transaction_interface::TransactionInterface;
 use crate::transaction::transaction_parts::Amount;
+use crate::transaction::unsigned_transaction::UnsignedTransaction;
 use std::marker::PhantomData;
 use tw_coin_entry::error::prelude::*;
 
@@ -12,6 +13,26 @@ pub trait FeeEstimator<Transaction> {
     fn estimate_fee(&self, tx: &Transaction) -> SigningResult<Amount>;
 }
 
+/// Lower and upper bounds of the fee required by a transaction.
+/// The actual fee depends on the size of the signatures and other spending data.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FeeBounds {
+    pub min: Amount,
+    pub max: Amount,
+}
+
+impl FeeBounds {
+    pub fn estimate<Transaction: TransactionInterface>(
+        unsigned_tx: &UnsignedTransaction<Transaction>,
+        fee_estimator: &dyn FeeEstimator<Transaction>,
+    ) -> SigningResult<FeeBounds> {
+        Ok(FeeBounds {
+            min: fee_estimator.estimate_fee(&unsigned_tx.estimate_transaction_min())?,
+            max: fee_estimator.estimate_fee(&unsigned_tx.estimate_transaction())?,
+        })
+    }
+}
+
 pub struct StandardFeeEstimator<Transaction> {
     policy: FeePolicy,
     _phantom: PhantomData<Transaction>,
@@ -5,6 +5,7 @@
 use crate::transaction::transaction_parts::Amount;
 
 pub mod fee_estimator;
+pub mod satisfaction_weight;
 
 /// Standard fee policy.
 pub enum FeePolicy {
@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::encode::compact_integer::CompactInteger;
+use crate::encode::Encodable;
+use crate::script::{Script, Witness};
+use crate::spending_data::SpendingData;
+
+/// Any byte that doesn't trigger serialization optimizations.
+const ESTIMATION_DEFAULT_BYTE: u8 = u8::MAX;
+/// The max length of a witness item encoded with a single byte length prefix.
+const MAX_SINGLE_BYTE_ITEM_LEN: usize = 252;
+/// The length of a compact integer prefix for values in range `253..=0xFFFF`.
+const TRIPLE_BYTE_PREFIX_LEN: usize = 3;
+
+/// Explicit size hints of the data required to spend a UTXO.
+///
+/// Used to estimate the transaction size when the signer doesn't know how the UTXO is spent,
+/// e.g. P2WSH multisig, P2TR script-path or a foreign `scriptPubkey`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SatisfactionWeight {
+    /// Size of the `scriptSig` in bytes, excluding its length prefix.
+    pub script_sig_size: usize,
+    /// Max size of the serialized witness in bytes, including the number of items and their length prefixes.
+    /// Zero if the UTXO is spent without a witness.
+    pub max_witness_size: usize,
+}
+
+impl SatisfactionWeight {
+    /// Returns dummy spending data of exactly the hinted sizes.
+    pub fn estimated_spending_data(&self) -> SpendingData {
+        SpendingData {
+            script_sig: Script::from(vec![ESTIMATION_DEFAULT_BYTE; self.script_sig_size]),
+            witness: self.estimated_witness(),
+        }
+    }
+
+    fn estimated_witness(&self) -> Witness {
+        let mut witness = Witness::new();
+        // One byte is taken by the number of witness items.
+        let mut remaining = self.max_witness_size.saturating_sub(1);
+
+        while remaining > 0 {
+            let item_len = match remaining {
+                // The whole remaining size fits a single item with a one-byte length prefix.
+                0..=253 => remaining - 1,
+                // Otherwise, a three-byte length prefix would exceed the remaining size.
+                254..=255 => MAX_SINGLE_BYTE_ITEM_LEN,
+                _ => (remaining - TRIPLE_BYTE_PREFIX_LEN).min(u16::MAX as usize),
+            };
+            let item = Script::from(vec![ESTIMATION_DEFAULT_BYTE; item_len]);
+            remaining -= CompactInteger::from(item_len).encoded_size() + item_len;
+            witness.push_item(item);
+        }
+
+        witness
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimated_spending_data_sizes() {
+        for max_witness_size in [2, 3, 107, 253, 254, 255, 256, 257, 258, 1000, 70_000] {
+            let weight = SatisfactionWeight {
+                script_sig_size: 35,
+                max_witness_size,
+            };
+            let spending_data = weight.estimated_spending_data();
+            assert_eq!(spending_data.script_sig.len(), 35);
+            assert_eq!(
+                spending_data.witness.encoded_size(),
+                max_witness_size,
+                "Invalid witness size: {max_witness_size}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_estimated_spending_data_no_witness() {
+        let weight = SatisfactionWeight {
+            script_sig_size: 0,
+            max_witness_size: 0,
+        };
+        let spending_data = weight.estimated_spending_data();
+        assert!(spending_data.script_sig.is_empty());
+        assert!(spending_data.witness.is_empty());
+    }
+}
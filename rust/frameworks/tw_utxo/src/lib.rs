@@ -5,6 +5,7 @@
 pub mod address;
 pub mod constants;
 pub mod context;
+pub mod descriptor;
 pub mod dust;
 pub mod encode;
 pub mod fee;
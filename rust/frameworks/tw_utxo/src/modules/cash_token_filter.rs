@@ -0,0 +1,278 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::transaction::asset::cash_token::{
+    CashToken, NftCapability, NonFungibleToken, PREFIX_TOKEN,
+};
+use crate::transaction::transaction_interface::{
+    TransactionInterface, TxInputInterface, TxOutputInterface,
+};
+use crate::transaction::unsigned_transaction::UnsignedTransaction;
+use std::collections::{BTreeMap, BTreeSet};
+use std::marker::PhantomData;
+use tw_coin_entry::error::prelude::*;
+use tw_encoding::hex::ToHex;
+use tw_hash::H256;
+
+/// Total amounts of tokens of a single category.
+#[derive(Default)]
+struct TokenBalance {
+    fungible: u64,
+    nfts: Vec<NonFungibleToken>,
+}
+
+/// Makes sure that Bitcoin Cash tokens are not burnt unintentionally.
+/// Does nothing if neither UTXOs nor outputs hold CashTokens.
+pub struct CashTokenFilter<Transaction> {
+    _phantom: PhantomData<Transaction>,
+}
+
+impl<Transaction: TransactionInterface> CashTokenFilter<Transaction> {
+    /// Filters UTXOs holding tokens of categories that are not sent by the transaction,
+    /// so the tokens remain in the wallet.
+    /// Returns an error if there are no valid UTXOs.
+    pub fn filter_inputs(
+        mut transaction: UnsignedTransaction<Transaction>,
+    ) -> SigningResult<UnsignedTransaction<Transaction>> {
+        let sent_categories: BTreeSet<H256> = transaction
+            .outputs()
+            .iter()
+            .filter_map(|output| CashToken::from_script_pubkey(output.script_pubkey()))
+            .map(|token| token.category)
+            .collect();
+
+        transaction.retain_inputs(|_utxo, utxo_args| {
+            match CashToken::from_script_pubkey(&utxo_args.prevout_script_pubkey) {
+                Some(token) => sent_categories.contains(&token.category),
+                None => true,
+            }
+        })?;
+
+        Ok(transaction)
+    }
+
+    /// Returns indexes of the UTXOs that must be spent by the transaction:
+    /// UTXOs holding tokens and genesis UTXOs of new token categories.
+    ///
+    /// Please note that [`CashTokenFilter::filter_inputs`] should be called beforehand.
+    pub fn required_inputs(transaction: &UnsignedTransaction<Transaction>) -> Vec<usize> {
+        let output_categories: BTreeSet<H256> = transaction
+            .outputs()
+            .iter()
+            .filter_map(|output| CashToken::from_script_pubkey(output.script_pubkey()))
+            .map(|token| token.category)
+            .collect();
+
+        transaction
+            .inputs()
+            .iter()
+            .zip(transaction.input_args())
+            .enumerate()
+            .filter(|(_, (utxo, utxo_args))| {
+                let holds_token =
+                    CashToken::from_script_pubkey(&utxo_args.prevout_script_pubkey).is_some();
+                holds_token || is_genesis_input(*utxo, &output_categories)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Checks if the transaction outputs do not send more tokens than available,
+    /// every output NFT descends from an input NFT of the same category,
+    /// and the transaction doesn't burn tokens.
+    /// Remaining fungible tokens are sent to the change output if possible.
+    ///
+    /// Please note that all UTXOs holding tokens are expected to be spent,
+    /// see [`CashTokenFilter::required_inputs`].
+    pub fn balance_tokens(
+        transaction: &UnsignedTransaction<Transaction>,
+        change_output: Option<Transaction::Output>,
+    ) -> SigningResult<Option<Transaction::Output>> {
+        let mut inputs_balance: BTreeMap<H256, TokenBalance> = BTreeMap::new();
+        for utxo_args in transaction.input_args() {
+            if let Some(token) = CashToken::from_script_pubkey(&utxo_args.prevout_script_pubkey) {
+                add_to_balance(&mut inputs_balance, &token)?;
+            }
+        }
+
+        let mut outputs_balance: BTreeMap<H256, TokenBalance> = BTreeMap::new();
+        for (output_index, output) in transaction.outputs().iter().enumerate() {
+            let script_pubkey = output.script_pubkey().as_slice();
+            match CashToken::decode_prefix(script_pubkey) {
+                Some((token, _)) => add_to_balance(&mut outputs_balance, &token)?,
+                // Locking bytecode never starts with `PREFIX_TOKEN`, so the prefix is malformed.
+                None if script_pubkey.first() == Some(&PREFIX_TOKEN) => {
+                    return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                        format!("Output #{output_index} has an invalid CashToken prefix"),
+                    );
+                },
+                None => (),
+            }
+        }
+
+        for (category, sent) in outputs_balance.iter() {
+            let Some(available) = inputs_balance.get(category) else {
+                // New tokens can be created by spending an output with the index 0 of the category transaction.
+                let has_genesis_input = transaction.inputs().iter().any(|utxo| {
+                    let out_point = utxo.previous_output();
+                    out_point.index == 0 && out_point.hash == *category
+                });
+                if !has_genesis_input {
+                    return SigningError::err(SigningErrorType::Error_invalid_utxo).context(
+                        format!(
+                            "Neither a UTXO holding '{}' tokens nor a genesis UTXO is provided",
+                            category.rev().to_hex()
+                        ),
+                    );
+                }
+                continue;
+            };
+
+            if sent.fungible > available.fungible {
+                return SigningError::err(SigningErrorType::Error_not_enough_utxos).context(
+                    format!(
+                        "Insufficient '{}' fungible tokens. Available '{}', required '{}'",
+                        category.rev().to_hex(),
+                        available.fungible,
+                        sent.fungible
+                    ),
+                );
+            }
+            check_nfts_descend(category, &available.nfts, &sent.nfts)?;
+        }
+
+        let mut change_token = None;
+        for (category, available) in inputs_balance.iter() {
+            let sent = outputs_balance.get(category);
+
+            let sent_nfts = sent.map(|sent| sent.nfts.len()).unwrap_or_default();
+            if available.nfts.len() > sent_nfts {
+                return SigningError::err(SigningErrorType::Error_invalid_params).context(format!(
+                    "Transaction would burn '{}' NFTs",
+                    category.rev().to_hex()
+                ));
+            }
+
+            let sent_fungible = sent.map(|sent| sent.fungible).unwrap_or_default();
+            let remaining = available.fungible - sent_fungible;
+            if remaining == 0 {
+                continue;
+            }
+            if change_token.is_some() {
+                return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                    "Change output can hold remaining fungible tokens of a single category only",
+                );
+            }
+            change_token = Some(CashToken {
+                category: *category,
+                nft: None,
+                amount: remaining,
+            });
+        }
+
+        let Some(change_token) = change_token else {
+            return Ok(change_output);
+        };
+        let Some(mut change_output) = change_output else {
+            return SigningError::err(SigningErrorType::Error_invalid_params).context(format!(
+                "Change output is required to keep the remaining '{}' fungible tokens",
+                change_token.category.rev().to_hex()
+            ));
+        };
+
+        let script_pubkey = change_token
+            .prepend_to(change_output.script_pubkey())
+            .context("Invalid change output")?;
+        change_output.set_script_pubkey(script_pubkey);
+        Ok(Some(change_output))
+    }
+}
+
+fn is_genesis_input<Input: TxInputInterface>(utxo: &Input, categories: &BTreeSet<H256>) -> bool {
+    let out_point = utxo.previous_output();
+    out_point.index == 0 && categories.contains(&out_point.hash)
+}
+
+/// Checks if the output NFTs can be created from the input NFTs of the same category:
+/// * a minting NFT can create any NFTs of the category,
+/// * a mutable NFT can be replaced with a single non-minting NFT with any commitment,
+/// * an immutable NFT can only be sent with the same commitment.
+fn check_nfts_descend(
+    category: &H256,
+    available: &[NonFungibleToken],
+    sent: &[NonFungibleToken],
+) -> SigningResult<()> {
+    let has_minting = available
+        .iter()
+        .any(|nft| nft.capability == NftCapability::Minting);
+    if has_minting {
+        return Ok(());
+    }
+
+    if sent.len() > available.len() {
+        return SigningError::err(SigningErrorType::Error_not_enough_utxos).context(format!(
+            "A minting NFT is required to create new '{}' NFTs",
+            category.rev().to_hex()
+        ));
+    }
+
+    let mut immutable_commitments: Vec<&[u8]> = available
+        .iter()
+        .filter(|nft| nft.capability == NftCapability::Immutable)
+        .map(|nft| nft.commitment.as_slice())
+        .collect();
+    let mutable_count = available
+        .iter()
+        .filter(|nft| nft.capability == NftCapability::Mutable)
+        .count();
+
+    // NFTs that cannot be sent as is, and need to be created from mutable NFTs.
+    let mut changed_count = 0;
+    for nft in sent {
+        match nft.capability {
+            NftCapability::Minting => {
+                return SigningError::err(SigningErrorType::Error_invalid_params).context(format!(
+                    "A minting NFT is required to create a minting '{}' NFT",
+                    category.rev().to_hex()
+                ));
+            },
+            NftCapability::Immutable => {
+                let same_commitment = immutable_commitments
+                    .iter()
+                    .position(|commitment| *commitment == nft.commitment.as_slice());
+                match same_commitment {
+                    Some(index) => {
+                        immutable_commitments.swap_remove(index);
+                    },
+                    None => changed_count += 1,
+                }
+            },
+            NftCapability::Mutable => changed_count += 1,
+        }
+    }
+
+    if changed_count > mutable_count {
+        return SigningError::err(SigningErrorType::Error_invalid_params).context(format!(
+            "Only minting or mutable NFTs can create or change commitments of '{}' NFTs",
+            category.rev().to_hex()
+        ));
+    }
+    Ok(())
+}
+
+fn add_to_balance(
+    balances: &mut BTreeMap<H256, TokenBalance>,
+    token: &CashToken,
+) -> SigningResult<()> {
+    let balance = balances.entry(token.category).or_default();
+    balance.fungible = balance
+        .fungible
+        .checked_add(token.amount)
+        .or_tw_err(SigningErrorType::Error_invalid_params)
+        .context("Fungible token amount overflow")?;
+    if let Some(ref nft) = token.nft {
+        balance.nfts.push(nft.clone());
+    }
+    Ok(())
+}
@@ -2,9 +2,11 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+pub mod cash_token_filter;
 pub mod keys_manager;
 pub mod sighash_computer;
 pub mod sighash_verifier;
+pub mod silent_payments;
 pub mod tx_compiler;
 pub mod tx_planner;
 pub mod tx_signer;
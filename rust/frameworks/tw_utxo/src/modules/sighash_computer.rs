@@ -4,6 +4,7 @@
 
 use crate::script::Script;
 use crate::signing_mode::SigningMethod;
+use crate::spending_data::{foreign_utxo_error, SpendingDataConstructor};
 use crate::transaction::transaction_interface::TransactionInterface;
 use crate::transaction::transaction_parts::Amount;
 use crate::transaction::unsigned_transaction::UnsignedTransaction;
@@ -12,7 +13,7 @@ use crate::transaction::{
 };
 use std::marker::PhantomData;
 use tw_coin_entry::coin_entry::PublicKeyBytes;
-use tw_coin_entry::error::prelude::SigningResult;
+use tw_coin_entry::error::prelude::*;
 use tw_hash::H256;
 
 #[derive(Debug, Clone)]
@@ -56,17 +57,33 @@ where
     pub fn preimage_tx(
         unsigned_tx: &UnsignedTransaction<Transaction>,
     ) -> SigningResult<TxPreimage> {
+        let spent_amounts: Vec<Amount> = unsigned_tx
+            .input_args()
+            .iter()
+            .map(|utxo| utxo.amount)
+            .collect();
+        let spent_script_pubkeys: Vec<Script> = unsigned_tx
+            .input_args()
+            .iter()
+            .map(|utxo| utxo.prevout_script_pubkey.clone())
+            .collect();
+
         unsigned_tx
             .input_args()
             .iter()
             .enumerate()
             .map(|(signing_input_index, utxo)| {
+                if let SpendingDataConstructor::Foreign = utxo.spending_data_constructor {
+                    return foreign_utxo_error();
+                }
                 let signing_method = utxo.signing_method;
 
                 let utxo_args = UtxoPreimageArgs {
                     input_index: signing_input_index,
                     script_pubkey: utxo.reveal_script_pubkey.clone(),
                     amount: utxo.amount,
+                    spent_amounts: spent_amounts.clone(),
+                    spent_script_pubkeys: spent_script_pubkeys.clone(),
                     // TODO move `leaf_hash_code_separator` to `UtxoTaprootPreimageArgs`.
                     leaf_hash_code_separator: utxo.leaf_hash_code_separator,
                     sighash_ty: utxo.sighash_ty,
@@ -80,32 +97,19 @@ where
                         (sighash, None)
                     },
                     SigningMethod::Taproot => {
-                        // TODO Move `tr_spent_amounts` and `tr_spent_script_pubkeys` logic to `Transaction::preimage_taproot_tx()`.
-                        let tr_spent_amounts: Vec<Amount> = unsigned_tx
-                            .input_args()
-                            .iter()
-                            .map(|utxo| utxo.amount)
-                            .collect();
-
-                        let tr_spent_script_pubkeys: Vec<Script> = unsigned_tx
-                            .input_args()
-                            .iter()
-                            .enumerate()
-                            .map(|(i, utxo)| match utxo.taproot_reveal_script_pubkey {
-                                // Use the scriptPubkey required to spend this UTXO.
-                                Some(ref tr_reveal_script) if i == signing_input_index => {
-                                    tr_reveal_script.clone()
-                                },
-                                // Use the original scriptPubkey declared in the unspent output for other UTXOs
-                                // (different from that we sign at this iteration).
-                                _ => utxo.prevout_script_pubkey.clone(),
-                            })
-                            .collect();
+                        // TODO Move `tr_spent_script_pubkeys` logic to `Transaction::preimage_taproot_tx()`.
+                        // Use the original scriptPubkey declared in the unspent output for other UTXOs
+                        // (different from that we sign at this iteration),
+                        // but the scriptPubkey required to spend this UTXO.
+                        let mut tr_spent_script_pubkeys = spent_script_pubkeys.clone();
+                        if let Some(ref tr_reveal_script) = utxo.taproot_reveal_script_pubkey {
+                            tr_spent_script_pubkeys[signing_input_index] = tr_reveal_script.clone();
+                        }
 
                         let tr = UtxoTaprootPreimageArgs {
                             args: utxo_args,
-                            spent_amounts: tr_spent_amounts,
-                            spent_script_pubkeys: tr_spent_script_pubkeys.clone(),
+                            spent_amounts: spent_amounts.clone(),
+                            spent_script_pubkeys: tr_spent_script_pubkeys,
                         };
 
                         let sighash = unsigned_tx.transaction().preimage_taproot_tx(&tr)?;
@@ -130,8 +134,8 @@ where
     pub fn get_taproot_tweak(utxo: &UtxoToSign) -> Option<TaprootTweak> {
         // Any empty leaf hash implies P2TR key-path (balance transfer)
         if utxo.leaf_hash_code_separator.is_none() {
-            // Tweak keypair for P2TR key-path (ie. zeroed Merkle root).
-            let merkle_root = None;
+            // Tweak keypair for P2TR key-path with the script tree merkle root if any.
+            let merkle_root = utxo.taproot_merkle_root;
             Some(TaprootTweak { merkle_root })
         } else {
             None
@@ -0,0 +1,257 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::silent_payment::SilentPaymentAddress;
+use crate::encode::Encodable;
+use crate::modules::keys_manager::KeysManager;
+use crate::modules::sighash_computer::TaprootTweak;
+use crate::script::standard_script::conditions;
+use crate::script::Script;
+use crate::transaction::transaction_interface::{
+    TransactionInterface, TxInputInterface, TxOutputInterface,
+};
+use crate::transaction::transaction_parts::OutPoint;
+use crate::transaction::unsigned_transaction::UnsignedTransaction;
+use secp256k1::{Parity, PublicKey, Scalar, Secp256k1, SecretKey};
+use std::collections::HashMap;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::hasher::sha256_ripemd;
+use tw_hash::sha2::sha256;
+use tw_hash::{H160, H256, H264};
+use tw_keypair::{ecdsa, schnorr};
+use tw_memory::Data;
+use tw_misc::traits::ToBytesZeroizing;
+
+const INPUTS_TAG: &[u8] = b"BIP0352/Inputs";
+const SHARED_SECRET_TAG: &[u8] = b"BIP0352/SharedSecret";
+
+/// BIP-352 silent payments sender.
+/// Derives unique P2TR output keys for silent payment addresses from the transaction inputs:
+/// https://github.com/bitcoin/bips/blob/master/bip-0352.mediawiki#creating-outputs
+pub struct SilentPaymentSender {
+    /// The lexicographically smallest outpoint of the transaction.
+    smallest_outpoint: Data,
+    /// Private keys of the inputs eligible for shared secret derivation.
+    private_keys: Vec<SecretKey>,
+}
+
+impl SilentPaymentSender {
+    /// Creates a sender with outpoints of **all** the transaction inputs.
+    pub fn new<'a, I>(outpoints: I) -> SigningResult<SilentPaymentSender>
+    where
+        I: IntoIterator<Item = &'a OutPoint>,
+    {
+        let smallest_outpoint = outpoints
+            .into_iter()
+            .map(|out_point| out_point.encode_out())
+            .min()
+            .or_tw_err(SigningErrorType::Error_missing_input_utxos)
+            .context("Silent payments require at least one input")?;
+
+        Ok(SilentPaymentSender {
+            smallest_outpoint,
+            private_keys: Vec::default(),
+        })
+    }
+
+    /// Creates a sender from the transaction inputs, and finds private keys of the eligible ones:
+    /// P2TR (key-path), P2WPKH, P2SH-P2WPKH and P2PKH (compressed public key only).
+    pub fn from_unsigned_tx<Transaction: TransactionInterface>(
+        unsigned_tx: &UnsignedTransaction<Transaction>,
+        keys_manager: &KeysManager,
+    ) -> SigningResult<SilentPaymentSender> {
+        let mut sender = Self::new(
+            unsigned_tx
+                .inputs()
+                .iter()
+                .map(|input| input.previous_output()),
+        )?;
+
+        for utxo_args in unsigned_tx.input_args() {
+            let prevout = &utxo_args.prevout_script_pubkey;
+
+            if conditions::is_p2pkh(prevout)
+                || conditions::is_p2wpkh(prevout)
+                || is_p2sh_p2wpkh(prevout, &utxo_args.spender_public_key)
+            {
+                // Inputs with uncompressed public keys are skipped.
+                if utxo_args.spender_public_key.len() != H264::LEN {
+                    continue;
+                }
+                let public =
+                    ecdsa::secp256k1::PublicKey::try_from(utxo_args.spender_public_key.as_slice())
+                        .into_tw()
+                        .context("Invalid ecdsa spender public key")?;
+                let private = keys_manager.get_ecdsa_private(&public)?;
+                sender.add_eligible_private(&private.to_zeroizing_vec(), false)?;
+            } else if let Some(output_key) = conditions::match_p2tr(prevout) {
+                let output_key = schnorr::XOnlyPublicKey::try_from(output_key.as_slice())
+                    .into_tw()
+                    .context("Invalid P2TR output key")?;
+                // Only key-path spending is supported, as the private key of the output key is required.
+                let private = keys_manager
+                    .get_schnorr_private(
+                        &output_key,
+                        &Some(TaprootTweak {
+                            merkle_root: utxo_args.taproot_merkle_root,
+                        }),
+                    )
+                    .context("Silent payments require Taproot inputs to be spent via key-path")?;
+                sender.add_eligible_private(&private.to_zeroizing_vec(), true)?;
+            }
+            // Other inputs, including P2SH inputs other than P2SH-P2WPKH, are not eligible for shared secret derivation.
+        }
+
+        Ok(sender)
+    }
+
+    /// Adds a private key of an eligible input.
+    /// Taproot private keys are negated if their public key has an odd Y coordinate.
+    pub fn add_eligible_private(
+        &mut self,
+        private: &[u8],
+        is_taproot: bool,
+    ) -> SigningResult<&mut Self> {
+        let mut private = SecretKey::from_slice(private)
+            .tw_err(|_| SigningErrorType::Error_invalid_private_key)?;
+
+        if is_taproot {
+            let (_, parity) = private.x_only_public_key(&Secp256k1::new());
+            if parity == Parity::Odd {
+                private = private.negate();
+            }
+        }
+
+        self.private_keys.push(private);
+        Ok(self)
+    }
+
+    /// Derives P2TR output keys for the given recipients in the same order.
+    /// Recipients that share the same scan public key get output keys with increasing `k` index.
+    pub fn derive_output_keys(
+        &self,
+        recipients: &[SilentPaymentAddress],
+    ) -> SigningResult<Vec<H256>> {
+        let secp = Secp256k1::new();
+
+        // a = sum(a_i)
+        let (first, rest) = self
+            .private_keys
+            .split_first()
+            .or_tw_err(SigningErrorType::Error_missing_private_key)
+            .context("Silent payments require at least one eligible input")?;
+        let input_private_sum = rest.iter().try_fold(*first, |sum, private| {
+            sum.add_tweak(&Scalar::from(*private))
+                .tw_err(|_| SigningErrorType::Error_invalid_private_key)
+                .context("Sum of the input private keys is zero")
+        })?;
+
+        // input_hash = hash_BIP0352/Inputs(outpoint_L || A)
+        let input_public_sum = input_private_sum.public_key(&secp);
+        let mut inputs_preimage = self.smallest_outpoint.clone();
+        inputs_preimage.extend_from_slice(&input_public_sum.serialize());
+        let input_hash = scalar_from_hash(tagged_hash(INPUTS_TAG, &inputs_preimage))?;
+
+        // input_hash * a
+        let ecdh_private = input_private_sum
+            .mul_tweak(&input_hash)
+            .tw_err(|_| SigningErrorType::Error_internal)?;
+
+        let mut scan_key_counters: HashMap<H264, u32> = HashMap::default();
+        recipients
+            .iter()
+            .map(|recipient| -> SigningResult<H256> {
+                let scan_pubkey = parse_public_key(recipient.scan_pubkey())?;
+                let spend_pubkey = parse_public_key(recipient.spend_pubkey())?;
+
+                let k = scan_key_counters
+                    .entry(*recipient.scan_pubkey())
+                    .or_default();
+
+                // ecdh_shared_secret = input_hash * a * B_scan
+                let shared_secret = scan_pubkey
+                    .mul_tweak(&secp, &Scalar::from(ecdh_private))
+                    .tw_err(|_| SigningErrorType::Error_internal)?;
+
+                // t_k = hash_BIP0352/SharedSecret(ecdh_shared_secret || ser_32(k))
+                let mut shared_secret_preimage = shared_secret.serialize().to_vec();
+                shared_secret_preimage.extend_from_slice(&k.to_be_bytes());
+                let t_k =
+                    scalar_from_hash(tagged_hash(SHARED_SECRET_TAG, &shared_secret_preimage))?;
+                *k += 1;
+
+                // P_k = B_spend + t_k * G
+                let output_key = spend_pubkey
+                    .add_exp_tweak(&secp, &t_k)
+                    .tw_err(|_| SigningErrorType::Error_internal)?;
+                let (output_key, _) = output_key.x_only_public_key();
+                Ok(H256::from(output_key.serialize()))
+            })
+            .collect()
+    }
+
+    /// Derives P2TR output keys for the given recipients,
+    /// and replaces `scriptPubkey` of the outputs at the corresponding indexes.
+    pub fn set_outputs<Transaction: TransactionInterface>(
+        &self,
+        unsigned_tx: &mut UnsignedTransaction<Transaction>,
+        recipients: &[(usize, SilentPaymentAddress)],
+    ) -> SigningResult<()> {
+        let addresses: Vec<_> = recipients
+            .iter()
+            .map(|(_, address)| address.clone())
+            .collect();
+        let output_keys = self.derive_output_keys(&addresses)?;
+
+        let outputs = unsigned_tx.outputs_mut();
+        for ((output_idx, _), output_key) in recipients.iter().zip(output_keys) {
+            let output = outputs
+                .get_mut(*output_idx)
+                .or_tw_err(SigningErrorType::Error_internal)
+                .with_context(|| format!("There is no silent payment output at {output_idx}"))?;
+            output.set_script_pubkey(conditions::new_p2tr_dangerous_assume_tweaked(&output_key));
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks whether the P2SH `prevout` commits to a P2WPKH redeem script of the spender public key.
+fn is_p2sh_p2wpkh(prevout: &Script, spender_public_key: &[u8]) -> bool {
+    let Some(script_hash) = conditions::match_p2sh(prevout) else {
+        return false;
+    };
+    // Only compressed public keys are allowed in P2WPKH.
+    if spender_public_key.len() != H264::LEN {
+        return false;
+    }
+
+    let pubkey_hash = H160::try_from(sha256_ripemd(spender_public_key).as_slice())
+        .expect("sha256_ripemd returns 20 bytes");
+    let redeem_script = conditions::new_p2wpkh(&pubkey_hash);
+    sha256_ripemd(redeem_script.as_slice()) == script_hash.as_slice()
+}
+
+fn tagged_hash(tag: &[u8], data: &[u8]) -> H256 {
+    let tag_hash = sha256(tag);
+
+    let mut preimage = Vec::with_capacity(tag_hash.len() * 2 + data.len());
+    preimage.extend_from_slice(&tag_hash);
+    preimage.extend_from_slice(&tag_hash);
+    preimage.extend_from_slice(data);
+
+    H256::try_from(sha256(&preimage).as_slice()).expect("sha256 hash is 32 bytes")
+}
+
+fn scalar_from_hash(hash: H256) -> SigningResult<Scalar> {
+    Scalar::from_be_bytes(hash.take())
+        .tw_err(|_| SigningErrorType::Error_internal)
+        .context("Silent payment tweak is out of the curve order")
+}
+
+fn parse_public_key(pubkey: &H264) -> SigningResult<PublicKey> {
+    PublicKey::from_slice(pubkey.as_slice())
+        .tw_err(|_| SigningErrorType::Error_invalid_address)
+        .context("Invalid silent payment public key")
+}
@@ -6,7 +6,7 @@ use crate::modules::sighash_verifier::SighashVerifier;
 use crate::sighash::SighashType;
 use crate::signature::{BitcoinEcdsaSignature, BitcoinSchnorrSignature, FromRawOrDerBytes};
 use crate::spending_data::{
-    EcdsaSpendingDataConstructor, SchnorrSpendingDataConstructor, SpendingData,
+    foreign_utxo_error, EcdsaSpendingDataConstructor, SchnorrSpendingDataConstructor, SpendingData,
     SpendingDataConstructor,
 };
 use crate::transaction::transaction_interface::{TransactionInterface, TxInputInterface};
@@ -60,6 +60,7 @@ where
                         utxo.sighash_ty,
                     )
                 },
+                SpendingDataConstructor::Foreign => foreign_utxo_error(),
             })
             .collect::<SigningResult<_>>()?;
 
@@ -2,15 +2,21 @@
 //
 // Copyright © 2017 Trust Wallet.
 
-use crate::context::UtxoContext;
+use crate::constants::MAX_TRANSACTION_WEIGHT;
+use crate::context::{ContextTransactionOutput, UtxoContext};
 use crate::dust::dust_filter::DustFilter;
 use crate::dust::DustPolicy;
+use crate::fee::fee_estimator::FeeEstimator;
+use crate::modules::cash_token_filter::CashTokenFilter;
 use crate::modules::utxo_selector::exact_selector::ExactInputSelector;
 use crate::modules::utxo_selector::max_selector::MaxInputSelector;
 use crate::modules::utxo_selector::{InputSelector, SelectResult};
-use crate::transaction::transaction_interface::TransactionInterface;
+use crate::transaction::transaction_interface::{TransactionInterface, TxInputInterface};
+use crate::transaction::transaction_parts::OutPoint;
 use crate::transaction::unsigned_transaction::UnsignedTransaction;
+use std::collections::HashSet;
 use std::marker::PhantomData;
+use std::ops::Range;
 use tw_coin_entry::error::prelude::*;
 
 /// For now, restrict the transaction size to be up to 100kb, i.e `max_block_size / 10`.
@@ -38,6 +44,33 @@ pub enum RequestType<Transaction: TransactionInterface> {
     },
 }
 
+/// Plan request that results in several transactions.
+pub struct BatchPlanRequest<Context: UtxoContext> {
+    pub ty: BatchRequestType<Context::Transaction>,
+    pub dust_policy: DustPolicy,
+    pub fee_estimator: Context::FeeEstimator,
+}
+
+pub enum BatchRequestType<Transaction: TransactionInterface> {
+    /// Consolidates all the UTXOs to the only output of the `unsigned_tx`.
+    /// The UTXOs are split into several transactions in the given order,
+    /// so every transaction doesn't exceed `max_inputs` and `max_weight` (if specified).
+    Consolidate {
+        unsigned_tx: UnsignedTransaction<Transaction>,
+        max_inputs: Option<usize>,
+        max_weight: Option<usize>,
+    },
+    /// Pays out to all the outputs of the `unsigned_tx`.
+    /// The outputs are split into several transactions by `max_outputs` (if specified),
+    /// each transaction selects its own UTXOs and gets a `change_output`.
+    Payout {
+        unsigned_tx: UnsignedTransaction<Transaction>,
+        change_output: Option<Transaction::Output>,
+        input_selector: InputSelector,
+        max_outputs: Option<usize>,
+    },
+}
+
 /// Transaction Planner with a standard Bitcoin behaviour.
 ///
 /// # Important
@@ -52,8 +85,10 @@ where
     Context: UtxoContext,
 {
     /// * Filters dust UTXOs
+    /// * Filters UTXOs holding CashTokens that are not sent, and checks token balances
     /// * Checks if all outputs are not dust
     /// * Select UTXOs as specified in the request
+    /// * Checks if the selected UTXOs timelocks are satisfied
     pub fn plan(
         request: PlanRequest<Context>,
     ) -> SigningResult<SelectResult<Context::Transaction>> {
@@ -61,26 +96,19 @@ where
 
         let select_result = match request.ty {
             RequestType::SendMax { unsigned_tx } => {
-                let unsigned_tx = dust_filter
-                    .filter_inputs(unsigned_tx)
-                    .context("Error filtering dust UTXOs")?;
-
-                MaxInputSelector::new(unsigned_tx)
-                    .select_max(request.dust_policy, &request.fee_estimator)
+                Self::select_max(unsigned_tx, request.dust_policy, &request.fee_estimator)
             },
             RequestType::SendExact {
                 unsigned_tx,
                 change_output,
                 input_selector,
-            } => {
-                let unsigned_tx = dust_filter
-                    .filter_inputs(unsigned_tx)
-                    .context("Error filtering dust UTXOs")?;
-
-                ExactInputSelector::new(unsigned_tx)
-                    .maybe_change_output(change_output)
-                    .select_inputs(request.dust_policy, input_selector, &request.fee_estimator)
-            },
+            } => Self::select_exact(
+                unsigned_tx,
+                change_output,
+                input_selector,
+                request.dust_policy,
+                &request.fee_estimator,
+            ),
         }
         .context("Error selecting UTXOs")?;
 
@@ -88,7 +116,232 @@ where
         dust_filter
             .check_outputs(&select_result.unsigned_tx)
             .context("There are dust output amounts")?;
+        select_result.unsigned_tx.check_timelocks()?;
 
         Ok(select_result)
     }
+
+    /// Plans several transactions as specified in the request.
+    /// Every result transaction is checked the same way as in [`TxPlanner::plan`].
+    pub fn plan_batch(
+        request: BatchPlanRequest<Context>,
+    ) -> SigningResult<Vec<SelectResult<Context::Transaction>>> {
+        let dust_filter = DustFilter::new(request.dust_policy);
+
+        let select_results = match request.ty {
+            BatchRequestType::Consolidate {
+                unsigned_tx,
+                max_inputs,
+                max_weight,
+            } => Self::plan_consolidation(
+                unsigned_tx,
+                max_inputs,
+                max_weight,
+                request.dust_policy,
+                &request.fee_estimator,
+            )?,
+            BatchRequestType::Payout {
+                unsigned_tx,
+                change_output,
+                input_selector,
+                max_outputs,
+            } => Self::plan_payout(
+                unsigned_tx,
+                change_output,
+                input_selector,
+                max_outputs,
+                request.dust_policy,
+                &request.fee_estimator,
+            )?,
+        };
+
+        for (tx_index, select_result) in select_results.iter().enumerate() {
+            dust_filter
+                .check_outputs(&select_result.unsigned_tx)
+                .with_context(|| {
+                    format!("There are dust output amounts in transaction #{tx_index}")
+                })?;
+            select_result
+                .unsigned_tx
+                .check_timelocks()
+                .with_context(|| format!("Invalid timelocks in transaction #{tx_index}"))?;
+        }
+
+        Ok(select_results)
+    }
+
+    fn select_max(
+        unsigned_tx: UnsignedTransaction<Context::Transaction>,
+        dust_policy: DustPolicy,
+        fee_estimator: &dyn FeeEstimator<Context::Transaction>,
+    ) -> SigningResult<SelectResult<Context::Transaction>> {
+        let unsigned_tx = DustFilter::new(dust_policy)
+            .filter_inputs(unsigned_tx)
+            .context("Error filtering dust UTXOs")?;
+        let unsigned_tx = CashTokenFilter::filter_inputs(unsigned_tx)
+            .context("Error filtering CashToken UTXOs")?;
+        CashTokenFilter::balance_tokens(&unsigned_tx, None)?;
+
+        MaxInputSelector::new(unsigned_tx).select_max(dust_policy, fee_estimator)
+    }
+
+    fn select_exact(
+        unsigned_tx: UnsignedTransaction<Context::Transaction>,
+        change_output: Option<ContextTransactionOutput<Context>>,
+        input_selector: InputSelector,
+        dust_policy: DustPolicy,
+        fee_estimator: &dyn FeeEstimator<Context::Transaction>,
+    ) -> SigningResult<SelectResult<Context::Transaction>> {
+        let unsigned_tx = DustFilter::new(dust_policy)
+            .filter_inputs(unsigned_tx)
+            .context("Error filtering dust UTXOs")?;
+        let unsigned_tx = CashTokenFilter::filter_inputs(unsigned_tx)
+            .context("Error filtering CashToken UTXOs")?;
+        let change_output = CashTokenFilter::balance_tokens(&unsigned_tx, change_output)?;
+        let required_inputs = CashTokenFilter::required_inputs(&unsigned_tx);
+
+        ExactInputSelector::new(unsigned_tx)
+            .maybe_change_output(change_output)
+            .required_inputs(required_inputs)
+            .select_inputs(dust_policy, input_selector, fee_estimator)
+    }
+
+    /// Splits the UTXOs into batches in the given order, so every batch doesn't exceed
+    /// `max_inputs` and `max_weight`, and sends max amount of each batch to the only output.
+    fn plan_consolidation(
+        unsigned_tx: UnsignedTransaction<Context::Transaction>,
+        max_inputs: Option<usize>,
+        max_weight: Option<usize>,
+        dust_policy: DustPolicy,
+        fee_estimator: &dyn FeeEstimator<Context::Transaction>,
+    ) -> SigningResult<Vec<SelectResult<Context::Transaction>>> {
+        if unsigned_tx.outputs().len() != 1 {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Expected exactly one transaction output when consolidating UTXOs");
+        }
+
+        let max_inputs = max_inputs.unwrap_or(usize::MAX);
+        if max_inputs == 0 {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Max number of inputs per transaction must be greater than zero");
+        }
+        // Do not allow to exceed the standard transaction weight.
+        let max_weight = max_weight
+            .unwrap_or(MAX_TRANSACTION_WEIGHT)
+            .min(MAX_TRANSACTION_WEIGHT);
+
+        let unsigned_tx = DustFilter::new(dust_policy)
+            .filter_inputs(unsigned_tx)
+            .context("Error filtering dust UTXOs")?;
+        let unsigned_tx = CashTokenFilter::filter_inputs(unsigned_tx)
+            .context("Error filtering CashToken UTXOs")?;
+
+        // Please note all the transaction inputs contain estimated script sigs.
+        let estimated_tx = unsigned_tx.estimate_transaction();
+        let mut batch_tx = estimated_tx.clone();
+
+        let mut batches: Vec<Range<usize>> = Vec::new();
+        let mut batch_start = 0;
+
+        for utxo_index in 0..estimated_tx.inputs().len() {
+            // Check if the UTXO can be spent on its own before choosing its batch.
+            batch_tx.replace_inputs(vec![estimated_tx.inputs()[utxo_index].clone()]);
+            if batch_tx.weight() > max_weight {
+                return SigningError::err(SigningErrorType::Error_tx_too_big).context(format!(
+                    "UTXO #{utxo_index} cannot be spent within the max transaction weight '{max_weight}'"
+                ));
+            }
+
+            let batch_len = utxo_index + 1 - batch_start;
+            batch_tx.replace_inputs(estimated_tx.inputs()[batch_start..=utxo_index].to_vec());
+
+            if batch_len <= max_inputs && batch_tx.weight() <= max_weight {
+                continue;
+            }
+
+            // The current UTXO doesn't fit the batch, so put it to the next one.
+            batches.push(batch_start..utxo_index);
+            batch_start = utxo_index;
+        }
+        batches.push(batch_start..estimated_tx.inputs().len());
+
+        batches
+            .into_iter()
+            .enumerate()
+            .map(|(tx_index, batch)| {
+                let mut batch_tx = unsigned_tx.clone();
+                batch_tx.set_inputs(
+                    unsigned_tx.inputs()[batch.clone()].to_vec(),
+                    unsigned_tx.input_args()[batch].to_vec(),
+                )?;
+                CashTokenFilter::balance_tokens(&batch_tx, None)?;
+
+                MaxInputSelector::new(batch_tx)
+                    .select_max(dust_policy, fee_estimator)
+                    .with_context(|| {
+                        format!("Error planning consolidation transaction #{tx_index}")
+                    })
+            })
+            .collect()
+    }
+
+    /// Splits the outputs into batches of `max_outputs` each,
+    /// and selects UTXOs for each batch so that the UTXOs are not spent twice.
+    fn plan_payout(
+        unsigned_tx: UnsignedTransaction<Context::Transaction>,
+        change_output: Option<ContextTransactionOutput<Context>>,
+        input_selector: InputSelector,
+        max_outputs: Option<usize>,
+        dust_policy: DustPolicy,
+        fee_estimator: &dyn FeeEstimator<Context::Transaction>,
+    ) -> SigningResult<Vec<SelectResult<Context::Transaction>>> {
+        let max_outputs = max_outputs.unwrap_or(usize::MAX);
+        if max_outputs == 0 {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Max number of outputs per transaction must be greater than zero");
+        }
+
+        // Check every payout amount before selecting UTXOs.
+        DustFilter::new(dust_policy)
+            .check_outputs(&unsigned_tx)
+            .context("There are dust payout amounts")?;
+
+        let payouts = unsigned_tx.outputs().to_vec();
+        let batches_count = payouts.chunks(max_outputs).len();
+        let mut remaining_tx = unsigned_tx;
+        let mut select_results = Vec::with_capacity(batches_count);
+
+        for (tx_index, batch) in payouts.chunks(max_outputs).enumerate() {
+            let mut batch_tx = remaining_tx.clone();
+            batch_tx.replace_outputs(batch.to_vec());
+
+            let select_result = Self::select_exact(
+                batch_tx,
+                change_output.clone(),
+                input_selector,
+                dust_policy,
+                fee_estimator,
+            )
+            .with_context(|| format!("Error planning payout transaction #{tx_index}"))?;
+
+            // Exclude the selected UTXOs from the next transactions.
+            if tx_index + 1 < batches_count {
+                let selected: HashSet<OutPoint> = select_result
+                    .unsigned_tx
+                    .inputs()
+                    .iter()
+                    .map(|utxo| *utxo.previous_output())
+                    .collect();
+                remaining_tx
+                    .retain_inputs(|utxo, _| !selected.contains(utxo.previous_output()))
+                    .with_context(|| {
+                        format!("No UTXOs left to plan payout transaction #{}", tx_index + 1)
+                    })?;
+            }
+
+            select_results.push(select_result);
+        }
+
+        Ok(select_results)
+    }
 }
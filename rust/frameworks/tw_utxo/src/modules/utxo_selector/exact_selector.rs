@@ -4,9 +4,10 @@
 
 use crate::constants::MAX_TRANSACTION_WEIGHT;
 use crate::dust::DustPolicy;
-use crate::fee::fee_estimator::FeeEstimator;
+use crate::fee::fee_estimator::{FeeBounds, FeeEstimator};
 use crate::modules::utxo_selector::{InputSelector, SelectPlan, SelectResult};
 use crate::script::{Script, Witness};
+use crate::transaction::asset::cash_token::has_token_prefix;
 use crate::transaction::transaction_interface::{
     TransactionInterface, TxInputInterface, TxOutputInterface,
 };
@@ -18,6 +19,8 @@ use tw_coin_entry::error::prelude::*;
 pub struct ExactInputSelector<Transaction: TransactionInterface> {
     unsigned_tx: UnsignedTransaction<Transaction>,
     change_output: Option<Transaction::Output>,
+    /// Indexes of the UTXOs that must be selected regardless of the amounts.
+    required_inputs: Vec<usize>,
 }
 
 impl<Transaction> ExactInputSelector<Transaction>
@@ -28,6 +31,7 @@ where
         ExactInputSelector {
             unsigned_tx,
             change_output: None,
+            required_inputs: Vec::default(),
         }
     }
 
@@ -36,6 +40,12 @@ where
         self
     }
 
+    /// Sets the UTXOs that must be selected, e.g. UTXOs holding CashTokens.
+    pub fn required_inputs(mut self, required_inputs: Vec<usize>) -> Self {
+        self.required_inputs = required_inputs;
+        self
+    }
+
     pub fn select_inputs(
         mut self,
         dust_policy: DustPolicy,
@@ -47,6 +57,17 @@ where
         // Calculate the total output amount.
         let mut total_out = self.unsigned_tx.total_output()?;
 
+        let dust_threshold = dust_policy.dust_threshold();
+        // The change output holding tokens must be included in the transaction, so it cannot be dust.
+        let min_change = match self.change_output {
+            Some(ref change_output)
+                if has_token_prefix(change_output.script_pubkey().as_slice()) =>
+            {
+                dust_threshold
+            },
+            _ => 0,
+        };
+
         if let Some(ref change_output) = self.change_output {
             // Push the change output to the temporary "estimated" transaction only to get the most max possible fee.
             // Later, if the result change amount is not dust (> dustThreshold),
@@ -56,11 +77,13 @@ where
 
         // Prepare the available UTXOs.
         // Please note all the Transaction inputs contain estimated script sigs.
-        let mut utxos: Vec<(Transaction::Input, &UtxoToSign)> = estimated_tx
+        let mut utxos: Vec<(usize, Transaction::Input, &UtxoToSign)> = estimated_tx
             .inputs()
             .iter()
             .cloned()
             .zip(self.unsigned_tx.input_args())
+            .enumerate()
+            .map(|(index, (input, arg))| (index, input, arg))
             .collect();
 
         // Sort the UTXOs.
@@ -68,12 +91,14 @@ where
             // Nothing to do.
             InputSelector::UseAll | InputSelector::InOrder => (),
             InputSelector::Ascending => {
-                utxos.sort_by(|(_, a), (_, b)| a.amount.cmp(&b.amount));
+                utxos.sort_by(|(_, _, a), (_, _, b)| a.amount.cmp(&b.amount));
             },
             InputSelector::Descending => {
-                utxos.sort_by(|(_, a), (_, b)| b.amount.cmp(&a.amount));
+                utxos.sort_by(|(_, _, a), (_, _, b)| b.amount.cmp(&a.amount));
             },
         }
+        // Required UTXOs go first. Please note the sort is stable.
+        utxos.sort_by_key(|(index, _, _)| !self.required_inputs.contains(index));
 
         // Select the UTXOs to cover all the outputs and the fee.
         let mut total_in = 0;
@@ -82,7 +107,7 @@ where
         let mut selected_utxo_args = Vec::with_capacity(utxos.len());
 
         let mut total_covered = false;
-        for (input, arg) in utxos {
+        for (_, input, arg) in utxos {
             // Estimated script_sig or witness MUST be set.
             debug_assert!(input.has_witness() || input.has_script_sig());
 
@@ -105,9 +130,10 @@ where
             // Estimate the transaction fee.
             tx_fee = fee_estimator.estimate_fee(&estimated_tx)?;
 
-            // Check if the total input amount covers the total output amount
-            // and the fee.
-            if total_in >= total_out + tx_fee {
+            // Check if all the required UTXOs are selected, and the total input amount covers
+            // the total output amount and the fee.
+            let required_selected = selected_utxos.len() >= self.required_inputs.len();
+            if required_selected && total_in >= total_out + tx_fee + min_change {
                 total_covered = true;
 
                 // Unless we're told to use all inputs, we can stop here.
@@ -130,7 +156,6 @@ where
         );
         let mut change = total_in - total_out - tx_fee;
 
-        let dust_threshold = dust_policy.dust_threshold();
         match self.change_output {
             Some(mut change_output) if change >= dust_threshold => {
                 change_output.set_value(change);
@@ -152,6 +177,7 @@ where
 
         debug_assert!(total_out <= total_in);
         let fee_estimate = total_in - total_out;
+        let fee_bounds = FeeBounds::estimate(&self.unsigned_tx, fee_estimator)?;
 
         let plan = SelectPlan {
             total_spend: total_in,
@@ -160,6 +186,7 @@ where
             // This was made for simplicity. Consider fixing this later.
             vsize_estimate: estimated_tx.vsize(),
             fee_estimate,
+            fee_bounds,
             change,
         };
         Ok(SelectResult {
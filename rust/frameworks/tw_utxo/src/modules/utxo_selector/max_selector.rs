@@ -4,7 +4,7 @@
 
 use crate::constants::MAX_TRANSACTION_WEIGHT;
 use crate::dust::DustPolicy;
-use crate::fee::fee_estimator::FeeEstimator;
+use crate::fee::fee_estimator::{FeeBounds, FeeEstimator};
 use crate::modules::utxo_selector::{SelectPlan, SelectResult};
 use crate::script::{Script, Witness};
 use crate::transaction::transaction_interface::{
@@ -108,11 +108,14 @@ where
             .expect("Transaction output length must be checked already")
             .set_value(max_to_send);
 
+        let fee_bounds = FeeBounds::estimate(&self.unsigned_tx, fee_estimator)?;
+
         let plan = SelectPlan {
             total_spend: total_in,
             total_send: max_to_send,
             vsize_estimate: estimated_tx.vsize(),
             fee_estimate: tx_fee,
+            fee_bounds,
             change: 0,
         };
         Ok(SelectResult {
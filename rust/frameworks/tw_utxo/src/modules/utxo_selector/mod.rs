@@ -2,6 +2,7 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::fee::fee_estimator::FeeBounds;
 use crate::transaction::transaction_parts::Amount;
 use crate::transaction::unsigned_transaction::UnsignedTransaction;
 
@@ -37,6 +38,9 @@ pub struct SelectPlan {
     pub vsize_estimate: usize,
     /// The estimated fees of the transaction in satoshis.
     pub fee_estimate: Amount,
+    /// Min and max fees required by the result transaction
+    /// depending on the actual size of the signatures and other spending data.
+    pub fee_bounds: FeeBounds,
     // Remaining change.
     // Zero if not applied.
     pub change: Amount,
@@ -9,6 +9,8 @@ use standard_script::opcodes::*;
 use tw_memory::Data;
 
 pub mod standard_script;
+pub mod tap_tree;
+pub mod timelock;
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Script {
@@ -38,10 +38,10 @@ pub fn new_p2pk(sig: &BitcoinEcdsaSignature) -> Script {
 /// ```txt
 /// <push><sig><push><pubkey>
 /// ```
-pub fn new_p2pkh(sig: &BitcoinEcdsaSignature, pubkey: &H264) -> Script {
+pub fn new_p2pkh(sig: &BitcoinEcdsaSignature, pubkey: &[u8]) -> Script {
     let mut s = Script::with_capacity(100);
     s.push_slice(&sig.serialize());
-    s.push_slice(pubkey.as_slice());
+    s.push_slice(pubkey);
     s
 }
 
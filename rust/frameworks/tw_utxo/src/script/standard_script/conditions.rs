@@ -109,6 +109,22 @@ pub fn new_p2tr_script_path(pubkey: &H264, merkle_root: &H256) -> Script {
     Script::from(script.to_vec())
 }
 
+/// Creates a bare multisig script that is usually used as a P2SH or P2WSH redeem script.
+///
+/// ```txt
+/// <k> <push><pubkey_1> ... <push><pubkey_n> <n> OP_CHECKMULTISIG
+/// ```
+pub fn new_multisig(threshold: u8, pubkeys: &[H264]) -> Script {
+    let mut s = Script::with_capacity(3 + pubkeys.len() * 34);
+    s.push_int(threshold as i64);
+    for pubkey in pubkeys {
+        s.push_slice(pubkey.as_slice());
+    }
+    s.push_int(pubkeys.len() as i64);
+    s.push(OP_CHECKMULTISIG);
+    s
+}
+
 pub fn new_op_return(data: &[u8]) -> Script {
     let mut s = Script::with_capacity(83);
     s.push(OP_RETURN);
@@ -215,3 +231,17 @@ pub fn match_op_return(s: &Script) -> Option<Data> {
         None
     }
 }
+
+/// Returns a witness version and a witness program if matched (BIP-141).
+pub fn match_witness_program(s: &Script) -> Option<(u8, &[u8])> {
+    let b = s.as_slice();
+    if b.len() < 4 || b.len() > 42 || b[1] as usize != b.len() - 2 {
+        return None;
+    }
+    let version = match b[0] {
+        OP_0 => 0,
+        OP_PUSHNUM_1..=OP_PUSHNUM_16 => b[0] - OP_PUSHNUM_1 + 1,
+        _ => return None,
+    };
+    Some((version, &b[2..]))
+}
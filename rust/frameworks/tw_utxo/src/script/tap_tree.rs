@@ -0,0 +1,240 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Taproot script tree as specified in
+//! [BIP-341](https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#constructing-and-spending-taproot-outputs).
+
+use crate::script::standard_script::conditions;
+use crate::script::Script;
+use bitcoin::hashes::Hash;
+use bitcoin::taproot::{LeafVersion, TapLeafHash, TaprootBuilder, TaprootSpendInfo};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_keypair::schnorr;
+use tw_memory::Data;
+
+/// Leaf version of the BIP-342 tapscript.
+pub const TAPSCRIPT_LEAF_VERSION: u8 = 0xc0;
+
+/// A Taproot script tree leaf.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TapLeaf {
+    script: Script,
+    version: u8,
+}
+
+impl TapLeaf {
+    /// Creates a leaf with a custom leaf version.
+    /// The version must be even and must not be `0x50` (reserved for the annex).
+    pub fn new(script: Script, version: u8) -> SigningResult<TapLeaf> {
+        LeafVersion::from_consensus(version)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .with_context(|| format!("Invalid Taproot leaf version: {version:#04x}"))?;
+        Ok(TapLeaf { script, version })
+    }
+
+    /// Creates a BIP-342 tapscript leaf.
+    pub fn tapscript(script: Script) -> TapLeaf {
+        TapLeaf {
+            script,
+            version: TAPSCRIPT_LEAF_VERSION,
+        }
+    }
+
+    pub fn script(&self) -> &Script {
+        &self.script
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn leaf_hash(&self) -> H256 {
+        let script_buf = bitcoin::ScriptBuf::from_bytes(self.script.to_vec());
+        let leaf_hash = TapLeafHash::from_script(&script_buf, self.leaf_version());
+        H256::from(leaf_hash.to_byte_array())
+    }
+
+    fn leaf_version(&self) -> LeafVersion {
+        LeafVersion::from_consensus(self.version).expect("Leaf version is checked at TapLeaf::new")
+    }
+
+    fn script_and_version(&self) -> (bitcoin::ScriptBuf, LeafVersion) {
+        (
+            bitcoin::ScriptBuf::from_bytes(self.script.to_vec()),
+            self.leaf_version(),
+        )
+    }
+}
+
+/// Taproot script tree builder.
+/// Leaves can either be added at explicit depths, or arranged by weights via [`TapTreeBuilder::with_huffman`].
+#[derive(Clone, Debug, Default)]
+pub struct TapTreeBuilder {
+    /// Leaves with their depths in the depth-first order.
+    leaves: Vec<(u8, TapLeaf)>,
+}
+
+impl TapTreeBuilder {
+    pub fn new() -> TapTreeBuilder {
+        TapTreeBuilder::default()
+    }
+
+    /// Adds a leaf at the given `depth`.
+    ///
+    /// # Note
+    ///
+    /// Leaves must be added in the depth-first search order,
+    /// the tree structure is validated at [`TapTreeBuilder::finalize`].
+    pub fn add_leaf(mut self, depth: u8, leaf: TapLeaf) -> TapTreeBuilder {
+        self.leaves.push((depth, leaf));
+        self
+    }
+
+    /// Creates a tree using the Huffman algorithm, so leaves with higher weights
+    /// (i.e. higher probability of being spent) are placed closer to the root
+    /// and have shorter control blocks.
+    ///
+    /// Leaves with equal weights keep their insertion order.
+    pub fn with_huffman(weighted_leaves: Vec<(u32, TapLeaf)>) -> SigningResult<TapTreeBuilder> {
+        if weighted_leaves.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Taproot script tree must contain at least one leaf");
+        }
+
+        enum Node {
+            Leaf(usize),
+            Branch(usize, usize),
+        }
+
+        let mut nodes: Vec<Node> = Vec::with_capacity(weighted_leaves.len() * 2);
+        // Min-heap of `(weight, node_index)`.
+        let mut heap = BinaryHeap::with_capacity(weighted_leaves.len());
+        for (leaf_idx, (weight, _)) in weighted_leaves.iter().enumerate() {
+            heap.push(Reverse((*weight as u64, nodes.len())));
+            nodes.push(Node::Leaf(leaf_idx));
+        }
+
+        // Combine the two lightest nodes until the root remains only.
+        while heap.len() > 1 {
+            let Reverse((left_weight, left)) = heap.pop().expect("heap.len() > 1");
+            let Reverse((right_weight, right)) = heap.pop().expect("heap.len() > 1");
+            heap.push(Reverse((left_weight + right_weight, nodes.len())));
+            nodes.push(Node::Branch(left, right));
+        }
+        let Reverse((_, root)) = heap.pop().expect("Checked that there is at least one leaf");
+
+        // Walk the tree in the depth-first order and collect leaf depths.
+        let mut leaf_depths = Vec::with_capacity(weighted_leaves.len());
+        let mut stack = vec![(root, 0_usize)];
+        while let Some((node_idx, depth)) = stack.pop() {
+            match nodes[node_idx] {
+                Node::Leaf(leaf_idx) => leaf_depths.push((leaf_idx, depth)),
+                Node::Branch(left, right) => {
+                    // Push the right node first to process the left one earlier.
+                    stack.push((right, depth + 1));
+                    stack.push((left, depth + 1));
+                },
+            }
+        }
+
+        let mut leaves: Vec<Option<TapLeaf>> = weighted_leaves
+            .into_iter()
+            .map(|(_, leaf)| Some(leaf))
+            .collect();
+        let mut builder = TapTreeBuilder::new();
+        for (leaf_idx, depth) in leaf_depths {
+            let depth = u8::try_from(depth)
+                .tw_err(|_| SigningErrorType::Error_invalid_params)
+                .context("Taproot script tree is too deep")?;
+            let leaf = leaves[leaf_idx].take().expect("Each leaf is visited once");
+            builder = builder.add_leaf(depth, leaf);
+        }
+        Ok(builder)
+    }
+
+    /// Finalizes the tree with the given `internal_key`.
+    pub fn finalize(self, internal_key: &schnorr::PublicKey) -> SigningResult<TapTree> {
+        if self.leaves.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Taproot script tree must contain at least one leaf");
+        }
+
+        // We're relying on the `bitcoin` crate to generate anything Taproot related.
+        let internal_xonly = bitcoin::secp256k1::XOnlyPublicKey::from_slice(
+            internal_key.x_only().bytes().as_slice(),
+        )
+        .tw_err(|_| SigningErrorType::Error_invalid_params)
+        .context("Invalid Taproot internal key")?;
+
+        let mut builder = TaprootBuilder::new();
+        for (depth, leaf) in self.leaves.iter() {
+            let (script, version) = leaf.script_and_version();
+            builder = builder
+                .add_leaf_with_ver(*depth, script, version)
+                .tw_err(|_| SigningErrorType::Error_invalid_params)
+                .with_context(|| format!("Cannot add a Taproot leaf at depth {depth}"))?;
+        }
+
+        let spend_info = builder
+            .finalize(&bitcoin::secp256k1::Secp256k1::new(), internal_xonly)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Taproot script tree is incomplete")?;
+
+        Ok(TapTree {
+            internal_key: internal_key.clone(),
+            leaves: self.leaves.into_iter().map(|(_, leaf)| leaf).collect(),
+            spend_info,
+        })
+    }
+}
+
+/// A finalized Taproot script tree.
+#[derive(Clone, Debug)]
+pub struct TapTree {
+    internal_key: schnorr::PublicKey,
+    /// Leaves in the depth-first order.
+    leaves: Vec<TapLeaf>,
+    spend_info: TaprootSpendInfo,
+}
+
+impl TapTree {
+    pub fn internal_key(&self) -> &schnorr::PublicKey {
+        &self.internal_key
+    }
+
+    /// Returns the tree leaves in the depth-first order.
+    pub fn leaves(&self) -> &[TapLeaf] {
+        &self.leaves
+    }
+
+    pub fn merkle_root(&self) -> H256 {
+        let root = self
+            .spend_info
+            .merkle_root()
+            .expect("The tree contains at least one leaf");
+        H256::from(root.to_byte_array())
+    }
+
+    /// Returns the tweaked x-only output key.
+    pub fn output_key(&self) -> H256 {
+        H256::from(self.spend_info.output_key().to_inner().serialize())
+    }
+
+    /// Returns a P2TR scriptPubkey committing to the tree.
+    pub fn script_pubkey(&self) -> Script {
+        conditions::new_p2tr_dangerous_assume_tweaked(&self.output_key())
+    }
+
+    /// Returns a serialized control block required to spend the given `leaf`.
+    pub fn control_block(&self, leaf: &TapLeaf) -> SigningResult<Data> {
+        self.spend_info
+            .control_block(&leaf.script_and_version())
+            .map(|control_block| control_block.serialize())
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("The leaf is not a part of the Taproot script tree")
+    }
+}
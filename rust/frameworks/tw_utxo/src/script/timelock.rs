@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Absolute ([BIP-65](https://github.com/bitcoin/bips/blob/master/bip-0065.mediawiki))
+//! and relative ([BIP-112](https://github.com/bitcoin/bips/blob/master/bip-0112.mediawiki)) timelocks.
+
+use crate::script::standard_script::opcodes::*;
+use crate::script::tap_tree::{TapLeaf, TapTree, TapTreeBuilder};
+use crate::script::Script;
+use tw_coin_entry::error::prelude::*;
+use tw_keypair::schnorr;
+
+/// `nLockTime` values below the threshold are interpreted as block heights, otherwise as UNIX timestamps.
+pub const LOCKTIME_THRESHOLD: u32 = 500_000_000;
+/// `nSequence` value that disables both `nLockTime` and relative timelocks of the input.
+pub const SEQUENCE_FINAL: u32 = 0xFFFF_FFFF;
+/// `nSequence` value that enables `nLockTime`, but disables a relative timelock of the input.
+pub const SEQUENCE_ENABLE_LOCKTIME: u32 = 0xFFFF_FFFE;
+/// If set, `nSequence` is not interpreted as a relative timelock (BIP-68).
+pub const SEQUENCE_LOCKTIME_DISABLE_FLAG: u32 = 1 << 31;
+/// If set, a relative timelock is specified in units of 512 seconds, otherwise in blocks (BIP-68).
+pub const SEQUENCE_LOCKTIME_TYPE_FLAG: u32 = 1 << 22;
+/// Bits of `nSequence` that hold a relative timelock value (BIP-68).
+pub const SEQUENCE_LOCKTIME_MASK: u32 = 0x0000_FFFF;
+/// Min transaction version that enables relative timelocks (BIP-68).
+pub const RELATIVE_TIMELOCK_MIN_TX_VERSION: i32 = 2;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Timelock {
+    /// Checked by `OP_CHECKLOCKTIMEVERIFY` against the transaction `nLockTime`.
+    /// Either a block height, or a UNIX timestamp if greater or equal to [`LOCKTIME_THRESHOLD`].
+    Absolute(u32),
+    /// Checked by `OP_CHECKSEQUENCEVERIFY` against the input `nSequence`.
+    /// Encoded as specified in BIP-68: either a number of blocks,
+    /// or a number of 512 seconds intervals if [`SEQUENCE_LOCKTIME_TYPE_FLAG`] is set.
+    Relative(u32),
+}
+
+impl Timelock {
+    pub fn absolute(locktime: u32) -> SigningResult<Timelock> {
+        if locktime == 0 {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Absolute timelock must be greater than zero");
+        }
+        Ok(Timelock::Absolute(locktime))
+    }
+
+    pub fn relative(sequence: u32) -> SigningResult<Timelock> {
+        if sequence & SEQUENCE_LOCKTIME_DISABLE_FLAG != 0 {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Relative timelock must not have the disable flag set");
+        }
+        if sequence & SEQUENCE_LOCKTIME_MASK == 0 {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Relative timelock must be greater than zero");
+        }
+        Ok(Timelock::Relative(sequence))
+    }
+
+    /// Returns a script that can be spent by the `pubkey` owner once the timelock expires:
+    ///
+    /// ```txt
+    /// <timelock> OP_CHECKLOCKTIMEVERIFY|OP_CHECKSEQUENCEVERIFY OP_DROP <pubkey> OP_CHECKSIG
+    /// ```
+    ///
+    /// `pubkey` is either a 33-byte ecdsa public key in P2WSH scripts,
+    /// or a 32-byte x-only public key in tapscripts.
+    pub fn script(&self, pubkey: &[u8]) -> Script {
+        let mut s = Script::with_capacity(48);
+        match self {
+            Timelock::Absolute(locktime) => {
+                s.push_int(*locktime as i64);
+                s.push(OP_CLTV);
+            },
+            Timelock::Relative(sequence) => {
+                s.push_int(*sequence as i64);
+                s.push(OP_CHECKSEQUENCEVERIFY);
+            },
+        }
+        s.push(OP_DROP);
+        s.push_slice(pubkey);
+        s.push(OP_CHECKSIG);
+        s
+    }
+
+    /// Returns a Taproot script tree consisting of the only [`Timelock::script`] leaf
+    /// that can be spent by the `spender`.
+    pub fn tap_tree(
+        &self,
+        internal_key: &schnorr::PublicKey,
+        spender: &schnorr::PublicKey,
+    ) -> SigningResult<(TapTree, TapLeaf)> {
+        let leaf = TapLeaf::tapscript(self.script(spender.x_only().bytes().as_slice()));
+        let tree = TapTreeBuilder::new()
+            .add_leaf(0, leaf.clone())
+            .finalize(internal_key)?;
+        Ok((tree, leaf))
+    }
+
+    /// Returns the `nSequence` of an input that satisfies the timelock.
+    pub fn sequence(&self) -> u32 {
+        match self {
+            Timelock::Absolute(_) => SEQUENCE_ENABLE_LOCKTIME,
+            Timelock::Relative(sequence) => *sequence,
+        }
+    }
+
+    /// Checks if a transaction input satisfies the timelock.
+    pub fn check(&self, tx_version: i32, tx_locktime: u32, sequence: u32) -> SigningResult<()> {
+        match *self {
+            Timelock::Absolute(locktime) => {
+                if sequence == SEQUENCE_FINAL {
+                    return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                        "Input nSequence must not be final to enable the transaction nLockTime",
+                    );
+                }
+                if (locktime < LOCKTIME_THRESHOLD) != (tx_locktime < LOCKTIME_THRESHOLD) {
+                    return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                        "Transaction nLockTime and the timelock must be both either block heights or timestamps",
+                    );
+                }
+                if tx_locktime < locktime {
+                    return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                        format!("Transaction nLockTime '{tx_locktime}' is less than the timelock '{locktime}'"),
+                    );
+                }
+            },
+            Timelock::Relative(locktime) => {
+                if tx_version < RELATIVE_TIMELOCK_MIN_TX_VERSION {
+                    return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                        format!("Transaction version must be at least {RELATIVE_TIMELOCK_MIN_TX_VERSION} to enable relative timelocks"),
+                    );
+                }
+                if sequence & SEQUENCE_LOCKTIME_DISABLE_FLAG != 0 {
+                    return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                        "Input nSequence must not have the relative timelock disable flag set",
+                    );
+                }
+                if sequence & SEQUENCE_LOCKTIME_TYPE_FLAG != locktime & SEQUENCE_LOCKTIME_TYPE_FLAG
+                {
+                    return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                        "Input nSequence and the timelock must be both either block-based or time-based",
+                    );
+                }
+                if sequence & SEQUENCE_LOCKTIME_MASK < locktime & SEQUENCE_LOCKTIME_MASK {
+                    return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                        format!("Input nSequence '{sequence}' is less than the relative timelock '{locktime}'"),
+                    );
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::ToHex;
+
+    const PUBKEY: &str = "037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf";
+
+    #[test]
+    fn test_timelock_script() {
+        let pubkey = tw_encoding::hex::decode(PUBKEY).unwrap();
+
+        // 800_000 = 0x0c3500.
+        let cltv = Timelock::absolute(800_000).unwrap().script(&pubkey);
+        assert_eq!(
+            cltv.as_slice().to_hex(),
+            format!("0300350cb17521{PUBKEY}ac")
+        );
+
+        // 144 blocks = 0x90, pushed as a 2-byte number because of the sign bit.
+        let csv = Timelock::relative(144).unwrap().script(&pubkey);
+        assert_eq!(csv.as_slice().to_hex(), format!("029000b27521{PUBKEY}ac"));
+
+        // Small numbers are pushed with dedicated opcodes.
+        let csv = Timelock::relative(10).unwrap().script(&pubkey);
+        assert_eq!(csv.as_slice().to_hex(), format!("5ab27521{PUBKEY}ac"));
+    }
+
+    #[test]
+    fn test_timelock_check_absolute() {
+        let timelock = Timelock::absolute(800_000).unwrap();
+        timelock
+            .check(1, 800_000, SEQUENCE_ENABLE_LOCKTIME)
+            .unwrap();
+        timelock.check(1, 800_001, 0).unwrap();
+
+        // Final sequence disables nLockTime.
+        timelock.check(1, 800_000, SEQUENCE_FINAL).unwrap_err();
+        // Locktime is not reached.
+        timelock
+            .check(1, 799_999, SEQUENCE_ENABLE_LOCKTIME)
+            .unwrap_err();
+        // Timestamp vs block height.
+        timelock
+            .check(1, LOCKTIME_THRESHOLD + 1, SEQUENCE_ENABLE_LOCKTIME)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_timelock_check_relative() {
+        let timelock = Timelock::relative(144).unwrap();
+        timelock.check(2, 0, 144).unwrap();
+        timelock.check(2, 0, 145).unwrap();
+
+        // Relative timelocks are disabled in version 1 transactions.
+        timelock.check(1, 0, 144).unwrap_err();
+        // Relative timelock is not reached.
+        timelock.check(2, 0, 143).unwrap_err();
+        // Disabled relative timelock.
+        timelock.check(2, 0, SEQUENCE_FINAL).unwrap_err();
+        // Time-based vs block-based.
+        timelock
+            .check(2, 0, SEQUENCE_LOCKTIME_TYPE_FLAG | 144)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_timelock_invalid() {
+        Timelock::absolute(0).unwrap_err();
+        Timelock::relative(0).unwrap_err();
+        Timelock::relative(SEQUENCE_LOCKTIME_DISABLE_FLAG | 144).unwrap_err();
+    }
+}
@@ -23,6 +23,8 @@ pub struct BitcoinEcdsaSignature {
 impl BitcoinEcdsaSignature {
     // The max size of the serialized signature including sighash type.
     const SER_SIZE: usize = 73;
+    // The size of a DER signature with 32-byte `r` and `s` values.
+    const MIN_DER_SIZE: usize = 70;
 
     pub fn new(sig: der::Signature, sighash_ty: SighashType) -> SigningResult<Self> {
         let der_sig = sig.der_bytes();
@@ -40,6 +42,14 @@ impl BitcoinEcdsaSignature {
         }
     }
 
+    /// Returns a signature of the min size produced in practice, i.e. with 32-byte `r` and `s` values.
+    pub fn estimated_min() -> Self {
+        BitcoinEcdsaSignature {
+            der_sig: vec![ESTIMATION_DEFAULT_BYTE; Self::MIN_DER_SIZE],
+            sighash_ty: ESTIMATION_DEFAULT_BYTE,
+        }
+    }
+
     pub fn serialize(&self) -> Data {
         let mut ser = Vec::with_capacity(Self::SER_SIZE);
         ser.extend_from_slice(self.der_sig.as_slice());
@@ -73,6 +83,20 @@ impl BitcoinSchnorrSignature {
         }
     }
 
+    /// Returns a signature of the min size.
+    /// The sighash type is omitted if the default Taproot sighash type is used.
+    pub fn estimated_min(sighash_ty: SighashType) -> BitcoinSchnorrSignature {
+        let sighash_ty = if sighash_ty.is_default_taproot_sighash() {
+            0
+        } else {
+            ESTIMATION_DEFAULT_BYTE
+        };
+        BitcoinSchnorrSignature {
+            sig: H512::from([ESTIMATION_DEFAULT_BYTE; H512::LEN]),
+            sighash_ty,
+        }
+    }
+
     pub fn serialize(&self) -> Vec<u8> {
         let mut ser = Vec::with_capacity(65);
         ser.extend_from_slice(self.sig.as_slice());
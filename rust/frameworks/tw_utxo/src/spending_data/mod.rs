@@ -6,6 +6,7 @@ use crate::script::{Script, Witness};
 use crate::signature::{BitcoinEcdsaSignature, BitcoinSchnorrSignature};
 use std::fmt;
 use std::rc::Rc;
+use tw_coin_entry::error::prelude::*;
 
 pub mod standard_constructor;
 
@@ -30,6 +31,10 @@ pub trait SchnorrSpendingDataConstructor: fmt::Debug {
 pub enum SpendingDataConstructor {
     Ecdsa(Rc<dyn EcdsaSpendingDataConstructor>),
     Schnorr(Rc<dyn SchnorrSpendingDataConstructor>),
+    /// The UTXO is spent by a script unknown to the signer.
+    /// Such UTXO can be planned if [`crate::fee::satisfaction_weight::SatisfactionWeight`] is specified,
+    /// but cannot be signed.
+    Foreign,
 }
 
 impl SpendingDataConstructor {
@@ -41,3 +46,9 @@ impl SpendingDataConstructor {
         SpendingDataConstructor::Schnorr(Rc::new(constructor))
     }
 }
+
+/// Returns an error stating that a [`SpendingDataConstructor::Foreign`] UTXO cannot be signed.
+pub fn foreign_utxo_error<T>() -> SigningResult<T> {
+    SigningError::err(SigningErrorType::Error_not_supported)
+        .context("Cannot sign a UTXO locked by a non-standard script. Such UTXO can be used to plan a transaction only")
+}
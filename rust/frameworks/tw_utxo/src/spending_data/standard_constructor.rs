@@ -13,7 +13,8 @@ use tw_memory::Data;
 
 #[derive(Clone, Debug)]
 pub struct P2PKH {
-    pub pubkey: H264,
+    /// Either compressed or uncompressed public key.
+    pub pubkey: Data,
 }
 
 impl EcdsaSpendingDataConstructor for P2PKH {
@@ -51,6 +52,46 @@ impl EcdsaSpendingDataConstructor for P2WPKH {
     }
 }
 
+/// Spends a P2WPKH output nested in P2SH.
+#[derive(Clone, Debug)]
+pub struct P2SHP2WPKH {
+    /// P2WPKH `scriptPubkey` the P2SH output commits to.
+    pub redeem_script: Script,
+    pub pubkey: H264,
+}
+
+impl EcdsaSpendingDataConstructor for P2SHP2WPKH {
+    fn get_spending_data(&self, sig: &BitcoinEcdsaSignature) -> SpendingData {
+        let mut script_sig = Script::new();
+        script_sig.push_slice(self.redeem_script.as_slice());
+
+        SpendingData {
+            script_sig,
+            witness: claims::new_p2wpkh(sig, self.pubkey),
+        }
+    }
+}
+
+/// Spends a P2WSH output whose witness script requires a single signature,
+/// e.g. a timelock script.
+#[derive(Clone, Debug)]
+pub struct P2WSHSingleSig {
+    pub witness_script: Script,
+}
+
+impl EcdsaSpendingDataConstructor for P2WSHSingleSig {
+    fn get_spending_data(&self, sig: &BitcoinEcdsaSignature) -> SpendingData {
+        let mut witness = Witness::new();
+        witness.push_item(Script::from(sig.serialize()));
+        witness.push_item(self.witness_script.clone());
+
+        SpendingData {
+            script_sig: Script::default(),
+            witness,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct P2TRKeyPath;
 
@@ -81,3 +122,41 @@ impl SchnorrSpendingDataConstructor for P2TRScriptPath {
         }
     }
 }
+
+/// A witness stack item that precedes a Taproot leaf script.
+#[derive(Clone, Debug)]
+pub enum TaprootWitnessItem {
+    /// Arbitrary data pushed as is, e.g. a preimage or an empty item to select an `OP_IF` branch.
+    Data(Data),
+    /// A placeholder for the spender's signature.
+    Signature,
+}
+
+/// Spends a Taproot leaf with user-supplied witness stack items.
+#[derive(Clone, Debug)]
+pub struct P2TRScriptPathWithWitness {
+    /// Witness items in the order they are pushed onto the witness stack.
+    pub witness_items: Vec<TaprootWitnessItem>,
+    pub payload: Script,
+    pub control_block: Data,
+}
+
+impl SchnorrSpendingDataConstructor for P2TRScriptPathWithWitness {
+    fn get_spending_data(&self, sig: &BitcoinSchnorrSignature) -> SpendingData {
+        let mut witness = Witness::new();
+        for item in self.witness_items.iter() {
+            let item = match item {
+                TaprootWitnessItem::Data(data) => data.clone(),
+                TaprootWitnessItem::Signature => sig.serialize(),
+            };
+            witness.push_item(Script::from(item));
+        }
+        witness.push_item(self.payload.clone());
+        witness.push_item(Script::from(self.control_block.clone()));
+
+        SpendingData {
+            script_sig: Script::default(),
+            witness,
+        }
+    }
+}
@@ -0,0 +1,386 @@
+//! Bitcoin Cash CashTokens primitives.
+//!
+//! Tokens are stored in the transaction outputs as a token prefix prepended to the locking bytecode:
+//! `PREFIX_TOKEN <category_id> <token_bitfield> [nft_commitment] [ft_amount]`.
+//!
+//! See the specification: https://github.com/cashtokens/cashtokens
+
+use crate::script::Script;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_memory::Data;
+
+/// The first byte of a token prefix. It's an undefined opcode, so a locking bytecode cannot start with it.
+pub const PREFIX_TOKEN: u8 = 0xef;
+/// The maximum length of a non-fungible token commitment.
+pub const MAX_COMMITMENT_LENGTH: usize = 40;
+/// The maximum fungible token amount that can be encoded, i.e. `i64::MAX`.
+pub const MAX_FUNGIBLE_AMOUNT: u64 = i64::MAX as u64;
+
+const RESERVED_BIT: u8 = 0b1000_0000;
+const HAS_COMMITMENT_LENGTH: u8 = 0b0100_0000;
+const HAS_NFT: u8 = 0b0010_0000;
+const HAS_AMOUNT: u8 = 0b0001_0000;
+const CAPABILITY_MASK: u8 = 0b0000_1111;
+
+/// Non-fungible token capability.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum NftCapability {
+    /// The commitment of the NFT cannot be changed.
+    Immutable = 0,
+    /// The commitment of the NFT can be changed once when the NFT is spent.
+    Mutable = 1,
+    /// The NFT can create new NFTs of the same category.
+    Minting = 2,
+}
+
+impl NftCapability {
+    fn from_u8(capability: u8) -> Option<NftCapability> {
+        match capability {
+            0 => Some(NftCapability::Immutable),
+            1 => Some(NftCapability::Mutable),
+            2 => Some(NftCapability::Minting),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NonFungibleToken {
+    pub capability: NftCapability,
+    /// Up to [`MAX_COMMITMENT_LENGTH`] bytes of arbitrary data.
+    pub commitment: Data,
+}
+
+/// Tokens held by a single transaction output.
+/// An output can hold fungible tokens and/or a non-fungible token of a single category.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CashToken {
+    /// Token category ID. Please note the category is stored in the same byte order as outpoint transaction hashes,
+    /// i.e. reversed relative to the displayed category.
+    pub category: H256,
+    pub nft: Option<NonFungibleToken>,
+    /// Fungible token amount. Zero if the output doesn't hold fungible tokens.
+    pub amount: u64,
+}
+
+impl CashToken {
+    /// Encodes the token prefix.
+    pub fn encode_prefix(&self) -> SigningResult<Data> {
+        self.validate()?;
+
+        let mut bitfield = 0;
+        if let Some(ref nft) = self.nft {
+            bitfield |= HAS_NFT | nft.capability as u8;
+            if !nft.commitment.is_empty() {
+                bitfield |= HAS_COMMITMENT_LENGTH;
+            }
+        }
+        if self.amount > 0 {
+            bitfield |= HAS_AMOUNT;
+        }
+
+        let mut prefix = Vec::with_capacity(1 + H256::LEN + 1);
+        prefix.push(PREFIX_TOKEN);
+        prefix.extend_from_slice(self.category.as_slice());
+        prefix.push(bitfield);
+
+        if let Some(ref nft) = self.nft {
+            if !nft.commitment.is_empty() {
+                encode_compact_size(nft.commitment.len() as u64, &mut prefix);
+                prefix.extend_from_slice(&nft.commitment);
+            }
+        }
+        if self.amount > 0 {
+            encode_compact_size(self.amount, &mut prefix);
+        }
+
+        Ok(prefix)
+    }
+
+    /// Returns a `scriptPubkey` with the token prefix prepended to the given locking bytecode.
+    pub fn prepend_to(&self, locking_script: &Script) -> SigningResult<Script> {
+        if has_token_prefix(locking_script.as_slice()) {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Locking script already contains a CashToken prefix");
+        }
+
+        let mut script_pubkey = self.encode_prefix()?;
+        script_pubkey.extend_from_slice(locking_script.as_slice());
+        Ok(Script::from(script_pubkey))
+    }
+
+    /// Decodes a token prefix from the beginning of the given `scriptPubkey`.
+    /// Returns the token and the length of the prefix,
+    /// or `None` if the `scriptPubkey` doesn't contain a valid token prefix.
+    pub fn decode_prefix(script_pubkey: &[u8]) -> Option<(CashToken, usize)> {
+        let mut reader = Reader::new(script_pubkey);
+
+        if reader.read_u8()? != PREFIX_TOKEN {
+            return None;
+        }
+        let category = H256::try_from(reader.read_slice(H256::LEN)?).ok()?;
+
+        let bitfield = reader.read_u8()?;
+        let capability = bitfield & CAPABILITY_MASK;
+        if bitfield & RESERVED_BIT != 0 {
+            return None;
+        }
+
+        let nft = if bitfield & HAS_NFT != 0 {
+            let commitment = if bitfield & HAS_COMMITMENT_LENGTH != 0 {
+                let len = reader.read_compact_size()? as usize;
+                reader.read_slice(len)?.to_vec()
+            } else {
+                Data::default()
+            };
+            Some(NonFungibleToken {
+                capability: NftCapability::from_u8(capability)?,
+                commitment,
+            })
+        } else {
+            // The capability and commitment can be set for non-fungible tokens only.
+            if bitfield & HAS_COMMITMENT_LENGTH != 0 || capability != 0 {
+                return None;
+            }
+            None
+        };
+
+        let amount = if bitfield & HAS_AMOUNT != 0 {
+            reader.read_compact_size()?
+        } else {
+            0
+        };
+
+        let token = CashToken {
+            category,
+            nft,
+            amount,
+        };
+        token.validate().ok()?;
+        Some((token, reader.position()))
+    }
+
+    /// Returns a token held by an output with the given `scriptPubkey` if any.
+    pub fn from_script_pubkey(script_pubkey: &Script) -> Option<CashToken> {
+        CashToken::decode_prefix(script_pubkey.as_slice()).map(|(token, _)| token)
+    }
+
+    /// Splits the given `scriptPubkey` into a token prefix and the locking bytecode.
+    /// The token prefix is empty if the output doesn't hold tokens.
+    pub fn split_script_pubkey(script_pubkey: &Script) -> (&[u8], &[u8]) {
+        let script_pubkey = script_pubkey.as_slice();
+        let prefix_len = CashToken::decode_prefix(script_pubkey)
+            .map(|(_, prefix_len)| prefix_len)
+            .unwrap_or_default();
+        script_pubkey.split_at(prefix_len)
+    }
+
+    pub fn validate(&self) -> SigningResult<()> {
+        if self.nft.is_none() && self.amount == 0 {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("CashToken must hold either a non-fungible token or a fungible amount");
+        }
+        if self.amount > MAX_FUNGIBLE_AMOUNT {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("CashToken fungible amount is too large");
+        }
+        if let Some(ref nft) = self.nft {
+            if nft.commitment.len() > MAX_COMMITMENT_LENGTH {
+                return SigningError::err(SigningErrorType::Error_invalid_params).context(format!(
+                    "CashToken commitment cannot be longer than {MAX_COMMITMENT_LENGTH} bytes"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks whether the given `scriptPubkey` starts with a valid token prefix.
+pub fn has_token_prefix(script_pubkey: &[u8]) -> bool {
+    CashToken::decode_prefix(script_pubkey).is_some()
+}
+
+fn encode_compact_size(value: u64, out: &mut Data) {
+    match value {
+        0..=0xFC => out.push(value as u8),
+        0xFD..=0xFFFF => {
+            out.push(0xFD);
+            out.extend_from_slice(&(value as u16).to_le_bytes());
+        },
+        0x10000..=0xFFFF_FFFF => {
+            out.push(0xFE);
+            out.extend_from_slice(&(value as u32).to_le_bytes());
+        },
+        _ => {
+            out.push(0xFF);
+            out.extend_from_slice(&value.to_le_bytes());
+        },
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data, position: 0 }
+    }
+
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        self.read_slice(1).map(|bytes| bytes[0])
+    }
+
+    fn read_slice(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(len)?;
+        let slice = self.data.get(self.position..end)?;
+        self.position = end;
+        Some(slice)
+    }
+
+    /// Reads a minimally encoded compact size.
+    fn read_compact_size(&mut self) -> Option<u64> {
+        let (value, min) = match self.read_u8()? {
+            0xFD => {
+                let bytes = self.read_slice(2)?.try_into().ok()?;
+                (u16::from_le_bytes(bytes) as u64, 0xFD)
+            },
+            0xFE => {
+                let bytes = self.read_slice(4)?.try_into().ok()?;
+                (u32::from_le_bytes(bytes) as u64, 0x10000)
+            },
+            0xFF => {
+                let bytes = self.read_slice(8)?.try_into().ok()?;
+                (u64::from_le_bytes(bytes), 0x1_0000_0000)
+            },
+            value => (value as u64, 0),
+        };
+        (value >= min).then_some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::ToHex;
+
+    const CATEGORY: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+    #[track_caller]
+    fn test_prefix_impl(token: CashToken, expected_prefix: &str) {
+        let prefix = token.encode_prefix().unwrap();
+        assert_eq!(prefix.to_hex(), expected_prefix);
+
+        let (decoded, prefix_len) = CashToken::decode_prefix(&prefix).unwrap();
+        assert_eq!(decoded, token);
+        assert_eq!(prefix_len, prefix.len());
+    }
+
+    #[test]
+    fn test_cash_token_prefix() {
+        let category = H256::from(CATEGORY);
+
+        // Fungible only.
+        test_prefix_impl(
+            CashToken {
+                category,
+                nft: None,
+                amount: 1,
+            },
+            &format!("ef{CATEGORY}1001"),
+        );
+        test_prefix_impl(
+            CashToken {
+                category,
+                nft: None,
+                amount: 253,
+            },
+            &format!("ef{CATEGORY}10fdfd00"),
+        );
+        // Immutable NFT without a commitment.
+        test_prefix_impl(
+            CashToken {
+                category,
+                nft: Some(NonFungibleToken {
+                    capability: NftCapability::Immutable,
+                    commitment: Data::default(),
+                }),
+                amount: 0,
+            },
+            &format!("ef{CATEGORY}20"),
+        );
+        // Mutable NFT with a commitment.
+        test_prefix_impl(
+            CashToken {
+                category,
+                nft: Some(NonFungibleToken {
+                    capability: NftCapability::Mutable,
+                    commitment: vec![0xcc],
+                }),
+                amount: 0,
+            },
+            &format!("ef{CATEGORY}6101cc"),
+        );
+        // Minting NFT with a commitment and fungible amount.
+        test_prefix_impl(
+            CashToken {
+                category,
+                nft: Some(NonFungibleToken {
+                    capability: NftCapability::Minting,
+                    commitment: vec![0xcc, 0xcc],
+                }),
+                amount: MAX_FUNGIBLE_AMOUNT,
+            },
+            &format!("ef{CATEGORY}7202ccccffffffffffffffff7f"),
+        );
+    }
+
+    #[test]
+    fn test_cash_token_invalid_prefix() {
+        let invalid = [
+            // No tokens.
+            format!("ef{CATEGORY}00"),
+            // Reserved bit.
+            format!("ef{CATEGORY}9001"),
+            // Capability without NFT.
+            format!("ef{CATEGORY}1101"),
+            // Invalid capability.
+            format!("ef{CATEGORY}23"),
+            // Non-minimal amount encoding.
+            format!("ef{CATEGORY}10fd0100"),
+            // Amount is too large.
+            format!("ef{CATEGORY}10ffffffffffffffffff"),
+            // Truncated commitment.
+            format!("ef{CATEGORY}6002cc"),
+            // Truncated category.
+            "efbbbb".to_string(),
+        ];
+        for prefix in invalid {
+            let prefix = tw_encoding::hex::decode(&prefix).unwrap();
+            assert_eq!(CashToken::decode_prefix(&prefix), None);
+        }
+    }
+
+    #[test]
+    fn test_cash_token_split_script_pubkey() {
+        let p2pkh = "76a914000000000000000000000000000000000000000088ac";
+        let script =
+            Script::from(tw_encoding::hex::decode(&format!("ef{CATEGORY}1001{p2pkh}")).unwrap());
+
+        let (prefix, locking) = CashToken::split_script_pubkey(&script);
+        assert_eq!(prefix.to_hex(), format!("ef{CATEGORY}1001"));
+        assert_eq!(locking.to_hex(), p2pkh);
+
+        let script = Script::from(tw_encoding::hex::decode(p2pkh).unwrap());
+        let (prefix, locking) = CashToken::split_script_pubkey(&script);
+        assert!(prefix.is_empty());
+        assert_eq!(locking.to_hex(), p2pkh);
+    }
+}
@@ -1,2 +1,4 @@
 pub mod brc20;
+pub mod cash_token;
 pub mod ordinal;
+pub mod runes;
@@ -0,0 +1,650 @@
+//! Runes protocol primitives.
+//!
+//! A rune transfer, mint or etching is described by a [`Runestone`] that is embedded into
+//! an `OP_RETURN OP_13 <data pushes...>` output. The payload is a sequence of LEB128 encoded
+//! integers that form `tag, value` pairs followed by an optional body of edicts.
+//!
+//! See the specification: https://docs.ordinals.com/runes/specification.html
+
+use crate::script::standard_script::opcodes::*;
+use crate::script::tap_tree::{TapLeaf, TapTree, TapTreeBuilder};
+use crate::script::Script;
+use bitcoin::blockdata::script::Instruction;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::*;
+use tw_keypair::schnorr;
+
+/// `OP_13` follows `OP_RETURN` to distinguish runestones from other `OP_RETURN` outputs.
+pub const RUNESTONE_MAGIC_NUMBER: u8 = OP_PUSHNUM_13;
+/// The maximum number of decimal places.
+pub const MAX_DIVISIBILITY: u8 = 38;
+/// Spacers can be set between the first 28 letters of the rune name.
+pub const MAX_SPACERS: u32 = 0b0000_0111_1111_1111_1111_1111_1111_1111;
+/// The maximum data push size allowed by the standardness rules.
+const MAX_PUSH_SIZE: usize = 520;
+/// The Unicode "bullet" character used to separate rune name letters.
+const SPACER: char = '•';
+
+#[repr(u8)]
+#[derive(Clone, Copy)]
+enum Tag {
+    Body = 0,
+    Divisibility = 1,
+    Flags = 2,
+    Spacers = 3,
+    Rune = 4,
+    Symbol = 5,
+    Premine = 6,
+    Cap = 8,
+    Amount = 10,
+    HeightStart = 12,
+    HeightEnd = 14,
+    OffsetStart = 16,
+    OffsetEnd = 18,
+    Mint = 20,
+    Pointer = 22,
+}
+
+impl Tag {
+    fn encode(self, values: &[u128], payload: &mut Vec<u8>) {
+        for value in values {
+            varint::encode_to(self as u128, payload);
+            varint::encode_to(*value, payload);
+        }
+    }
+
+    fn encode_option<T: Into<u128>>(self, value: Option<T>, payload: &mut Vec<u8>) {
+        if let Some(value) = value {
+            self.encode(&[value.into()], payload);
+        }
+    }
+
+    /// Takes first `N` values of the tag if they are converted successfully.
+    /// Otherwise, the values are left in `fields`.
+    fn take<const N: usize, T>(
+        self,
+        fields: &mut BTreeMap<u128, VecDeque<u128>>,
+        with: impl Fn([u128; N]) -> Option<T>,
+    ) -> Option<T> {
+        let field = fields.get_mut(&(self as u128))?;
+
+        let mut values = [0_u128; N];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = *field.get(i)?;
+        }
+        let value = with(values)?;
+
+        field.drain(0..N);
+        if field.is_empty() {
+            fields.remove(&(self as u128));
+        }
+        Some(value)
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy)]
+enum Flag {
+    Etching = 0,
+    Terms = 1,
+    Turbo = 2,
+}
+
+impl Flag {
+    fn mask(self) -> u128 {
+        1 << (self as u8)
+    }
+
+    /// Clears the flag and returns whether it was set.
+    fn take(self, flags: &mut u128) -> bool {
+        let set = *flags & self.mask() != 0;
+        *flags &= !self.mask();
+        set
+    }
+}
+
+/// Identifies a rune by the block height and the transaction index of its etching.
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct RuneId {
+    pub block: u64,
+    pub tx: u32,
+}
+
+impl RuneId {
+    pub fn new(block: u64, tx: u32) -> SigningResult<RuneId> {
+        if block == 0 && tx > 0 {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Rune ID transaction index must be 0 at block 0");
+        }
+        Ok(RuneId { block, tx })
+    }
+
+    /// Returns the `(block, tx)` difference from the `previous` rune ID.
+    /// Rune IDs must be sorted in the ascending order.
+    fn delta(self, previous: RuneId) -> (u128, u128) {
+        let block = self.block - previous.block;
+        let tx = if block == 0 {
+            self.tx - previous.tx
+        } else {
+            self.tx
+        };
+        (block as u128, tx as u128)
+    }
+
+    /// Applies the `(block, tx)` difference to the current rune ID.
+    fn next(self, block: u128, tx: u128) -> Option<RuneId> {
+        let block = u64::try_from(block).ok()?;
+        let tx = u32::try_from(tx).ok()?;
+
+        let next_block = self.block.checked_add(block)?;
+        let next_tx = if block == 0 {
+            self.tx.checked_add(tx)?
+        } else {
+            tx
+        };
+        RuneId::new(next_block, next_tx).ok()
+    }
+}
+
+impl FromStr for RuneId {
+    type Err = SigningError;
+
+    /// Parses a rune ID in the `BLOCK:TX` format.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (block, tx) = s
+            .split_once(':')
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("Rune ID must be in the 'BLOCK:TX' format")?;
+        let block = block
+            .parse()
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Invalid Rune ID block height")?;
+        let tx = tx
+            .parse()
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Invalid Rune ID transaction index")?;
+        RuneId::new(block, tx)
+    }
+}
+
+impl fmt::Display for RuneId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.block, self.tx)
+    }
+}
+
+/// A rune name encoded as a modified base-26 integer, i.e. `A = 0`, `Z = 25`, `AA = 26`.
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Rune(pub u128);
+
+impl Rune {
+    /// Returns the data that must be committed to in a Taproot script of the etching transaction input.
+    pub fn commitment(&self) -> Vec<u8> {
+        let bytes = self.0.to_le_bytes();
+        let end = bytes
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |last| last + 1);
+        bytes[..end].to_vec()
+    }
+}
+
+impl FromStr for Rune {
+    type Err = SigningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Rune name must not be empty");
+        }
+
+        let mut x = 0_u128;
+        for (i, c) in s.chars().enumerate() {
+            if i > 0 {
+                x = x
+                    .checked_add(1)
+                    .or_tw_err(SigningErrorType::Error_invalid_params)
+                    .context("Rune name is out of range")?;
+            }
+            if !c.is_ascii_uppercase() {
+                return SigningError::err(SigningErrorType::Error_invalid_params)
+                    .with_context(|| format!("Invalid Rune name character: '{c}'"));
+            }
+            x = x
+                .checked_mul(26)
+                .and_then(|x| x.checked_add(c as u128 - 'A' as u128))
+                .or_tw_err(SigningErrorType::Error_invalid_params)
+                .context("Rune name is out of range")?;
+        }
+        Ok(Rune(x))
+    }
+}
+
+impl fmt::Display for Rune {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `u128::MAX + 1` overflows, so the greatest name is hardcoded.
+        if self.0 == u128::MAX {
+            return f.write_str("BCGDENLQRQWDSLRUGSNLBTMFIJAV");
+        }
+
+        let mut n = self.0 + 1;
+        let mut symbol = Vec::new();
+        while n > 0 {
+            symbol.push(b'A' + ((n - 1) % 26) as u8);
+            n = (n - 1) / 26;
+        }
+        symbol.reverse();
+        f.write_str(&String::from_utf8_lossy(&symbol))
+    }
+}
+
+/// A rune name with optional spacers, e.g. `UNCOMMON•GOODS`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SpacedRune {
+    pub rune: Rune,
+    /// Bit `i` is set if a spacer goes after the `i`-th letter.
+    pub spacers: u32,
+}
+
+impl FromStr for SpacedRune {
+    type Err = SigningError;
+
+    /// Parses a rune name that may contain `•` or `.` spacers between letters.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rune = String::with_capacity(s.len());
+        let mut spacers = 0_u32;
+
+        for c in s.chars() {
+            match c {
+                'A'..='Z' => rune.push(c),
+                '.' | SPACER => {
+                    if rune.is_empty() {
+                        return SigningError::err(SigningErrorType::Error_invalid_params)
+                            .context("Rune name must not start with a spacer");
+                    }
+                    let flag = 1_u32
+                        .checked_shl(rune.len() as u32 - 1)
+                        .filter(|flag| flag & MAX_SPACERS != 0)
+                        .or_tw_err(SigningErrorType::Error_invalid_params)
+                        .context("Too many spacers")?;
+                    if spacers & flag != 0 {
+                        return SigningError::err(SigningErrorType::Error_invalid_params)
+                            .context("Rune name must not contain double spacers");
+                    }
+                    spacers |= flag;
+                },
+                _ => {
+                    return SigningError::err(SigningErrorType::Error_invalid_params)
+                        .with_context(|| format!("Invalid Rune name character: '{c}'"))
+                },
+            }
+        }
+
+        if (32 - spacers.leading_zeros()) as usize >= rune.len() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Rune name must not end with a spacer");
+        }
+
+        Ok(SpacedRune {
+            rune: Rune::from_str(&rune)?,
+            spacers,
+        })
+    }
+}
+
+impl fmt::Display for SpacedRune {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rune = self.rune.to_string();
+        for (i, c) in rune.chars().enumerate() {
+            write!(f, "{c}")?;
+            if i < rune.len() - 1 && self.spacers & (1 << i) != 0 {
+                write!(f, "{SPACER}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Transfers `amount` of the `id` rune to the `output`.
+/// Zero `amount` means all remaining runes of the `id`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Edict {
+    pub id: RuneId,
+    pub amount: u128,
+    pub output: u32,
+}
+
+/// Open minting terms of a rune.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Terms {
+    /// The amount of runes each mint transaction receives.
+    pub amount: Option<u128>,
+    /// The maximum number of mints.
+    pub cap: Option<u128>,
+    /// Absolute block heights `[start, end)` within which minting is open.
+    pub height: (Option<u64>, Option<u64>),
+    /// Block heights `[start, end)` relative to the etching block within which minting is open.
+    pub offset: (Option<u64>, Option<u64>),
+}
+
+/// Creates a new rune.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Etching {
+    pub divisibility: Option<u8>,
+    /// The amount of runes allocated to the etcher.
+    pub premine: Option<u128>,
+    /// If not specified, a reserved rune name is assigned.
+    pub rune: Option<Rune>,
+    pub spacers: Option<u32>,
+    pub symbol: Option<char>,
+    pub terms: Option<Terms>,
+    /// Opts into future protocol changes.
+    pub turbo: bool,
+}
+
+impl Etching {
+    /// Returns the total supply of the rune if it does not overflow.
+    pub fn supply(&self) -> Option<u128> {
+        let premine = self.premine.unwrap_or_default();
+        let cap = self.terms.and_then(|terms| terms.cap).unwrap_or_default();
+        let amount = self
+            .terms
+            .and_then(|terms| terms.amount)
+            .unwrap_or_default();
+        premine.checked_add(cap.checked_mul(amount)?)
+    }
+}
+
+/// A message embedded into an `OP_RETURN` output that transfers, mints or etches runes.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Runestone {
+    pub edicts: Vec<Edict>,
+    pub etching: Option<Etching>,
+    pub mint: Option<RuneId>,
+    /// The output that receives unallocated runes.
+    /// If not specified, the first non-`OP_RETURN` output is used.
+    pub pointer: Option<u32>,
+}
+
+impl Runestone {
+    /// Encodes the runestone into an `OP_RETURN OP_13 <payload>` script.
+    pub fn encipher(&self) -> Script {
+        let mut payload = Vec::new();
+
+        if let Some(etching) = self.etching {
+            let mut flags = Flag::Etching.mask();
+            if etching.terms.is_some() {
+                flags |= Flag::Terms.mask();
+            }
+            if etching.turbo {
+                flags |= Flag::Turbo.mask();
+            }
+            Tag::Flags.encode(&[flags], &mut payload);
+
+            Tag::Rune.encode_option(etching.rune.map(|rune| rune.0), &mut payload);
+            Tag::Divisibility.encode_option(etching.divisibility, &mut payload);
+            Tag::Spacers.encode_option(etching.spacers, &mut payload);
+            Tag::Symbol.encode_option(etching.symbol.map(u32::from), &mut payload);
+            Tag::Premine.encode_option(etching.premine, &mut payload);
+
+            if let Some(terms) = etching.terms {
+                Tag::Amount.encode_option(terms.amount, &mut payload);
+                Tag::Cap.encode_option(terms.cap, &mut payload);
+                Tag::HeightStart.encode_option(terms.height.0, &mut payload);
+                Tag::HeightEnd.encode_option(terms.height.1, &mut payload);
+                Tag::OffsetStart.encode_option(terms.offset.0, &mut payload);
+                Tag::OffsetEnd.encode_option(terms.offset.1, &mut payload);
+            }
+        }
+
+        if let Some(RuneId { block, tx }) = self.mint {
+            Tag::Mint.encode(&[block as u128, tx as u128], &mut payload);
+        }
+
+        Tag::Pointer.encode_option(self.pointer, &mut payload);
+
+        if !self.edicts.is_empty() {
+            varint::encode_to(Tag::Body as u128, &mut payload);
+
+            let mut edicts = self.edicts.clone();
+            edicts.sort_by_key(|edict| edict.id);
+
+            let mut previous = RuneId::default();
+            for edict in edicts {
+                let (block, tx) = edict.id.delta(previous);
+                varint::encode_to(block, &mut payload);
+                varint::encode_to(tx, &mut payload);
+                varint::encode_to(edict.amount, &mut payload);
+                varint::encode_to(edict.output as u128, &mut payload);
+                previous = edict.id;
+            }
+        }
+
+        let mut script = Script::with_capacity(payload.len() + 4);
+        script.push(OP_RETURN);
+        script.push(RUNESTONE_MAGIC_NUMBER);
+        for chunk in payload.chunks(MAX_PUSH_SIZE) {
+            script.push_slice(chunk);
+        }
+        script
+    }
+
+    /// Decodes a runestone from an `OP_RETURN OP_13 <payload>` script.
+    ///
+    /// Returns an error if the script is not a runestone or if it is a cenotaph,
+    /// i.e. a malformed runestone that burns all input runes.
+    ///
+    /// # Note
+    ///
+    /// Edict outputs and the pointer are not checked against the number of transaction outputs.
+    pub fn decipher(script: &Script) -> SigningResult<Runestone> {
+        let payload = Self::payload(script)?;
+        let integers = varint::decode_all(&payload)?;
+
+        let mut fields: BTreeMap<u128, VecDeque<u128>> = BTreeMap::new();
+        let mut edicts = Vec::new();
+
+        for i in (0..integers.len()).step_by(2) {
+            let tag = integers[i];
+
+            if tag == Tag::Body as u128 {
+                let mut id = RuneId::default();
+                for chunk in integers[i + 1..].chunks(4) {
+                    if chunk.len() != 4 {
+                        return cenotaph("Trailing integers in the runestone body");
+                    }
+                    let Some(next) = id.next(chunk[0], chunk[1]) else {
+                        return cenotaph("Invalid edict rune ID");
+                    };
+                    let Ok(output) = u32::try_from(chunk[3]) else {
+                        return cenotaph("Invalid edict output");
+                    };
+                    edicts.push(Edict {
+                        id: next,
+                        amount: chunk[2],
+                        output,
+                    });
+                    id = next;
+                }
+                break;
+            }
+
+            let Some(value) = integers.get(i + 1) else {
+                return cenotaph("Truncated runestone field");
+            };
+            fields.entry(tag).or_default().push_back(*value);
+        }
+
+        let mut flags = Tag::Flags
+            .take(&mut fields, |[flags]| Some(flags))
+            .unwrap_or_default();
+
+        let etching = if Flag::Etching.take(&mut flags) {
+            let terms = if Flag::Terms.take(&mut flags) {
+                Some(Terms {
+                    amount: Tag::Amount.take(&mut fields, |[amount]| Some(amount)),
+                    cap: Tag::Cap.take(&mut fields, |[cap]| Some(cap)),
+                    height: (
+                        Tag::HeightStart.take(&mut fields, |[start]| u64::try_from(start).ok()),
+                        Tag::HeightEnd.take(&mut fields, |[end]| u64::try_from(end).ok()),
+                    ),
+                    offset: (
+                        Tag::OffsetStart.take(&mut fields, |[start]| u64::try_from(start).ok()),
+                        Tag::OffsetEnd.take(&mut fields, |[end]| u64::try_from(end).ok()),
+                    ),
+                })
+            } else {
+                None
+            };
+
+            Some(Etching {
+                divisibility: Tag::Divisibility.take(&mut fields, |[divisibility]| {
+                    u8::try_from(divisibility)
+                        .ok()
+                        .filter(|divisibility| *divisibility <= MAX_DIVISIBILITY)
+                }),
+                premine: Tag::Premine.take(&mut fields, |[premine]| Some(premine)),
+                rune: Tag::Rune.take(&mut fields, |[rune]| Some(Rune(rune))),
+                spacers: Tag::Spacers.take(&mut fields, |[spacers]| {
+                    u32::try_from(spacers)
+                        .ok()
+                        .filter(|spacers| *spacers <= MAX_SPACERS)
+                }),
+                symbol: Tag::Symbol.take(&mut fields, |[symbol]| {
+                    char::from_u32(u32::try_from(symbol).ok()?)
+                }),
+                terms,
+                turbo: Flag::Turbo.take(&mut flags),
+            })
+        } else {
+            None
+        };
+
+        let mint = Tag::Mint.take(&mut fields, |[block, tx]| {
+            RuneId::new(u64::try_from(block).ok()?, u32::try_from(tx).ok()?).ok()
+        });
+        let pointer = Tag::Pointer.take(&mut fields, |[pointer]| u32::try_from(pointer).ok());
+
+        if matches!(etching, Some(etching) if etching.supply().is_none()) {
+            return cenotaph("Rune supply overflows u128");
+        }
+        if flags != 0 {
+            return cenotaph("Unrecognized runestone flag");
+        }
+        // Unrecognized even tags make the runestone a cenotaph, odd tags are ignored.
+        if fields.keys().any(|tag| tag % 2 == 0) {
+            return cenotaph("Unrecognized even runestone tag");
+        }
+
+        Ok(Runestone {
+            edicts,
+            etching,
+            mint,
+            pointer,
+        })
+    }
+
+    /// Concatenates data pushes that follow `OP_RETURN OP_13`.
+    fn payload(script: &Script) -> SigningResult<Vec<u8>> {
+        let script = bitcoin::Script::from_bytes(script.as_slice());
+        let mut instructions = script.instructions();
+
+        if instructions.next() != Some(Ok(Instruction::Op(bitcoin::opcodes::all::OP_RETURN))) {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Runestone script must start with OP_RETURN");
+        }
+        if instructions.next() != Some(Ok(Instruction::Op(bitcoin::opcodes::all::OP_PUSHNUM_13))) {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Runestone script must contain OP_13 magic number");
+        }
+
+        let mut payload = Vec::new();
+        for instruction in instructions {
+            match instruction {
+                Ok(Instruction::PushBytes(push)) => payload.extend_from_slice(push.as_bytes()),
+                Ok(Instruction::Op(_)) => return cenotaph("Runestone contains a non-push opcode"),
+                Err(_) => return cenotaph("Runestone contains an invalid data push"),
+            }
+        }
+        Ok(payload)
+    }
+}
+
+fn cenotaph<T>(reason: &str) -> SigningResult<T> {
+    SigningError::err(SigningErrorType::Error_invalid_params)
+        .with_context(|| format!("Cenotaph: {reason}"))
+}
+
+/// Creates a Taproot script tree that commits to the `rune` name.
+///
+/// An etching transaction must spend an output of this tree via [`rune_commitment_leaf`]
+/// once the commit transaction has at least 6 confirmations.
+pub fn rune_commitment_tree(pubkey: &schnorr::PublicKey, rune: &Rune) -> SigningResult<TapTree> {
+    TapTreeBuilder::new()
+        .add_leaf(0, rune_commitment_leaf(pubkey, rune))
+        .finalize(pubkey)
+}
+
+/// Creates a Taproot leaf `<x-only pubkey> OP_CHECKSIG OP_FALSE OP_IF <commitment> OP_ENDIF`
+/// that reveals the `rune` commitment and can be spent by the `pubkey` owner.
+pub fn rune_commitment_leaf(pubkey: &schnorr::PublicKey, rune: &Rune) -> TapLeaf {
+    let mut script = Script::new();
+    script.push_slice(pubkey.x_only().bytes().as_slice());
+    script.push(OP_CHECKSIG);
+    script.push(OP_FALSE);
+    script.push(OP_IF);
+    script.push_slice(&rune.commitment());
+    script.push(OP_ENDIF);
+    TapLeaf::tapscript(script)
+}
+
+mod varint {
+    use tw_coin_entry::error::prelude::*;
+
+    /// The maximum length of a LEB128 encoded `u128`.
+    const MAX_LEN: usize = 19;
+
+    pub fn encode_to(mut n: u128, out: &mut Vec<u8>) {
+        while n >> 7 > 0 {
+            out.push(n as u8 | 0x80);
+            n >>= 7;
+        }
+        out.push(n as u8);
+    }
+
+    /// Decodes a LEB128 encoded `u128` and returns the number of bytes read.
+    fn decode(buffer: &[u8]) -> SigningResult<(u128, usize)> {
+        let mut n = 0_u128;
+
+        for (i, byte) in buffer.iter().enumerate() {
+            if i >= MAX_LEN {
+                return super::cenotaph("Varint is too long");
+            }
+
+            let value = (*byte & 0x7f) as u128;
+            // The last byte can contain 2 bits only.
+            if i == MAX_LEN - 1 && value & 0b0111_1100 != 0 {
+                return super::cenotaph("Varint overflows u128");
+            }
+
+            n |= value << (7 * i);
+            if *byte & 0x80 == 0 {
+                return Ok((n, i + 1));
+            }
+        }
+
+        super::cenotaph("Varint is unterminated")
+    }
+
+    pub fn decode_all(mut buffer: &[u8]) -> SigningResult<Vec<u128>> {
+        let mut integers = Vec::new();
+        while !buffer.is_empty() {
+            let (n, length) = decode(buffer)?;
+            integers.push(n);
+            buffer = &buffer[length..];
+        }
+        Ok(integers)
+    }
+}
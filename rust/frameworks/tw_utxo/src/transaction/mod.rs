@@ -2,6 +2,8 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::fee::satisfaction_weight::SatisfactionWeight;
+use crate::script::timelock::Timelock;
 use crate::script::Script;
 use crate::sighash::SighashType;
 use crate::signing_mode::SigningMethod;
@@ -34,6 +36,11 @@ pub struct UtxoPreimageArgs {
     /// Script for claiming [`UtxoPreimageArgs::input_index`] UTXO.
     pub script_pubkey: Script,
     pub amount: Amount,
+    /// Amounts of all UTXOs spent by the transaction.
+    /// Required by sighash algorithms that commit to every input amount, e.g. Zcash ZIP-244.
+    pub spent_amounts: Vec<Amount>,
+    /// Original `scriptPubkey`s of all UTXOs spent by the transaction.
+    pub spent_script_pubkeys: Vec<Script>,
     pub sighash_ty: SighashType,
     pub leaf_hash_code_separator: Option<(H256, u32)>,
     pub tx_hasher: Hasher,
@@ -69,4 +76,13 @@ pub struct UtxoToSign {
     pub leaf_hash_code_separator: Option<(H256, u32)>,
     pub tx_hasher: Hasher,
     pub sighash_ty: SighashType,
+    /// Explicit size hints of the spending data.
+    /// If set, used to estimate the transaction size instead of the [`UtxoToSign::spending_data_constructor`].
+    pub satisfaction_weight: Option<SatisfactionWeight>,
+    /// Timelock the UTXO is locked with.
+    /// If set, the transaction `nLockTime` and the input `nSequence` are checked to satisfy the timelock.
+    pub timelock: Option<Timelock>,
+    /// Merkle root of the Taproot script tree the UTXO commits to.
+    /// Used to tweak the private key if the UTXO is spent via the key path.
+    pub taproot_merkle_root: Option<H256>,
 }
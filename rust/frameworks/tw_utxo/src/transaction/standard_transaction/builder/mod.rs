@@ -10,6 +10,7 @@ use tw_hash::H256;
 mod output;
 mod utxo;
 
+use crate::script::timelock::Timelock;
 use crate::transaction::unsigned_transaction::UnsignedTransaction;
 use crate::transaction::UtxoToSign;
 pub use output::OutputBuilder;
@@ -68,11 +69,25 @@ impl TransactionBuilder {
     }
 
     pub fn build(self) -> SigningResult<UnsignedTransaction<Transaction>> {
+        // If the lock time is not set explicitly, use the max absolute timelock of the UTXOs.
+        let locktime = match self.locktime {
+            0 => self
+                .utxo_args
+                .iter()
+                .filter_map(|utxo_args| match utxo_args.timelock {
+                    Some(Timelock::Absolute(locktime)) => Some(locktime),
+                    _ => None,
+                })
+                .max()
+                .unwrap_or_default(),
+            locktime => locktime,
+        };
+
         let transaction = Transaction {
             version: self.version as i32,
             inputs: self.inputs,
             outputs: self.outputs,
-            locktime: self.locktime,
+            locktime,
         };
         UnsignedTransaction::new(transaction, self.utxo_args)
     }
@@ -1,8 +1,9 @@
 use super::TransactionOutput;
 use crate::{
-    script::{standard_script::conditions, Script},
+    script::{standard_script::conditions, tap_tree::TapTree, timelock::Timelock, Script},
     transaction::{
         asset::brc20::{BRC20TransferInscription, Brc20Ticker},
+        asset::runes::{rune_commitment_tree, Rune, Runestone},
         transaction_parts::Amount,
     },
 };
@@ -126,6 +127,36 @@ impl OutputBuilder {
         }
     }
 
+    /// Creates a P2TR output committing to the whole script `tree`.
+    pub fn p2tr_script_tree(self, tree: &TapTree) -> TransactionOutput {
+        TransactionOutput {
+            value: self.amount,
+            script_pubkey: tree.script_pubkey(),
+        }
+    }
+
+    /// Creates a P2WSH output locked by [`Timelock::script`].
+    pub fn p2wsh_timelock(
+        self,
+        pubkey: &ecdsa::secp256k1::PublicKey,
+        timelock: Timelock,
+    ) -> TransactionOutput {
+        let witness_script = timelock.script(pubkey.compressed().as_slice());
+        self.p2wsh(&witness_script)
+    }
+
+    /// Creates a P2TR output that can be spent via the [`Timelock::script`] leaf by the `spender`,
+    /// or via the key path by the `internal_key` owner.
+    pub fn p2tr_timelock(
+        self,
+        internal_key: &schnorr::PublicKey,
+        spender: &schnorr::PublicKey,
+        timelock: Timelock,
+    ) -> SigningResult<TransactionOutput> {
+        let (tree, _leaf) = timelock.tap_tree(internal_key, spender)?;
+        Ok(self.p2tr_script_tree(&tree))
+    }
+
     pub fn brc20_transfer(
         self,
         pubkey: &schnorr::PublicKey,
@@ -139,6 +170,33 @@ impl OutputBuilder {
         Ok(self.p2tr_script_path(pubkey, merkle_root))
     }
 
+    /// Creates a P2TR output that commits to the `rune` name.
+    /// It should be spent by [`super::UtxoBuilder::rune_etching_reveal`] in the etching transaction.
+    pub fn rune_etching_commit(
+        self,
+        pubkey: &schnorr::PublicKey,
+        rune: &Rune,
+    ) -> SigningResult<TransactionOutput> {
+        let tree = rune_commitment_tree(pubkey, rune)?;
+        Ok(self.p2tr_script_tree(&tree))
+    }
+
+    pub fn runestone(self, runestone: &Runestone) -> SigningResult<TransactionOutput> {
+        // The standard `OP_RETURN` script is limited by the data and up to 3 opcodes.
+        let max_script_len = OP_RETURN_DATA_LIMIT + 3;
+        let script_pubkey = runestone.encipher();
+        if script_pubkey.len() > max_script_len {
+            return SigningError::err(SigningErrorType::Error_invalid_memo).context(format!(
+                "Runestone script can be up to {max_script_len} bytes"
+            ));
+        }
+
+        Ok(TransactionOutput {
+            value: self.amount,
+            script_pubkey,
+        })
+    }
+
     pub fn op_return(self, data: &[u8]) -> SigningResult<TransactionOutput> {
         if data.len() > OP_RETURN_DATA_LIMIT {
             return SigningError::err(SigningErrorType::Error_invalid_memo).context(format!(
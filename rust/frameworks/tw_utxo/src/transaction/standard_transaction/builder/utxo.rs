@@ -1,16 +1,20 @@
 use super::TransactionInput;
+use crate::fee::satisfaction_weight::SatisfactionWeight;
+use crate::script::tap_tree::{TapLeaf, TapTree, TAPSCRIPT_LEAF_VERSION};
+use crate::script::timelock::{Timelock, SEQUENCE_FINAL};
 use crate::sighash::SighashType;
+use crate::spending_data::standard_constructor::TaprootWitnessItem;
 use crate::spending_data::{standard_constructor, SpendingDataConstructor};
 use crate::transaction::UtxoToSign;
 use crate::{
     script::{standard_script::conditions, Script, Witness},
     signing_mode::SigningMethod,
     transaction::asset::brc20::{BRC20TransferInscription, Brc20Ticker},
+    transaction::asset::runes::{rune_commitment_leaf, rune_commitment_tree, Rune},
     transaction::transaction_parts::{Amount, OutPoint},
 };
-use bitcoin::hashes::Hash;
 use tw_coin_entry::error::prelude::*;
-use tw_hash::{hasher::Hasher, ripemd::bitcoin_hash_160, H160, H256};
+use tw_hash::{hasher::Hasher, ripemd::bitcoin_hash_160, sha2::sha256, H160, H256};
 use tw_keypair::{ecdsa, schnorr};
 use tw_memory::Data;
 use tw_misc::traits::ToBytesVec;
@@ -23,6 +27,7 @@ pub struct UtxoBuilder {
     prev_index: Option<u32>,
     amount: Option<Amount>,
     sighash_ty: Option<SighashType>,
+    satisfaction_weight: Option<SatisfactionWeight>,
 }
 
 impl UtxoBuilder {
@@ -38,6 +43,7 @@ impl UtxoBuilder {
             prev_index: None,
             amount: None,
             sighash_ty: None,
+            satisfaction_weight: None,
         }
     }
 
@@ -66,6 +72,12 @@ impl UtxoBuilder {
         self
     }
 
+    /// Sets explicit size hints of the spending data used to estimate the transaction size.
+    pub fn satisfaction_weight(mut self, satisfaction_weight: SatisfactionWeight) -> Self {
+        self.satisfaction_weight = Some(satisfaction_weight);
+        self
+    }
+
     fn finalize_out_point(&mut self) -> SigningResult<()> {
         // Populate the input with the previous output.
         self.input.previous_output.hash = self
@@ -80,6 +92,13 @@ impl UtxoBuilder {
         Ok(())
     }
 
+    /// The input cannot be spent with the final `nSequence`, so replace it with one that satisfies the timelock.
+    fn finalize_timelock_sequence(&mut self, timelock: &Timelock) {
+        if self.input.sequence == SEQUENCE_FINAL {
+            self.input.sequence = timelock.sequence();
+        }
+    }
+
     fn finalize_amount(&self) -> SigningResult<Amount> {
         self.amount
             .or_tw_err(SigningErrorType::Error_internal)
@@ -147,15 +166,33 @@ impl UtxoBuilder {
                 leaf_hash_code_separator: None,
                 tx_hasher: DEFAULT_TX_HASHER,
                 sighash_ty,
+                satisfaction_weight: self.satisfaction_weight,
+                timelock: None,
+                taproot_merkle_root: None,
             },
         ))
     }
 
     pub fn p2pkh(
-        mut self,
+        self,
         pubkey: &ecdsa::secp256k1::PublicKey,
     ) -> SigningResult<(TransactionInput, UtxoToSign)> {
-        let h = bitcoin_hash_160(pubkey.compressed().as_slice());
+        self.p2pkh_with_pubkey_bytes(pubkey.compressed().to_vec())
+    }
+
+    /// Spends a P2PKH output locked with an uncompressed public key, e.g. created by a legacy wallet.
+    pub fn p2pkh_uncompressed(
+        self,
+        pubkey: &ecdsa::secp256k1::PublicKey,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        self.p2pkh_with_pubkey_bytes(pubkey.uncompressed().to_vec())
+    }
+
+    fn p2pkh_with_pubkey_bytes(
+        mut self,
+        pubkey: Data,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let h = bitcoin_hash_160(&pubkey);
         let pubkey_hash: H160 = h.as_slice().try_into().expect("hash length is 20 bytes");
 
         self.finalize_out_point()?;
@@ -176,14 +213,17 @@ impl UtxoBuilder {
                 // When the sighash is signed, build a P2PKH script_sig.
                 spending_data_constructor: SpendingDataConstructor::ecdsa(
                     standard_constructor::P2PKH {
-                        pubkey: pubkey.compressed(),
+                        pubkey: pubkey.clone(),
                     },
                 ),
-                spender_public_key: pubkey.compressed().to_vec(),
+                spender_public_key: pubkey,
                 amount,
                 leaf_hash_code_separator: None,
                 tx_hasher: DEFAULT_TX_HASHER,
                 sighash_ty,
+                satisfaction_weight: self.satisfaction_weight,
+                timelock: None,
+                taproot_merkle_root: None,
             },
         ))
     }
@@ -246,10 +286,112 @@ impl UtxoBuilder {
                 leaf_hash_code_separator: None,
                 tx_hasher: DEFAULT_TX_HASHER,
                 sighash_ty,
+                satisfaction_weight: self.satisfaction_weight,
+                timelock: None,
+                taproot_merkle_root: None,
+            },
+        ))
+    }
+
+    /// Spends a P2WPKH output nested in P2SH, e.g. created by a BIP-49 wallet.
+    pub fn p2sh_p2wpkh(
+        mut self,
+        pubkey: &ecdsa::secp256k1::PublicKey,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let h = bitcoin_hash_160(pubkey.compressed().as_slice());
+        let pubkey_hash: H160 = h.as_slice().try_into().expect("hash length is 20 bytes");
+
+        let redeem_script = conditions::new_p2wpkh(&pubkey_hash);
+        let h = bitcoin_hash_160(redeem_script.as_slice());
+        let redeem_script_hash: H160 = h.as_slice().try_into().expect("hash length is 20 bytes");
+
+        self.finalize_out_point()?;
+        let amount = self.finalize_amount()?;
+        let sighash_ty = self.finalize_sighash_type()?;
+
+        Ok((
+            self.input,
+            UtxoToSign {
+                prevout_script_pubkey: conditions::new_p2sh(&redeem_script_hash),
+                // P2SH-P2WPKH UTXO is signed the same way as P2WPKH.
+                reveal_script_pubkey: conditions::new_p2pkh(&pubkey_hash),
+                taproot_reveal_script_pubkey: None,
+                // When the sighash is signed, build a script_sig with the redeem script and a P2WPKH witness.
+                spending_data_constructor: SpendingDataConstructor::ecdsa(
+                    standard_constructor::P2SHP2WPKH {
+                        redeem_script,
+                        pubkey: pubkey.compressed(),
+                    },
+                ),
+                spender_public_key: pubkey.compressed().to_vec(),
+                signing_method: SigningMethod::Segwit,
+                amount,
+                leaf_hash_code_separator: None,
+                tx_hasher: DEFAULT_TX_HASHER,
+                sighash_ty,
+                satisfaction_weight: self.satisfaction_weight,
+                timelock: None,
+                taproot_merkle_root: None,
             },
         ))
     }
 
+    /// Spends a P2WSH output locked by [`Timelock::script`] via the `pubkey` signature.
+    /// Sets the input `nSequence` that satisfies the timelock, unless it was set explicitly.
+    pub fn p2wsh_timelock(
+        mut self,
+        pubkey: &ecdsa::secp256k1::PublicKey,
+        timelock: Timelock,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let witness_script = timelock.script(pubkey.compressed().as_slice());
+        let h = sha256(witness_script.as_slice());
+        let witness_script_hash: H256 = h.as_slice().try_into().expect("hash length is 32 bytes");
+
+        self.finalize_out_point()?;
+        self.finalize_timelock_sequence(&timelock);
+        let amount = self.finalize_amount()?;
+        let sighash_ty = self.finalize_sighash_type()?;
+
+        Ok((
+            self.input,
+            UtxoToSign {
+                prevout_script_pubkey: conditions::new_p2wsh(&witness_script_hash),
+                // P2WSH UTXO is signed with the witness script as the scriptCode.
+                reveal_script_pubkey: witness_script.clone(),
+                taproot_reveal_script_pubkey: None,
+                spending_data_constructor: SpendingDataConstructor::ecdsa(
+                    standard_constructor::P2WSHSingleSig { witness_script },
+                ),
+                spender_public_key: pubkey.compressed().to_vec(),
+                signing_method: SigningMethod::Segwit,
+                amount,
+                leaf_hash_code_separator: None,
+                tx_hasher: DEFAULT_TX_HASHER,
+                sighash_ty,
+                satisfaction_weight: self.satisfaction_weight,
+                timelock: Some(timelock),
+                taproot_merkle_root: None,
+            },
+        ))
+    }
+
+    /// Spends a P2TR output created by [`super::OutputBuilder::p2tr_timelock`] via the `spender` signature.
+    /// Sets the input `nSequence` that satisfies the timelock, unless it was set explicitly.
+    pub fn p2tr_timelock(
+        mut self,
+        internal_key: &schnorr::PublicKey,
+        spender: &schnorr::PublicKey,
+        timelock: Timelock,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let (tree, leaf) = timelock.tap_tree(internal_key, spender)?;
+
+        self.finalize_timelock_sequence(&timelock);
+        let (utxo, mut utxo_args) =
+            self.p2tr_script_tree_leaf(&tree, &leaf, spender, vec![TaprootWitnessItem::Signature])?;
+        utxo_args.timelock = Some(timelock);
+        Ok((utxo, utxo_args))
+    }
+
     pub fn p2tr_key_path(
         self,
         pubkey: &schnorr::PublicKey,
@@ -258,6 +400,19 @@ impl UtxoBuilder {
         self.p2tr_key_path_with_tweaked_pubkey(&tweaked_pubkey.x_only())
     }
 
+    /// Spends a P2TR output committing to a script tree with the given `merkle_root` via the key path.
+    pub fn p2tr_key_path_with_merkle_root(
+        self,
+        pubkey: &schnorr::PublicKey,
+        merkle_root: H256,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let tweaked_pubkey = pubkey.tweak(Some(merkle_root));
+        let (utxo, mut utxo_args) =
+            self.p2tr_key_path_with_tweaked_pubkey(&tweaked_pubkey.x_only())?;
+        utxo_args.taproot_merkle_root = Some(merkle_root);
+        Ok((utxo, utxo_args))
+    }
+
     pub fn p2tr_key_path_with_tweaked_pubkey(
         mut self,
         tweaked_pubkey: &schnorr::XOnlyPublicKey,
@@ -287,6 +442,54 @@ impl UtxoBuilder {
                 // Note that we don't use the default double-hasher.
                 tx_hasher: Hasher::Sha256,
                 sighash_ty,
+                satisfaction_weight: self.satisfaction_weight,
+                timelock: None,
+                taproot_merkle_root: None,
+            },
+        ))
+    }
+
+    /// Spends a UTXO locked by a script unknown to the signer, e.g. P2SH or P2WSH multisig.
+    /// Such UTXO can be used to plan a transaction, but cannot be signed.
+    ///
+    /// Requires [`UtxoBuilder::satisfaction_weight`] to be set, as the spending data size cannot be estimated otherwise.
+    pub fn foreign(
+        mut self,
+        script_pubkey: Script,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        self.finalize_out_point()?;
+        let amount = self.finalize_amount()?;
+        let sighash_ty = self.finalize_sighash_type()?;
+        let satisfaction_weight = self
+            .satisfaction_weight
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("Satisfaction weight must be specified to spend a non-standard UTXO")?;
+
+        // The signing method is informative only, as the UTXO cannot be signed.
+        let signing_method = if conditions::is_p2tr(&script_pubkey) {
+            SigningMethod::Taproot
+        } else if conditions::is_p2wsh(&script_pubkey) || conditions::is_p2wpkh(&script_pubkey) {
+            SigningMethod::Segwit
+        } else {
+            SigningMethod::Legacy
+        };
+
+        Ok((
+            self.input,
+            UtxoToSign {
+                prevout_script_pubkey: script_pubkey.clone(),
+                reveal_script_pubkey: script_pubkey,
+                taproot_reveal_script_pubkey: None,
+                signing_method,
+                spending_data_constructor: SpendingDataConstructor::Foreign,
+                spender_public_key: Data::default(),
+                amount,
+                leaf_hash_code_separator: None,
+                tx_hasher: DEFAULT_TX_HASHER,
+                sighash_ty,
+                satisfaction_weight: Some(satisfaction_weight),
+                timelock: None,
+                taproot_merkle_root: None,
             },
         ))
     }
@@ -295,6 +498,46 @@ impl UtxoBuilder {
         P2TRScriptPathUtxoBuilder::new(self)
     }
 
+    /// Spend a P2TR output via the script path of the given `leaf` of the `tree`.
+    /// The witness stack consists of `witness_items` followed by the leaf script and its control block.
+    pub fn p2tr_script_tree_leaf(
+        self,
+        tree: &TapTree,
+        leaf: &TapLeaf,
+        spender: &schnorr::PublicKey,
+        witness_items: Vec<TaprootWitnessItem>,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let control_block = tree.control_block(leaf)?;
+
+        let spending_data_ctor =
+            SpendingDataConstructor::schnorr(standard_constructor::P2TRScriptPathWithWitness {
+                witness_items,
+                payload: leaf.script().clone(),
+                control_block: control_block.clone(),
+            });
+
+        self.p2tr_script_path()
+            .reveal_script_pubkey(leaf.script().clone())
+            .leaf_version(leaf.version())
+            .spender_public_key(spender)
+            .restore_prevout_script_pubkey(tree.internal_key(), &tree.merkle_root())
+            .control_block(control_block)
+            .custom_spending_data_ctor(spending_data_ctor)
+            .build()
+    }
+
+    /// Spends an output created by [`super::OutputBuilder::rune_etching_commit`],
+    /// revealing the `rune` commitment required by the etching transaction.
+    pub fn rune_etching_reveal(
+        self,
+        pubkey: &schnorr::PublicKey,
+        rune: &Rune,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let tree = rune_commitment_tree(pubkey, rune)?;
+        let leaf = rune_commitment_leaf(pubkey, rune);
+        self.p2tr_script_tree_leaf(&tree, &leaf, pubkey, vec![TaprootWitnessItem::Signature])
+    }
+
     pub fn brc20_transfer(
         self,
         pubkey: &schnorr::PublicKey,
@@ -345,6 +588,10 @@ pub struct P2TRScriptPathUtxoBuilder {
     /// Optional.
     taproot_reveal_script_pubkey: Option<Script>,
 
+    /// Optional.
+    /// If not specified, [`TAPSCRIPT_LEAF_VERSION`] is used.
+    leaf_version: Option<u8>,
+
     /// Inner UTXO builder.
     utxo_builder: UtxoBuilder,
 }
@@ -358,6 +605,7 @@ impl P2TRScriptPathUtxoBuilder {
             control_block: None,
             spending_data_ctor: None,
             taproot_reveal_script_pubkey: None,
+            leaf_version: None,
             utxo_builder,
         }
     }
@@ -408,6 +656,11 @@ impl P2TRScriptPathUtxoBuilder {
         self
     }
 
+    pub fn leaf_version(mut self, leaf_version: u8) -> Self {
+        self.leaf_version = Some(leaf_version);
+        self
+    }
+
     pub fn build(mut self) -> SigningResult<(TransactionInput, UtxoToSign)> {
         let reveal_script_pubkey = self
             .reveal_script_pubkey
@@ -437,7 +690,8 @@ impl P2TRScriptPathUtxoBuilder {
             }),
         };
 
-        let leaf_hash = Self::leaf_hash(&reveal_script_pubkey);
+        let leaf_version = self.leaf_version.unwrap_or(TAPSCRIPT_LEAF_VERSION);
+        let leaf_hash = TapLeaf::new(reveal_script_pubkey.clone(), leaf_version)?.leaf_hash();
 
         self.utxo_builder.finalize_out_point()?;
         let amount = self.utxo_builder.finalize_amount()?;
@@ -459,19 +713,12 @@ impl P2TRScriptPathUtxoBuilder {
                 // Note that we don't use the default double-hasher.
                 tx_hasher: Hasher::Sha256,
                 sighash_ty,
+                satisfaction_weight: self.utxo_builder.satisfaction_weight,
+                timelock: None,
+                taproot_merkle_root: None,
             },
         ))
     }
-
-    /// Construct the leaf hash.
-    fn leaf_hash(reveal_script_pubkey: &Script) -> H256 {
-        let script_buf = bitcoin::ScriptBuf::from_bytes(reveal_script_pubkey.to_vec());
-        let leaf_hash = bitcoin::taproot::TapLeafHash::from_script(
-            &script_buf,
-            bitcoin::taproot::LeafVersion::TapScript,
-        );
-        H256::from(leaf_hash.to_byte_array())
-    }
 }
 
 impl Default for UtxoBuilder {
@@ -348,6 +348,10 @@ impl TxOutputInterface for TransactionOutput {
     fn script_pubkey(&self) -> &Script {
         &self.script_pubkey
     }
+
+    fn set_script_pubkey(&mut self, script_pubkey: Script) {
+        self.script_pubkey = script_pubkey;
+    }
 }
 
 impl Encodable for TransactionOutput {
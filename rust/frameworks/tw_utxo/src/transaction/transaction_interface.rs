@@ -73,4 +73,6 @@ pub trait TxOutputInterface: Clone + Default + Encodable {
     fn set_value(&mut self, value: Amount);
 
     fn script_pubkey(&self) -> &Script;
+
+    fn set_script_pubkey(&mut self, script_pubkey: Script);
 }
@@ -2,6 +2,7 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::transaction::asset::cash_token::CashToken;
 use crate::transaction::transaction_interface::TransactionInterface;
 use crate::transaction::transaction_sighash::witness0_sighash::Witness0Sighash;
 use crate::transaction::UtxoPreimageArgs;
@@ -17,6 +18,14 @@ pub struct ForkIdSighash<Transaction: TransactionInterface> {
 
 impl<Transaction: TransactionInterface> ForkIdSighash<Transaction> {
     pub fn sighash_tx(tx: &Transaction, args: &UtxoPreimageArgs) -> SigningResult<H256> {
-        Witness0Sighash::sighash_tx(tx, args)
+        // If the UTXO being spent holds CashTokens, its token prefix is committed before the scriptCode.
+        // https://github.com/cashtokens/cashtokens#signing-serialization-of-tokens
+        let token_prefix = args
+            .spent_script_pubkeys
+            .get(args.input_index)
+            .map(|prevout_script_pubkey| CashToken::split_script_pubkey(prevout_script_pubkey).0)
+            .unwrap_or_default();
+
+        Witness0Sighash::sighash_tx_with_token_prefix(tx, args, token_prefix)
     }
 }
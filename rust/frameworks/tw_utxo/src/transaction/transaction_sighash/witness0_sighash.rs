@@ -18,6 +18,16 @@ pub struct Witness0Sighash<Transaction: TransactionInterface> {
 
 impl<Transaction: TransactionInterface> Witness0Sighash<Transaction> {
     pub fn sighash_tx(tx: &Transaction, args: &UtxoPreimageArgs) -> SigningResult<H256> {
+        Self::sighash_tx_with_token_prefix(tx, args, &[])
+    }
+
+    /// Computes a sighash with the `token_prefix` of the UTXO being spent inserted before the scriptCode.
+    /// The prefix must be empty unless the UTXO holds CashTokens.
+    pub(crate) fn sighash_tx_with_token_prefix(
+        tx: &Transaction,
+        args: &UtxoPreimageArgs,
+        token_prefix: &[u8],
+    ) -> SigningResult<H256> {
         let input = tx
             .inputs()
             .get(args.input_index)
@@ -47,6 +57,7 @@ impl<Transaction: TransactionInterface> Witness0Sighash<Transaction> {
         // may already be contained in hashSequence.
         stream
             .append(input.previous_output())
+            .append_raw_slice(token_prefix)
             .append(&args.script_pubkey)
             .append(&args.amount)
             .append(&input.sequence())
@@ -2,8 +2,9 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::script::{Script, Witness};
 use crate::signature::{BitcoinEcdsaSignature, BitcoinSchnorrSignature};
-use crate::spending_data::SpendingDataConstructor;
+use crate::spending_data::{SpendingData, SpendingDataConstructor};
 use crate::transaction::transaction_interface::{
     TransactionInterface, TxInputInterface, TxOutputInterface,
 };
@@ -11,6 +12,13 @@ use crate::transaction::transaction_parts::Amount;
 use crate::transaction::UtxoToSign;
 use tw_coin_entry::error::prelude::*;
 
+#[derive(Clone, Copy)]
+enum SizeBound {
+    Min,
+    Max,
+}
+
+#[derive(Clone)]
 pub struct UnsignedTransaction<Transaction> {
     transaction: Transaction,
     utxo_args: Vec<UtxoToSign>,
@@ -61,21 +69,56 @@ where
         self.transaction.push_output(output);
     }
 
+    pub fn replace_outputs(&mut self, outputs: Vec<Transaction::Output>) {
+        self.transaction.replace_outputs(outputs);
+    }
+
+    /// Returns the transaction with the spending data of the max possible size.
     pub fn estimate_transaction(&self) -> Transaction {
+        self.estimate_transaction_impl(SizeBound::Max)
+    }
+
+    /// Returns the transaction with the spending data of the min possible size.
+    pub fn estimate_transaction_min(&self) -> Transaction {
+        self.estimate_transaction_impl(SizeBound::Min)
+    }
+
+    fn estimate_transaction_impl(&self, bound: SizeBound) -> Transaction {
         let mut estimated_tx = self.transaction.clone();
 
         debug_assert_eq!(self.transaction.inputs().len(), self.utxo_args.len());
 
         for (utxo_args, utxo) in self.utxo_args.iter().zip(estimated_tx.inputs_mut()) {
+            // Explicit hints take precedence over the standard spending data.
+            if let Some(satisfaction_weight) = utxo_args.satisfaction_weight {
+                let estimated_spending = satisfaction_weight.estimated_spending_data();
+                utxo.set_script_sig(estimated_spending.script_sig);
+                utxo.set_witness(estimated_spending.witness);
+                continue;
+            }
+
             let estimated_spending = match utxo_args.spending_data_constructor {
                 SpendingDataConstructor::Ecdsa(ref ecdsa_constructor) => {
-                    let estimated_sig = BitcoinEcdsaSignature::estimated();
+                    let estimated_sig = match bound {
+                        SizeBound::Min => BitcoinEcdsaSignature::estimated_min(),
+                        SizeBound::Max => BitcoinEcdsaSignature::estimated(),
+                    };
                     ecdsa_constructor.get_spending_data(&estimated_sig)
                 },
                 SpendingDataConstructor::Schnorr(ref schnorr_constructor) => {
-                    let estimated_sig = BitcoinSchnorrSignature::estimated();
+                    let estimated_sig = match bound {
+                        SizeBound::Min => {
+                            BitcoinSchnorrSignature::estimated_min(utxo_args.sighash_ty)
+                        },
+                        SizeBound::Max => BitcoinSchnorrSignature::estimated(),
+                    };
                     schnorr_constructor.get_spending_data(&estimated_sig)
                 },
+                // Foreign UTXOs are always created with a satisfaction weight.
+                SpendingDataConstructor::Foreign => SpendingData {
+                    script_sig: Script::default(),
+                    witness: Witness::default(),
+                },
             };
 
             utxo.set_script_sig(estimated_spending.script_sig);
@@ -141,6 +184,27 @@ where
             .context("Sum of Transaction output amounts is too big")
     }
 
+    /// Checks if the transaction `nLockTime`, version and the inputs `nSequence` satisfy the UTXOs timelocks.
+    pub fn check_timelocks(&self) -> SigningResult<()> {
+        let version = self.transaction.version();
+        let locktime = self.transaction.locktime();
+
+        for (input_index, (utxo, utxo_args)) in self
+            .transaction
+            .inputs()
+            .iter()
+            .zip(self.utxo_args.iter())
+            .enumerate()
+        {
+            if let Some(timelock) = utxo_args.timelock {
+                timelock
+                    .check(version, locktime, utxo.sequence())
+                    .with_context(|| format!("Input #{input_index} timelock is not satisfied"))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Calculates the unsigned transaction fee by the formula:
     /// `sum(input) - sum(output)`
     pub fn fee(&self) -> SigningResult<Amount> {
@@ -0,0 +1,165 @@
+use std::str::FromStr;
+use tw_coin_entry::test_utils::test_context::TestCoinContext;
+use tw_encoding::hex::ToHex;
+use tw_utxo::descriptor::Descriptor;
+
+const XPUB: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+
+fn bitcoin_context() -> TestCoinContext {
+    TestCoinContext {
+        p2pkh: Some(0),
+        p2sh: Some(5),
+        ..TestCoinContext::default()
+    }
+    .with_hrp("bc")
+}
+
+fn test_descriptor(desc: &str, index: u32, expected_script: &str, expected_address: &str) {
+    let descriptor = Descriptor::from_str(desc).unwrap();
+    let script = descriptor.script_pubkey_at(index).unwrap();
+    assert_eq!(script.as_slice().to_hex(), expected_script, "{desc}");
+
+    let address = descriptor.address_at(&bitcoin_context(), index).unwrap();
+    assert_eq!(address.to_string(), expected_address, "{desc}");
+}
+
+#[test]
+fn test_descriptor_pkh() {
+    test_descriptor(
+        "pkh(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5)#8fhd9pwu",
+        0,
+        "76a91406afd46bcdfd22ef94ac122aa11f241244a37ecc88ac",
+        "1cMh228HTCiwS8ZsaakH8A8wze1JR5ZsP",
+    );
+}
+
+#[test]
+fn test_descriptor_wpkh() {
+    test_descriptor(
+        "wpkh(02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9)#8zl0zxma",
+        0,
+        "00147dd65592d0ab2fe0d0257d571abf032cd9db93dc",
+        "bc1q0ht9tyks4vh7p5p904t340cr9nvahy7u3re7zg",
+    );
+}
+
+#[test]
+fn test_descriptor_sh_wpkh() {
+    test_descriptor(
+        "sh(wpkh(03fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556))#qkrrc7je",
+        0,
+        "a914cc6ffbc0bf31af759451068f90ba7a0272b6b33287",
+        "3LKyvRN6SmYXGBNn8fcQvYxW9MGKtwcinN",
+    );
+}
+
+#[test]
+fn test_descriptor_wsh_multi() {
+    test_descriptor(
+        "wsh(sortedmulti(2,03fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556,02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5,02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9))#2qx4dnyw",
+        0,
+        "00208b4c1b678c301c2c65e2a38b669b83282fbb6ba71b8a1e0bf71be07f86e3e8d1",
+        "bc1q3dxpkeuvxqwzce0z5w9kdxur9qhmk6a8rw9puzlhr0s8lphrargsyh3s9x",
+    );
+
+    let unsorted = Descriptor::from_str("wsh(multi(2,03fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556,02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5,02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9))").unwrap();
+    assert_eq!(
+        unsorted.script_pubkey_at(0).unwrap().as_slice().to_hex(),
+        "0020ddffa01c80ff50b6194b3820cd2811c7c7208c6bb49307a2a435fc93d4ce6e30"
+    );
+    assert_eq!(unsorted.checksum(), "shqvpqw5");
+}
+
+#[test]
+fn test_descriptor_tr_key_path() {
+    test_descriptor(
+        "tr(f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9)#5ceacj8z",
+        0,
+        "5120418c46636d9e1a683f58e35b42336e776fdcc3b2d4e39e7a0bf1ab0716e3c5fa",
+        "bc1pgxxyvcmdncdxs06cudd5yvmwwahaesaj6n3eu7st7x4sw9hrchaqjy33gs",
+    );
+}
+
+#[test]
+fn test_descriptor_tr_script_tree() {
+    test_descriptor(
+        "tr(f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9,{pk(c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5),multi_a(1,fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556,f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9)})#7ynnw727",
+        0,
+        "51207585044fb475b0ef7840bc9692541ba2259e3a97292c21e3ad03970b0bcf39e0",
+        "bc1pwkzsgna5wkcw77zqhjtfy4qm5gjeuw5h9ykzrcadqwtskz7088sq72dqc8",
+    );
+}
+
+#[test]
+fn test_descriptor_tr_merkle_root() {
+    // The merkle root of a single leaf tree is the leaf hash.
+    let descriptor = Descriptor::from_str("tr(03a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c,pk(6666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536))").unwrap();
+    let merkle_root = descriptor.tap_tree_merkle_root_at(0).unwrap().unwrap();
+    assert_eq!(
+        merkle_root.to_hex(),
+        "50d08a02ee67c4872f0a0b32e09b641e5a8f070de5e643c838621b6d0b6906fd"
+    );
+
+    let descriptor = Descriptor::from_str(
+        "tr(f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9)",
+    )
+    .unwrap();
+    assert_eq!(descriptor.tap_tree_merkle_root_at(0).unwrap(), None);
+}
+
+#[test]
+fn test_descriptor_xpub_wildcard() {
+    let desc = format!("wpkh([d34db33f/84'/0'/0']{XPUB}/0/*)");
+    let descriptor = Descriptor::from_str(&desc).unwrap();
+    assert!(descriptor.has_wildcard());
+    assert_eq!(descriptor.checksum(), "zpuvqnlw");
+    assert_eq!(
+        descriptor.to_string_with_checksum(),
+        format!("{desc}#zpuvqnlw")
+    );
+
+    test_descriptor(
+        &desc,
+        0,
+        "00140d1c9c02a7be9ba8b8842804feb961481ce6561b",
+        "bc1qp5wfcq48h6d63wyy9qz0awtpfqwwv4sma86mhz",
+    );
+    test_descriptor(
+        &desc,
+        1,
+        "00141a4c3d16409dddc499160230dc84a1182b2ab38e",
+        "bc1qrfxr69jqnhwufxgkqgcdep9prq4j4vuw2wyg0v",
+    );
+
+    let desc = format!("tr({XPUB}/0/*)#svstklww");
+    test_descriptor(
+        &desc,
+        7,
+        "51205ff54e5d1fd091d65937a92b9f7315547d3cf138cb6f3266d2bb3ec6bc2e53ce",
+        "bc1ptl65uhgl6zgavkfh4y4e7uc4237neufcedhnyekjhvlvd0pw208qcc7unz",
+    );
+}
+
+#[test]
+fn test_descriptor_invalid() {
+    // Invalid checksum.
+    Descriptor::from_str(
+        "pkh(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5)#8fhd9pwv",
+    )
+    .unwrap_err();
+    // Uncompressed keys are not allowed within segwit descriptors.
+    Descriptor::from_str("wpkh(04c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee51ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a)").unwrap_err();
+    // X-only keys are allowed within `tr()` only.
+    Descriptor::from_str("wpkh(f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9)")
+        .unwrap_err();
+    // Hardened derivation requires a private key.
+    Descriptor::from_str(&format!("wpkh({XPUB}/0'/*)")).unwrap_err();
+    Descriptor::from_str(&format!("wpkh({XPUB}/0/*')")).unwrap_err();
+    // Threshold is greater than the number of keys.
+    Descriptor::from_str(
+        "wsh(multi(2,02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5))",
+    )
+    .unwrap_err();
+    // Unsupported expression.
+    Descriptor::from_str("raw(deadbeef)").unwrap_err();
+}
@@ -0,0 +1,259 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::test_utils::test_context::TestCoinContext;
+use tw_utxo::address::extended_key::{
+    AddressChain, AddressType, ExtendedKeyVersion, ExtendedPublicKey,
+};
+
+// Account keys of the "abandon abandon ... about" mnemonic.
+const BIP44_XPUB: &str = "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj";
+const BIP49_YPUB: &str = "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP";
+const BIP84_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+const BIP84_XPUB: &str = "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+const BIP86_XPUB: &str = "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+const BIP49_TESTNET_UPUB: &str = "upub5EFU65HtV5TeiSHmZZm7FUffBGy8UKeqp7vw43jYbvZPpoVsgU93oac7Wk3u6moKegAEWtGNF8DehrnHtv21XXEMYRUocHqguyjknFHYfgY";
+const BIP84_TESTNET_VPUB: &str = "vpub5Y6cjg78GGuNLsaPhmYsiw4gYX3HoQiRBiSwDaBXKUafCt9bNwWQiitDk5VZ5BVxYnQdwoTyXSs2JHRPAgjAvtbBrf8ZhDYe2jWAqvZVnsc";
+const BIP84_TESTNET_TPUB: &str = "tpubDC8msFGeGuwnKG9Upg7DM2b4DaRqg3CUZa5g8v2SRQ6K4NSkxUgd7HsL2XVWbVm39yBA4LAxysQAm397zwQSQoQgewGiYZqrA9DsP4zbQ1M";
+
+fn bitcoin_context() -> TestCoinContext {
+    TestCoinContext {
+        p2pkh: Some(0),
+        p2sh: Some(5),
+        ..TestCoinContext::default()
+    }
+    .with_hrp("bc")
+}
+
+fn bitcoin_testnet_context() -> TestCoinContext {
+    TestCoinContext {
+        p2pkh: Some(111),
+        p2sh: Some(196),
+        ..TestCoinContext::default()
+    }
+    .with_hrp("tb")
+}
+
+fn test_addresses(
+    coin: &TestCoinContext,
+    key: &str,
+    chain: AddressChain,
+    expected_version: ExtendedKeyVersion,
+    expected: &[&str],
+) {
+    let xpub = ExtendedPublicKey::from_str(key).unwrap();
+    assert_eq!(xpub.version(), expected_version);
+    assert_eq!(xpub.depth(), 3);
+    assert_eq!(xpub.to_string(), key);
+
+    let address_type = xpub.address_type();
+    let addresses = xpub
+        .derive_addresses(coin, address_type, chain, 0..expected.len() as u32)
+        .unwrap();
+    let addresses: Vec<_> = addresses.iter().map(ToString::to_string).collect();
+    assert_eq!(addresses, expected);
+
+    // `derive_address` should give the same result as the batch derivation.
+    let last_index = expected.len() as u32 - 1;
+    let last = xpub
+        .derive_address(coin, address_type, chain, last_index)
+        .unwrap();
+    assert_eq!(last.to_string(), expected[expected.len() - 1]);
+}
+
+#[test]
+fn test_extended_key_bip44_addresses() {
+    let coin = bitcoin_context();
+    test_addresses(
+        &coin,
+        BIP44_XPUB,
+        AddressChain::Receive,
+        ExtendedKeyVersion::Xpub,
+        &[
+            "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+            "1Ak8PffB2meyfYnbXZR9EGfLfFZVpzJvQP",
+        ],
+    );
+    test_addresses(
+        &coin,
+        BIP44_XPUB,
+        AddressChain::Change,
+        ExtendedKeyVersion::Xpub,
+        &[
+            "1J3J6EvPrv8q6AC3VCjWV45Uf3nssNMRtH",
+            "13vKxXzHXXd8HquAYdpkJoi9ULVXUgfpS5",
+        ],
+    );
+}
+
+#[test]
+fn test_extended_key_bip49_addresses() {
+    let coin = bitcoin_context();
+    test_addresses(
+        &coin,
+        BIP49_YPUB,
+        AddressChain::Receive,
+        ExtendedKeyVersion::Ypub,
+        &[
+            "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+            "3LtMnn87fqUeHBUG414p9CWwnoV6E2pNKS",
+        ],
+    );
+    test_addresses(
+        &coin,
+        BIP49_YPUB,
+        AddressChain::Change,
+        ExtendedKeyVersion::Ypub,
+        &[
+            "34K56kSjgUCUSD8GTtuF7c9Zzwokbs6uZ7",
+            "3516F2wmK51jVRrggEJsTUBNWMSLLjzvJ2",
+        ],
+    );
+}
+
+#[test]
+fn test_extended_key_bip84_addresses() {
+    let coin = bitcoin_context();
+    test_addresses(
+        &coin,
+        BIP84_ZPUB,
+        AddressChain::Receive,
+        ExtendedKeyVersion::Zpub,
+        &[
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g",
+        ],
+    );
+    test_addresses(
+        &coin,
+        BIP84_ZPUB,
+        AddressChain::Change,
+        ExtendedKeyVersion::Zpub,
+        &[
+            "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el",
+            "bc1qggnasd834t54yulsep6fta8lpjekv4zj6gv5rf",
+        ],
+    );
+}
+
+#[test]
+fn test_extended_key_bip86_addresses() {
+    let coin = bitcoin_context();
+    let xpub = ExtendedPublicKey::from_str(BIP86_XPUB).unwrap();
+
+    let receive = xpub
+        .derive_addresses(&coin, AddressType::P2TR, AddressChain::Receive, 0..2)
+        .unwrap();
+    let receive: Vec<_> = receive.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        receive,
+        [
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+        ]
+    );
+
+    let change = xpub
+        .derive_address(&coin, AddressType::P2TR, AddressChain::Change, 0)
+        .unwrap();
+    assert_eq!(
+        change.to_string(),
+        "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7"
+    );
+}
+
+#[test]
+fn test_extended_key_testnet_addresses() {
+    let coin = bitcoin_testnet_context();
+    test_addresses(
+        &coin,
+        BIP49_TESTNET_UPUB,
+        AddressChain::Receive,
+        ExtendedKeyVersion::Upub,
+        &[
+            "2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2",
+            "2N55m54k8vr95ggehfUcNkdbUuQvaqG2GxK",
+        ],
+    );
+    test_addresses(
+        &coin,
+        BIP84_TESTNET_VPUB,
+        AddressChain::Receive,
+        ExtendedKeyVersion::Vpub,
+        &[
+            "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl",
+            "tb1qd7spv5q28348xl4myc8zmh983w5jx32cjhkn97",
+        ],
+    );
+}
+
+#[test]
+fn test_extended_key_override_address_type() {
+    // A plain `xpub` doesn't tell the script type, so it can be overridden by the caller.
+    let coin = bitcoin_context();
+    let xpub = ExtendedPublicKey::from_str(BIP84_XPUB).unwrap();
+    assert_eq!(xpub.address_type(), AddressType::P2PKH);
+
+    let address = xpub
+        .derive_address(&coin, AddressType::P2WPKH, AddressChain::Receive, 0)
+        .unwrap();
+    assert_eq!(
+        address.to_string(),
+        "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+    );
+}
+
+#[test]
+fn test_extended_key_convert_version() {
+    let zpub = ExtendedPublicKey::from_str(BIP84_ZPUB).unwrap();
+    assert_eq!(
+        zpub.to_string_with_version(ExtendedKeyVersion::Xpub)
+            .unwrap(),
+        BIP84_XPUB
+    );
+
+    let xpub = ExtendedPublicKey::from_str(BIP84_XPUB).unwrap();
+    assert_eq!(
+        xpub.to_string_with_version(ExtendedKeyVersion::Zpub)
+            .unwrap(),
+        BIP84_ZPUB
+    );
+    assert_eq!(xpub.public_key(), zpub.public_key());
+
+    let vpub = ExtendedPublicKey::from_str(BIP84_TESTNET_VPUB).unwrap();
+    assert_eq!(
+        vpub.to_string_with_version(ExtendedKeyVersion::Tpub)
+            .unwrap(),
+        BIP84_TESTNET_TPUB
+    );
+
+    let err = zpub
+        .to_string_with_version(ExtendedKeyVersion::Vpub)
+        .unwrap_err();
+    assert_eq!(*err.error_type(), SigningErrorType::Error_invalid_params);
+}
+
+#[test]
+fn test_extended_key_hardened_derivation() {
+    let xpub = ExtendedPublicKey::from_str(BIP84_ZPUB).unwrap();
+    let err = xpub.derive(&[0, 0x8000_0000]).unwrap_err();
+    assert_eq!(*err.error_type(), SigningErrorType::Error_not_supported);
+}
+
+#[test]
+fn test_extended_key_invalid() {
+    // Invalid checksum.
+    let mut invalid = BIP84_ZPUB.to_string();
+    invalid.pop();
+    invalid.push('t');
+    let err = ExtendedPublicKey::from_str(&invalid).unwrap_err();
+    assert_eq!(*err.error_type(), SigningErrorType::Error_invalid_params);
+
+    // Extended private key version.
+    let xprv = "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu";
+    let err = ExtendedPublicKey::from_str(xprv).unwrap_err();
+    assert_eq!(*err.error_type(), SigningErrorType::Error_not_supported);
+}
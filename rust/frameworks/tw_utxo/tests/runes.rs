@@ -0,0 +1,210 @@
+use std::str::FromStr;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_keypair::schnorr;
+use tw_utxo::script::Script;
+use tw_utxo::transaction::asset::runes::{
+    rune_commitment_leaf, rune_commitment_tree, Edict, Etching, Rune, RuneId, Runestone,
+    SpacedRune, Terms,
+};
+
+const PUBKEY: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+/// Please note the runestone vectors are built from the Runes specification and are not taken from mainnet,
+/// so they verify that `encipher` and `decipher` are consistent with each other and with the spec.
+fn test_runestone(runestone: Runestone, expected_script: &str) {
+    let script = runestone.encipher();
+    assert_eq!(script.as_slice().to_hex(), expected_script);
+
+    let decoded = Runestone::decipher(&script).unwrap();
+    assert_eq!(decoded, runestone);
+}
+
+fn decipher(script: &str) -> Runestone {
+    Runestone::decipher(&Script::from(script.decode_hex().unwrap())).unwrap()
+}
+
+#[test]
+fn test_rune_name() {
+    assert_eq!(Rune::from_str("A").unwrap(), Rune(0));
+    assert_eq!(Rune::from_str("Z").unwrap(), Rune(25));
+    assert_eq!(Rune::from_str("AA").unwrap(), Rune(26));
+    assert_eq!(Rune::from_str("ZZ").unwrap(), Rune(701));
+    assert_eq!(
+        Rune::from_str("UNCOMMONGOODS").unwrap(),
+        Rune(2055900680524219742)
+    );
+    assert_eq!(
+        Rune::from_str("BCGDENLQRQWDSLRUGSNLBTMFIJAV").unwrap(),
+        Rune(u128::MAX)
+    );
+
+    assert_eq!(Rune(0).to_string(), "A");
+    assert_eq!(Rune(701).to_string(), "ZZ");
+    assert_eq!(Rune(2055900680524219742).to_string(), "UNCOMMONGOODS");
+    assert_eq!(Rune(u128::MAX).to_string(), "BCGDENLQRQWDSLRUGSNLBTMFIJAV");
+
+    assert_eq!(
+        Rune(2055900680524219742).commitment().to_hex(),
+        "5e4521bcc606881c"
+    );
+    assert!(Rune(0).commitment().is_empty());
+
+    Rune::from_str("").unwrap_err();
+    Rune::from_str("abc").unwrap_err();
+    Rune::from_str("BCGDENLQRQWDSLRUGSNLBTMFIJAW").unwrap_err();
+}
+
+#[test]
+fn test_spaced_rune() {
+    let spaced = SpacedRune::from_str("UNCOMMON•GOODS").unwrap();
+    assert_eq!(spaced.rune, Rune(2055900680524219742));
+    assert_eq!(spaced.spacers, 0b1000_0000);
+    assert_eq!(spaced.to_string(), "UNCOMMON•GOODS");
+
+    let spaced = SpacedRune::from_str("DOG.GO.TO.THE.MOON").unwrap();
+    assert_eq!(spaced.spacers, 0b10_0101_0100);
+    assert_eq!(spaced.to_string(), "DOG•GO•TO•THE•MOON");
+
+    SpacedRune::from_str("•UNCOMMON").unwrap_err();
+    SpacedRune::from_str("UNCOMMON•").unwrap_err();
+    SpacedRune::from_str("UNCOMMON••GOODS").unwrap_err();
+    SpacedRune::from_str("UNCOMMON GOODS").unwrap_err();
+}
+
+#[test]
+fn test_rune_id() {
+    let id = RuneId::from_str("840000:3").unwrap();
+    assert_eq!(
+        id,
+        RuneId {
+            block: 840000,
+            tx: 3
+        }
+    );
+    assert_eq!(id.to_string(), "840000:3");
+
+    RuneId::from_str("840000").unwrap_err();
+    RuneId::from_str("0:1").unwrap_err();
+    RuneId::from_str("840000:4294967296").unwrap_err();
+}
+
+#[test]
+fn test_runestone_transfer() {
+    test_runestone(
+        Runestone {
+            edicts: vec![Edict {
+                id: RuneId::new(840000, 3).unwrap(),
+                amount: 1000,
+                output: 1,
+            }],
+            ..Runestone::default()
+        },
+        "6a5d0800c0a23303e80701",
+    );
+}
+
+#[test]
+fn test_runestone_transfer_edicts_sorted() {
+    let runestone = Runestone {
+        edicts: vec![
+            Edict {
+                id: RuneId::new(840000, 5).unwrap(),
+                amount: 0,
+                output: 2,
+            },
+            Edict {
+                id: RuneId::new(840000, 3).unwrap(),
+                amount: 1000,
+                output: 1,
+            },
+        ],
+        ..Runestone::default()
+    };
+
+    // Edicts are sorted by rune IDs, and the IDs are delta-encoded.
+    let script = runestone.encipher();
+    assert_eq!(script.as_slice().to_hex(), "6a5d0e00c0a23303e8070100020002");
+
+    let decoded = Runestone::decipher(&script).unwrap();
+    assert_eq!(decoded.edicts[0], runestone.edicts[1]);
+    assert_eq!(decoded.edicts[1], runestone.edicts[0]);
+}
+
+#[test]
+fn test_runestone_etching() {
+    test_runestone(
+        Runestone {
+            etching: Some(Etching {
+                divisibility: Some(2),
+                premine: Some(1_000_000),
+                rune: Some(Rune(2055900680524219742)),
+                spacers: Some(0b1000_0000),
+                symbol: Some('⧉'),
+                terms: Some(Terms {
+                    amount: Some(100),
+                    cap: Some(10_000),
+                    height: (Some(840_000), Some(1_050_000)),
+                    offset: (None, None),
+                }),
+                turbo: false,
+            }),
+            pointer: Some(1),
+            ..Runestone::default()
+        },
+        "6a5d27020304de8a85e1ebd881c41c010203800105c95306c0843d0a6408904e0cc0a2330e908b401601",
+    );
+}
+
+#[test]
+fn test_runestone_mint() {
+    test_runestone(
+        Runestone {
+            mint: Some(RuneId::new(1, 0).unwrap()),
+            pointer: Some(1),
+            ..Runestone::default()
+        },
+        "6a5d06140114001601",
+    );
+}
+
+#[test]
+fn test_runestone_cenotaph() {
+    // Unrecognized odd tags are ignored.
+    assert_eq!(
+        decipher("6a5d0419011601"),
+        Runestone {
+            pointer: Some(1),
+            ..Runestone::default()
+        }
+    );
+
+    // Unrecognized even tag.
+    Runestone::decipher(&Script::from("6a5d021801".decode_hex().unwrap())).unwrap_err();
+    // Truncated field.
+    Runestone::decipher(&Script::from("6a5d0116".decode_hex().unwrap())).unwrap_err();
+    // Unterminated varint.
+    Runestone::decipher(&Script::from("6a5d0180".decode_hex().unwrap())).unwrap_err();
+    // Non-push opcode.
+    Runestone::decipher(&Script::from("6a5d021601ac".decode_hex().unwrap())).unwrap_err();
+    // Not a runestone.
+    Runestone::decipher(&Script::from("6a021601".decode_hex().unwrap())).unwrap_err();
+}
+
+#[test]
+fn test_rune_commitment() {
+    let pubkey = schnorr::PublicKey::try_from(PUBKEY.decode_hex().unwrap().as_slice()).unwrap();
+    let rune = Rune(2055900680524219742);
+
+    let leaf = rune_commitment_leaf(&pubkey, &rune);
+    assert_eq!(
+        leaf.script().as_slice().to_hex(),
+        "2079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac0063085e4521bcc606881c68"
+    );
+
+    let tree = rune_commitment_tree(&pubkey, &rune).unwrap();
+    assert_eq!(tree.merkle_root(), leaf.leaf_hash());
+    assert_eq!(
+        tree.output_key().to_hex(),
+        "6a7965c472c4609e0a3aad0b36b24a064568ee5675cba835f73159046dd35169"
+    );
+}
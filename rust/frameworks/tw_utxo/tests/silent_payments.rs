@@ -0,0 +1,132 @@
+use std::str::FromStr;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_utxo::address::silent_payment::SilentPaymentAddress;
+use tw_utxo::modules::silent_payments::SilentPaymentSender;
+use tw_utxo::transaction::standard_transaction::builder::txid_from_str_and_rev;
+use tw_utxo::transaction::transaction_parts::OutPoint;
+
+const ALICE_ADDRESS: &str = "sp1qqgste7k9hx0qftg6qmwlkqtwuy6cycyavzmzj85c6qdfhjdpdjtdgqjuexzk6murw56suy3e0rd2cgqvycxttddwsvgxe2usfpxumr70xc9pkqwv";
+const BOB_ADDRESS: &str = "sp1qqfwtmurydewmf64rnrektuh20g8r6svm0cpnpcuuay4ammw2cnumcqmhftnlsk9fgy09aapyddcvvk4v2eyeszl9c9ufrwlvz7y4mgqgev2ps4wz";
+
+fn out_point(txid: &str, index: u32) -> OutPoint {
+    OutPoint {
+        hash: txid_from_str_and_rev(txid).unwrap(),
+        index,
+    }
+}
+
+fn address(s: &str) -> SilentPaymentAddress {
+    SilentPaymentAddress::from_str(s).unwrap()
+}
+
+/// "Simple send: two inputs" test vector from BIP-352.
+#[test]
+fn test_silent_payment_two_inputs() {
+    let out_points = [
+        out_point(
+            "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16",
+            0,
+        ),
+        out_point(
+            "a1075db55d416d3ca199f55b6084e2115b9345e16c5cf302fc80e9d5fbf5d48d",
+            0,
+        ),
+    ];
+
+    let mut sender = SilentPaymentSender::new(&out_points).unwrap();
+    sender
+        .add_eligible_private(
+            &"eadc78165ff1f8ea94ad7cfdc54990738a4c53f6e0507b42154201b8e5dff3b1"
+                .decode_hex()
+                .unwrap(),
+            false,
+        )
+        .unwrap()
+        .add_eligible_private(
+            &"93f5ed907ad5b2bdbbdcb5d9116ebc0a4e1f92f910d5260237fa45a9408aad16"
+                .decode_hex()
+                .unwrap(),
+            false,
+        )
+        .unwrap();
+
+    let output_keys = sender
+        .derive_output_keys(&[address(ALICE_ADDRESS)])
+        .unwrap();
+    assert_eq!(
+        output_keys[0].to_hex(),
+        "3e9fce73d4e77a4809908e3c3a2e54ee147b9312dc5044a193d1fc85de46e3c1"
+    );
+}
+
+#[test]
+fn test_silent_payment_taproot_input_multiple_recipients() {
+    let out_points = [
+        out_point(
+            "2b871b6c1112ad0a777f6db1f7a7709154c4d9af8e771ba4eca148915f830e9d",
+            2,
+        ),
+        out_point(
+            "b3faa197c6303b3510c33a6760a8d6742b5ed413389573ad97c83dcdebb3c036",
+            1,
+        ),
+        // Not eligible input, but it's still used to find the smallest outpoint.
+        out_point(
+            "060437bb67bd0bbfadd49200a7636b7fe85764d76fade3cb9b4c6dcbe1a0039c",
+            0,
+        ),
+    ];
+
+    let mut sender = SilentPaymentSender::new(&out_points).unwrap();
+    sender
+        // Taproot private key with an odd Y coordinate of its public key.
+        .add_eligible_private(
+            &"e2bb19236c64f79c03ff7100b47e7de6f8c976b46eda561f948300e8fea485e3"
+                .decode_hex()
+                .unwrap(),
+            true,
+        )
+        .unwrap()
+        .add_eligible_private(
+            &"428d66be0b5a620f126a00fa67637222ce3dc9badfe5c605189520760810cfac"
+                .decode_hex()
+                .unwrap(),
+            false,
+        )
+        .unwrap();
+
+    let output_keys = sender
+        .derive_output_keys(&[
+            address(ALICE_ADDRESS),
+            address(BOB_ADDRESS),
+            address(ALICE_ADDRESS),
+        ])
+        .unwrap();
+    let output_keys: Vec<_> = output_keys.iter().map(|key| key.to_hex()).collect();
+    assert_eq!(
+        output_keys,
+        [
+            // k = 0
+            "661aacbcb5a94b46b5ebf4bdd053c9d6f7847be43df434bc1f551fe59f223d42",
+            // k = 0
+            "e888e381b8f461215d084a99d09c7a10efd2cbb5310c2dd52b0b92f8840c5c9a",
+            // k = 1
+            "7785e7ed221e52218789b0d808354b258b2b0482e00f8a2f0fa9aad55174728a",
+        ]
+    );
+}
+
+#[test]
+fn test_silent_payment_no_eligible_inputs() {
+    let out_points = [out_point(
+        "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16",
+        0,
+    )];
+
+    let sender = SilentPaymentSender::new(&out_points).unwrap();
+    sender
+        .derive_output_keys(&[address(ALICE_ADDRESS)])
+        .unwrap_err();
+
+    SilentPaymentSender::new(&[]).unwrap_err();
+}
@@ -0,0 +1,126 @@
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_keypair::schnorr;
+use tw_utxo::script::tap_tree::{TapLeaf, TapTreeBuilder};
+use tw_utxo::script::Script;
+
+const INTERNAL_KEY: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+const LEAF_A: &str = "20c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5ac";
+const LEAF_B: &str = "20f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9ac";
+const LEAF_C: &str = "20e493dbf1c10d80f3581e4904930b1404cc6c13900ee0758474fa94abe8c4cd13ac";
+
+const MERKLE_ROOT: &str = "59a1175e678ad3c45081ac12f1fa70394fe4baf56422a85876a7d76f2e3a0469";
+const OUTPUT_KEY: &str = "749fff232df2cec69afc67cb189fb19dbdac2f750c13931f578e092e6a048945";
+
+fn internal_key() -> schnorr::PublicKey {
+    schnorr::PublicKey::try_from(INTERNAL_KEY.decode_hex().unwrap().as_slice()).unwrap()
+}
+
+fn tapscript(hex: &str) -> TapLeaf {
+    TapLeaf::tapscript(Script::from(hex.decode_hex().unwrap()))
+}
+
+#[test]
+fn test_tap_tree_explicit_depths() {
+    let tree = TapTreeBuilder::new()
+        .add_leaf(1, tapscript(LEAF_A))
+        .add_leaf(2, tapscript(LEAF_B))
+        .add_leaf(2, tapscript(LEAF_C))
+        .finalize(&internal_key())
+        .unwrap();
+
+    assert_eq!(
+        tapscript(LEAF_A).leaf_hash().to_hex(),
+        "ab11b8ce98a88b0dccf33a8144f90266dd8228b9fec6fa0cc0f7d4c0a28b8977"
+    );
+    assert_eq!(tree.merkle_root().to_hex(), MERKLE_ROOT);
+    assert_eq!(tree.output_key().to_hex(), OUTPUT_KEY);
+    assert_eq!(
+        tree.script_pubkey().as_slice().to_hex(),
+        format!("5120{OUTPUT_KEY}")
+    );
+
+    let control_block_a = tree.control_block(&tapscript(LEAF_A)).unwrap();
+    assert_eq!(
+        control_block_a.to_hex(),
+        "c079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+        7440bb380e4f15ed0c602b85a9fa4208b577987feba3bca5b2945d0048038385"
+    );
+
+    let control_block_b = tree.control_block(&tapscript(LEAF_B)).unwrap();
+    assert_eq!(
+        control_block_b.to_hex(),
+        "c079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+        0e167d3d619d2b16fb08736a6e696eb6814c6d99a578c8c268d4195aeb9f72d1\
+        ab11b8ce98a88b0dccf33a8144f90266dd8228b9fec6fa0cc0f7d4c0a28b8977"
+    );
+}
+
+#[test]
+fn test_tap_tree_huffman() {
+    // The heaviest leaf `C` should be placed at depth 1, `A` and `B` at depth 2.
+    let tree = TapTreeBuilder::with_huffman(vec![
+        (1, tapscript(LEAF_A)),
+        (1, tapscript(LEAF_B)),
+        (2, tapscript(LEAF_C)),
+    ])
+    .unwrap()
+    .finalize(&internal_key())
+    .unwrap();
+
+    assert_eq!(
+        tree.leaves(),
+        &[tapscript(LEAF_C), tapscript(LEAF_A), tapscript(LEAF_B)]
+    );
+    // Branches are sorted lexicographically, so the tree commits to the same root.
+    assert_eq!(tree.merkle_root().to_hex(), MERKLE_ROOT);
+    assert_eq!(tree.output_key().to_hex(), OUTPUT_KEY);
+    assert_eq!(
+        tree.control_block(&tapscript(LEAF_C)).unwrap().len(),
+        33 + 32
+    );
+    assert_eq!(
+        tree.control_block(&tapscript(LEAF_A)).unwrap().len(),
+        33 + 32 * 2
+    );
+}
+
+#[test]
+fn test_tap_tree_single_leaf() {
+    let tree = TapTreeBuilder::with_huffman(vec![(1, tapscript(LEAF_A))])
+        .unwrap()
+        .finalize(&internal_key())
+        .unwrap();
+
+    assert_eq!(tree.merkle_root(), tapscript(LEAF_A).leaf_hash());
+    assert_eq!(
+        tree.control_block(&tapscript(LEAF_A)).unwrap().to_hex(),
+        "c179be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+    );
+}
+
+#[test]
+fn test_tap_tree_invalid() {
+    let script = Script::from(LEAF_A.decode_hex().unwrap());
+    // Odd leaf version.
+    TapLeaf::new(script.clone(), 0xc1).unwrap_err();
+    // Reserved for the annex.
+    TapLeaf::new(script.clone(), 0x50).unwrap_err();
+    TapLeaf::new(script, 0xc2).unwrap();
+
+    // Incomplete tree.
+    TapTreeBuilder::new()
+        .add_leaf(1, tapscript(LEAF_A))
+        .finalize(&internal_key())
+        .unwrap_err();
+    // Empty tree.
+    TapTreeBuilder::new().finalize(&internal_key()).unwrap_err();
+    TapTreeBuilder::with_huffman(Vec::new()).unwrap_err();
+
+    // The leaf is not a part of the tree.
+    let tree = TapTreeBuilder::new()
+        .add_leaf(1, tapscript(LEAF_A))
+        .add_leaf(1, tapscript(LEAF_B))
+        .finalize(&internal_key())
+        .unwrap();
+    tree.control_block(&tapscript(LEAF_C)).unwrap_err();
+}
@@ -0,0 +1,256 @@
+use tw_coin_entry::error::prelude::*;
+use tw_encoding::hex::DecodeHex;
+use tw_keypair::ecdsa;
+use tw_utxo::address::standard_bitcoin::StandardBitcoinAddress;
+use tw_utxo::context::{AddressPrefixes, UtxoContext};
+use tw_utxo::dust::DustPolicy;
+use tw_utxo::fee::fee_estimator::StandardFeeEstimator;
+use tw_utxo::fee::FeePolicy;
+use tw_utxo::modules::tx_planner::{BatchPlanRequest, BatchRequestType, TxPlanner};
+use tw_utxo::modules::utxo_selector::{InputSelector, SelectResult};
+use tw_utxo::script::Script;
+use tw_utxo::sighash::SighashType;
+use tw_utxo::transaction::standard_transaction::builder::{
+    txid_from_str_and_rev, OutputBuilder, TransactionBuilder, UtxoBuilder,
+};
+use tw_utxo::transaction::standard_transaction::Transaction;
+use tw_utxo::transaction::transaction_interface::{
+    TransactionInterface, TxInputInterface, TxOutputInterface,
+};
+use tw_utxo::transaction::unsigned_transaction::UnsignedTransaction;
+
+const PUBKEY: &str = "037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf";
+const TXID: &str = "1e1cdc48aa990d7e154a161d5b5f1cad737742e97d2712ab188027bb42e6e47b";
+const DUST: i64 = 546;
+const FEE_PER_VB: i64 = 1;
+
+struct TestContext;
+
+impl UtxoContext for TestContext {
+    type Address = StandardBitcoinAddress;
+    type Transaction = Transaction;
+    type FeeEstimator = StandardFeeEstimator<Self::Transaction>;
+
+    fn addr_to_script_pubkey(
+        _addr: &Self::Address,
+        _prefixes: AddressPrefixes,
+    ) -> SigningResult<Script> {
+        unreachable!()
+    }
+}
+
+fn pubkey() -> ecdsa::secp256k1::PublicKey {
+    let pubkey = PUBKEY.decode_hex().unwrap();
+    ecdsa::secp256k1::PublicKey::try_from(pubkey.as_slice()).unwrap()
+}
+
+/// Builds a transaction spending P2WPKH UTXOs to P2WPKH outputs.
+fn p2wpkh_transaction(utxos: &[i64], outputs: &[i64]) -> UnsignedTransaction<Transaction> {
+    let pubkey = pubkey();
+    let txid = txid_from_str_and_rev(TXID).unwrap();
+
+    let mut builder = TransactionBuilder::new();
+    for (vout, amount) in utxos.iter().enumerate() {
+        let (utxo, arg) = UtxoBuilder::new()
+            .prev_txid(txid)
+            .prev_index(vout as u32)
+            .amount(*amount)
+            .sighash_type(SighashType::default())
+            .p2wpkh(&pubkey)
+            .unwrap();
+        builder.push_input(utxo, arg);
+    }
+    for amount in outputs {
+        builder.push_output(OutputBuilder::new(*amount).p2wpkh(&pubkey));
+    }
+    builder.build().unwrap()
+}
+
+fn plan_batch(ty: BatchRequestType<Transaction>) -> SigningResult<Vec<SelectResult<Transaction>>> {
+    TxPlanner::<TestContext>::plan_batch(BatchPlanRequest {
+        ty,
+        dust_policy: DustPolicy::FixedAmount(DUST),
+        fee_estimator: StandardFeeEstimator::new(FeePolicy::FeePerVb(FEE_PER_VB)),
+    })
+}
+
+fn input_indexes(result: &SelectResult<Transaction>) -> Vec<u32> {
+    result
+        .unsigned_tx
+        .inputs()
+        .iter()
+        .map(|utxo| utxo.previous_output().index)
+        .collect()
+}
+
+fn output_amounts(result: &SelectResult<Transaction>) -> Vec<i64> {
+    result
+        .unsigned_tx
+        .outputs()
+        .iter()
+        .map(|output| output.value())
+        .collect()
+}
+
+#[test]
+fn test_consolidate_max_inputs() {
+    let unsigned_tx = p2wpkh_transaction(&[10_000; 5], &[0]);
+    let results = plan_batch(BatchRequestType::Consolidate {
+        unsigned_tx,
+        max_inputs: Some(2),
+        max_weight: None,
+    })
+    .unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(input_indexes(&results[0]), vec![0, 1]);
+    assert_eq!(input_indexes(&results[1]), vec![2, 3]);
+    assert_eq!(input_indexes(&results[2]), vec![4]);
+
+    // Weight: 166 + 273 * inputs. 2 inputs: 712 (178 vbytes), 1 input: 439 (110 vbytes).
+    let fees: Vec<_> = results.iter().map(|res| res.plan.fee_estimate).collect();
+    assert_eq!(fees, vec![178, 178, 110]);
+    assert_eq!(output_amounts(&results[0]), vec![19_822]);
+    assert_eq!(output_amounts(&results[1]), vec![19_822]);
+    assert_eq!(output_amounts(&results[2]), vec![9_890]);
+}
+
+#[test]
+fn test_consolidate_max_weight() {
+    let unsigned_tx = p2wpkh_transaction(&[10_000; 5], &[0]);
+    let results = plan_batch(BatchRequestType::Consolidate {
+        unsigned_tx,
+        max_inputs: None,
+        // 3 inputs weigh 985, 4 inputs weigh 1258.
+        max_weight: Some(1_000),
+    })
+    .unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(input_indexes(&results[0]), vec![0, 1, 2]);
+    assert_eq!(input_indexes(&results[1]), vec![3, 4]);
+
+    assert_eq!(results[0].plan.fee_estimate, 247);
+    assert_eq!(output_amounts(&results[0]), vec![29_753]);
+    assert_eq!(results[1].plan.fee_estimate, 178);
+    assert_eq!(output_amounts(&results[1]), vec![19_822]);
+}
+
+#[test]
+fn test_consolidate_utxo_too_big() {
+    let unsigned_tx = p2wpkh_transaction(&[10_000; 2], &[0]);
+    let err = plan_batch(BatchRequestType::Consolidate {
+        unsigned_tx,
+        max_inputs: None,
+        // A transaction with 1 input weighs 439.
+        max_weight: Some(400),
+    })
+    .unwrap_err();
+    assert_eq!(*err.error_type(), SigningErrorType::Error_tx_too_big);
+}
+
+#[test]
+fn test_consolidate_utxo_too_big_not_first() {
+    let pubkey = pubkey();
+    let txid = txid_from_str_and_rev(TXID).unwrap();
+
+    // Two P2WPKH UTXOs fit a single transaction, but the P2PKH one doesn't fit any.
+    let mut builder = TransactionBuilder::new();
+    for vout in 0..3 {
+        let utxo_builder = UtxoBuilder::new()
+            .prev_txid(txid)
+            .prev_index(vout)
+            .amount(10_000)
+            .sighash_type(SighashType::default());
+        let (utxo, arg) = if vout == 2 {
+            utxo_builder.p2pkh(&pubkey).unwrap()
+        } else {
+            utxo_builder.p2wpkh(&pubkey).unwrap()
+        };
+        builder.push_input(utxo, arg);
+    }
+    builder.push_output(OutputBuilder::new(0).p2wpkh(&pubkey));
+
+    let err = plan_batch(BatchRequestType::Consolidate {
+        unsigned_tx: builder.build().unwrap(),
+        max_inputs: None,
+        // 2 P2WPKH inputs weigh 712, 1 P2PKH input weighs 760.
+        max_weight: Some(720),
+    })
+    .unwrap_err();
+    assert_eq!(*err.error_type(), SigningErrorType::Error_tx_too_big);
+    assert!(err.to_string().contains("UTXO #2"), "{err}");
+}
+
+#[test]
+fn test_consolidate_multiple_outputs_error() {
+    let unsigned_tx = p2wpkh_transaction(&[10_000; 2], &[0, 0]);
+    let err = plan_batch(BatchRequestType::Consolidate {
+        unsigned_tx,
+        max_inputs: None,
+        max_weight: None,
+    })
+    .unwrap_err();
+    assert_eq!(*err.error_type(), SigningErrorType::Error_invalid_params);
+}
+
+#[test]
+fn test_payout_max_outputs() {
+    let unsigned_tx = p2wpkh_transaction(&[20_000; 3], &[5_000, 6_000, 7_000]);
+    let change_output = OutputBuilder::new(0).p2wpkh(&pubkey());
+    let results = plan_batch(BatchRequestType::Payout {
+        unsigned_tx,
+        change_output: Some(change_output),
+        input_selector: InputSelector::InOrder,
+        max_outputs: Some(2),
+    })
+    .unwrap();
+
+    assert_eq!(results.len(), 2);
+
+    // 1 input, 3 outputs. Weight: 687, vsize: 172.
+    assert_eq!(input_indexes(&results[0]), vec![0]);
+    assert_eq!(output_amounts(&results[0]), vec![5_000, 6_000, 8_828]);
+    assert_eq!(results[0].plan.fee_estimate, 172);
+    assert_eq!(results[0].plan.change, 8_828);
+
+    // 1 input, 2 outputs. Weight: 563, vsize: 141.
+    // The first UTXO is spent by the previous transaction already.
+    assert_eq!(input_indexes(&results[1]), vec![1]);
+    assert_eq!(output_amounts(&results[1]), vec![7_000, 12_859]);
+    assert_eq!(results[1].plan.fee_estimate, 141);
+    assert_eq!(results[1].plan.change, 12_859);
+}
+
+#[test]
+fn test_payout_not_enough_utxos_for_next_transaction() {
+    let unsigned_tx = p2wpkh_transaction(&[20_000], &[5_000, 6_000]);
+    let err = plan_batch(BatchRequestType::Payout {
+        unsigned_tx,
+        change_output: None,
+        input_selector: InputSelector::InOrder,
+        max_outputs: Some(1),
+    })
+    .unwrap_err();
+    assert_eq!(
+        *err.error_type(),
+        SigningErrorType::Error_missing_input_utxos
+    );
+}
+
+#[test]
+fn test_payout_dust_output() {
+    let unsigned_tx = p2wpkh_transaction(&[20_000; 3], &[5_000, DUST - 1, 7_000]);
+    let err = plan_batch(BatchRequestType::Payout {
+        unsigned_tx,
+        change_output: None,
+        input_selector: InputSelector::InOrder,
+        max_outputs: None,
+    })
+    .unwrap_err();
+    assert_eq!(
+        *err.error_type(),
+        SigningErrorType::Error_dust_amount_requested
+    );
+    assert!(err.to_string().contains("Output #1"), "{err}");
+}
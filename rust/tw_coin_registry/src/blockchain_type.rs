@@ -14,10 +14,12 @@ pub enum BlockchainType {
     Bitcoin,
     BitcoinCash,
     Cosmos,
+    Dogecoin,
     Ethereum,
     Greenfield,
     InternetComputer,
     Komodo,
+    Litecoin,
     NativeEvmos,
     NativeInjective,
     Pactus,
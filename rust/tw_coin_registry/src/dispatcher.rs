@@ -9,7 +9,7 @@ use crate::error::{RegistryError, RegistryResult};
 use crate::registry::get_coin_item;
 use tw_aptos::entry::AptosEntry;
 use tw_binance::entry::BinanceEntry;
-use tw_bitcoin::entry::BitcoinEntry;
+use tw_bitcoin::entry::{BitcoinEntry, DogecoinEntry, LitecoinEntry};
 use tw_bitcoincash::entry::BitcoinCashEntry;
 use tw_coin_entry::coin_entry_ext::CoinEntryExt;
 use tw_cosmos::entry::CosmosEntry;
@@ -36,13 +36,15 @@ pub type EvmEntryExtStaticRef = &'static dyn EvmEntryExt;
 // start_of_blockchain_entries - USED TO GENERATE CODE
 const APTOS: AptosEntry = AptosEntry;
 const BINANCE: BinanceEntry = BinanceEntry;
-const BITCOIN: BitcoinEntry = BitcoinEntry;
+const BITCOIN: BitcoinEntry = BitcoinEntry::new();
 const BITCOIN_CASH: BitcoinCashEntry = BitcoinCashEntry;
 const COSMOS: CosmosEntry = CosmosEntry;
+const DOGECOIN: DogecoinEntry = DogecoinEntry::new();
 const ETHEREUM: EthereumEntry = EthereumEntry;
 const GREENFIELD: GreenfieldEntry = GreenfieldEntry;
 const INTERNET_COMPUTER: InternetComputerEntry = InternetComputerEntry;
 const KOMODO: KomodoEntry = KomodoEntry;
+const LITECOIN: LitecoinEntry = LitecoinEntry::new();
 const NATIVE_EVMOS: NativeEvmosEntry = NativeEvmosEntry;
 const NATIVE_INJECTIVE: NativeInjectiveEntry = NativeInjectiveEntry;
 const PACTUS: PactusEntry = PactusEntry;
@@ -63,10 +65,12 @@ pub fn blockchain_dispatcher(blockchain: BlockchainType) -> RegistryResult<CoinE
         BlockchainType::Bitcoin => Ok(&BITCOIN),
         BlockchainType::BitcoinCash => Ok(&BITCOIN_CASH),
         BlockchainType::Cosmos => Ok(&COSMOS),
+        BlockchainType::Dogecoin => Ok(&DOGECOIN),
         BlockchainType::Ethereum => Ok(&ETHEREUM),
         BlockchainType::Greenfield => Ok(&GREENFIELD),
         BlockchainType::InternetComputer => Ok(&INTERNET_COMPUTER),
         BlockchainType::Komodo => Ok(&KOMODO),
+        BlockchainType::Litecoin => Ok(&LITECOIN),
         BlockchainType::NativeEvmos => Ok(&NATIVE_EVMOS),
         BlockchainType::NativeInjective => Ok(&NATIVE_INJECTIVE),
         BlockchainType::Pactus => Ok(&PACTUS),
@@ -2,17 +2,21 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::abi::custom_error::CustomError;
+use crate::abi::event::Event;
 use crate::abi::function::Function;
 use crate::abi::{AbiErrorKind, AbiResult};
 use serde::{Deserialize, Deserializer};
 use std::collections::BTreeMap;
 use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
 
 /// API building calls to contracts ABI.
-/// Consider adding missing field such as `errors`, `events` etc.
 #[derive(Clone, Debug, Default)]
 pub struct Contract {
     pub functions: BTreeMap<String, Vec<Function>>,
+    pub events: BTreeMap<String, Vec<Event>>,
+    pub errors: BTreeMap<String, Vec<CustomError>>,
 }
 
 impl Contract {
@@ -26,6 +30,22 @@ impl Contract {
             .or_tw_err(AbiErrorKind::Error_abi_mismatch)
             .with_context(|| format!("The given Smart Contract does not have '{name}' function"))
     }
+
+    /// Finds a function by the first four bytes of its signature hash.
+    pub fn function_by_short_signature(&self, short_signature: &[u8]) -> Option<&Function> {
+        self.functions
+            .values()
+            .flatten()
+            .find(|fun| fun.short_signature().as_slice() == short_signature)
+    }
+
+    /// Finds a non-anonymous event by the first topic of a log, i.e. the hash of the event signature.
+    pub fn event_by_topic(&self, topic: &H256) -> Option<&Event> {
+        self.events
+            .values()
+            .flatten()
+            .find(|event| !event.anonymous && event.topic() == *topic)
+    }
 }
 
 impl<'de> Deserialize<'de> for Contract {
@@ -33,11 +53,12 @@ impl<'de> Deserialize<'de> for Contract {
     where
         D: Deserializer<'de>,
     {
-        /// Consider adding missing field such as `errors`, `events` etc.
         #[derive(Deserialize)]
         #[serde(tag = "type", rename_all = "snake_case")]
         enum Operation {
             Function(Function),
+            Event(Event),
+            Error(CustomError),
             #[serde(other)]
             Unsupported,
         }
@@ -46,6 +67,8 @@ impl<'de> Deserialize<'de> for Contract {
 
         let mut result = Contract {
             functions: BTreeMap::default(),
+            events: BTreeMap::default(),
+            errors: BTreeMap::default(),
         };
         for operation in operations {
             match operation {
@@ -54,6 +77,16 @@ impl<'de> Deserialize<'de> for Contract {
                     .entry(fun.name.clone())
                     .or_default()
                     .push(fun),
+                Operation::Event(event) => result
+                    .events
+                    .entry(event.name.clone())
+                    .or_default()
+                    .push(event),
+                Operation::Error(error) => result
+                    .errors
+                    .entry(error.name.clone())
+                    .or_default()
+                    .push(error),
                 Operation::Unsupported => (),
             }
         }
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::decode::decode_params;
+use crate::abi::param::Param;
+use crate::abi::param_token::NamedToken;
+use crate::abi::signature::short_signature;
+use crate::abi::AbiResult;
+use itertools::Itertools;
+use serde::Deserialize;
+use tw_hash::H32;
+
+/// Solidity custom error, e.g. `error InsufficientBalance(uint256 available, uint256 required)`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct CustomError {
+    /// Error name.
+    pub name: String,
+    /// Error parameters.
+    pub inputs: Vec<Param>,
+}
+
+impl CustomError {
+    /// Returns a signature that uniquely identifies this error.
+    ///
+    /// Example: `InsufficientBalance(uint256,uint256)`
+    pub fn signature(&self) -> String {
+        let inputs = self.inputs.iter().map(|p| p.kind.to_type_long()).join(",");
+        format!("{}({inputs})", self.name)
+    }
+
+    /// Returns the first four bytes of the Keccak-256 hash of the error signature.
+    /// Revert data of the error is prefixed with this selector.
+    pub fn short_signature(&self) -> H32 {
+        let input_param_types: Vec<_> =
+            self.inputs.iter().map(|param| param.kind.clone()).collect();
+        short_signature(&self.name, &input_param_types)
+    }
+
+    /// Parses the error parameters (revert data without the selector) to a list of tokens.
+    pub fn decode_input(&self, data: &[u8]) -> AbiResult<Vec<NamedToken>> {
+        decode_params(&self.inputs, data)
+    }
+}
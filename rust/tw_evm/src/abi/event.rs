@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::decode::{decode_params, decode_value};
+use crate::abi::non_empty_array::NonEmptyBytes;
+use crate::abi::param::Param;
+use crate::abi::param_token::NamedToken;
+use crate::abi::param_type::ParamType;
+use crate::abi::signature::long_signature;
+use crate::abi::token::Token;
+use crate::abi::{AbiError, AbiErrorKind, AbiResult};
+use itertools::Itertools;
+use serde::Deserialize;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct EventParam {
+    #[serde(flatten)]
+    pub param: Param,
+    /// Whether the param is stored in the log topics instead of the log data.
+    #[serde(default)]
+    pub indexed: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Event {
+    /// Event name.
+    pub name: String,
+    /// Event input.
+    pub inputs: Vec<EventParam>,
+    /// Anonymous events do not emit their signature hash as the first topic.
+    #[serde(default)]
+    pub anonymous: bool,
+}
+
+impl Event {
+    /// Returns a signature that uniquely identifies this event.
+    ///
+    /// Example: `Transfer(address,address,uint256)`
+    pub fn signature(&self) -> String {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| input.param.kind.to_type_long())
+            .join(",");
+        format!("{}({inputs})", self.name)
+    }
+
+    /// Returns the Keccak-256 hash of the event signature, i.e. the first topic of the log.
+    pub fn topic(&self) -> H256 {
+        let input_param_types: Vec<_> = self
+            .inputs
+            .iter()
+            .map(|input| input.param.kind.clone())
+            .collect();
+        long_signature(&self.name, &input_param_types)
+    }
+
+    /// Decodes the event parameters from the given log `topics` and `data`.
+    /// The parameters are returned in the order they are declared in the ABI.
+    ///
+    /// Please note that indexed parameters of dynamic types (`string`, `bytes`, arrays and tuples)
+    /// are stored as a Keccak-256 hash of their value, so they are decoded as `bytes32`.
+    pub fn decode_log(&self, topics: &[H256], data: &[u8]) -> AbiResult<Vec<NamedToken>> {
+        let indexed_topics = if self.anonymous {
+            topics
+        } else {
+            match topics.split_first() {
+                Some((topic0, rest)) if *topic0 == self.topic() => rest,
+                _ => {
+                    return AbiError::err(AbiErrorKind::Error_abi_mismatch)
+                        .with_context(|| format!("The log is not a '{}' event", self.signature()))
+                },
+            }
+        };
+
+        let indexed_count = self.inputs.iter().filter(|input| input.indexed).count();
+        if indexed_topics.len() != indexed_count {
+            return AbiError::err(AbiErrorKind::Error_decoding_data).with_context(|| {
+                format!(
+                    "Expected {indexed_count} indexed topics, found {}",
+                    indexed_topics.len()
+                )
+            });
+        }
+
+        let non_indexed: Vec<_> = self
+            .inputs
+            .iter()
+            .filter(|input| !input.indexed)
+            .map(|input| input.param.clone())
+            .collect();
+        let mut non_indexed_tokens = decode_params(&non_indexed, data)?.into_iter();
+        let mut indexed_topics = indexed_topics.iter();
+
+        self.inputs
+            .iter()
+            .map(|input| {
+                if input.indexed {
+                    let topic = indexed_topics
+                        .next()
+                        .expect("The number of topics is checked above");
+                    decode_topic(&input.param, topic)
+                } else {
+                    non_indexed_tokens
+                        .next()
+                        .or_tw_err(AbiErrorKind::Error_internal)
+                        .context("Expected a decoded non-indexed parameter")
+                }
+            })
+            .collect()
+    }
+}
+
+fn decode_topic(param: &Param, topic: &H256) -> AbiResult<NamedToken> {
+    let token = if is_hashed_when_indexed(&param.kind) {
+        let hash = NonEmptyBytes::new(topic.to_vec()).expect("H256 must not be empty");
+        Token::FixedBytes(hash)
+    } else {
+        decode_value(&param.kind, topic.as_slice())?
+    };
+    Ok(NamedToken::with_param_and_token(param, token))
+}
+
+/// Values of reference types are not stored in topics directly, but as their Keccak-256 hash.
+/// https://docs.soliditylang.org/en/latest/abi-spec.html#encoding-of-indexed-event-parameters
+fn is_hashed_when_indexed(kind: &ParamType) -> bool {
+    matches!(
+        kind,
+        ParamType::Bytes
+            | ParamType::String
+            | ParamType::Array { .. }
+            | ParamType::FixedArray { .. }
+            | ParamType::Tuple { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::Address;
+    use tw_encoding::hex::DecodeHex;
+    use tw_hash::sha3::keccak256;
+    use tw_number::U256;
+
+    #[test]
+    fn test_decode_log_with_dynamic_indexed_param() {
+        let abi = r#"{
+            "name": "Registered",
+            "anonymous": false,
+            "inputs": [
+                { "name": "owner", "type": "address", "indexed": true },
+                { "name": "label", "type": "string", "indexed": true },
+                { "name": "amount", "type": "uint256", "indexed": false }
+            ]
+        }"#;
+        let event: Event = serde_json::from_str(abi).unwrap();
+        assert_eq!(event.signature(), "Registered(address,string,uint256)");
+
+        let label_hash = H256::try_from(keccak256(b"wallet").as_slice()).unwrap();
+        let topics = [
+            event.topic(),
+            H256::from("0000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa261520"),
+            label_hash,
+        ];
+        let data = "0000000000000000000000000000000000000000000000000000000000000064"
+            .decode_hex()
+            .unwrap();
+
+        let decoded = event.decode_log(&topics, &data).unwrap();
+        let values: Vec<_> = decoded.into_iter().map(|token| token.value).collect();
+        assert_eq!(
+            values,
+            vec![
+                Token::Address(Address::from("0x4bbeEB066eD09B7AEd07bF39EEe0460DFa261520")),
+                Token::FixedBytes(NonEmptyBytes::new(label_hash.to_vec()).unwrap()),
+                Token::u256(U256::from(100u64)),
+            ]
+        );
+    }
+}
@@ -12,6 +12,7 @@ use crate::abi::{AbiError, AbiErrorKind, AbiResult};
 use itertools::Itertools;
 use serde::Deserialize;
 use tw_coin_entry::error::prelude::*;
+use tw_hash::H32;
 use tw_memory::Data;
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -43,6 +44,13 @@ impl Function {
         }
     }
 
+    /// Returns the first four bytes of the Keccak-256 hash of the function signature.
+    pub fn short_signature(&self) -> H32 {
+        let input_param_types: Vec<_> =
+            self.inputs.iter().map(|param| param.kind.clone()).collect();
+        short_signature(&self.name, &input_param_types)
+    }
+
     /// Parses the ABI function input to a list of tokens.
     pub fn decode_input(&self, data: &[u8]) -> AbiResult<Vec<NamedToken>> {
         decode_params(&self.inputs, data)
@@ -51,20 +59,20 @@ impl Function {
     /// Encodes function input to Eth ABI binary.
     pub fn encode_input(&self, tokens: &[Token]) -> AbiResult<Data> {
         // Check if the given tokens match `Self::inputs` ABI.
-        let input_param_types: Vec<_> =
-            self.inputs.iter().map(|param| param.kind.clone()).collect();
-        for (token_idx, (token, kind)) in tokens.iter().zip(input_param_types.iter()).enumerate() {
-            let actual_kind = token.to_param_type();
-            if actual_kind != *kind {
+        // Compare the canonical types, as tuple component names and internal types don't affect the encoding.
+        for (token_idx, (token, param)) in tokens.iter().zip(self.inputs.iter()).enumerate() {
+            let expected_type = param.kind.to_type_long();
+            let actual_type = token.to_param_type().to_type_long();
+            if actual_type != expected_type {
                 return AbiError::err(AbiErrorKind::Error_abi_mismatch).with_context(|| {
                     format!(
-                        "Expected {kind:?} type parameter at {token_idx}, found {actual_kind:?}"
+                        "Expected {expected_type} type parameter at {token_idx}, found {actual_type}"
                     )
                 });
             }
         }
 
-        let signed = short_signature(&self.name, &input_param_types);
+        let signed = self.short_signature();
         let encoded = encode_tokens(tokens);
         Ok(signed.into_iter().chain(encoded).collect())
     }
@@ -0,0 +1,478 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Human-readable ABI parser.
+//! https://docs.ethers.org/v5/api/utils/abi/formats/#abi-formats--human-readable-abi
+//!
+//! Examples of supported fragments:
+//! - `function transfer(address to, uint256 amount) external returns (bool)`
+//! - `transfer(address,uint256)`
+//! - `function fill((address maker, uint256[] amounts)[] orders, bytes calldata data) payable`
+//! - `event Transfer(address indexed from, address indexed to, uint256 value)`
+//! - `error InsufficientBalance(uint256 available, uint256 required)`
+
+use crate::abi::contract::Contract;
+use crate::abi::custom_error::CustomError;
+use crate::abi::event::{Event, EventParam};
+use crate::abi::function::Function;
+use crate::abi::non_empty_array::NonZeroLen;
+use crate::abi::param::Param;
+use crate::abi::param_type::ParamType;
+use crate::abi::{AbiError, AbiErrorKind, AbiResult};
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::*;
+
+const FUNCTION_MODIFIERS: [&str; 7] = [
+    "external",
+    "public",
+    "view",
+    "pure",
+    "payable",
+    "nonpayable",
+    "constant",
+];
+
+/// A parsed human-readable ABI fragment.
+#[derive(Clone, Debug)]
+pub enum Fragment {
+    Function(Function),
+    Event(Event),
+    Error(CustomError),
+}
+
+impl Fragment {
+    pub fn parse(s: &str) -> AbiResult<Fragment> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let fragment = parser.parse_fragment()?;
+        if let Some(token) = parser.peek() {
+            return AbiError::err(AbiErrorKind::Error_invalid_abi)
+                .with_context(|| format!("Unexpected '{token}' at the end of the fragment"));
+        }
+        Ok(fragment)
+    }
+}
+
+/// Parses a human-readable function fragment.
+/// The `function` keyword can be omitted, e.g. `transfer(address,uint256)`.
+pub fn parse_function(s: &str) -> AbiResult<Function> {
+    match Fragment::parse(s)? {
+        Fragment::Function(fun) => Ok(fun),
+        _ => AbiError::err(AbiErrorKind::Error_invalid_abi)
+            .with_context(|| format!("Expected a function fragment: {s}")),
+    }
+}
+
+/// Parses a human-readable contract ABI, i.e. a list of fragments.
+pub fn parse_contract<'a, I>(fragments: I) -> AbiResult<Contract>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut contract = Contract::default();
+    for fragment in fragments {
+        match Fragment::parse(fragment)? {
+            Fragment::Function(fun) => contract
+                .functions
+                .entry(fun.name.clone())
+                .or_default()
+                .push(fun),
+            Fragment::Event(event) => contract
+                .events
+                .entry(event.name.clone())
+                .or_default()
+                .push(event),
+            Fragment::Error(error) => contract
+                .errors
+                .entry(error.name.clone())
+                .or_default()
+                .push(error),
+        }
+    }
+    Ok(contract)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Token<'a> {
+    OpenParen,
+    CloseParen,
+    Comma,
+    /// A keyword, a name or a type with an optional array suffix, e.g. `uint256[2][]`.
+    /// Can also be an array suffix only if it follows a tuple, e.g. `[]` in `(uint256,bool)[]`.
+    Word(&'a str),
+}
+
+impl std::fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::OpenParen => write!(f, "("),
+            Token::CloseParen => write!(f, ")"),
+            Token::Comma => write!(f, ","),
+            Token::Word(word) => write!(f, "{word}"),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> AbiResult<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut word_start = None;
+
+    for (idx, ch) in s.char_indices() {
+        let token = match ch {
+            '(' => Some(Token::OpenParen),
+            ')' => Some(Token::CloseParen),
+            ',' => Some(Token::Comma),
+            ch if ch.is_whitespace() => None,
+            ch if ch.is_ascii_alphanumeric() || matches!(ch, '_' | '$' | '[' | ']') => {
+                word_start.get_or_insert(idx);
+                continue;
+            },
+            ch => {
+                return AbiError::err(AbiErrorKind::Error_invalid_abi)
+                    .with_context(|| format!("Unexpected '{ch}' character at {idx}"))
+            },
+        };
+
+        if let Some(start) = word_start.take() {
+            tokens.push(Token::Word(&s[start..idx]));
+        }
+        tokens.extend(token);
+    }
+
+    if let Some(start) = word_start {
+        tokens.push(Token::Word(&s[start..]));
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_fragment(&mut self) -> AbiResult<Fragment> {
+        let first = self.expect_word()?;
+        let (keyword, name) = match first {
+            "function" | "event" | "error" => (first, self.expect_word()?),
+            // The `function` keyword can be omitted.
+            name => ("function", name),
+        };
+        check_identifier(name)?;
+        let name = name.to_string();
+
+        match keyword {
+            "function" => self.parse_function_rest(name).map(Fragment::Function),
+            "event" => self.parse_event_rest(name).map(Fragment::Event),
+            _ => {
+                let inputs = self.parse_params()?;
+                Ok(Fragment::Error(CustomError { name, inputs }))
+            },
+        }
+    }
+
+    fn parse_function_rest(&mut self, name: String) -> AbiResult<Function> {
+        let inputs = self.parse_params()?;
+
+        let mut outputs = Vec::default();
+        while let Some(Token::Word(word)) = self.peek() {
+            self.pos += 1;
+            match word {
+                "returns" => outputs = self.parse_params()?,
+                // Visibility and state mutability do not affect the encoding.
+                modifier if FUNCTION_MODIFIERS.contains(&modifier) => (),
+                unexpected => {
+                    return AbiError::err(AbiErrorKind::Error_invalid_abi)
+                        .with_context(|| format!("Unexpected function modifier: {unexpected}"))
+                },
+            }
+        }
+
+        Ok(Function {
+            name,
+            inputs,
+            outputs,
+        })
+    }
+
+    fn parse_event_rest(&mut self, name: String) -> AbiResult<Event> {
+        self.expect(Token::OpenParen)?;
+        let mut inputs = Vec::default();
+        if !self.next_if(Token::CloseParen) {
+            loop {
+                let (param, indexed) = self.parse_param(true)?;
+                inputs.push(EventParam { param, indexed });
+                if !self.next_if(Token::Comma) {
+                    break;
+                }
+            }
+            self.expect(Token::CloseParen)?;
+        }
+
+        let anonymous = self.next_if(Token::Word("anonymous"));
+        Ok(Event {
+            name,
+            inputs,
+            anonymous,
+        })
+    }
+
+    /// Parses a parenthesized list of params: `(type [name], ...)`.
+    fn parse_params(&mut self) -> AbiResult<Vec<Param>> {
+        self.expect(Token::OpenParen)?;
+        let mut params = Vec::default();
+        if self.next_if(Token::CloseParen) {
+            return Ok(params);
+        }
+
+        loop {
+            let (param, _indexed) = self.parse_param(false)?;
+            params.push(param);
+            if !self.next_if(Token::Comma) {
+                break;
+            }
+        }
+        self.expect(Token::CloseParen)?;
+        Ok(params)
+    }
+
+    /// Parses a single param: `type [indexed] [memory|calldata|storage] [name]`.
+    /// Returns the param and whether it's marked as `indexed`.
+    fn parse_param(&mut self, allow_indexed: bool) -> AbiResult<(Param, bool)> {
+        let kind = self.parse_type()?;
+
+        let mut indexed = false;
+        let mut name = None;
+        while let Some(Token::Word(word)) = self.peek() {
+            self.pos += 1;
+            match word {
+                "indexed" if allow_indexed && !indexed && name.is_none() => indexed = true,
+                "memory" | "calldata" | "storage" if name.is_none() => (),
+                word if name.is_none() => {
+                    check_identifier(word)?;
+                    name = Some(word.to_string());
+                },
+                unexpected => {
+                    return AbiError::err(AbiErrorKind::Error_invalid_abi)
+                        .with_context(|| format!("Unexpected '{unexpected}' after a parameter"))
+                },
+            }
+        }
+
+        let param = Param {
+            name,
+            kind,
+            internal_type: None,
+        };
+        Ok((param, indexed))
+    }
+
+    /// Parses a type: `uint256[]`, `(uint256,address)[2]` or `tuple(uint256,address)[2]`.
+    fn parse_type(&mut self) -> AbiResult<ParamType> {
+        // `tuple` keyword followed by components is an alternative tuple syntax.
+        if self.peek() == Some(Token::Word("tuple"))
+            && self.tokens.get(self.pos + 1) == Some(&Token::OpenParen)
+        {
+            self.pos += 1;
+        }
+
+        if self.peek() != Some(Token::OpenParen) {
+            let type_str = self.expect_word()?;
+            let kind = ParamType::try_from_type_short(type_str)
+                .with_context(|| format!("Error parsing '{type_str}' type"))?;
+            // Also covers arrays of tuples, e.g. `tuple[]` or `tuple[2]`.
+            if kind.has_tuple_components() {
+                return AbiError::err(AbiErrorKind::Error_invalid_param_type).context(
+                    "Tuple components must be specified explicitly, e.g. `(uint256,bool)`",
+                );
+            }
+            return Ok(kind);
+        }
+
+        let params = self.parse_params()?;
+        if params.is_empty() {
+            return AbiError::err(AbiErrorKind::Error_invalid_param_type)
+                .context("Tuple must have at least one component");
+        }
+        let mut kind = ParamType::Tuple { params };
+
+        // Array suffix can follow a tuple type, e.g. `(uint256,bool)[2][]`.
+        if let Some(Token::Word(suffix)) = self.peek() {
+            if suffix.starts_with('[') {
+                self.pos += 1;
+                kind = wrap_into_arrays(kind, suffix)?;
+            }
+        }
+        Ok(kind)
+    }
+
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next_if(&mut self, expected: Token<'a>) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, expected: Token<'a>) -> AbiResult<()> {
+        match self.peek() {
+            Some(token) if token == expected => {
+                self.pos += 1;
+                Ok(())
+            },
+            Some(token) => AbiError::err(AbiErrorKind::Error_invalid_abi)
+                .with_context(|| format!("Expected '{expected}', found '{token}'")),
+            None => AbiError::err(AbiErrorKind::Error_invalid_abi)
+                .with_context(|| format!("Expected '{expected}', found the end of the fragment")),
+        }
+    }
+
+    fn expect_word(&mut self) -> AbiResult<&'a str> {
+        match self.peek() {
+            Some(Token::Word(word)) => {
+                self.pos += 1;
+                Ok(word)
+            },
+            Some(token) => AbiError::err(AbiErrorKind::Error_invalid_abi)
+                .with_context(|| format!("Expected a word, found '{token}'")),
+            None => AbiError::err(AbiErrorKind::Error_invalid_abi)
+                .context("Expected a word, found the end of the fragment"),
+        }
+    }
+}
+
+/// Wraps the given `kind` into arrays according to the `suffix`, e.g. `[2][]`.
+fn wrap_into_arrays(mut kind: ParamType, suffix: &str) -> AbiResult<ParamType> {
+    let mut remaining = suffix;
+    while !remaining.is_empty() {
+        let Some((len_str, rest)) = remaining
+            .strip_prefix('[')
+            .and_then(|inner| inner.split_once(']'))
+        else {
+            return AbiError::err(AbiErrorKind::Error_invalid_param_type)
+                .with_context(|| format!("Invalid array suffix: {suffix}"));
+        };
+
+        kind = if len_str.is_empty() {
+            ParamType::Array {
+                kind: Box::new(kind),
+            }
+        } else {
+            let len = usize::from_str(len_str)
+                .tw_err(|_| AbiErrorKind::Error_invalid_param_type)
+                .with_context(|| format!("Invalid fixed array length: {len_str}"))?;
+            ParamType::FixedArray {
+                kind: Box::new(kind),
+                len: NonZeroLen::new(len)?,
+            }
+        };
+        remaining = rest;
+    }
+    Ok(kind)
+}
+
+fn check_identifier(s: &str) -> AbiResult<()> {
+    let starts_with_digit = s.chars().next().map_or(true, |ch| ch.is_ascii_digit());
+    let valid_chars = s
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '$'));
+    if starts_with_digit || !valid_chars {
+        return AbiError::err(AbiErrorKind::Error_invalid_abi)
+            .with_context(|| format!("Invalid identifier: {s}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_function() {
+        let fun =
+            parse_function("function transfer(address to, uint256 amount) external returns (bool)")
+                .unwrap();
+        assert_eq!(fun.name, "transfer");
+        assert_eq!(fun.inputs[0].name.as_deref(), Some("to"));
+        assert_eq!(fun.inputs[1].kind, ParamType::u256());
+        assert_eq!(fun.signature(), "transfer(address,uint256):(bool)");
+
+        let fun = parse_function("transfer(address,uint256)").unwrap();
+        assert_eq!(fun.signature(), "transfer(address,uint256)");
+        assert!(fun.inputs.iter().all(|input| input.name.is_none()));
+    }
+
+    #[test]
+    fn test_parse_function_with_tuples() {
+        let fun = parse_function(
+            "function fill((address maker, uint256[] amounts)[2][] orders, tuple(bytes32,bool) flag, bytes calldata data) payable",
+        )
+        .unwrap();
+        assert_eq!(
+            fun.signature(),
+            "fill((address,uint256[])[2][],(bytes32,bool),bytes)"
+        );
+        let ParamType::Array { kind } = &fun.inputs[0].kind else {
+            panic!("Expected an array, found {:?}", fun.inputs[0].kind);
+        };
+        let ParamType::FixedArray { kind, len } = kind.as_ref() else {
+            panic!("Expected a fixed array, found {kind:?}");
+        };
+        assert_eq!(len.get(), 2);
+        let ParamType::Tuple { params } = kind.as_ref() else {
+            panic!("Expected a tuple, found {kind:?}");
+        };
+        assert_eq!(params[1].name.as_deref(), Some("amounts"));
+    }
+
+    #[test]
+    fn test_parse_event_and_error() {
+        let Fragment::Event(event) = Fragment::parse(
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        )
+        .unwrap() else {
+            panic!("Expected an event");
+        };
+        assert_eq!(event.signature(), "Transfer(address,address,uint256)");
+        let indexed: Vec<_> = event.inputs.iter().map(|input| input.indexed).collect();
+        assert_eq!(indexed, [true, true, false]);
+        assert!(!event.anonymous);
+
+        let Fragment::Error(error) =
+            Fragment::parse("error InsufficientBalance(uint256 available, uint256 required)")
+                .unwrap()
+        else {
+            panic!("Expected an error");
+        };
+        assert_eq!(error.signature(), "InsufficientBalance(uint256,uint256)");
+        assert_eq!(error.short_signature().to_vec(), [0xcf, 0x47, 0x91, 0x81]);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let invalid = [
+            "",
+            "function",
+            "function transfer(address to uint256 amount)",
+            "function transfer(address,uint256",
+            "function transfer(address indexed to)",
+            "function transfer(address,uint256) returns",
+            "function transfer(address,uint256) internal",
+            "function transfer(uint7)",
+            "function transfer(tuple)",
+            "function f(tuple[] x)",
+            "function f(tuple[2] x)",
+            "function transfer(()[])",
+            "function 1transfer()",
+            "function transfer(address) extra(",
+            "event Transfer(address indexed indexed from)",
+        ];
+        for fragment in invalid {
+            Fragment::parse(fragment).unwrap_err();
+        }
+    }
+}
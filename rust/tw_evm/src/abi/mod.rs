@@ -5,9 +5,12 @@
 use tw_coin_entry::error::prelude::*;
 
 pub mod contract;
+pub mod custom_error;
 pub mod decode;
 pub mod encode;
+pub mod event;
 pub mod function;
+pub mod human_readable;
 pub mod non_empty_array;
 pub mod param;
 pub mod param_token;
@@ -21,6 +21,10 @@ lazy_static! {
 pub struct Erc1155;
 
 impl Erc1155 {
+    pub fn contract() -> &'static Contract {
+        &ERC1155
+    }
+
     pub fn encode_safe_transfer_from(
         from: Address,
         to: Address,
@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::contract::Contract;
+use crate::abi::decode::decode_params;
+use crate::abi::non_empty_array::NonEmptyBytes;
+use crate::abi::token::Token;
+use crate::abi::AbiResult;
+use lazy_static::lazy_static;
+use tw_hash::{H256, H32};
+use tw_memory::Data;
+
+/// Generated via https://remix.ethereum.org
+/// https://github.com/OpenZeppelin/openzeppelin-contracts/blob/v4.9.2/contracts/interfaces/IERC1271.sol
+const ERC1271_ABI: &str = include_str!("resource/erc1271.abi.json");
+
+lazy_static! {
+    static ref ERC1271: Contract = serde_json::from_str(ERC1271_ABI).unwrap();
+}
+
+/// The value returned by `isValidSignature` if the signature is valid,
+/// i.e. `bytes4(keccak256("isValidSignature(bytes32,bytes)"))`.
+/// cbindgen:ignore
+pub const ERC1271_MAGIC_VALUE: H32 = H32::from_array([0x16, 0x26, 0xba, 0x7e]);
+
+pub struct Erc1271;
+
+impl Erc1271 {
+    pub fn encode_is_valid_signature(hash: H256, signature: Data) -> AbiResult<Data> {
+        let func = ERC1271.function("isValidSignature")?;
+        let hash = NonEmptyBytes::new(hash.to_vec()).expect("H256 must not be empty");
+        func.encode_input(&[Token::FixedBytes(hash), Token::Bytes(signature)])
+    }
+
+    /// Decodes the `isValidSignature` output and checks whether it's the magic value.
+    pub fn decode_is_valid_signature(output: &[u8]) -> AbiResult<bool> {
+        let func = ERC1271.function("isValidSignature")?;
+        let tokens = decode_params(&func.outputs, output)?;
+
+        let is_valid = match tokens.first().map(|token| &token.value) {
+            Some(Token::FixedBytes(magic_value)) => {
+                magic_value.as_slice() == ERC1271_MAGIC_VALUE.as_slice()
+            },
+            _ => false,
+        };
+        Ok(is_valid)
+    }
+}
@@ -21,6 +21,10 @@ lazy_static! {
 pub struct Erc20;
 
 impl Erc20 {
+    pub fn contract() -> &'static Contract {
+        &ERC20
+    }
+
     pub fn transfer(recipient: Address, amount: U256) -> AbiResult<Data> {
         let func = ERC20.function("transfer")?;
         func.encode_input(&[Token::Address(recipient), Token::u256(amount)])
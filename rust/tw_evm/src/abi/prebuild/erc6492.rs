@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::contract::Contract;
+use crate::abi::decode::decode_params;
+use crate::abi::non_empty_array::NonEmptyBytes;
+use crate::abi::token::Token;
+use crate::abi::AbiResult;
+use crate::address::Address;
+use lazy_static::lazy_static;
+use tw_hash::H256;
+use tw_memory::Data;
+
+/// Generated via https://remix.ethereum.org
+/// `UniversalSigValidator` of https://eips.ethereum.org/EIPS/eip-6492#reference-implementation
+const ERC6492_ABI: &str = include_str!("resource/erc6492.abi.json");
+
+lazy_static! {
+    static ref ERC6492: Contract = serde_json::from_str(ERC6492_ABI).unwrap();
+}
+
+pub struct Erc6492;
+
+impl Erc6492 {
+    /// Encodes the `isValidSig` call of the `UniversalSigValidator` contract.
+    /// `signature` is passed as is, i.e. wrapped according to ERC-6492 if the account is not deployed yet.
+    pub fn encode_is_valid_sig(signer: Address, hash: H256, signature: Data) -> AbiResult<Data> {
+        let func = ERC6492.function("isValidSig")?;
+        let hash = NonEmptyBytes::new(hash.to_vec()).expect("H256 must not be empty");
+        func.encode_input(&[
+            Token::Address(signer),
+            Token::FixedBytes(hash),
+            Token::Bytes(signature),
+        ])
+    }
+
+    /// Decodes the `isValidSig` output.
+    pub fn decode_is_valid_sig(output: &[u8]) -> AbiResult<bool> {
+        let func = ERC6492.function("isValidSig")?;
+        let tokens = decode_params(&func.outputs, output)?;
+
+        let is_valid = matches!(
+            tokens.first().map(|token| &token.value),
+            Some(Token::Bool(true))
+        );
+        Ok(is_valid)
+    }
+}
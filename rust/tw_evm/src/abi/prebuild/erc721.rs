@@ -21,6 +21,10 @@ lazy_static! {
 pub struct Erc721;
 
 impl Erc721 {
+    pub fn contract() -> &'static Contract {
+        &ERC721
+    }
+
     pub fn encode_transfer_from(from: Address, to: Address, token_id: U256) -> AbiResult<Data> {
         let func = ERC721.function("transferFrom")?;
         func.encode_input(&[
@@ -3,6 +3,45 @@
 // Copyright © 2017 Trust Wallet.
 
 pub mod erc1155;
+pub mod erc1271;
 pub mod erc20;
 pub mod erc4337;
+pub mod erc6492;
 pub mod erc721;
+pub mod safe;
+
+use crate::abi::function::Function;
+use erc1155::Erc1155;
+use erc20::Erc20;
+use erc721::Erc721;
+
+/// Token standard of a prebuilt contract ABI.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContractStandard {
+    Erc20,
+    Erc721,
+    Erc1155,
+}
+
+/// Finds a function of the prebuilt ERC-20, ERC-721 or ERC-1155 ABIs by its short signature.
+/// Returns `None` standard if the function is declared by several standards,
+/// e.g. `approve` and `transferFrom` selectors of ERC-20 and ERC-721 collide.
+pub fn find_token_function(
+    short_signature: &[u8],
+) -> Option<(Option<ContractStandard>, &'static Function)> {
+    let mut found = [
+        (ContractStandard::Erc20, Erc20::contract()),
+        (ContractStandard::Erc721, Erc721::contract()),
+        (ContractStandard::Erc1155, Erc1155::contract()),
+    ]
+    .into_iter()
+    .filter_map(|(standard, contract)| {
+        contract
+            .function_by_short_signature(short_signature)
+            .map(|fun| (standard, fun))
+    });
+
+    let (standard, function) = found.next()?;
+    let is_ambiguous = found.next().is_some();
+    Some(((!is_ambiguous).then_some(standard), function))
+}
@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::contract::Contract;
+use crate::abi::non_empty_array::NonEmptyBytes;
+use crate::abi::token::Token;
+use crate::abi::uint::UintBits;
+use crate::abi::AbiResult;
+use crate::address::Address;
+use lazy_static::lazy_static;
+use tw_hash::H256;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// Generated via https://remix.ethereum.org
+/// https://github.com/safe-global/safe-smart-account/blob/v1.4.1/contracts/Safe.sol
+const SAFE_ABI: &str = include_str!("resource/safe.abi.json");
+/// Generated via https://remix.ethereum.org
+/// https://github.com/safe-global/safe-smart-account/blob/v1.4.1/contracts/libraries/MultiSend.sol
+const SAFE_MULTI_SEND_ABI: &str = include_str!("resource/safe.multi_send.abi.json");
+
+lazy_static! {
+    static ref SAFE: Contract = serde_json::from_str(SAFE_ABI).unwrap();
+    static ref SAFE_MULTI_SEND: Contract = serde_json::from_str(SAFE_MULTI_SEND_ABI).unwrap();
+}
+
+/// `Enum.Operation` of the Safe contracts.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[repr(u8)]
+pub enum SafeOperation {
+    #[default]
+    Call = 0,
+    /// The call is executed in the context of the Safe.
+    /// Usually used to call `MultiSend`.
+    DelegateCall = 1,
+}
+
+impl SafeOperation {
+    pub fn to_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+pub struct ExecTransactionArgs {
+    pub to: Address,
+    pub value: U256,
+    pub data: Data,
+    pub operation: SafeOperation,
+    pub safe_tx_gas: U256,
+    pub base_gas: U256,
+    pub gas_price: U256,
+    pub gas_token: Address,
+    pub refund_receiver: Address,
+    /// Owner signatures sorted by the owner addresses.
+    pub signatures: Data,
+}
+
+/// A call batched by `MultiSend`.
+pub struct MultiSendArgs {
+    pub operation: SafeOperation,
+    pub to: Address,
+    pub value: U256,
+    pub data: Data,
+}
+
+impl MultiSendArgs {
+    /// Encodes the call as
+    /// `operation (1 byte) || to (20 bytes) || value (32 bytes) || data length (32 bytes) || data`.
+    pub fn encode_packed(&self) -> Data {
+        let mut packed = Vec::with_capacity(1 + Address::LEN + 2 * U256::BYTES + self.data.len());
+        packed.push(self.operation.to_u8());
+        packed.extend_from_slice(self.to.as_slice());
+        packed.extend_from_slice(self.value.to_big_endian().as_slice());
+        packed.extend_from_slice(U256::from(self.data.len()).to_big_endian().as_slice());
+        packed.extend_from_slice(&self.data);
+        packed
+    }
+}
+
+pub struct Safe;
+
+impl Safe {
+    pub fn contract() -> &'static Contract {
+        &SAFE
+    }
+
+    pub fn encode_exec_transaction(args: ExecTransactionArgs) -> AbiResult<Data> {
+        let func = SAFE.function("execTransaction")?;
+        func.encode_input(&[
+            Token::Address(args.to),
+            Token::u256(args.value),
+            Token::Bytes(args.data),
+            Token::Uint {
+                uint: U256::from(args.operation.to_u8()),
+                bits: UintBits::new(8)?,
+            },
+            Token::u256(args.safe_tx_gas),
+            Token::u256(args.base_gas),
+            Token::u256(args.gas_price),
+            Token::Address(args.gas_token),
+            Token::Address(args.refund_receiver),
+            Token::Bytes(args.signatures),
+        ])
+    }
+
+    /// Approves the given Safe transaction hash on behalf of the caller,
+    /// so the caller's signature can be passed as pre-validated.
+    pub fn encode_approve_hash(safe_tx_hash: H256) -> AbiResult<Data> {
+        let func = SAFE.function("approveHash")?;
+        let hash = NonEmptyBytes::new(safe_tx_hash.to_vec()).expect("H256 must not be empty");
+        func.encode_input(&[Token::FixedBytes(hash)])
+    }
+}
+
+pub struct SafeMultiSend;
+
+impl SafeMultiSend {
+    /// Encodes a `multiSend` call that executes the given calls in a single transaction.
+    /// Please note the Safe transaction calling `MultiSend` must use [`SafeOperation::DelegateCall`].
+    pub fn encode_multi_send<I>(args: I) -> AbiResult<Data>
+    where
+        I: IntoIterator<Item = MultiSendArgs>,
+    {
+        let func = SAFE_MULTI_SEND.function("multiSend")?;
+
+        let transactions: Data = args
+            .into_iter()
+            .flat_map(|call| call.encode_packed())
+            .collect();
+        func.encode_input(&[Token::Bytes(transactions)])
+    }
+}
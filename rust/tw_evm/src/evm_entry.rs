@@ -5,8 +5,10 @@
 use crate::abi::AbiResult;
 use crate::evm_context::EvmContext;
 use crate::modules::abi_encoder::AbiEncoder;
+use crate::modules::message_signer::EthMessageSigner;
 use crate::modules::rlp_encoder::RlpEncoder;
 use tw_memory::Data;
+use tw_proto::Ethereum::Proto as EthProto;
 use tw_proto::EthereumAbi::Proto as AbiProto;
 use tw_proto::EthereumRlp::Proto as RlpProto;
 use tw_proto::{deserialize, serialize, ProtoResult};
@@ -64,6 +66,30 @@ pub trait EvmEntry {
     ) -> AbiProto::FunctionEncodingOutput<'static> {
         AbiEncoder::<Self::Context>::encode_contract_call(input)
     }
+
+    /// Decodes an event log according to a given contract ABI.
+    #[inline]
+    fn decode_abi_event_log(
+        input: AbiProto::EventLogDecodingInput<'_>,
+    ) -> AbiProto::EventLogDecodingOutput<'static> {
+        AbiEncoder::<Self::Context>::decode_event_log(input)
+    }
+
+    /// Prepares the call to verify a smart contract account signature (ERC-1271 or ERC-6492).
+    #[inline]
+    fn prepare_contract_signature_verification(
+        input: EthProto::ContractSignatureVerifyingInput<'_>,
+    ) -> EthProto::ContractSignatureVerifyingOutput<'static> {
+        EthMessageSigner::prepare_contract_signature_verification(input)
+    }
+
+    /// Decodes the result of the smart contract account signature verification call.
+    #[inline]
+    fn decode_contract_signature_result(
+        input: EthProto::ContractSignatureResultInput<'_>,
+    ) -> EthProto::ContractSignatureResultOutput<'static> {
+        EthMessageSigner::decode_contract_signature_result(input)
+    }
 }
 
 /// The [`EvmEntry`] trait extension.
@@ -88,6 +114,15 @@ pub trait EvmEntryExt {
 
     /// Decodes an Eth ABI value according to a given type.
     fn decode_abi_value(&self, input: &[u8]) -> ProtoResult<Data>;
+
+    /// Decodes an event log according to a given contract ABI.
+    fn decode_abi_event_log(&self, input: &[u8]) -> ProtoResult<Data>;
+
+    /// Prepares the call to verify a smart contract account signature (ERC-1271 or ERC-6492).
+    fn prepare_contract_signature_verification(&self, input: &[u8]) -> ProtoResult<Data>;
+
+    /// Decodes the result of the smart contract account signature verification call.
+    fn decode_contract_signature_result(&self, input: &[u8]) -> ProtoResult<Data>;
 }
 
 impl<T> EvmEntryExt for T
@@ -132,4 +167,22 @@ where
         let output = <Self as EvmEntry>::decode_abi_value(input);
         serialize(&output)
     }
+
+    fn decode_abi_event_log(&self, input: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::decode_abi_event_log(input);
+        serialize(&output)
+    }
+
+    fn prepare_contract_signature_verification(&self, input: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::prepare_contract_signature_verification(input);
+        serialize(&output)
+    }
+
+    fn decode_contract_signature_result(&self, input: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::decode_contract_signature_result(input);
+        serialize(&output)
+    }
 }
@@ -71,23 +71,34 @@ impl Eip712Message {
 
         Ok(msg)
     }
-}
 
-impl EthMessage for Eip712Message {
-    fn hash(&self) -> MessageSigningResult<H256> {
+    /// Returns the hash of the `EIP712Domain` struct, i.e. the domain separator.
+    pub fn domain_separator(&self) -> MessageSigningResult<H256> {
         let domain_hash = encode_data(
             &self.types,
             PropertyType::Custom(EIP712_DOMAIN.to_string()),
             &self.domain,
         )
         .context("Error encoding EIP712Domain")?;
+        Ok(H256::try_from(domain_hash.as_slice()).expect("Expected 32-byte hash"))
+    }
 
+    /// Returns the hash of the primary type struct.
+    pub fn struct_hash(&self) -> MessageSigningResult<H256> {
         let primary_data_hash = encode_data(
             &self.types,
             PropertyType::Custom(self.primary_type.clone()),
             &self.message,
         )
         .context("Error encoding primary type")?;
+        Ok(H256::try_from(primary_data_hash.as_slice()).expect("Expected 32-byte hash"))
+    }
+}
+
+impl EthMessage for Eip712Message {
+    fn hash(&self) -> MessageSigningResult<H256> {
+        let domain_hash = self.domain_separator()?;
+        let primary_data_hash = self.struct_hash()?;
 
         let concat = [
             PREFIX.as_slice(),
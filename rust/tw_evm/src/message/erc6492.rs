@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! ERC-6492 signatures of smart contract accounts that are not deployed yet.
+//! https://eips.ethereum.org/EIPS/eip-6492
+
+use crate::abi::decode::decode_params;
+use crate::abi::encode::encode_tokens;
+use crate::abi::param::Param;
+use crate::abi::param_type::ParamType;
+use crate::abi::token::Token;
+use crate::abi::{AbiError, AbiErrorKind, AbiResult};
+use crate::address::Address;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_memory::Data;
+
+/// The suffix that distinguishes ERC-6492 wrapped signatures.
+/// cbindgen:ignore
+pub const ERC6492_MAGIC_SUFFIX: &str =
+    "6492649264926492649264926492649264926492649264926492649264926492";
+
+/// A signature wrapped as `abi.encode(factory, factoryCalldata, signature) || magicSuffix`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Erc6492Signature {
+    /// Factory contract address that deploys the smart contract account.
+    pub factory: Address,
+    /// Call data of the factory call that deploys the smart contract account.
+    pub factory_calldata: Data,
+    /// The signature to be verified by the account once it's deployed.
+    pub signature: Data,
+}
+
+impl Erc6492Signature {
+    /// Checks whether the given signature is wrapped according to ERC-6492.
+    pub fn is_wrapped(signature: &[u8]) -> bool {
+        signature.ends_with(Self::magic_suffix().as_slice())
+    }
+
+    pub fn magic_suffix() -> H256 {
+        H256::from(ERC6492_MAGIC_SUFFIX)
+    }
+
+    /// Parses the wrapped signature.
+    pub fn decode(wrapped: &[u8]) -> AbiResult<Erc6492Signature> {
+        let encoded = wrapped
+            .strip_suffix(Self::magic_suffix().as_slice())
+            .or_tw_err(AbiErrorKind::Error_decoding_data)
+            .context("The signature is not wrapped according to ERC-6492")?;
+
+        let params = [
+            named_param("factory", ParamType::Address),
+            named_param("factoryCalldata", ParamType::Bytes),
+            named_param("signature", ParamType::Bytes),
+        ];
+        let mut tokens = decode_params(&params, encoded)?
+            .into_iter()
+            .map(|token| token.value);
+
+        match (tokens.next(), tokens.next(), tokens.next()) {
+            (
+                Some(Token::Address(factory)),
+                Some(Token::Bytes(factory_calldata)),
+                Some(Token::Bytes(signature)),
+            ) => Ok(Erc6492Signature {
+                factory,
+                factory_calldata,
+                signature,
+            }),
+            _ => AbiError::err(AbiErrorKind::Error_internal)
+                .context("Unexpected tokens decoded from ERC-6492 signature"),
+        }
+    }
+
+    /// Encodes the wrapped signature.
+    pub fn encode(&self) -> Data {
+        let mut wrapped = encode_tokens(&[
+            Token::Address(self.factory),
+            Token::Bytes(self.factory_calldata.clone()),
+            Token::Bytes(self.signature.clone()),
+        ]);
+        wrapped.extend_from_slice(Self::magic_suffix().as_slice());
+        wrapped
+    }
+}
+
+fn named_param(name: &str, kind: ParamType) -> Param {
+    Param {
+        name: Some(name.to_string()),
+        kind,
+        internal_type: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::DecodeHex;
+
+    #[test]
+    fn test_erc6492_signature_encode_decode() {
+        let signature = Erc6492Signature {
+            factory: Address::from("0xf471789937856D80e589F5996cf8b0511DDD9de4"),
+            factory_calldata: "5fbfb9cf".decode_hex().unwrap(),
+            signature: vec![1; 65],
+        };
+
+        let wrapped = signature.encode();
+        assert!(Erc6492Signature::is_wrapped(&wrapped));
+        assert_eq!(Erc6492Signature::decode(&wrapped).unwrap(), signature);
+
+        // Regular 65-byte signature is not wrapped.
+        assert!(!Erc6492Signature::is_wrapped(&signature.signature));
+        Erc6492Signature::decode(&signature.signature).unwrap_err();
+    }
+}
@@ -7,6 +7,9 @@ use tw_hash::H256;
 
 pub mod eip191;
 pub mod eip712;
+pub mod erc6492;
+pub mod permit;
+pub mod safe;
 pub mod signature;
 
 pub type EthMessageBoxed = Box<dyn EthMessage>;
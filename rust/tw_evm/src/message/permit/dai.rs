@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::message::eip712::eip712_message::Eip712Message;
+use crate::message::eip712::message_types::MessageTypesBuilder;
+use crate::message::eip712::property::PropertyType;
+use crate::message::permit::{build_eip712_message, PermitDomain};
+use crate::message::{EthMessage, MessageSigningResult};
+use serde_json::json;
+use tw_hash::H256;
+use tw_number::U256;
+
+/// DAI-style `Permit` that either allows an unlimited allowance or revokes it.
+/// https://github.com/makerdao/dss/blob/fa4f6630afb0624d04a003e920b0d71a00331d98/src/dai.sol#L117
+///
+/// # EIP712 type
+///
+/// ```json
+/// {
+///     "Permit": [
+///         { "name": "holder", "type": "address" },
+///         { "name": "spender", "type": "address" },
+///         { "name": "nonce", "type": "uint256" },
+///         { "name": "expiry", "type": "uint256" },
+///         { "name": "allowed", "type": "bool" }
+///     ]
+/// }
+/// ```
+pub struct DaiPermit {
+    pub domain: PermitDomain,
+    pub holder: Address,
+    pub spender: Address,
+    pub nonce: U256,
+    pub expiry: U256,
+    pub allowed: bool,
+}
+
+impl DaiPermit {
+    const TYPE_NAME: &'static str = "Permit";
+
+    pub fn to_eip712_message(&self) -> MessageSigningResult<Eip712Message> {
+        let mut types_builder = MessageTypesBuilder::default();
+        if let Some(mut permit_builder) = types_builder.add_custom_type(Self::TYPE_NAME.to_string())
+        {
+            permit_builder
+                .add_property("holder", PropertyType::Address)
+                .add_property("spender", PropertyType::Address)
+                .add_property("nonce", PropertyType::Uint)
+                .add_property("expiry", PropertyType::Uint)
+                .add_property("allowed", PropertyType::Bool);
+        }
+
+        let message = json!({
+            "holder": self.holder,
+            "spender": self.spender,
+            "nonce": self.nonce.to_string(),
+            "expiry": self.expiry.to_string(),
+            "allowed": self.allowed,
+        });
+        build_eip712_message(types_builder, &self.domain, Self::TYPE_NAME, message)
+    }
+}
+
+impl EthMessage for DaiPermit {
+    fn hash(&self) -> MessageSigningResult<H256> {
+        self.to_eip712_message()?.hash()
+    }
+}
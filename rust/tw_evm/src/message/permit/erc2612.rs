@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::message::eip712::eip712_message::Eip712Message;
+use crate::message::eip712::message_types::MessageTypesBuilder;
+use crate::message::eip712::property::PropertyType;
+use crate::message::permit::{build_eip712_message, PermitDomain};
+use crate::message::{EthMessage, MessageSigningResult};
+use serde_json::json;
+use tw_hash::H256;
+use tw_number::U256;
+
+/// EIP-2612 `Permit` of an ERC-20 token.
+/// https://eips.ethereum.org/EIPS/eip-2612
+///
+/// # EIP712 type
+///
+/// ```json
+/// {
+///     "Permit": [
+///         { "name": "owner", "type": "address" },
+///         { "name": "spender", "type": "address" },
+///         { "name": "value", "type": "uint256" },
+///         { "name": "nonce", "type": "uint256" },
+///         { "name": "deadline", "type": "uint256" }
+///     ]
+/// }
+/// ```
+pub struct Erc2612Permit {
+    pub domain: PermitDomain,
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    pub nonce: U256,
+    pub deadline: U256,
+}
+
+impl Erc2612Permit {
+    const TYPE_NAME: &'static str = "Permit";
+
+    pub fn to_eip712_message(&self) -> MessageSigningResult<Eip712Message> {
+        let mut types_builder = MessageTypesBuilder::default();
+        if let Some(mut permit_builder) = types_builder.add_custom_type(Self::TYPE_NAME.to_string())
+        {
+            permit_builder
+                .add_property("owner", PropertyType::Address)
+                .add_property("spender", PropertyType::Address)
+                .add_property("value", PropertyType::Uint)
+                .add_property("nonce", PropertyType::Uint)
+                .add_property("deadline", PropertyType::Uint);
+        }
+
+        let message = json!({
+            "owner": self.owner,
+            "spender": self.spender,
+            "value": self.value.to_string(),
+            "nonce": self.nonce.to_string(),
+            "deadline": self.deadline.to_string(),
+        });
+        build_eip712_message(types_builder, &self.domain, Self::TYPE_NAME, message)
+    }
+}
+
+impl EthMessage for Erc2612Permit {
+    fn hash(&self) -> MessageSigningResult<H256> {
+        self.to_eip712_message()?.hash()
+    }
+}
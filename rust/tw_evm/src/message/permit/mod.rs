@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! EIP-712 messages used to approve token spending with a signature instead of an `approve` transaction.
+
+use crate::address::Address;
+use crate::message::eip712::eip712_message::Eip712Message;
+use crate::message::eip712::message_types::MessageTypesBuilder;
+use crate::message::eip712::property::PropertyType;
+use crate::message::{MessageSigningErrorKind, MessageSigningResult};
+use serde::Serialize;
+use serde_json::Value as Json;
+use tw_coin_entry::error::prelude::*;
+use tw_number::U256;
+
+pub mod dai;
+pub mod erc2612;
+pub mod permit2;
+
+/// # EIP712 type
+///
+/// ```json
+/// {
+///     "EIP712Domain": [
+///         { "name": "name", "type": "string" },
+///         { "name": "version", "type": "string" },
+///         { "name": "chainId", "type": "uint256" },
+///         { "name": "verifyingContract", "type": "address" }
+///     ]
+/// }
+/// ```
+///
+/// `version` is omitted if not set, e.g. in the Permit2 domain.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermitDomain {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(serialize_with = "U256::as_decimal_str")]
+    pub chain_id: U256,
+    pub verifying_contract: Address,
+}
+
+impl PermitDomain {
+    const TYPE_NAME: &'static str = "EIP712Domain";
+
+    pub fn declare_eip712_types(&self, builder: &mut MessageTypesBuilder) {
+        if let Some(mut domain_builder) = builder.add_custom_type(Self::TYPE_NAME.to_string()) {
+            domain_builder.add_property("name", PropertyType::String);
+            if self.version.is_some() {
+                domain_builder.add_property("version", PropertyType::String);
+            }
+            domain_builder
+                .add_property("chainId", PropertyType::Uint)
+                .add_property("verifyingContract", PropertyType::Address);
+        }
+    }
+}
+
+/// Generates an EIP712 message with the given custom types, `domain` and the primary type `message`.
+fn build_eip712_message(
+    mut types_builder: MessageTypesBuilder,
+    domain: &PermitDomain,
+    primary_type: &str,
+    message: Json,
+) -> MessageSigningResult<Eip712Message> {
+    domain.declare_eip712_types(&mut types_builder);
+
+    Ok(Eip712Message {
+        types: types_builder.build(),
+        domain: serde_json::to_value(domain)
+            .tw_err(|_| MessageSigningErrorKind::Internal)
+            .context("Error serializing EIP712Domain as JSON")?,
+        primary_type: primary_type.to_string(),
+        message,
+    })
+}
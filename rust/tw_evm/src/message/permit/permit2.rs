@@ -0,0 +1,278 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Uniswap Permit2 messages.
+//! https://github.com/Uniswap/permit2
+
+use crate::address::Address;
+use crate::message::eip712::eip712_message::Eip712Message;
+use crate::message::eip712::message_types::MessageTypesBuilder;
+use crate::message::eip712::property::PropertyType;
+use crate::message::permit::{build_eip712_message, PermitDomain};
+use crate::message::{
+    EthMessage, MessageSigningError, MessageSigningErrorKind, MessageSigningResult,
+};
+use serde_json::{json, Value as Json};
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_number::U256;
+
+/// The canonical Permit2 contract address, the same on all supported chains.
+pub const PERMIT2_ADDRESS: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+
+const PERMIT2_DOMAIN_NAME: &str = "Permit2";
+const UINT160_BITS: usize = 160;
+const UINT48_BITS: usize = 48;
+
+/// Returns the Permit2 domain. Please note it doesn't have a version.
+pub fn permit2_domain(chain_id: U256, permit2_contract: Address) -> PermitDomain {
+    PermitDomain {
+        name: PERMIT2_DOMAIN_NAME.to_string(),
+        version: None,
+        chain_id,
+        verifying_contract: permit2_contract,
+    }
+}
+
+/// # EIP712 type
+///
+/// ```json
+/// {
+///     "PermitDetails": [
+///         { "name": "token", "type": "address" },
+///         { "name": "amount", "type": "uint160" },
+///         { "name": "expiration", "type": "uint48" },
+///         { "name": "nonce", "type": "uint48" }
+///     ]
+/// }
+/// ```
+pub struct PermitDetails {
+    pub token: Address,
+    /// Must fit `uint160`.
+    pub amount: U256,
+    /// Must fit `uint48`.
+    pub expiration: u64,
+    /// Must fit `uint48`.
+    pub nonce: u64,
+}
+
+impl PermitDetails {
+    const TYPE_NAME: &'static str = "PermitDetails";
+
+    fn declare_eip712_types(builder: &mut MessageTypesBuilder) {
+        if let Some(mut details_builder) = builder.add_custom_type(Self::TYPE_NAME.to_string()) {
+            details_builder
+                .add_property("token", PropertyType::Address)
+                .add_uint_property("amount", UINT160_BITS)
+                .add_uint_property("expiration", UINT48_BITS)
+                .add_uint_property("nonce", UINT48_BITS);
+        }
+    }
+
+    fn to_json(&self) -> MessageSigningResult<Json> {
+        check_bits(self.amount, UINT160_BITS).context("Invalid 'amount'")?;
+        check_bits(U256::from(self.expiration), UINT48_BITS).context("Invalid 'expiration'")?;
+        check_bits(U256::from(self.nonce), UINT48_BITS).context("Invalid 'nonce'")?;
+
+        Ok(json!({
+            "token": self.token,
+            "amount": self.amount.to_string(),
+            "expiration": self.expiration.to_string(),
+            "nonce": self.nonce.to_string(),
+        }))
+    }
+}
+
+/// Allowance-based `PermitSingle` of the `AllowanceTransfer` contract.
+///
+/// # EIP712 type
+///
+/// ```json
+/// {
+///     "PermitSingle": [
+///         { "name": "details", "type": "PermitDetails" },
+///         { "name": "spender", "type": "address" },
+///         { "name": "sigDeadline", "type": "uint256" }
+///     ]
+/// }
+/// ```
+pub struct PermitSingle {
+    pub domain: PermitDomain,
+    pub details: PermitDetails,
+    pub spender: Address,
+    pub sig_deadline: U256,
+}
+
+impl PermitSingle {
+    const TYPE_NAME: &'static str = "PermitSingle";
+
+    pub fn to_eip712_message(&self) -> MessageSigningResult<Eip712Message> {
+        let mut types_builder = MessageTypesBuilder::default();
+        PermitDetails::declare_eip712_types(&mut types_builder);
+        if let Some(mut permit_builder) = types_builder.add_custom_type(Self::TYPE_NAME.to_string())
+        {
+            permit_builder
+                .add_property(
+                    "details",
+                    PropertyType::Custom(PermitDetails::TYPE_NAME.to_string()),
+                )
+                .add_property("spender", PropertyType::Address)
+                .add_property("sigDeadline", PropertyType::Uint);
+        }
+
+        let message = json!({
+            "details": self.details.to_json()?,
+            "spender": self.spender,
+            "sigDeadline": self.sig_deadline.to_string(),
+        });
+        build_eip712_message(types_builder, &self.domain, Self::TYPE_NAME, message)
+    }
+}
+
+impl EthMessage for PermitSingle {
+    fn hash(&self) -> MessageSigningResult<H256> {
+        self.to_eip712_message()?.hash()
+    }
+}
+
+/// Allowance-based `PermitBatch` of the `AllowanceTransfer` contract.
+///
+/// # EIP712 type
+///
+/// ```json
+/// {
+///     "PermitBatch": [
+///         { "name": "details", "type": "PermitDetails[]" },
+///         { "name": "spender", "type": "address" },
+///         { "name": "sigDeadline", "type": "uint256" }
+///     ]
+/// }
+/// ```
+pub struct PermitBatch {
+    pub domain: PermitDomain,
+    pub details: Vec<PermitDetails>,
+    pub spender: Address,
+    pub sig_deadline: U256,
+}
+
+impl PermitBatch {
+    const TYPE_NAME: &'static str = "PermitBatch";
+
+    pub fn to_eip712_message(&self) -> MessageSigningResult<Eip712Message> {
+        let mut types_builder = MessageTypesBuilder::default();
+        PermitDetails::declare_eip712_types(&mut types_builder);
+        if let Some(mut permit_builder) = types_builder.add_custom_type(Self::TYPE_NAME.to_string())
+        {
+            let details_type = PropertyType::Custom(PermitDetails::TYPE_NAME.to_string());
+            permit_builder
+                .add_property("details", PropertyType::Array(Box::new(details_type)))
+                .add_property("spender", PropertyType::Address)
+                .add_property("sigDeadline", PropertyType::Uint);
+        }
+
+        let details = self
+            .details
+            .iter()
+            .enumerate()
+            .map(|(idx, details)| {
+                details
+                    .to_json()
+                    .with_context(|| format!("Invalid permit details at {idx}"))
+            })
+            .collect::<MessageSigningResult<Vec<_>>>()?;
+
+        let message = json!({
+            "details": details,
+            "spender": self.spender,
+            "sigDeadline": self.sig_deadline.to_string(),
+        });
+        build_eip712_message(types_builder, &self.domain, Self::TYPE_NAME, message)
+    }
+}
+
+impl EthMessage for PermitBatch {
+    fn hash(&self) -> MessageSigningResult<H256> {
+        self.to_eip712_message()?.hash()
+    }
+}
+
+/// Signature-based `PermitTransferFrom` of the `SignatureTransfer` contract.
+/// `spender` is the address allowed to call `permitTransferFrom`.
+///
+/// # EIP712 type
+///
+/// ```json
+/// {
+///     "TokenPermissions": [
+///         { "name": "token", "type": "address" },
+///         { "name": "amount", "type": "uint256" }
+///     ],
+///     "PermitTransferFrom": [
+///         { "name": "permitted", "type": "TokenPermissions" },
+///         { "name": "spender", "type": "address" },
+///         { "name": "nonce", "type": "uint256" },
+///         { "name": "deadline", "type": "uint256" }
+///     ]
+/// }
+/// ```
+pub struct PermitTransferFrom {
+    pub domain: PermitDomain,
+    pub token: Address,
+    pub amount: U256,
+    pub spender: Address,
+    pub nonce: U256,
+    pub deadline: U256,
+}
+
+impl PermitTransferFrom {
+    const TYPE_NAME: &'static str = "PermitTransferFrom";
+    const TOKEN_PERMISSIONS_TYPE_NAME: &'static str = "TokenPermissions";
+
+    pub fn to_eip712_message(&self) -> MessageSigningResult<Eip712Message> {
+        let mut types_builder = MessageTypesBuilder::default();
+        if let Some(mut permissions_builder) =
+            types_builder.add_custom_type(Self::TOKEN_PERMISSIONS_TYPE_NAME.to_string())
+        {
+            permissions_builder
+                .add_property("token", PropertyType::Address)
+                .add_property("amount", PropertyType::Uint);
+        }
+        if let Some(mut permit_builder) = types_builder.add_custom_type(Self::TYPE_NAME.to_string())
+        {
+            permit_builder
+                .add_property(
+                    "permitted",
+                    PropertyType::Custom(Self::TOKEN_PERMISSIONS_TYPE_NAME.to_string()),
+                )
+                .add_property("spender", PropertyType::Address)
+                .add_property("nonce", PropertyType::Uint)
+                .add_property("deadline", PropertyType::Uint);
+        }
+
+        let message = json!({
+            "permitted": {
+                "token": self.token,
+                "amount": self.amount.to_string(),
+            },
+            "spender": self.spender,
+            "nonce": self.nonce.to_string(),
+            "deadline": self.deadline.to_string(),
+        });
+        build_eip712_message(types_builder, &self.domain, Self::TYPE_NAME, message)
+    }
+}
+
+impl EthMessage for PermitTransferFrom {
+    fn hash(&self) -> MessageSigningResult<H256> {
+        self.to_eip712_message()?.hash()
+    }
+}
+
+fn check_bits(value: U256, max_bits: usize) -> MessageSigningResult<()> {
+    if value.bits() > max_bits {
+        return MessageSigningError::err(MessageSigningErrorKind::InvalidParameterValue)
+            .with_context(|| format!("Value does not fit uint{max_bits}: {value}"));
+    }
+    Ok(())
+}
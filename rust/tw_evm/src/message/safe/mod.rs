@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Safe (formerly Gnosis Safe) multisig transactions.
+//! https://github.com/safe-global/safe-smart-account
+
+use crate::abi::prebuild::safe::{ExecTransactionArgs, SafeOperation};
+use crate::address::Address;
+use crate::message::eip712::eip712_message::Eip712Message;
+use crate::message::eip712::message_types::MessageTypesBuilder;
+use crate::message::eip712::property::PropertyType;
+use crate::message::{
+    EthMessage, MessageSigningError, MessageSigningErrorKind, MessageSigningResult,
+};
+use serde_json::json;
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::*;
+use tw_encoding::hex;
+use tw_hash::H256;
+use tw_memory::Data;
+use tw_number::U256;
+
+pub mod signature;
+
+const DOMAIN_TYPE_NAME: &str = "EIP712Domain";
+const SAFE_TX_TYPE_NAME: &str = "SafeTx";
+const UINT8_BITS: usize = 8;
+
+/// Safe contract versions that differ in the `SafeTx` typed data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SafeVersion {
+    /// Versions prior to v1.0.0.
+    /// `SafeTx` has `dataGas` instead of `baseGas`, the domain has no `chainId`.
+    V0_1_0,
+    /// v1.0.0 - v1.2.0. The domain has no `chainId`.
+    V1_0_0,
+    /// v1.3.0 and later.
+    V1_3_0,
+}
+
+impl SafeVersion {
+    fn has_chain_id(self) -> bool {
+        self == SafeVersion::V1_3_0
+    }
+
+    fn base_gas_name(self) -> &'static str {
+        match self {
+            SafeVersion::V0_1_0 => "dataGas",
+            SafeVersion::V1_0_0 | SafeVersion::V1_3_0 => "baseGas",
+        }
+    }
+}
+
+impl FromStr for SafeVersion {
+    type Err = MessageSigningError;
+
+    /// Parses the value returned by `Safe.VERSION()`, e.g. `1.3.0` or `1.3.0+L2`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let version = s.split('+').next().unwrap_or_default();
+        let mut numbers = version.split('.').map(u32::from_str);
+
+        match (numbers.next(), numbers.next()) {
+            (Some(Ok(0)), Some(Ok(_))) => Ok(SafeVersion::V0_1_0),
+            (Some(Ok(1)), Some(Ok(0..=2))) => Ok(SafeVersion::V1_0_0),
+            (Some(Ok(_)), Some(Ok(_))) => Ok(SafeVersion::V1_3_0),
+            _ => MessageSigningError::err(MessageSigningErrorKind::InvalidParameterValue)
+                .with_context(|| format!("Invalid Safe version: {s}")),
+        }
+    }
+}
+
+/// Safe transaction to be signed by the owners.
+///
+/// # EIP712 type
+///
+/// ```json
+/// {
+///     "EIP712Domain": [
+///         { "name": "chainId", "type": "uint256" },
+///         { "name": "verifyingContract", "type": "address" }
+///     ],
+///     "SafeTx": [
+///         { "name": "to", "type": "address" },
+///         { "name": "value", "type": "uint256" },
+///         { "name": "data", "type": "bytes" },
+///         { "name": "operation", "type": "uint8" },
+///         { "name": "safeTxGas", "type": "uint256" },
+///         { "name": "baseGas", "type": "uint256" },
+///         { "name": "gasPrice", "type": "uint256" },
+///         { "name": "gasToken", "type": "address" },
+///         { "name": "refundReceiver", "type": "address" },
+///         { "name": "nonce", "type": "uint256" }
+///     ]
+/// }
+/// ```
+///
+/// See [`SafeVersion`] for the differences between the Safe versions.
+pub struct SafeTx {
+    /// The Safe contract address.
+    pub safe: Address,
+    pub chain_id: U256,
+    pub version: SafeVersion,
+    pub to: Address,
+    pub value: U256,
+    pub data: Data,
+    pub operation: SafeOperation,
+    pub safe_tx_gas: U256,
+    /// Also known as `dataGas` prior to v1.0.0.
+    pub base_gas: U256,
+    pub gas_price: U256,
+    pub gas_token: Address,
+    pub refund_receiver: Address,
+    pub nonce: U256,
+}
+
+impl SafeTx {
+    pub fn to_eip712_message(&self) -> MessageSigningResult<Eip712Message> {
+        let mut types_builder = MessageTypesBuilder::default();
+        if let Some(mut domain_builder) =
+            types_builder.add_custom_type(DOMAIN_TYPE_NAME.to_string())
+        {
+            if self.version.has_chain_id() {
+                domain_builder.add_property("chainId", PropertyType::Uint);
+            }
+            domain_builder.add_property("verifyingContract", PropertyType::Address);
+        }
+        if let Some(mut tx_builder) = types_builder.add_custom_type(SAFE_TX_TYPE_NAME.to_string()) {
+            tx_builder
+                .add_property("to", PropertyType::Address)
+                .add_property("value", PropertyType::Uint)
+                .add_property("data", PropertyType::Bytes)
+                .add_uint_property("operation", UINT8_BITS)
+                .add_property("safeTxGas", PropertyType::Uint)
+                .add_property(self.version.base_gas_name(), PropertyType::Uint)
+                .add_property("gasPrice", PropertyType::Uint)
+                .add_property("gasToken", PropertyType::Address)
+                .add_property("refundReceiver", PropertyType::Address)
+                .add_property("nonce", PropertyType::Uint);
+        }
+
+        let domain = if self.version.has_chain_id() {
+            json!({
+                "chainId": self.chain_id.to_string(),
+                "verifyingContract": self.safe,
+            })
+        } else {
+            json!({ "verifyingContract": self.safe })
+        };
+
+        let message = json!({
+            "to": self.to,
+            "value": self.value.to_string(),
+            "data": hex::encode(&self.data, true),
+            "operation": self.operation.to_u8(),
+            "safeTxGas": self.safe_tx_gas.to_string(),
+            self.version.base_gas_name(): self.base_gas.to_string(),
+            "gasPrice": self.gas_price.to_string(),
+            "gasToken": self.gas_token,
+            "refundReceiver": self.refund_receiver,
+            "nonce": self.nonce.to_string(),
+        });
+
+        Ok(Eip712Message {
+            types: types_builder.build(),
+            domain,
+            primary_type: SAFE_TX_TYPE_NAME.to_string(),
+            message,
+        })
+    }
+
+    /// Returns `execTransaction` arguments with the given encoded owner signatures.
+    /// See [`signature::encode_signatures`].
+    pub fn exec_transaction_args(&self, signatures: Data) -> ExecTransactionArgs {
+        ExecTransactionArgs {
+            to: self.to,
+            value: self.value,
+            data: self.data.clone(),
+            operation: self.operation,
+            safe_tx_gas: self.safe_tx_gas,
+            base_gas: self.base_gas,
+            gas_price: self.gas_price,
+            gas_token: self.gas_token,
+            refund_receiver: self.refund_receiver,
+            signatures,
+        }
+    }
+}
+
+impl EthMessage for SafeTx {
+    /// Returns the `safeTxHash` as computed by `Safe.getTransactionHash`.
+    fn hash(&self) -> MessageSigningResult<H256> {
+        self.to_eip712_message()?.hash()
+    }
+}
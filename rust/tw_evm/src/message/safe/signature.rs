@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Owner signatures of a Safe transaction.
+//! https://docs.safe.global/advanced/smart-account-signatures
+
+use crate::address::Address;
+use crate::message::eip191::Eip191Message;
+use crate::message::{
+    EthMessage, MessageSigningError, MessageSigningErrorKind, MessageSigningResult,
+};
+use tw_coin_entry::error::prelude::*;
+use tw_encoding::hex;
+use tw_hash::{H256, H520};
+use tw_keypair::ecdsa::secp256k1;
+use tw_keypair::traits::SigningKeyTrait;
+use tw_keypair::KeyPairResult;
+use tw_memory::Data;
+
+/// `v` of ECDSA signatures is `27` or `28`.
+const ECDSA_V_OFFSET: u8 = 27;
+/// `v` of `eth_sign` signatures is increased by 4 to distinguish them from ECDSA ones.
+const ETH_SIGN_V_OFFSET: u8 = ECDSA_V_OFFSET + 4;
+/// `v` of pre-validated signatures.
+const PRE_VALIDATED_V: u8 = 1;
+
+/// A 65-byte `r || s || v` signature of a Safe owner.
+#[derive(Clone)]
+pub struct SafeSignature {
+    owner: Address,
+    signature: H520,
+}
+
+impl SafeSignature {
+    /// Signs the `safeTxHash` directly, e.g. as the EIP-712 typed data.
+    pub fn sign_ecdsa(
+        private_key: &secp256k1::PrivateKey,
+        safe_tx_hash: H256,
+    ) -> KeyPairResult<SafeSignature> {
+        let signature = private_key.sign(safe_tx_hash)?;
+        Ok(Self::with_ecdsa(private_key, &signature, ECDSA_V_OFFSET))
+    }
+
+    /// Signs the `safeTxHash` as an EIP-191 personal message, i.e. via `eth_sign`.
+    pub fn sign_eth_sign(
+        private_key: &secp256k1::PrivateKey,
+        safe_tx_hash: H256,
+    ) -> KeyPairResult<SafeSignature> {
+        let message = Eip191Message::new(hex::encode(safe_tx_hash.as_slice(), true));
+        let message_hash = message
+            .hash()
+            .expect("Hashing an EIP-191 message never fails");
+        let signature = private_key.sign(message_hash)?;
+        Ok(Self::with_ecdsa(private_key, &signature, ETH_SIGN_V_OFFSET))
+    }
+
+    /// Returns a signature of an owner that either has approved the hash via `approveHash`,
+    /// or sends the `execTransaction` transaction.
+    pub fn pre_validated(owner: Address) -> SafeSignature {
+        let mut signature = H520::default();
+        signature[12..32].copy_from_slice(owner.as_slice());
+        signature[64] = PRE_VALIDATED_V;
+        SafeSignature { owner, signature }
+    }
+
+    pub fn owner(&self) -> Address {
+        self.owner
+    }
+
+    pub fn to_bytes(&self) -> H520 {
+        self.signature
+    }
+
+    fn with_ecdsa(
+        private_key: &secp256k1::PrivateKey,
+        signature: &secp256k1::Signature,
+        v_offset: u8,
+    ) -> SafeSignature {
+        let mut bytes = H520::default();
+        bytes[..32].copy_from_slice(signature.r().as_slice());
+        bytes[32..64].copy_from_slice(signature.s().as_slice());
+        bytes[64] = signature.v() + v_offset;
+
+        SafeSignature {
+            owner: Address::with_secp256k1_pubkey(&private_key.public()),
+            signature: bytes,
+        }
+    }
+}
+
+/// Concatenates the owner signatures sorted by the owner addresses in ascending order
+/// as expected by `Safe.checkSignatures`.
+pub fn encode_signatures<I>(signatures: I) -> MessageSigningResult<Data>
+where
+    I: IntoIterator<Item = SafeSignature>,
+{
+    let mut signatures: Vec<_> = signatures.into_iter().collect();
+    signatures.sort_by(|a, b| a.owner.as_slice().cmp(b.owner.as_slice()));
+
+    if let Some(duplicate) = signatures
+        .windows(2)
+        .find(|pair| pair[0].owner == pair[1].owner)
+    {
+        let owner = duplicate[0].owner;
+        return MessageSigningError::err(MessageSigningErrorKind::InvalidParameterValue)
+            .with_context(|| format!("Duplicate signature of the '{owner}' owner"));
+    }
+
+    Ok(signatures
+        .iter()
+        .flat_map(|signature| signature.signature.as_slice().iter().copied())
+        .collect())
+}
@@ -2,8 +2,10 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::abi::contract::Contract;
 use crate::abi::decode::{decode_params, decode_value};
 use crate::abi::function::Function;
+use crate::abi::human_readable::parse_function;
 use crate::abi::param::Param;
 use crate::abi::param_token::NamedToken;
 use crate::abi::param_type::ParamType;
@@ -19,7 +21,7 @@ use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::str::FromStr;
 use tw_encoding::hex::as_hex;
-use tw_hash::H32;
+use tw_hash::{H256, H32};
 use tw_misc::traits::ToBytesVec;
 use tw_number::{I256, U256};
 use tw_proto::EthereumAbi::Proto;
@@ -78,6 +80,14 @@ impl<Context: EvmContext> AbiEncoder<Context> {
             .unwrap_or_else(|err| abi_output_error!(Proto::FunctionEncodingOutput, err))
     }
 
+    #[inline]
+    pub fn decode_event_log(
+        input: Proto::EventLogDecodingInput<'_>,
+    ) -> Proto::EventLogDecodingOutput<'static> {
+        Self::decode_event_log_impl(input)
+            .unwrap_or_else(|err| abi_output_error!(Proto::EventLogDecodingOutput, err))
+    }
+
     fn decode_contract_call_impl(
         input: Proto::ContractCallDecodingInput,
     ) -> AbiResult<Proto::ContractCallDecodingOutput<'static>> {
@@ -179,6 +189,47 @@ impl<Context: EvmContext> AbiEncoder<Context> {
         })
     }
 
+    fn decode_event_log_impl(
+        input: Proto::EventLogDecodingInput<'_>,
+    ) -> AbiResult<Proto::EventLogDecodingOutput<'static>> {
+        let contract: Contract = serde_json::from_str(&input.abi_json)
+            .tw_err(|_| AbiErrorKind::Error_invalid_abi)
+            .context("Error deserializing Smart Contract ABI as JSON")?;
+
+        let topics = input
+            .topics
+            .iter()
+            .map(|topic| {
+                H256::try_from(topic.as_ref())
+                    .tw_err(|_| AbiErrorKind::Error_decoding_data)
+                    .context("Each log topic must be 32 bytes long")
+            })
+            .collect::<AbiResult<Vec<_>>>()?;
+
+        let topic0 = topics
+            .first()
+            .or_tw_err(AbiErrorKind::Error_decoding_data)
+            .context("Expected at least one log topic")?;
+        let event = contract
+            .event_by_topic(topic0)
+            .or_tw_err(AbiErrorKind::Error_abi_mismatch)
+            .with_context(|| format!("Contract ABI does not have an event with {topic0} topic"))?;
+
+        let decoded_params = event.decode_log(&topics, &input.data)?;
+
+        // Serialize the Proto parameters.
+        let params = decoded_params
+            .into_iter()
+            .map(Self::named_token_to_proto)
+            .collect();
+
+        Ok(Proto::EventLogDecodingOutput {
+            event_signature: event.signature().into(),
+            params,
+            ..Proto::EventLogDecodingOutput::default()
+        })
+    }
+
     fn get_function_signature_from_proto_impl(input: Proto::FunctionGetTypeInput<'_>) -> String {
         let function_inputs = input
             .inputs
@@ -196,9 +247,13 @@ impl<Context: EvmContext> AbiEncoder<Context> {
     }
 
     fn get_function_signature_from_abi_impl(function_abi: &str) -> AbiResult<String> {
-        let mut fun: Function = serde_json::from_str(function_abi)
-            .tw_err(|_| AbiErrorKind::Error_invalid_abi)
-            .context("Error deserializing Function ABI as JSON")?;
+        let mut fun: Function = if function_abi.trim_start().starts_with('{') {
+            serde_json::from_str(function_abi)
+                .tw_err(|_| AbiErrorKind::Error_invalid_abi)
+                .context("Error deserializing Function ABI as JSON")?
+        } else {
+            parse_function(function_abi).context("Error parsing human-readable Function ABI")?
+        };
 
         // Clear the `outputs` to avoid adding them to the signature.
         fun.outputs.clear();
@@ -217,10 +272,27 @@ impl<Context: EvmContext> AbiEncoder<Context> {
             tokens.push(named_token.value);
         }
 
-        let fun = Function {
-            name: input.function_name.to_string(),
-            inputs: input_types,
-            ..Function::default()
+        let fun = if input.human_readable_abi.is_empty() {
+            Function {
+                name: input.function_name.to_string(),
+                inputs: input_types,
+                ..Function::default()
+            }
+        } else {
+            let mut fun = parse_function(&input.human_readable_abi)
+                .context("Error parsing human-readable Function ABI")?;
+            if fun.inputs.len() != tokens.len() {
+                return AbiError::err(AbiErrorKind::Error_abi_mismatch).with_context(|| {
+                    format!(
+                        "Expected {} parameters, found {}",
+                        fun.inputs.len(),
+                        tokens.len()
+                    )
+                });
+            }
+            // Clear the `outputs` to avoid adding them to the signature.
+            fun.outputs.clear();
+            fun
         };
 
         let encoded = fun.encode_input(&tokens)?;
@@ -418,7 +490,7 @@ impl<Context: EvmContext> AbiEncoder<Context> {
         }
     }
 
-    fn named_token_to_proto(token: NamedToken) -> Proto::Token<'static> {
+    pub fn named_token_to_proto(token: NamedToken) -> Proto::Token<'static> {
         Proto::Token {
             name: Cow::Owned(token.name.unwrap_or_default()),
             ..Self::token_to_proto(token.value)
@@ -2,17 +2,23 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::abi::abi_to_signing_error;
+use crate::abi::prebuild::erc1271::Erc1271;
+use crate::abi::prebuild::erc6492::Erc6492;
+use crate::address::Address;
 use crate::message::eip191::Eip191Message;
 use crate::message::eip712::eip712_message::Eip712Message;
+use crate::message::erc6492::Erc6492Signature;
 use crate::message::signature::{MessageSignature, SignatureType};
 use crate::message::{to_signing, EthMessage, EthMessageBoxed};
+use crate::modules::permit_builder::PermitBuilder;
 use std::borrow::Cow;
 use std::str::FromStr;
 use tw_coin_entry::coin_context::CoinContext;
 use tw_coin_entry::error::prelude::*;
 use tw_coin_entry::modules::message_signer::MessageSigner;
 use tw_coin_entry::signing_output_error;
-use tw_encoding::hex::ToHex;
+use tw_encoding::hex::{DecodeHex, ToHex};
 use tw_keypair::ecdsa::secp256k1;
 use tw_keypair::ecdsa::signature::VerifySignature;
 use tw_keypair::traits::{SigningKeyTrait, VerifyingKeyTrait};
@@ -57,6 +63,24 @@ impl MessageSigner for EthMessageSigner {
 }
 
 impl EthMessageSigner {
+    /// Prepares the call to verify a smart contract account signature,
+    /// as it can't be verified offline (ERC-1271).
+    /// The signature can be wrapped according to ERC-6492.
+    pub fn prepare_contract_signature_verification(
+        input: Proto::ContractSignatureVerifyingInput<'_>,
+    ) -> Proto::ContractSignatureVerifyingOutput<'static> {
+        Self::prepare_contract_signature_verification_impl(input)
+            .unwrap_or_else(|e| signing_output_error!(Proto::ContractSignatureVerifyingOutput, e))
+    }
+
+    /// Decodes the result of the call prepared by [`EthMessageSigner::prepare_contract_signature_verification`].
+    pub fn decode_contract_signature_result(
+        input: Proto::ContractSignatureResultInput<'_>,
+    ) -> Proto::ContractSignatureResultOutput<'static> {
+        Self::decode_contract_signature_result_impl(input)
+            .unwrap_or_else(|e| signing_output_error!(Proto::ContractSignatureResultOutput, e))
+    }
+
     fn message_preimage_hashes_impl(
         input: Proto::MessageSigningInput<'_>,
     ) -> SigningResult<CompilerProto::PreSigningOutput<'static>> {
@@ -75,6 +99,7 @@ impl EthMessageSigner {
         let private_key = secp256k1::PrivateKey::try_from(input.private_key.as_ref())?;
         let signature_type =
             Self::signature_type_from_proto(input.message_type, input.chain_id.clone());
+        let erc6492 = input.erc6492.clone();
 
         let msg = Self::message_from_proto(input)?;
 
@@ -82,13 +107,26 @@ impl EthMessageSigner {
         let secp_sign = private_key.sign(hash_to_sign)?;
         let prepared_sign = MessageSignature::prepared(secp_sign, signature_type)?;
 
+        let signature = prepared_sign.to_bytes().to_vec();
+        let signature = match erc6492 {
+            Some(wrapper) => Self::erc6492_from_proto(&wrapper, signature)?.encode(),
+            None => signature,
+        };
+
         Ok(Proto::MessageSigningOutput {
-            signature: Cow::Owned(prepared_sign.to_bytes().to_hex()),
+            signature: Cow::Owned(signature.to_hex()),
             ..Proto::MessageSigningOutput::default()
         })
     }
 
     fn verify_message_impl(input: Proto::MessageVerifyingInput<'_>) -> SigningResult<bool> {
+        if Self::is_erc6492_signature(&input.signature) {
+            return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                "ERC-6492 signature must be verified by the smart contract account, \
+                use `prepare_contract_signature_verification` instead",
+            );
+        }
+
         let public_key = secp256k1::PublicKey::try_from(input.public_key.as_ref())?;
 
         let msg_hash = Self::message_from_str(&input.message)?
@@ -104,7 +142,91 @@ impl EthMessageSigner {
         Ok(valid)
     }
 
+    fn prepare_contract_signature_verification_impl(
+        input: Proto::ContractSignatureVerifyingInput<'_>,
+    ) -> SigningResult<Proto::ContractSignatureVerifyingOutput<'static>> {
+        let signer = Address::from_str(&input.signer)
+            .map_err(SigningError::from)
+            .context("Invalid signer address")?;
+        let message_hash = Self::message_from_str(&input.message)?
+            .hash()
+            .map_err(to_signing)?;
+        let signature = input
+            .signature
+            .decode_hex()
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Invalid signature hex")?;
+
+        if !Erc6492Signature::is_wrapped(&signature) {
+            let call_data = Erc1271::encode_is_valid_signature(message_hash, signature.clone())
+                .map_err(abi_to_signing_error)?;
+            return Ok(Proto::ContractSignatureVerifyingOutput {
+                message_hash: Cow::Owned(message_hash.to_vec()),
+                signature: Cow::Owned(signature),
+                call_data: Cow::Owned(call_data),
+                call_type: Proto::ContractSignatureCallType::ERC1271,
+                call_to: Cow::Owned(signer.to_string()),
+                ..Proto::ContractSignatureVerifyingOutput::default()
+            });
+        }
+
+        let wrapped = Erc6492Signature::decode(&signature)
+            .map_err(abi_to_signing_error)
+            .context("Invalid ERC-6492 signature")?;
+        let validator = Address::from_str(&input.validator)
+            .map_err(SigningError::from)
+            .context("Invalid ERC-6492 validator address")?;
+        // The validator expects the wrapped signature to deploy the account if needed.
+        let call_data = Erc6492::encode_is_valid_sig(signer, message_hash, signature)
+            .map_err(abi_to_signing_error)?;
+
+        Ok(Proto::ContractSignatureVerifyingOutput {
+            message_hash: Cow::Owned(message_hash.to_vec()),
+            signature: Cow::Owned(wrapped.signature),
+            erc6492: Some(Proto::Erc6492Wrapper {
+                factory: Cow::Owned(wrapped.factory.to_string()),
+                factory_calldata: Cow::Owned(wrapped.factory_calldata),
+            }),
+            call_data: Cow::Owned(call_data),
+            call_type: Proto::ContractSignatureCallType::ERC6492,
+            call_to: Cow::Owned(validator.to_string()),
+            ..Proto::ContractSignatureVerifyingOutput::default()
+        })
+    }
+
+    fn decode_contract_signature_result_impl(
+        input: Proto::ContractSignatureResultInput<'_>,
+    ) -> SigningResult<Proto::ContractSignatureResultOutput<'static>> {
+        // `eth_call` returns no data if there is no contract deployed at the called address.
+        if input.call_result.is_empty() {
+            return Ok(Proto::ContractSignatureResultOutput {
+                valid: false,
+                ..Proto::ContractSignatureResultOutput::default()
+            });
+        }
+
+        let valid = match input.call_type {
+            Proto::ContractSignatureCallType::ERC1271 => {
+                Erc1271::decode_is_valid_signature(&input.call_result)
+            },
+            Proto::ContractSignatureCallType::ERC6492 => {
+                Erc6492::decode_is_valid_sig(&input.call_result)
+            },
+        }
+        .map_err(abi_to_signing_error)
+        .context("Invalid call result")?;
+
+        Ok(Proto::ContractSignatureResultOutput {
+            valid,
+            ..Proto::ContractSignatureResultOutput::default()
+        })
+    }
+
     fn message_from_proto(input: Proto::MessageSigningInput<'_>) -> SigningResult<EthMessageBoxed> {
+        if let Some(ref permit) = input.permit {
+            return Self::permit_from_proto(permit, input.message_type, input.chain_id);
+        }
+
         match input.message_type {
             Proto::MessageType::MessageType_legacy
             | Proto::MessageType::MessageType_eip155
@@ -126,6 +248,48 @@ impl EthMessageSigner {
         }
     }
 
+    fn permit_from_proto(
+        permit: &Proto::Permit,
+        message_type: Proto::MessageType,
+        maybe_chain_id: Option<Proto::MaybeChainId>,
+    ) -> SigningResult<EthMessageBoxed> {
+        match message_type {
+            Proto::MessageType::MessageType_typed
+            | Proto::MessageType::MessageType_typed_eip155 => (),
+            _ => {
+                return SigningError::err(SigningErrorType::Error_invalid_params)
+                    .context("Permit can be signed as a typed message only")
+            },
+        }
+
+        let chain_id = maybe_chain_id
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("Chain ID must be set to sign a permit")?
+            .chain_id;
+        PermitBuilder::permit_from_proto(permit, U256::from(chain_id))
+    }
+
+    fn erc6492_from_proto(
+        wrapper: &Proto::Erc6492Wrapper,
+        signature: Vec<u8>,
+    ) -> SigningResult<Erc6492Signature> {
+        let factory = Address::from_str(&wrapper.factory)
+            .map_err(SigningError::from)
+            .context("Invalid ERC-6492 factory address")?;
+        Ok(Erc6492Signature {
+            factory,
+            factory_calldata: wrapper.factory_calldata.to_vec(),
+            signature,
+        })
+    }
+
+    fn is_erc6492_signature(signature: &str) -> bool {
+        signature
+            .decode_hex()
+            .map(|signature| Erc6492Signature::is_wrapped(&signature))
+            .unwrap_or_default()
+    }
+
     fn message_from_str(user_message: &str) -> SigningResult<EthMessageBoxed> {
         match Eip712Message::new(user_message) {
             Ok(typed_data) => Ok(typed_data.into_boxed()),
@@ -5,7 +5,9 @@
 pub mod abi_encoder;
 pub mod compiler;
 pub mod message_signer;
+pub mod permit_builder;
 pub mod rlp_encoder;
 pub mod signer;
+pub mod transaction_decoder;
 pub mod transaction_util;
 pub mod tx_builder;
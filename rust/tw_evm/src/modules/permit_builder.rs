@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::message::permit::dai::DaiPermit;
+use crate::message::permit::erc2612::Erc2612Permit;
+use crate::message::permit::permit2::{
+    permit2_domain, PermitBatch, PermitDetails, PermitSingle, PermitTransferFrom, PERMIT2_ADDRESS,
+};
+use crate::message::permit::PermitDomain;
+use crate::message::{EthMessage, EthMessageBoxed};
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::*;
+use tw_number::U256;
+use tw_proto::Ethereum::Proto;
+use Proto::mod_Permit::OneOfpermit_oneof as PermitType;
+
+pub struct PermitBuilder;
+
+impl PermitBuilder {
+    /// Builds a typed data message from the given `permit`.
+    /// `chain_id` is used in the EIP-712 domain.
+    pub fn permit_from_proto(
+        permit: &Proto::Permit,
+        chain_id: U256,
+    ) -> SigningResult<EthMessageBoxed> {
+        match permit.permit_oneof {
+            PermitType::erc2612(ref permit) => Self::erc2612_from_proto(permit, chain_id)
+                .map(Erc2612Permit::into_boxed)
+                .context("Invalid ERC-2612 permit"),
+            PermitType::dai(ref permit) => Self::dai_from_proto(permit, chain_id)
+                .map(DaiPermit::into_boxed)
+                .context("Invalid DAI permit"),
+            PermitType::permit2_single(ref permit) => {
+                Self::permit2_single_from_proto(permit, chain_id)
+                    .map(PermitSingle::into_boxed)
+                    .context("Invalid Permit2 PermitSingle")
+            },
+            PermitType::permit2_batch(ref permit) => {
+                Self::permit2_batch_from_proto(permit, chain_id)
+                    .map(PermitBatch::into_boxed)
+                    .context("Invalid Permit2 PermitBatch")
+            },
+            PermitType::permit2_transfer_from(ref permit) => {
+                Self::permit2_transfer_from_proto(permit, chain_id)
+                    .map(PermitTransferFrom::into_boxed)
+                    .context("Invalid Permit2 PermitTransferFrom")
+            },
+            PermitType::None => SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("No permit specified"),
+        }
+    }
+
+    fn erc2612_from_proto(
+        permit: &Proto::ERC2612Permit,
+        chain_id: U256,
+    ) -> SigningResult<Erc2612Permit> {
+        Ok(Erc2612Permit {
+            domain: Self::domain_from_proto(permit.domain.as_ref(), chain_id)?,
+            owner: Self::parse_address(&permit.owner).context("Invalid owner")?,
+            spender: Self::parse_address(&permit.spender).context("Invalid spender")?,
+            value: Self::parse_u256(&permit.value).context("Invalid value")?,
+            nonce: Self::parse_u256(&permit.nonce).context("Invalid nonce")?,
+            deadline: Self::parse_u256(&permit.deadline).context("Invalid deadline")?,
+        })
+    }
+
+    fn dai_from_proto(permit: &Proto::DaiPermit, chain_id: U256) -> SigningResult<DaiPermit> {
+        Ok(DaiPermit {
+            domain: Self::domain_from_proto(permit.domain.as_ref(), chain_id)?,
+            holder: Self::parse_address(&permit.holder).context("Invalid holder")?,
+            spender: Self::parse_address(&permit.spender).context("Invalid spender")?,
+            nonce: Self::parse_u256(&permit.nonce).context("Invalid nonce")?,
+            expiry: Self::parse_u256(&permit.expiry).context("Invalid expiry")?,
+            allowed: permit.allowed,
+        })
+    }
+
+    fn permit2_single_from_proto(
+        permit: &Proto::Permit2Single,
+        chain_id: U256,
+    ) -> SigningResult<PermitSingle> {
+        let details = permit
+            .details
+            .as_ref()
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("No permit details specified")?;
+
+        Ok(PermitSingle {
+            domain: Self::permit2_domain_from_proto(&permit.permit2_contract, chain_id)?,
+            details: Self::permit2_details_from_proto(details)?,
+            spender: Self::parse_address(&permit.spender).context("Invalid spender")?,
+            sig_deadline: Self::parse_u256(&permit.sig_deadline).context("Invalid sigDeadline")?,
+        })
+    }
+
+    fn permit2_batch_from_proto(
+        permit: &Proto::Permit2Batch,
+        chain_id: U256,
+    ) -> SigningResult<PermitBatch> {
+        if permit.details.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("No permit details specified");
+        }
+        let details = permit
+            .details
+            .iter()
+            .map(Self::permit2_details_from_proto)
+            .collect::<SigningResult<Vec<_>>>()?;
+
+        Ok(PermitBatch {
+            domain: Self::permit2_domain_from_proto(&permit.permit2_contract, chain_id)?,
+            details,
+            spender: Self::parse_address(&permit.spender).context("Invalid spender")?,
+            sig_deadline: Self::parse_u256(&permit.sig_deadline).context("Invalid sigDeadline")?,
+        })
+    }
+
+    fn permit2_transfer_from_proto(
+        permit: &Proto::Permit2TransferFrom,
+        chain_id: U256,
+    ) -> SigningResult<PermitTransferFrom> {
+        Ok(PermitTransferFrom {
+            domain: Self::permit2_domain_from_proto(&permit.permit2_contract, chain_id)?,
+            token: Self::parse_address(&permit.token).context("Invalid token")?,
+            amount: Self::parse_u256(&permit.amount).context("Invalid amount")?,
+            spender: Self::parse_address(&permit.spender).context("Invalid spender")?,
+            nonce: Self::parse_u256(&permit.nonce).context("Invalid nonce")?,
+            deadline: Self::parse_u256(&permit.deadline).context("Invalid deadline")?,
+        })
+    }
+
+    fn permit2_details_from_proto(details: &Proto::Permit2Details) -> SigningResult<PermitDetails> {
+        Ok(PermitDetails {
+            token: Self::parse_address(&details.token).context("Invalid token")?,
+            amount: Self::parse_u256(&details.amount).context("Invalid amount")?,
+            expiration: details.expiration,
+            nonce: details.nonce,
+        })
+    }
+
+    fn domain_from_proto(
+        domain: Option<&Proto::PermitDomain>,
+        chain_id: U256,
+    ) -> SigningResult<PermitDomain> {
+        let domain = domain
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("No permit domain specified")?;
+
+        if domain.name.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Permit domain name must be set");
+        }
+        let version = if domain.version.is_empty() {
+            None
+        } else {
+            Some(domain.version.to_string())
+        };
+
+        Ok(PermitDomain {
+            name: domain.name.to_string(),
+            version,
+            chain_id,
+            verifying_contract: Self::parse_address(&domain.verifying_contract)
+                .context("Invalid domain verifying contract")?,
+        })
+    }
+
+    fn permit2_domain_from_proto(
+        permit2_contract: &str,
+        chain_id: U256,
+    ) -> SigningResult<PermitDomain> {
+        let permit2_contract = if permit2_contract.is_empty() {
+            PERMIT2_ADDRESS
+        } else {
+            permit2_contract
+        };
+        let permit2_contract =
+            Self::parse_address(permit2_contract).context("Invalid Permit2 contract")?;
+        Ok(permit2_domain(chain_id, permit2_contract))
+    }
+
+    fn parse_u256(encoded: &[u8]) -> SigningResult<U256> {
+        U256::from_big_endian_slice(encoded).into_tw()
+    }
+
+    fn parse_address(addr: &str) -> SigningResult<Address> {
+        Address::from_str(addr).map_err(SigningError::from)
+    }
+}
@@ -54,6 +54,7 @@ impl<Context: EvmContext> Signer<Context> {
             s: Cow::from(eth_signature.s().to_big_endian().to_vec()),
             data: Cow::from(signed.payload()),
             pre_hash: Cow::from(pre_hash.to_vec()),
+            encoded_network: Cow::from(signed.encode_network().unwrap_or_default()),
             ..Proto::SigningOutput::default()
         })
     }
@@ -0,0 +1,486 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::function::Function;
+use crate::abi::prebuild::{find_token_function, ContractStandard};
+use crate::address::Address;
+use crate::evm_context::StandardEvmContext;
+use crate::modules::abi_encoder::AbiEncoder;
+use crate::rlp::decode::RlpItem;
+use crate::rlp::list::RlpList;
+use crate::transaction::transaction_eip4844::EIP7594_WRAPPER_VERSION;
+use std::borrow::Cow;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::modules::transaction_decoder::TransactionDecoder;
+use tw_coin_entry::signing_output_error;
+use tw_hash::sha3::keccak256;
+use tw_hash::{H256, H32};
+use tw_keypair::ecdsa::secp256k1;
+use tw_memory::Data;
+use tw_number::U256;
+use tw_proto::Ethereum::Proto;
+
+const EIP2930_TX_TYPE: u8 = 0x01;
+const EIP1559_TX_TYPE: u8 = 0x02;
+const EIP4844_TX_TYPE: u8 = 0x03;
+const EIP7702_TX_TYPE: u8 = 0x04;
+/// Legacy transactions are RLP lists, and an RLP list header is at least `0xc0`.
+const RLP_LIST_OFFSET: u8 = 0xc0;
+
+/// Number of the `[v, r, s]` signature items.
+const SIGNATURE_ITEMS: usize = 3;
+const LEGACY_UNSIGNED_ITEMS: usize = 6;
+const SIGNATURE_V_MIN_LEN: usize = 1;
+const LEGACY_V_OFFSET: u64 = 27;
+const EIP155_V_OFFSET: u64 = 35;
+
+/// Signature as it is encoded in a transaction.
+struct TxSignature {
+    v: U256,
+    recovery_id: u8,
+    r: H256,
+    s: H256,
+}
+
+/// Transaction fields decoded from RLP.
+/// `input.transaction` and `input.to_address` are set from `amount`, `payload` and `to`.
+struct DecodedTransaction {
+    input: Proto::SigningInput<'static>,
+    to: Option<Address>,
+    amount: U256,
+    payload: Data,
+    pre_hash: H256,
+    signature: Option<TxSignature>,
+}
+
+/// Decodes raw legacy, EIP-155 and EIP-2718 typed transactions.
+pub struct EvmTransactionDecoder;
+
+impl TransactionDecoder for EvmTransactionDecoder {
+    type Output = Proto::DecodingTransactionOutput<'static>;
+
+    fn decode_transaction(&self, coin: &dyn CoinContext, tx: &[u8]) -> Self::Output {
+        Self::decode_transaction_impl(coin, tx)
+            .unwrap_or_else(|e| signing_output_error!(Proto::DecodingTransactionOutput, e))
+    }
+}
+
+impl EvmTransactionDecoder {
+    fn decode_transaction_impl(
+        _coin: &dyn CoinContext,
+        tx: &[u8],
+    ) -> SigningResult<Proto::DecodingTransactionOutput<'static>> {
+        let tx_type = *tx
+            .first()
+            .or_tw_err(SigningErrorType::Error_input_parse)
+            .context("Empty transaction")?;
+
+        let decoded = match tx_type {
+            RLP_LIST_OFFSET..=u8::MAX => Self::decode_legacy(tx)?,
+            EIP2930_TX_TYPE | EIP1559_TX_TYPE | EIP4844_TX_TYPE | EIP7702_TX_TYPE => {
+                Self::decode_typed(tx_type, &tx[1..])?
+            },
+            _ => {
+                return SigningError::err(SigningErrorType::Error_not_supported)
+                    .with_context(|| format!("Unsupported transaction type: {tx_type}"))
+            },
+        };
+
+        let mut output = Proto::DecodingTransactionOutput {
+            pre_hash: Cow::Owned(decoded.pre_hash.to_vec()),
+            contract_call: Self::decode_contract_call(&decoded.payload),
+            ..Proto::DecodingTransactionOutput::default()
+        };
+
+        if let Some(signature) = decoded.signature {
+            let secp_signature = secp256k1::Signature::try_from_parts(
+                signature.r,
+                signature.s,
+                signature.recovery_id,
+            )
+            .into_tw()
+            .context("Invalid transaction signature")?;
+            let public_key = secp256k1::PublicKey::recover(secp_signature, decoded.pre_hash)
+                .into_tw()
+                .context("Error recovering the sender public key")?;
+
+            output.sender = Cow::Owned(Address::with_secp256k1_pubkey(&public_key).to_string());
+            output.v = Cow::Owned(
+                signature
+                    .v
+                    .to_big_endian_compact_min_len(SIGNATURE_V_MIN_LEN),
+            );
+            output.r = Cow::Owned(signature.r.to_vec());
+            output.s = Cow::Owned(signature.s.to_vec());
+        }
+
+        let mut input = decoded.input;
+        input.to_address = Cow::Owned(decoded.to.map(|to| to.to_string()).unwrap_or_default());
+        input.transaction = Some(Self::transaction_payload(decoded.amount, decoded.payload));
+        output.transaction = Some(input);
+
+        Ok(output)
+    }
+
+    /// Decodes `rlp([nonce, gas_price, gas_limit, to, value, data])` pre-EIP-155 transaction,
+    /// or `rlp([nonce, gas_price, gas_limit, to, value, data, v, r, s])`.
+    /// An unsigned EIP-155 transaction has `v = chain_id, r = 0, s = 0`.
+    fn decode_legacy(tx: &[u8]) -> SigningResult<DecodedTransaction> {
+        let items = RlpItem::decode(tx)?.as_list()?;
+        if items.len() != LEGACY_UNSIGNED_ITEMS
+            && items.len() != LEGACY_UNSIGNED_ITEMS + SIGNATURE_ITEMS
+        {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .with_context(|| format!("Unexpected legacy transaction items: {}", items.len()));
+        }
+        let (fields, signature_items) = items.split_at(LEGACY_UNSIGNED_ITEMS);
+
+        let mut chain_id = None;
+        let mut signature = None;
+        // Unsigned transactions are hashed as they are.
+        let mut pre_hash = hash(tx);
+
+        if let Some((v, r, s)) = Self::decode_signature_items(signature_items)? {
+            let (v_chain_id, recovery_id) = Self::legacy_recovery_id(v)?;
+            chain_id = v_chain_id;
+
+            let mut list = RlpList::new();
+            for field in fields.iter() {
+                list.append_raw_encoded(field.as_raw());
+            }
+            if let Some(chain_id) = chain_id {
+                list.append(&chain_id)
+                    .append(&U256::zero())
+                    .append(&U256::zero());
+            }
+            pre_hash = hash(&list.finish());
+
+            signature = Some(TxSignature {
+                v,
+                recovery_id,
+                r,
+                s,
+            });
+        } else if let Some(v) = signature_items.first() {
+            chain_id = Some(v.as_u256()?);
+        }
+
+        let input = Proto::SigningInput {
+            chain_id: u256_to_proto(chain_id.unwrap_or_default()),
+            nonce: u256_to_proto(fields[0].as_u256()?),
+            tx_mode: Proto::TransactionMode::Legacy,
+            gas_price: u256_to_proto(fields[1].as_u256()?),
+            gas_limit: u256_to_proto(fields[2].as_u256()?),
+            ..Proto::SigningInput::default()
+        };
+
+        Ok(DecodedTransaction {
+            input,
+            to: fields[3].as_address_optional()?,
+            amount: fields[4].as_u256()?,
+            payload: fields[5].as_data()?.to_vec(),
+            pre_hash,
+            signature,
+        })
+    }
+
+    /// Decodes `tx_type || rlp([chain_id, nonce, ..., y_parity, r, s])` typed transaction.
+    /// `body` is the transaction without the type byte.
+    fn decode_typed(tx_type: u8, body: &[u8]) -> SigningResult<DecodedTransaction> {
+        let mut items = RlpItem::decode(body)?.as_list()?;
+
+        // Blob transactions can be in the network form:
+        // `rlp([tx_payload_body, blobs, commitments, proofs])` or
+        // `rlp([tx_payload_body, wrapper_version, blobs, commitments, cell_proofs])`.
+        let mut blob_sidecar = None;
+        if tx_type == EIP4844_TX_TYPE && matches!(items.first(), Some(item) if item.is_list()) {
+            blob_sidecar = Some(Self::decode_blob_sidecar(&items)?);
+            items = items[0].as_list()?;
+        }
+
+        let unsigned_items = match tx_type {
+            EIP2930_TX_TYPE => 8,
+            EIP1559_TX_TYPE => 9,
+            EIP4844_TX_TYPE => 11,
+            _ => 10,
+        };
+        if items.len() != unsigned_items && items.len() != unsigned_items + SIGNATURE_ITEMS {
+            return SigningError::err(SigningErrorType::Error_input_parse).with_context(|| {
+                format!(
+                    "Unexpected type {tx_type} transaction items: {}",
+                    items.len()
+                )
+            });
+        }
+        let (fields, signature_items) = items.split_at(unsigned_items);
+
+        let mut list = RlpList::new();
+        for field in fields.iter() {
+            list.append_raw_encoded(field.as_raw());
+        }
+        let mut preimage = vec![tx_type];
+        preimage.extend_from_slice(&list.finish());
+        let pre_hash = hash(&preimage);
+
+        let signature = match Self::decode_signature_items(signature_items)? {
+            Some((v, r, s)) => {
+                if v > U256::from(1_u64) {
+                    return SigningError::err(SigningErrorType::Error_input_parse)
+                        .context("Typed transaction 'y_parity' must be 0 or 1");
+                }
+                Some(TxSignature {
+                    v,
+                    recovery_id: v.low_u8(),
+                    r,
+                    s,
+                })
+            },
+            None => None,
+        };
+
+        let mut input = Proto::SigningInput {
+            chain_id: u256_to_proto(fields[0].as_u256()?),
+            nonce: u256_to_proto(fields[1].as_u256()?),
+            ..Proto::SigningInput::default()
+        };
+
+        // EIP-2930 transactions have `gas_price` instead of the EIP-1559 fee fields.
+        let fields = if tx_type == EIP2930_TX_TYPE {
+            input.tx_mode = Proto::TransactionMode::AccessList;
+            input.gas_price = u256_to_proto(fields[2].as_u256()?);
+            &fields[3..]
+        } else {
+            input.max_inclusion_fee_per_gas = u256_to_proto(fields[2].as_u256()?);
+            input.max_fee_per_gas = u256_to_proto(fields[3].as_u256()?);
+            &fields[4..]
+        };
+
+        // `[gas_limit, to, value, data, access_list, ...]`
+        input.gas_limit = u256_to_proto(fields[0].as_u256()?);
+        input.access_list = Self::decode_access_list(&fields[4])?;
+
+        match tx_type {
+            EIP1559_TX_TYPE => input.tx_mode = Proto::TransactionMode::Enveloped,
+            EIP4844_TX_TYPE => {
+                input.tx_mode = Proto::TransactionMode::Blob;
+                input.max_fee_per_blob_gas = u256_to_proto(fields[5].as_u256()?);
+                input.blob_versioned_hashes = fields[6]
+                    .as_list()?
+                    .iter()
+                    .map(|hash| Ok(Cow::Owned(hash.as_h256()?.to_vec())))
+                    .collect::<SigningResult<_>>()?;
+                input.blob_sidecar = blob_sidecar;
+            },
+            EIP7702_TX_TYPE => {
+                input.tx_mode = Proto::TransactionMode::SetCode;
+                input.authorization_list = Self::decode_authorization_list(&fields[5])?;
+            },
+            _ => (),
+        }
+
+        Ok(DecodedTransaction {
+            input,
+            to: fields[1].as_address_optional()?,
+            amount: fields[2].as_u256()?,
+            payload: fields[3].as_data()?.to_vec(),
+            pre_hash,
+            signature,
+        })
+    }
+
+    /// Returns `None` if there are no signature items, or `r` and `s` are zero.
+    fn decode_signature_items(items: &[RlpItem]) -> SigningResult<Option<(U256, H256, H256)>> {
+        let [v, r, s] = items else {
+            return Ok(None);
+        };
+        let (v, r, s) = (v.as_u256()?, r.as_u256()?, s.as_u256()?);
+        if r.is_zero() && s.is_zero() {
+            return Ok(None);
+        }
+        Ok(Some((v, r.to_big_endian(), s.to_big_endian())))
+    }
+
+    /// Returns an optional chain ID and the recovery ID encoded in the legacy `v`.
+    fn legacy_recovery_id(v: U256) -> SigningResult<(Option<U256>, u8)> {
+        let legacy_v_offset = U256::from(LEGACY_V_OFFSET);
+        let eip155_v_offset = U256::from(EIP155_V_OFFSET);
+
+        if v == legacy_v_offset || v == legacy_v_offset + 1_u64 {
+            return Ok((None, (v - legacy_v_offset).low_u8()));
+        }
+        if v < eip155_v_offset {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .with_context(|| format!("Invalid signature 'v': {v}"));
+        }
+
+        let v = v - eip155_v_offset;
+        Ok((Some(v / 2_u64), (v % 2_u64).low_u8()))
+    }
+
+    fn decode_access_list(item: &RlpItem) -> SigningResult<Vec<Proto::Access<'static>>> {
+        item.as_list()?
+            .iter()
+            .map(|access| {
+                let [address, keys]: [RlpItem; 2] = access
+                    .as_list()?
+                    .try_into()
+                    .ok()
+                    .or_tw_err(SigningErrorType::Error_input_parse)
+                    .context("Access list item must be '[address, storage_keys]'")?;
+
+                let stored_keys = keys
+                    .as_list()?
+                    .iter()
+                    .map(|key| Ok(Cow::Owned(key.as_h256()?.to_vec())))
+                    .collect::<SigningResult<_>>()?;
+
+                Ok(Proto::Access {
+                    address: Cow::Owned(address.as_address()?.to_string()),
+                    stored_keys,
+                })
+            })
+            .collect()
+    }
+
+    fn decode_authorization_list(
+        item: &RlpItem,
+    ) -> SigningResult<Vec<Proto::Authorization<'static>>> {
+        item.as_list()?
+            .iter()
+            .map(|authorization| {
+                let [chain_id, address, nonce, y_parity, r, s]: [RlpItem; 6] = authorization
+                    .as_list()?
+                    .try_into()
+                    .ok()
+                    .or_tw_err(SigningErrorType::Error_input_parse)
+                    .context(
+                        "Authorization must be '[chain_id, address, nonce, y_parity, r, s]'",
+                    )?;
+
+                let y_parity = y_parity.as_u256()?;
+                if y_parity > U256::from(1_u64) {
+                    return SigningError::err(SigningErrorType::Error_input_parse)
+                        .context("Authorization 'y_parity' must be 0 or 1");
+                }
+
+                let mut signature = Vec::with_capacity(secp256k1::Signature::len());
+                signature.extend_from_slice(r.as_u256()?.to_big_endian().as_slice());
+                signature.extend_from_slice(s.as_u256()?.to_big_endian().as_slice());
+                signature.push(y_parity.low_u8());
+
+                Ok(Proto::Authorization {
+                    chain_id: u256_to_proto(chain_id.as_u256()?),
+                    address: Cow::Owned(address.as_address()?.to_string()),
+                    nonce: u256_to_proto(nonce.as_u256()?),
+                    signature: Cow::Owned(signature),
+                })
+            })
+            .collect()
+    }
+
+    fn decode_blob_sidecar(items: &[RlpItem]) -> SigningResult<Proto::BlobSidecar<'static>> {
+        let (version, blobs, commitments, proofs) = match items {
+            [_tx_payload_body, blobs, commitments, proofs] => (
+                Proto::BlobSidecarVersion::BlobProofs,
+                blobs,
+                commitments,
+                proofs,
+            ),
+            [_tx_payload_body, wrapper_version, blobs, commitments, cell_proofs] => {
+                if wrapper_version.as_u256()? != U256::from(EIP7594_WRAPPER_VERSION) {
+                    return SigningError::err(SigningErrorType::Error_not_supported)
+                        .context("Unsupported blob transaction wrapper version");
+                }
+                (
+                    Proto::BlobSidecarVersion::CellProofs,
+                    blobs,
+                    commitments,
+                    cell_proofs,
+                )
+            },
+            _ => {
+                return SigningError::err(SigningErrorType::Error_input_parse)
+                    .context("Invalid blob transaction network form")
+            },
+        };
+
+        let data_list = |item: &RlpItem| -> SigningResult<Vec<Cow<'static, [u8]>>> {
+            item.as_list()?
+                .iter()
+                .map(|data| Ok(Cow::Owned(data.as_data()?.to_vec())))
+                .collect()
+        };
+
+        Ok(Proto::BlobSidecar {
+            blobs: data_list(blobs)?,
+            commitments: data_list(commitments)?,
+            proofs: data_list(proofs)?,
+            version,
+        })
+    }
+
+    fn transaction_payload(amount: U256, payload: Data) -> Proto::Transaction<'static> {
+        use Proto::mod_Transaction::OneOftransaction_oneof as Tx;
+
+        let amount = u256_to_proto(amount);
+        let transaction_oneof = if payload.is_empty() {
+            Tx::transfer(Proto::mod_Transaction::Transfer {
+                amount,
+                data: Cow::default(),
+            })
+        } else {
+            Tx::contract_generic(Proto::mod_Transaction::ContractGeneric {
+                amount,
+                data: Cow::Owned(payload),
+            })
+        };
+        Proto::Transaction { transaction_oneof }
+    }
+
+    /// Decodes the call data if it matches a function of the prebuilt token ABIs.
+    fn decode_contract_call(payload: &[u8]) -> Option<Proto::DecodedContractCall<'static>> {
+        if payload.len() < H32::LEN {
+            return None;
+        }
+        let (short_signature, encoded_params) = payload.split_at(H32::LEN);
+
+        let (standard, function) = find_token_function(short_signature)?;
+        let mut params = function.decode_input(encoded_params).ok()?;
+
+        let standard = match standard {
+            Some(ContractStandard::Erc20) => Proto::ContractStandard::ERC20,
+            Some(ContractStandard::Erc721) => Proto::ContractStandard::ERC721,
+            Some(ContractStandard::Erc1155) => Proto::ContractStandard::ERC1155,
+            None => {
+                // The parameter names differ between the standards, e.g. `amount` vs `tokenId`.
+                params.iter_mut().for_each(|param| param.name = None);
+                Proto::ContractStandard::UnknownStandard
+            },
+        };
+        // Do not include the function outputs into the signature.
+        let function_signature = Function {
+            outputs: Vec::new(),
+            ..function.clone()
+        }
+        .signature();
+
+        Some(Proto::DecodedContractCall {
+            standard,
+            function_signature: Cow::Owned(function_signature),
+            params: params
+                .into_iter()
+                .map(AbiEncoder::<StandardEvmContext>::named_token_to_proto)
+                .collect(),
+        })
+    }
+}
+
+fn hash(data: &[u8]) -> H256 {
+    H256::try_from(keccak256(data).as_slice()).expect("keccak256 returns 32 bytes")
+}
+
+fn u256_to_proto(num: U256) -> Cow<'static, [u8]> {
+    Cow::Owned(num.to_big_endian_compact())
+}
@@ -10,14 +10,26 @@ use crate::abi::prebuild::erc721::Erc721;
 use crate::address::{Address, EvmAddress};
 use crate::evm_context::EvmContext;
 use crate::transaction::access_list::{Access, AccessList};
+use crate::transaction::authorization_list::{
+    Authorization, AuthorizationList, SignedAuthorization,
+};
 use crate::transaction::transaction_eip1559::TransactionEip1559;
+use crate::transaction::transaction_eip2930::TransactionEip2930;
+use crate::transaction::transaction_eip4844::{
+    kzg_to_versioned_hash, BlobSidecar, BlobSidecarVersion, TransactionEip4844, BLOB_SIZE,
+    KZG_COMMITMENT_SIZE, KZG_PROOF_SIZE, VERSIONED_HASH_VERSION_KZG,
+};
+use crate::transaction::transaction_eip7702::TransactionEip7702;
 use crate::transaction::transaction_non_typed::TransactionNonTyped;
 use crate::transaction::user_operation::UserOperation;
+use crate::transaction::user_operation_v0_7::UserOperationV0_7;
 use crate::transaction::UnsignedTransactionBox;
+use std::borrow::Cow;
 use std::marker::PhantomData;
 use std::str::FromStr;
 use tw_coin_entry::error::prelude::*;
 use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
 use tw_memory::Data;
 use tw_number::U256;
 use tw_proto::Common::Proto::SigningError as CommonError;
@@ -148,8 +160,7 @@ impl<Context: EvmContext> TxBuilder<Context> {
                 let payload = Erc4337SimpleAccount::encode_execute_batch(calls)
                     .map_err(abi_to_signing_error)?;
 
-                return Self::user_operation_from_proto(input, payload)
-                    .map(UserOperation::into_boxed);
+                return Self::user_operation_from_proto(input, payload);
             },
             Tx::None => {
                 return SigningError::err(SigningErrorType::Error_invalid_params)
@@ -164,6 +175,21 @@ impl<Context: EvmContext> TxBuilder<Context> {
             TxMode::Enveloped => {
                 Self::transaction_eip1559_from_proto(input, eth_amount, payload, to)?.into_boxed()
             },
+            TxMode::AccessList => {
+                Self::transaction_eip2930_from_proto(input, eth_amount, payload, to)?.into_boxed()
+            },
+            TxMode::SetCode => {
+                let to = to
+                    .or_tw_err(SigningErrorType::Error_invalid_address)
+                    .context("Set-code transaction cannot be used to create a contract")?;
+                Self::transaction_eip7702_from_proto(input, eth_amount, payload, to)?.into_boxed()
+            },
+            TxMode::Blob => {
+                let to = to
+                    .or_tw_err(SigningErrorType::Error_invalid_address)
+                    .context("Blob transaction cannot be used to create a contract")?;
+                Self::transaction_eip4844_from_proto(input, eth_amount, payload, to)?.into_boxed()
+            },
             TxMode::UserOp => {
                 let to = to
                     .or_tw_err(SigningErrorType::Error_invalid_address)
@@ -176,7 +202,7 @@ impl<Context: EvmContext> TxBuilder<Context> {
                 })
                 .map_err(abi_to_signing_error)?;
 
-                Self::user_operation_from_proto(input, payload)?.into_boxed()
+                Self::user_operation_from_proto(input, payload)?
             },
         };
         Ok(tx)
@@ -266,15 +292,163 @@ impl<Context: EvmContext> TxBuilder<Context> {
         })
     }
 
+    #[inline]
+    fn transaction_eip2930_from_proto(
+        input: &Proto::SigningInput,
+        eth_amount: U256,
+        payload: Data,
+        to_address: Option<Address>,
+    ) -> SigningResult<TransactionEip2930> {
+        let nonce = U256::from_big_endian_slice(&input.nonce)
+            .into_tw()
+            .context("Invalid nonce")?;
+
+        let gas_price = U256::from_big_endian_slice(&input.gas_price)
+            .into_tw()
+            .context("Invalid gas price")?;
+
+        let gas_limit = U256::from_big_endian_slice(&input.gas_limit)
+            .into_tw()
+            .context("Invalid gas limit")?;
+
+        let access_list =
+            Self::parse_access_list(&input.access_list).context("Invalid access list")?;
+
+        Ok(TransactionEip2930 {
+            nonce,
+            gas_price,
+            gas_limit,
+            to: to_address,
+            amount: eth_amount,
+            payload,
+            access_list,
+        })
+    }
+
+    #[inline]
+    fn transaction_eip7702_from_proto(
+        input: &Proto::SigningInput,
+        eth_amount: U256,
+        payload: Data,
+        to_address: Address,
+    ) -> SigningResult<TransactionEip7702> {
+        let nonce = U256::from_big_endian_slice(&input.nonce)
+            .into_tw()
+            .context("Invalid nonce")?;
+
+        let gas_limit = U256::from_big_endian_slice(&input.gas_limit)
+            .into_tw()
+            .context("Invalid gas limit")?;
+
+        let max_inclusion_fee_per_gas =
+            U256::from_big_endian_slice(&input.max_inclusion_fee_per_gas)
+                .into_tw()
+                .context("Invalid max inclusion fee per gas")?;
+
+        let max_fee_per_gas = U256::from_big_endian_slice(&input.max_fee_per_gas)
+            .into_tw()
+            .context("Invalid max fee per gas")?;
+
+        let access_list =
+            Self::parse_access_list(&input.access_list).context("Invalid access list")?;
+
+        let authorization_list =
+            Self::parse_authorization_list(input).context("Invalid authorization list")?;
+
+        Ok(TransactionEip7702 {
+            nonce,
+            max_inclusion_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit,
+            to: to_address,
+            amount: eth_amount,
+            payload,
+            access_list,
+            authorization_list,
+        })
+    }
+
+    #[inline]
+    fn transaction_eip4844_from_proto(
+        input: &Proto::SigningInput,
+        eth_amount: U256,
+        payload: Data,
+        to_address: Address,
+    ) -> SigningResult<TransactionEip4844> {
+        let nonce = U256::from_big_endian_slice(&input.nonce)
+            .into_tw()
+            .context("Invalid nonce")?;
+
+        let gas_limit = U256::from_big_endian_slice(&input.gas_limit)
+            .into_tw()
+            .context("Invalid gas limit")?;
+
+        let max_inclusion_fee_per_gas =
+            U256::from_big_endian_slice(&input.max_inclusion_fee_per_gas)
+                .into_tw()
+                .context("Invalid max inclusion fee per gas")?;
+
+        let max_fee_per_gas = U256::from_big_endian_slice(&input.max_fee_per_gas)
+            .into_tw()
+            .context("Invalid max fee per gas")?;
+
+        let max_fee_per_blob_gas = U256::from_big_endian_slice(&input.max_fee_per_blob_gas)
+            .into_tw()
+            .context("Invalid max fee per blob gas")?;
+
+        let access_list =
+            Self::parse_access_list(&input.access_list).context("Invalid access list")?;
+
+        let sidecar = input
+            .blob_sidecar
+            .as_ref()
+            .map(Self::parse_blob_sidecar)
+            .transpose()
+            .context("Invalid blob sidecar")?;
+
+        let blob_versioned_hashes = Self::parse_blob_versioned_hashes(input, sidecar.as_ref())
+            .context("Invalid blob versioned hashes")?;
+
+        Ok(TransactionEip4844 {
+            nonce,
+            max_inclusion_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit,
+            to: to_address,
+            amount: eth_amount,
+            payload,
+            access_list,
+            max_fee_per_blob_gas,
+            blob_versioned_hashes,
+            sidecar,
+        })
+    }
+
     fn user_operation_from_proto(
         input: &Proto::SigningInput,
         erc4337_payload: Data,
-    ) -> SigningResult<UserOperation> {
-        let Some(ref user_op) = input.user_operation else {
-            return SigningError::err(CommonError::Error_invalid_params)
-                .context("No user operation specified");
-        };
+    ) -> SigningResult<Box<dyn UnsignedTransactionBox>> {
+        use Proto::mod_SigningInput::OneOfuser_operation_oneof as UserOp;
+
+        match input.user_operation_oneof {
+            UserOp::user_operation(ref user_op) => {
+                Self::user_operation_v0_6_from_proto(input, user_op, erc4337_payload)
+                    .map(UserOperation::into_boxed)
+            },
+            UserOp::user_operation_v0_7(ref user_op) => {
+                Self::user_operation_v0_7_from_proto(input, user_op, erc4337_payload)
+                    .map(UserOperationV0_7::into_boxed)
+            },
+            UserOp::None => SigningError::err(CommonError::Error_invalid_params)
+                .context("No user operation specified"),
+        }
+    }
 
+    fn user_operation_v0_6_from_proto(
+        input: &Proto::SigningInput,
+        user_op: &Proto::UserOperation,
+        erc4337_payload: Data,
+    ) -> SigningResult<UserOperation> {
         let nonce = U256::from_big_endian_slice(&input.nonce)
             .into_tw()
             .context("Invalid nonce")?;
@@ -321,6 +495,76 @@ impl<Context: EvmContext> TxBuilder<Context> {
         })
     }
 
+    fn user_operation_v0_7_from_proto(
+        input: &Proto::SigningInput,
+        user_op: &Proto::UserOperationV0_7,
+        erc4337_payload: Data,
+    ) -> SigningResult<UserOperationV0_7> {
+        let nonce = U256::from_big_endian_slice(&input.nonce)
+            .into_tw()
+            .context("Invalid nonce")?;
+
+        let call_gas_limit = Self::parse_u128(&input.gas_limit).context("Invalid gas limit")?;
+
+        let max_priority_fee_per_gas = Self::parse_u128(&input.max_inclusion_fee_per_gas)
+            .context("Invalid max inclusion fee per gas")?;
+
+        let max_fee_per_gas =
+            Self::parse_u128(&input.max_fee_per_gas).context("Invalid max fee per gas")?;
+
+        let entry_point =
+            Self::parse_address(user_op.entry_point.as_ref()).context("Invalid entry point")?;
+
+        let sender = Self::parse_address(user_op.sender.as_ref())
+            .context("Invalid User Operation sender")?;
+
+        let factory =
+            Self::parse_address_optional(user_op.factory.as_ref()).context("Invalid factory")?;
+
+        let verification_gas_limit = Self::parse_u128(&user_op.verification_gas_limit)
+            .context("Invalid verification gas limit")?;
+
+        let pre_verification_gas = U256::from_big_endian_slice(&user_op.pre_verification_gas)
+            .into_tw()
+            .context("Invalid pre-verification gas")?;
+
+        let paymaster = Self::parse_address_optional(user_op.paymaster.as_ref())
+            .context("Invalid paymaster")?;
+
+        let paymaster_verification_gas_limit =
+            Self::parse_u128(&user_op.paymaster_verification_gas_limit)
+                .context("Invalid paymaster verification gas limit")?;
+
+        let paymaster_post_op_gas_limit = Self::parse_u128(&user_op.paymaster_post_op_gas_limit)
+            .context("Invalid paymaster post-op gas limit")?;
+
+        Ok(UserOperationV0_7 {
+            sender,
+            nonce,
+            factory,
+            factory_data: user_op.factory_data.to_vec(),
+            call_data: erc4337_payload,
+            call_gas_limit,
+            verification_gas_limit,
+            pre_verification_gas,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            paymaster,
+            paymaster_verification_gas_limit,
+            paymaster_post_op_gas_limit,
+            paymaster_data: user_op.paymaster_data.to_vec(),
+            entry_point,
+        })
+    }
+
+    /// Parses a big-endian number that must fit into `uint128`.
+    fn parse_u128(encoded: &[u8]) -> SigningResult<u128> {
+        let num = U256::from_big_endian_slice(encoded).into_tw()?;
+        u128::try_from(num)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Expected a number that fits into uint128")
+    }
+
     fn parse_address(addr: &str) -> SigningResult<Address> {
         Context::Address::from_str(addr)
             .map(Context::Address::into)
@@ -356,4 +600,139 @@ impl<Context: EvmContext> TxBuilder<Context> {
         }
         Ok(access)
     }
+
+    fn parse_authorization_list(input: &Proto::SigningInput) -> SigningResult<AuthorizationList> {
+        if input.authorization_list.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Set-code transaction requires at least one authorization");
+        }
+
+        let mut authorization_list = AuthorizationList::default();
+        for authorization_proto in input.authorization_list.iter() {
+            let authorization = Self::parse_authorization(authorization_proto, &input.private_key)?;
+            authorization_list.add_authorization(authorization);
+        }
+        Ok(authorization_list)
+    }
+
+    fn parse_authorization(
+        authorization_proto: &Proto::Authorization,
+        private_key: &[u8],
+    ) -> SigningResult<SignedAuthorization> {
+        let chain_id = U256::from_big_endian_slice(&authorization_proto.chain_id)
+            .into_tw()
+            .context("Invalid authorization chain ID")?;
+        let address = Self::parse_address(&authorization_proto.address)
+            .context("Invalid authorization address")?;
+        let nonce = U256::from_big_endian_slice(&authorization_proto.nonce)
+            .into_tw()
+            .context("Invalid authorization nonce")?;
+
+        let authorization = Authorization {
+            chain_id,
+            address,
+            nonce,
+        };
+
+        if !authorization_proto.signature.is_empty() {
+            let signature = secp256k1::Signature::from_bytes(&authorization_proto.signature)
+                .into_tw()
+                .context("Invalid authorization signature")?;
+            return Ok(authorization.into_signed(signature));
+        }
+
+        let private_key = secp256k1::PrivateKey::try_from(private_key)
+            .into_tw()
+            .context("Either authorization signature or private key must be provided")?;
+        authorization.sign(&private_key)
+    }
+
+    fn parse_blob_sidecar(sidecar_proto: &Proto::BlobSidecar) -> SigningResult<BlobSidecar> {
+        let version = match sidecar_proto.version {
+            Proto::BlobSidecarVersion::BlobProofs => BlobSidecarVersion::Eip4844,
+            Proto::BlobSidecarVersion::CellProofs => BlobSidecarVersion::Eip7594,
+        };
+
+        let blobs_count = sidecar_proto.blobs.len();
+        if sidecar_proto.commitments.len() != blobs_count {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Number of blobs and commitments must be the same");
+        }
+        if sidecar_proto.proofs.len() != blobs_count * version.proofs_per_blob() {
+            return SigningError::err(SigningErrorType::Error_invalid_params).with_context(|| {
+                format!("Expected {} KZG proofs per blob", version.proofs_per_blob())
+            });
+        }
+
+        let parse_items = |items: &[Cow<[u8]>], expected_size: usize, name: &str| {
+            items
+                .iter()
+                .map(|item| {
+                    if item.len() != expected_size {
+                        return SigningError::err(SigningErrorType::Error_invalid_params)
+                            .with_context(|| format!("Expected {name} of {expected_size} bytes"));
+                    }
+                    Ok(item.to_vec())
+                })
+                .collect::<SigningResult<Vec<_>>>()
+        };
+
+        Ok(BlobSidecar {
+            version,
+            blobs: parse_items(&sidecar_proto.blobs, BLOB_SIZE, "blob")?,
+            commitments: parse_items(
+                &sidecar_proto.commitments,
+                KZG_COMMITMENT_SIZE,
+                "KZG commitment",
+            )?,
+            proofs: parse_items(&sidecar_proto.proofs, KZG_PROOF_SIZE, "KZG proof")?,
+        })
+    }
+
+    /// Parses the blob versioned hashes or computes them from the `sidecar` commitments.
+    fn parse_blob_versioned_hashes(
+        input: &Proto::SigningInput,
+        sidecar: Option<&BlobSidecar>,
+    ) -> SigningResult<Vec<H256>> {
+        let from_commitments = sidecar.map(|sidecar| {
+            sidecar
+                .commitments
+                .iter()
+                .map(|commitment| kzg_to_versioned_hash(commitment))
+                .collect::<Vec<_>>()
+        });
+
+        let hashes = if input.blob_versioned_hashes.is_empty() {
+            from_commitments.clone().unwrap_or_default()
+        } else {
+            input
+                .blob_versioned_hashes
+                .iter()
+                .map(|hash| {
+                    H256::try_from(hash.as_ref())
+                        .tw_err(|_| SigningErrorType::Error_invalid_params)
+                        .context("Blob versioned hash must be 32 bytes")
+                })
+                .collect::<SigningResult<Vec<_>>>()?
+        };
+
+        if hashes.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Blob transaction requires at least one blob");
+        }
+        if hashes
+            .iter()
+            .any(|hash| hash[0] != VERSIONED_HASH_VERSION_KZG)
+        {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Unsupported blob versioned hash version");
+        }
+        if let Some(from_commitments) = from_commitments {
+            if from_commitments != hashes {
+                return SigningError::err(SigningErrorType::Error_invalid_params)
+                    .context("Blob versioned hashes do not match the KZG commitments");
+            }
+        }
+        Ok(hashes)
+    }
 }
@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::{H160, H256};
+use tw_number::U256;
+
+/// A view of an RLP encoded item.
+#[derive(Clone)]
+pub struct RlpItem<'a> {
+    inner: rlp::Rlp<'a>,
+}
+
+impl<'a> RlpItem<'a> {
+    /// Parses an RLP item that must take exactly the whole `bytes`.
+    pub fn decode(bytes: &'a [u8]) -> SigningResult<RlpItem<'a>> {
+        let inner = rlp::Rlp::new(bytes);
+        let payload_info = inner
+            .payload_info()
+            .tw_err(|_| SigningErrorType::Error_input_parse)
+            .context("Invalid RLP header")?;
+        if payload_info.total() != bytes.len() {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context("RLP item length does not match the input length");
+        }
+        Ok(RlpItem { inner })
+    }
+
+    /// Returns the item encoded with its header.
+    #[inline]
+    pub fn as_raw(&self) -> &'a [u8] {
+        self.inner.as_raw()
+    }
+
+    #[inline]
+    pub fn is_list(&self) -> bool {
+        self.inner.is_list()
+    }
+
+    pub fn as_list(&self) -> SigningResult<Vec<RlpItem<'a>>> {
+        let count = self
+            .inner
+            .item_count()
+            .tw_err(|_| SigningErrorType::Error_input_parse)
+            .context("Expected an RLP list")?;
+
+        (0..count)
+            .map(|idx| {
+                self.inner
+                    .at(idx)
+                    .map(|inner| RlpItem { inner })
+                    .tw_err(|_| SigningErrorType::Error_input_parse)
+                    .with_context(|| format!("Invalid RLP list item at {idx}"))
+            })
+            .collect()
+    }
+
+    pub fn as_data(&self) -> SigningResult<&'a [u8]> {
+        self.inner
+            .data()
+            .tw_err(|_| SigningErrorType::Error_input_parse)
+            .context("Expected an RLP string")
+    }
+
+    pub fn as_u256(&self) -> SigningResult<U256> {
+        let data = self.as_data()?;
+        if data.first() == Some(&0) {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context("RLP number must not have leading zeros");
+        }
+        U256::from_big_endian_slice(data)
+            .into_tw()
+            .context("Invalid RLP number")
+    }
+
+    pub fn as_h256(&self) -> SigningResult<H256> {
+        H256::try_from(self.as_data()?)
+            .tw_err(|_| SigningErrorType::Error_input_parse)
+            .context("Expected 32 bytes")
+    }
+
+    pub fn as_address(&self) -> SigningResult<Address> {
+        let bytes = H160::try_from(self.as_data()?)
+            .tw_err(|_| SigningErrorType::Error_input_parse)
+            .context("Expected a 20-byte address")?;
+        Ok(Address::from_bytes(bytes))
+    }
+
+    /// Returns `None` if the item is an empty string, e.g. a contract creation destination.
+    pub fn as_address_optional(&self) -> SigningResult<Option<Address>> {
+        if self.as_data()?.is_empty() {
+            return Ok(None);
+        }
+        self.as_address().map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::DecodeHex;
+
+    #[test]
+    fn test_decode_rlp_list() {
+        let encoded = "dd8080809400000000000000000000000000000000000000008080808080"
+            .decode_hex()
+            .unwrap();
+        let item = RlpItem::decode(&encoded).unwrap();
+        assert!(item.is_list());
+
+        let items = item.as_list().unwrap();
+        assert_eq!(items.len(), 9);
+        assert_eq!(items[0].as_u256().unwrap(), U256::zero());
+        assert_eq!(items[3].as_address().unwrap(), Address::default());
+        assert!(items[5].as_data().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_decode_rlp_invalid() {
+        // Trailing bytes.
+        let encoded = "c08080".decode_hex().unwrap();
+        RlpItem::decode(&encoded).unwrap_err();
+
+        // Number with a leading zero.
+        let encoded = "820001".decode_hex().unwrap();
+        let item = RlpItem::decode(&encoded).unwrap();
+        item.as_u256().unwrap_err();
+    }
+}
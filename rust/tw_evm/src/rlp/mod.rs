@@ -5,6 +5,7 @@
 use crate::rlp::buffer::RlpBuffer;
 
 pub mod buffer;
+pub mod decode;
 pub mod impls;
 pub mod list;
 
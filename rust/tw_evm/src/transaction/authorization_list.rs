@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::rlp::buffer::RlpBuffer;
+use crate::rlp::list::RlpList;
+use crate::rlp::RlpEncode;
+use crate::transaction::signature::{EthSignature, Signature};
+use tw_coin_entry::error::prelude::*;
+use tw_hash::sha3::keccak256;
+use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
+use tw_keypair::traits::SigningKeyTrait;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// Magic prefix of the authorization pre-image.
+const SET_CODE_AUTHORIZATION_MAGIC: u8 = 0x05;
+
+/// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) authorization
+/// that allows the authority account to delegate to the code at `address`.
+pub struct Authorization {
+    /// Chain ID the authorization is valid on. Zero means any chain.
+    pub chain_id: U256,
+    pub address: Address,
+    /// Nonce of the authority account.
+    pub nonce: U256,
+}
+
+impl Authorization {
+    /// Returns `0x05 || rlp([chain_id, address, nonce])`.
+    pub fn encode(&self) -> Data {
+        let mut list = RlpList::new();
+        list.append(&self.chain_id)
+            .append(&self.address)
+            .append(&self.nonce);
+
+        let mut preimage = vec![SET_CODE_AUTHORIZATION_MAGIC];
+        preimage.extend_from_slice(&list.finish());
+        preimage
+    }
+
+    pub fn pre_hash(&self) -> H256 {
+        let hash = keccak256(&self.encode());
+        H256::try_from(hash.as_slice()).expect("keccak256 returns 32 bytes")
+    }
+
+    pub fn sign(self, private_key: &secp256k1::PrivateKey) -> SigningResult<SignedAuthorization> {
+        let signature = private_key.sign(self.pre_hash())?;
+        Ok(self.into_signed(signature))
+    }
+
+    pub fn into_signed(self, signature: secp256k1::Signature) -> SignedAuthorization {
+        SignedAuthorization {
+            authorization: self,
+            signature: Signature::new(signature),
+        }
+    }
+}
+
+/// Authorization tuple: `[chain_id, address, nonce, y_parity, r, s]`.
+pub struct SignedAuthorization {
+    pub authorization: Authorization,
+    signature: Signature,
+}
+
+impl SignedAuthorization {
+    #[inline]
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+}
+
+impl RlpEncode for SignedAuthorization {
+    fn rlp_append(&self, buf: &mut RlpBuffer) {
+        buf.begin_list();
+        self.authorization.chain_id.rlp_append(buf);
+        self.authorization.address.rlp_append(buf);
+        self.authorization.nonce.rlp_append(buf);
+        // `v` is either 0 or 1 as no replay protection is applied.
+        self.signature.v().rlp_append(buf);
+        self.signature.r().rlp_append(buf);
+        self.signature.s().rlp_append(buf);
+        buf.finalize_list();
+    }
+}
+
+/// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) authorization list.
+#[derive(Default)]
+pub struct AuthorizationList(Vec<SignedAuthorization>);
+
+impl AuthorizationList {
+    #[inline]
+    pub fn add_authorization(&mut self, authorization: SignedAuthorization) -> &mut Self {
+        self.0.push(authorization);
+        self
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl RlpEncode for AuthorizationList {
+    fn rlp_append(&self, buf: &mut RlpBuffer) {
+        buf.begin_list();
+
+        for authorization in self.0.iter() {
+            authorization.rlp_append(buf);
+        }
+
+        buf.finalize_list();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::{self, ToHex};
+
+    #[test]
+    fn test_sign_authorization() {
+        let authorization = Authorization {
+            chain_id: U256::from(1_u64),
+            address: Address::from("0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B"),
+            nonce: U256::from(1_u64),
+        };
+        assert_eq!(
+            authorization.encode().to_hex(),
+            "05d7019463c0c19a282a1b52b07dd5a65b58948a07dae32b01"
+        );
+        assert_eq!(
+            authorization.pre_hash().to_hex(),
+            "73c37d50a1a39a021d14f800a6e7b8ef6a29e1f8ea5fa2fcbf91ba4a5a58abac"
+        );
+
+        let private_key = secp256k1::PrivateKey::try_from(
+            "4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904",
+        )
+        .unwrap();
+        let signed = authorization.sign(&private_key).unwrap();
+        assert_eq!(signed.signature().to_rsv_bytes().to_hex(), "6e68a9eb48d83104756e83bcf868b9d8f8fa21097026ecf9035ae2be4314f697731e64a7ac248a0eaff0c35096e44e69539944449f533af8948ca87cdd72c92201");
+
+        let mut list = AuthorizationList::default();
+        list.add_authorization(signed);
+        let mut rlp = RlpList::new();
+        rlp.append(&list);
+        assert_eq!(hex::encode(rlp.finish(), false), "f85ef85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0101a06e68a9eb48d83104756e83bcf868b9d8f8fa21097026ecf9035ae2be4314f697a0731e64a7ac248a0eaff0c35096e44e69539944449f533af8948ca87cdd72c92201");
+    }
+}
@@ -17,10 +17,15 @@ use tw_memory::Data;
 use tw_number::U256;
 
 pub mod access_list;
+pub mod authorization_list;
 pub mod signature;
 pub mod transaction_eip1559;
+pub mod transaction_eip2930;
+pub mod transaction_eip4844;
+pub mod transaction_eip7702;
 pub mod transaction_non_typed;
 pub mod user_operation;
+pub mod user_operation_v0_7;
 
 pub trait TransactionCommon {
     fn payload(&self) -> Data;
@@ -48,6 +53,12 @@ pub trait SignedTransaction: TransactionCommon {
 
     fn encode(&self) -> Data;
 
+    /// Encodes the transaction in a network form if it differs from [`SignedTransaction::encode`].
+    /// For example, EIP-4844 transactions are broadcast along with the blobs.
+    fn encode_network(&self) -> Option<Data> {
+        None
+    }
+
     fn signature(&self) -> &Self::Signature;
 }
 
@@ -95,6 +106,8 @@ where
 pub trait SignedTransactionBox: TransactionCommon {
     fn encode(&self) -> Data;
 
+    fn encode_network(&self) -> Option<Data>;
+
     fn signature(&self) -> &dyn EthSignature;
 }
 
@@ -106,6 +119,10 @@ where
         <Self as SignedTransaction>::encode(self)
     }
 
+    fn encode_network(&self) -> Option<Data> {
+        <Self as SignedTransaction>::encode_network(self)
+    }
+
     fn signature(&self) -> &dyn EthSignature {
         <Self as SignedTransaction>::signature(self)
     }
@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::rlp::list::RlpList;
+use crate::transaction::access_list::AccessList;
+use crate::transaction::signature::{EthSignature, Signature};
+use crate::transaction::{SignedTransaction, TransactionCommon, UnsignedTransaction};
+use tw_coin_entry::error::prelude::*;
+use tw_keypair::ecdsa::secp256k1;
+use tw_memory::Data;
+use tw_number::U256;
+
+const EIP2930_TX_TYPE: u8 = 0x01;
+
+/// [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) transaction.
+/// Has the same fee structure as legacy transactions, but includes an access list.
+pub struct TransactionEip2930 {
+    pub nonce: U256,
+    pub gas_price: U256,
+    pub gas_limit: U256,
+    pub to: Option<Address>,
+    pub amount: U256,
+    pub payload: Data,
+    pub access_list: AccessList,
+}
+
+impl TransactionCommon for TransactionEip2930 {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.payload.clone()
+    }
+}
+
+impl UnsignedTransaction for TransactionEip2930 {
+    type SignedTransaction = SignedTransactionEip2930;
+
+    #[inline]
+    fn encode(&self, chain_id: U256) -> Data {
+        encode_transaction(self, chain_id, None)
+    }
+
+    #[inline]
+    fn try_into_signed(
+        self,
+        signature: secp256k1::Signature,
+        chain_id: U256,
+    ) -> SigningResult<Self::SignedTransaction> {
+        Ok(SignedTransactionEip2930 {
+            unsigned: self,
+            signature: Signature::new(signature),
+            chain_id,
+        })
+    }
+}
+
+pub struct SignedTransactionEip2930 {
+    unsigned: TransactionEip2930,
+    signature: Signature,
+    chain_id: U256,
+}
+
+impl TransactionCommon for SignedTransactionEip2930 {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.unsigned.payload.clone()
+    }
+}
+
+impl SignedTransaction for SignedTransactionEip2930 {
+    type Signature = Signature;
+
+    #[inline]
+    fn encode(&self) -> Data {
+        encode_transaction(&self.unsigned, self.chain_id, Some(&self.signature))
+    }
+
+    #[inline]
+    fn signature(&self) -> &Self::Signature {
+        &self.signature
+    }
+}
+
+fn encode_transaction(
+    tx: &TransactionEip2930,
+    chain_id: U256,
+    signature: Option<&Signature>,
+) -> Data {
+    let mut list = RlpList::new();
+    list.append(&chain_id)
+        .append(&tx.nonce)
+        .append(&tx.gas_price)
+        .append(&tx.gas_limit)
+        .append(&tx.to)
+        .append(&tx.amount)
+        .append(tx.payload.as_slice())
+        .append(&tx.access_list);
+
+    if let Some(signature) = signature {
+        list.append(&signature.v());
+        list.append(&signature.r());
+        list.append(&signature.s());
+    }
+
+    let tx_encoded = list.finish();
+
+    let mut envelope = Vec::with_capacity(tx_encoded.len() + 1);
+    envelope.push(EIP2930_TX_TYPE);
+    envelope.extend_from_slice(tx_encoded.as_slice());
+    envelope
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::access_list::Access;
+    use tw_encoding::hex;
+    use tw_hash::H256;
+
+    #[test]
+    fn test_encode_transaction_eip2930_with_access_list() {
+        let mut access = Access::new(Address::from("0xdAC17F958D2ee523a2206206994597C13D831ec7"));
+        access.add_storage_key(H256::from(
+            "0x76c8f33bcdf467e4f1313522c10a40512a867cdcd34f2b898232ad4669200764",
+        ));
+
+        let mut access_list = AccessList::default();
+        access_list.add_access(access);
+
+        let tx = TransactionEip2930 {
+            nonce: U256::from(6_u64),
+            gas_price: U256::from(20_000_000_000_u64),
+            gas_limit: U256::from(30_000_u64),
+            to: Some(Address::from("0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7")),
+            amount: U256::from(543_210_987_654_321_u64),
+            payload: Data::default(),
+            access_list,
+        };
+        let chain_id = U256::from(1_u64);
+        let actual = tx.encode(chain_id);
+
+        let expected = "01f86301068504a817c80082753094b9f5771c27664bf2282d98e09d7f50cec7cb01a78701ee0c29f50cb180f838f794dac17f958d2ee523a2206206994597c13d831ec7e1a076c8f33bcdf467e4f1313522c10a40512a867cdcd34f2b898232ad4669200764";
+        assert_eq!(hex::encode(actual, false), expected);
+    }
+}
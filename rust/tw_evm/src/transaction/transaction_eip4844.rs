@@ -0,0 +1,210 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::rlp::list::RlpList;
+use crate::transaction::access_list::AccessList;
+use crate::transaction::signature::{EthSignature, Signature};
+use crate::transaction::{SignedTransaction, TransactionCommon, UnsignedTransaction};
+use tw_coin_entry::error::prelude::*;
+use tw_hash::sha2::sha256;
+use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
+use tw_memory::Data;
+use tw_number::U256;
+
+const EIP4844_TX_TYPE: u8 = 0x03;
+
+pub const BLOB_SIZE: usize = 131_072;
+pub const KZG_COMMITMENT_SIZE: usize = 48;
+pub const KZG_PROOF_SIZE: usize = 48;
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+/// Number of cells of an extended blob, i.e. number of cell proofs per blob.
+pub const CELLS_PER_EXT_BLOB: usize = 128;
+/// `wrapper_version` of the EIP-7594 network form.
+pub const EIP7594_WRAPPER_VERSION: u8 = 0x01;
+
+/// Computes a blob versioned hash as `0x01 || sha256(commitment)[1..]`.
+pub fn kzg_to_versioned_hash(commitment: &[u8]) -> H256 {
+    let mut hash = H256::try_from(sha256(commitment).as_slice()).expect("sha256 returns 32 bytes");
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    hash
+}
+
+/// Network form of a blob transaction.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BlobSidecarVersion {
+    /// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844) form with a KZG proof per blob.
+    #[default]
+    Eip4844,
+    /// [EIP-7594](https://eips.ethereum.org/EIPS/eip-7594) form with [`CELLS_PER_EXT_BLOB`] cell proofs per blob.
+    Eip7594,
+}
+
+impl BlobSidecarVersion {
+    pub fn proofs_per_blob(self) -> usize {
+        match self {
+            BlobSidecarVersion::Eip4844 => 1,
+            BlobSidecarVersion::Eip7594 => CELLS_PER_EXT_BLOB,
+        }
+    }
+}
+
+/// Blobs with their KZG commitments and proofs.
+/// Not a part of the signed transaction, but required to broadcast it.
+pub struct BlobSidecar {
+    pub version: BlobSidecarVersion,
+    pub blobs: Vec<Data>,
+    pub commitments: Vec<Data>,
+    pub proofs: Vec<Data>,
+}
+
+/// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844) blob transaction.
+/// Fee fields are the same as in EIP-1559 transactions.
+pub struct TransactionEip4844 {
+    pub nonce: U256,
+    pub max_inclusion_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: U256,
+    /// Blob transactions cannot be used to create contracts.
+    pub to: Address,
+    pub amount: U256,
+    pub payload: Data,
+    pub access_list: AccessList,
+    pub max_fee_per_blob_gas: U256,
+    pub blob_versioned_hashes: Vec<H256>,
+    pub sidecar: Option<BlobSidecar>,
+}
+
+impl TransactionCommon for TransactionEip4844 {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.payload.clone()
+    }
+}
+
+impl UnsignedTransaction for TransactionEip4844 {
+    type SignedTransaction = SignedTransactionEip4844;
+
+    #[inline]
+    fn encode(&self, chain_id: U256) -> Data {
+        encode_transaction(self, chain_id, None)
+    }
+
+    #[inline]
+    fn try_into_signed(
+        self,
+        signature: secp256k1::Signature,
+        chain_id: U256,
+    ) -> SigningResult<Self::SignedTransaction> {
+        Ok(SignedTransactionEip4844 {
+            unsigned: self,
+            signature: Signature::new(signature),
+            chain_id,
+        })
+    }
+}
+
+pub struct SignedTransactionEip4844 {
+    unsigned: TransactionEip4844,
+    signature: Signature,
+    chain_id: U256,
+}
+
+impl TransactionCommon for SignedTransactionEip4844 {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.unsigned.payload.clone()
+    }
+}
+
+impl SignedTransaction for SignedTransactionEip4844 {
+    type Signature = Signature;
+
+    #[inline]
+    fn encode(&self) -> Data {
+        encode_transaction(&self.unsigned, self.chain_id, Some(&self.signature))
+    }
+
+    /// Returns `0x03 || rlp([tx_payload_body, blobs, commitments, proofs])`,
+    /// or `0x03 || rlp([tx_payload_body, wrapper_version, blobs, commitments, cell_proofs])`
+    /// for EIP-7594, if the blob sidecar is specified.
+    fn encode_network(&self) -> Option<Data> {
+        let sidecar = self.unsigned.sidecar.as_ref()?;
+
+        let mut list = RlpList::new();
+        list.append_list(transaction_body(
+            &self.unsigned,
+            self.chain_id,
+            Some(&self.signature),
+        ));
+        if sidecar.version == BlobSidecarVersion::Eip7594 {
+            list.append(&U256::from(EIP7594_WRAPPER_VERSION));
+        }
+        list.append_list(data_list(&sidecar.blobs))
+            .append_list(data_list(&sidecar.commitments))
+            .append_list(data_list(&sidecar.proofs));
+
+        Some(envelope(list.finish()))
+    }
+
+    #[inline]
+    fn signature(&self) -> &Self::Signature {
+        &self.signature
+    }
+}
+
+fn encode_transaction(
+    tx: &TransactionEip4844,
+    chain_id: U256,
+    signature: Option<&Signature>,
+) -> Data {
+    envelope(transaction_body(tx, chain_id, signature).finish())
+}
+
+fn transaction_body(
+    tx: &TransactionEip4844,
+    chain_id: U256,
+    signature: Option<&Signature>,
+) -> RlpList {
+    let mut blob_versioned_hashes = RlpList::new();
+    for hash in tx.blob_versioned_hashes.iter() {
+        blob_versioned_hashes.append(hash);
+    }
+
+    let mut list = RlpList::new();
+    list.append(&chain_id)
+        .append(&tx.nonce)
+        .append(&tx.max_inclusion_fee_per_gas)
+        .append(&tx.max_fee_per_gas)
+        .append(&tx.gas_limit)
+        .append(&tx.to)
+        .append(&tx.amount)
+        .append(tx.payload.as_slice())
+        .append(&tx.access_list)
+        .append(&tx.max_fee_per_blob_gas)
+        .append_list(blob_versioned_hashes);
+
+    if let Some(signature) = signature {
+        list.append(&signature.v());
+        list.append(&signature.r());
+        list.append(&signature.s());
+    }
+    list
+}
+
+fn data_list(items: &[Data]) -> RlpList {
+    let mut list = RlpList::new();
+    for item in items.iter() {
+        list.append(item.as_slice());
+    }
+    list
+}
+
+fn envelope(tx_encoded: Data) -> Data {
+    let mut envelope = Vec::with_capacity(tx_encoded.len() + 1);
+    envelope.push(EIP4844_TX_TYPE);
+    envelope.extend_from_slice(tx_encoded.as_slice());
+    envelope
+}
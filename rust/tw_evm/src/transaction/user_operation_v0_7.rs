@@ -0,0 +1,279 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::encode::encode_tokens;
+use crate::abi::non_empty_array::NonEmptyBytes;
+use crate::abi::token::Token;
+use crate::address::Address;
+use crate::transaction::signature::Signature;
+use crate::transaction::{SignedTransaction, TransactionCommon, UnsignedTransaction};
+use serde::Serialize;
+use tw_coin_entry::error::prelude::*;
+use tw_encoding::hex;
+use tw_hash::sha3::keccak256;
+use tw_hash::H256;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// ERC-4337 UserOperation for EntryPoint v0.7.
+/// Gas limits and fees are packed into 16-byte halves of 32-byte words in `PackedUserOperation`,
+/// that's why they are limited to `u128`.
+/// https://github.com/eth-infinitism/account-abstraction/blob/v0.7.0/contracts/interfaces/PackedUserOperation.sol
+pub struct UserOperationV0_7 {
+    pub sender: Address,
+    pub nonce: U256,
+    /// Account factory, set if the account is not deployed yet.
+    pub factory: Option<Address>,
+    pub factory_data: Data,
+    pub call_data: Data,
+    pub call_gas_limit: u128,
+    pub verification_gas_limit: u128,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    /// Paymaster sponsoring the operation, not set for self-sponsored operations.
+    pub paymaster: Option<Address>,
+    pub paymaster_verification_gas_limit: u128,
+    pub paymaster_post_op_gas_limit: u128,
+    pub paymaster_data: Data,
+    pub entry_point: Address,
+}
+
+impl UserOperationV0_7 {
+    /// Returns `factory || factory_data`, or empty bytes if there is no factory.
+    pub fn init_code(&self) -> Data {
+        match self.factory {
+            Some(factory) => concat(&[factory.as_slice(), &self.factory_data]),
+            None => Data::default(),
+        }
+    }
+
+    /// Returns `verification_gas_limit || call_gas_limit`.
+    pub fn account_gas_limits(&self) -> H256 {
+        pack_u128(self.verification_gas_limit, self.call_gas_limit)
+    }
+
+    /// Returns `max_priority_fee_per_gas || max_fee_per_gas`.
+    pub fn gas_fees(&self) -> H256 {
+        pack_u128(self.max_priority_fee_per_gas, self.max_fee_per_gas)
+    }
+
+    /// Returns `paymaster || paymaster_verification_gas_limit || paymaster_post_op_gas_limit || paymaster_data`,
+    /// or empty bytes if there is no paymaster.
+    pub fn paymaster_and_data(&self) -> Data {
+        match self.paymaster {
+            Some(paymaster) => concat(&[
+                paymaster.as_slice(),
+                &self.paymaster_verification_gas_limit.to_be_bytes(),
+                &self.paymaster_post_op_gas_limit.to_be_bytes(),
+                &self.paymaster_data,
+            ]),
+            None => Data::default(),
+        }
+    }
+}
+
+impl TransactionCommon for UserOperationV0_7 {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.call_data.clone()
+    }
+}
+
+impl UnsignedTransaction for UserOperationV0_7 {
+    type SignedTransaction = SignedUserOperationV0_7;
+
+    fn pre_hash(&self, chain_id: U256) -> H256 {
+        let tokens = [
+            Token::FixedBytes(keccak256_bytes(&self.encode(chain_id))),
+            Token::Address(self.entry_point),
+            Token::u256(chain_id),
+        ];
+        let pre_hash = keccak256(&encode_tokens(&tokens));
+        H256::try_from(pre_hash.as_slice()).expect("keccak256 returns 32 bytes")
+    }
+
+    /// Encodes `PackedUserOperation` without the signature as it's done in `UserOperationLib.encode`.
+    fn encode(&self, _chain_id: U256) -> Data {
+        let tokens = [
+            Token::Address(self.sender),
+            Token::u256(self.nonce),
+            Token::FixedBytes(keccak256_bytes(&self.init_code())),
+            Token::FixedBytes(keccak256_bytes(&self.call_data)),
+            Token::FixedBytes(h256_bytes(self.account_gas_limits())),
+            Token::u256(self.pre_verification_gas),
+            Token::FixedBytes(h256_bytes(self.gas_fees())),
+            Token::FixedBytes(keccak256_bytes(&self.paymaster_and_data())),
+        ];
+
+        encode_tokens(&tokens)
+    }
+
+    #[inline]
+    fn try_into_signed(
+        self,
+        signature: tw_keypair::ecdsa::secp256k1::Signature,
+        _chain_id: U256,
+    ) -> SigningResult<Self::SignedTransaction> {
+        Ok(SignedUserOperationV0_7 {
+            unsigned: self,
+            signature: Signature::new(signature),
+        })
+    }
+}
+
+pub struct SignedUserOperationV0_7 {
+    unsigned: UserOperationV0_7,
+    signature: Signature,
+}
+
+impl TransactionCommon for SignedUserOperationV0_7 {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.unsigned.call_data.clone()
+    }
+}
+
+impl SignedTransaction for SignedUserOperationV0_7 {
+    type Signature = Signature;
+
+    /// Encodes the operation in the unpacked form expected by `eth_sendUserOperation` of v0.7 bundlers.
+    /// Factory and paymaster fields are omitted if not set.
+    fn encode(&self) -> Data {
+        let mut signature = self.signature.to_rsv_bytes();
+        signature[64] += 27;
+
+        let prefix = true;
+        let op = &self.unsigned;
+
+        let tx = SignedUserOperationV0_7Serde {
+            call_data: hex::encode(&op.call_data, prefix),
+            call_gas_limit: op.call_gas_limit.to_string(),
+            factory: op.factory.map(|factory| factory.to_string()),
+            factory_data: op.factory.map(|_| hex::encode(&op.factory_data, prefix)),
+            max_fee_per_gas: op.max_fee_per_gas.to_string(),
+            max_priority_fee_per_gas: op.max_priority_fee_per_gas.to_string(),
+            nonce: op.nonce.to_string(),
+            paymaster: op.paymaster.map(|paymaster| paymaster.to_string()),
+            paymaster_data: op
+                .paymaster
+                .map(|_| hex::encode(&op.paymaster_data, prefix)),
+            paymaster_post_op_gas_limit: op
+                .paymaster
+                .map(|_| op.paymaster_post_op_gas_limit.to_string()),
+            paymaster_verification_gas_limit: op
+                .paymaster
+                .map(|_| op.paymaster_verification_gas_limit.to_string()),
+            pre_verification_gas: op.pre_verification_gas.to_string(),
+            sender: op.sender.to_string(),
+            signature: hex::encode(signature.as_slice(), prefix),
+            verification_gas_limit: op.verification_gas_limit.to_string(),
+        };
+        serde_json::to_string(&tx)
+            .expect("Simple structure should never fail on serialization")
+            .into_bytes()
+    }
+
+    #[inline]
+    fn signature(&self) -> &Self::Signature {
+        &self.signature
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignedUserOperationV0_7Serde {
+    call_data: String,
+    call_gas_limit: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    factory: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    factory_data: Option<String>,
+    max_fee_per_gas: String,
+    max_priority_fee_per_gas: String,
+    nonce: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paymaster: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paymaster_data: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paymaster_post_op_gas_limit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paymaster_verification_gas_limit: Option<String>,
+    pre_verification_gas: String,
+    sender: String,
+    signature: String,
+    verification_gas_limit: String,
+}
+
+fn pack_u128(high: u128, low: u128) -> H256 {
+    let mut packed = H256::default();
+    packed[..16].copy_from_slice(&high.to_be_bytes());
+    packed[16..].copy_from_slice(&low.to_be_bytes());
+    packed
+}
+
+fn concat(parts: &[&[u8]]) -> Data {
+    parts.iter().flat_map(|part| part.iter().copied()).collect()
+}
+
+fn keccak256_bytes(data: &[u8]) -> NonEmptyBytes {
+    NonEmptyBytes::new(keccak256(data)).expect("keccak256 must not return an empty hash")
+}
+
+fn h256_bytes(hash: H256) -> NonEmptyBytes {
+    NonEmptyBytes::new(hash.to_vec()).expect("H256 must not be empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::prebuild::erc4337::{Erc4337SimpleAccount, ExecuteArgs};
+
+    #[test]
+    fn test_encode_user_operation_v0_7() {
+        let chain_id = U256::from(97u64);
+
+        let execute_args = ExecuteArgs {
+            to: Address::from("0x61061fCAE11fD5461535e134EfF67A98CFFF44E9"),
+            value: U256::from(0x2_386f_26fc_10000u64),
+            data: Vec::default(),
+        };
+        let call_data = Erc4337SimpleAccount::encode_execute(execute_args).unwrap();
+
+        let user_op = UserOperationV0_7 {
+            sender: Address::from("0xb16Db98B365B1f89191996942612B14F1Da4Bd5f"),
+            nonce: U256::from(2u64),
+            factory: None,
+            factory_data: Vec::default(),
+            call_data,
+            call_gas_limit: 0x186a0,
+            verification_gas_limit: 0x186a0,
+            pre_verification_gas: U256::from(0xb708u64),
+            max_fee_per_gas: 0x1_a339_c9e9,
+            max_priority_fee_per_gas: 0x1_a339_c9e9,
+            paymaster: None,
+            paymaster_verification_gas_limit: 0,
+            paymaster_post_op_gas_limit: 0,
+            paymaster_data: Vec::default(),
+            entry_point: Address::from("0x0000000071727De22E5E9d8BAf0edAc6f37da032"),
+        };
+
+        assert_eq!(
+            user_op.account_gas_limits(),
+            H256::from("000000000000000000000000000186a0000000000000000000000000000186a0")
+        );
+        assert_eq!(
+            user_op.gas_fees(),
+            H256::from("000000000000000000000001a339c9e9000000000000000000000001a339c9e9")
+        );
+        assert!(user_op.init_code().is_empty());
+        assert!(user_op.paymaster_and_data().is_empty());
+
+        let pre_hash = user_op.pre_hash(chain_id);
+        let expected_pre_hash =
+            H256::from("eb139097200394126d3c9a8623f199aa735d360ad78254b4278419f3501d99be");
+        assert_eq!(pre_hash, expected_pre_hash);
+    }
+}
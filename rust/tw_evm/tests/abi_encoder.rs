@@ -95,6 +95,7 @@ fn test_encode_contract_call_impl(
     let input = Proto::FunctionEncodingInput {
         function_name: function_name.into(),
         tokens,
+        ..Proto::FunctionEncodingInput::default()
     };
 
     let output = AbiEncoder::<StandardEvmContext>::encode_contract_call(input);
@@ -488,6 +489,7 @@ fn test_encode_params_monster() {
     let encoding_input = Proto::FunctionEncodingInput {
         function_name: "monster".into(),
         tokens,
+        ..Proto::FunctionEncodingInput::default()
     };
     let output = AbiEncoder::<StandardEvmContext>::encode_contract_call(encoding_input);
 
@@ -661,6 +663,7 @@ fn test_encode_params_invalid_address() {
         let encoding_input = Proto::FunctionEncodingInput {
             function_name: "NonExisting".into(),
             tokens: vec![named_token("", token.clone())],
+            ..Proto::FunctionEncodingInput::default()
         };
 
         let output = AbiEncoder::<StandardEvmContext>::encode_contract_call(encoding_input);
@@ -685,3 +688,97 @@ fn test_decode_contract_call_error() {
     assert_eq!(output.error, AbiErrorKind::Error_abi_mismatch);
     assert!(!output.error_message.is_empty());
 }
+
+#[test]
+fn test_decode_event_log() {
+    let abi_json = json!([
+        {
+            "type": "function",
+            "name": "transfer",
+            "inputs": [
+                { "name": "to", "type": "address" },
+                { "name": "value", "type": "uint256" }
+            ],
+            "outputs": [{ "name": "", "type": "bool" }]
+        },
+        {
+            "type": "event",
+            "name": "Transfer",
+            "anonymous": false,
+            "inputs": [
+                { "indexed": true, "name": "from", "type": "address" },
+                { "indexed": true, "name": "to", "type": "address" },
+                { "indexed": false, "name": "value", "type": "uint256" }
+            ]
+        }
+    ]);
+    let abi_json = serde_json::to_string(&abi_json).unwrap();
+
+    let topics = [
+        "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+        "0000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa261520",
+        "0000000000000000000000006b175474e89094c44da98b954eedeac495271d0f",
+    ];
+    let input = Proto::EventLogDecodingInput {
+        abi_json: abi_json.into(),
+        topics: topics
+            .iter()
+            .map(|topic| topic.decode_hex().unwrap().into())
+            .collect(),
+        data: "00000000000000000000000000000000000000000000000000000000000f4240"
+            .decode_hex()
+            .unwrap()
+            .into(),
+    };
+
+    let output = AbiEncoder::<StandardEvmContext>::decode_event_log(input);
+    assert_eq!(output.error, AbiErrorKind::OK);
+    assert!(output.error_message.is_empty());
+
+    assert_eq!(output.event_signature, "Transfer(address,address,uint256)");
+    let expected_params = vec![
+        named_token(
+            "from",
+            TokenEnum::address("0x4bbeEB066eD09B7AEd07bF39EEe0460DFa261520".into()),
+        ),
+        named_token(
+            "to",
+            TokenEnum::address("0x6B175474E89094C44Da98b954EedeAC495271d0F".into()),
+        ),
+        named_token("value", u_number_n::<256>(1_000_000)),
+    ];
+    assert_eq!(output.params, expected_params);
+}
+
+#[test]
+fn test_decode_event_log_error() {
+    let abi_json = json!([
+        {
+            "type": "event",
+            "name": "Approval",
+            "anonymous": false,
+            "inputs": [
+                { "indexed": true, "name": "owner", "type": "address" },
+                { "indexed": true, "name": "spender", "type": "address" },
+                { "indexed": false, "name": "value", "type": "uint256" }
+            ]
+        }
+    ]);
+    let abi_json = serde_json::to_string(&abi_json).unwrap();
+
+    // `Transfer` event topic.
+    let input = Proto::EventLogDecodingInput {
+        abi_json: abi_json.into(),
+        topics: vec![
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+                .decode_hex()
+                .unwrap()
+                .into(),
+        ],
+        data: Cow::default(),
+    };
+
+    let output = AbiEncoder::<StandardEvmContext>::decode_event_log(input);
+    assert_eq!(output.error, AbiErrorKind::Error_abi_mismatch);
+    assert!(!output.error_message.is_empty());
+}
@@ -12,6 +12,8 @@ use tw_evm::modules::signer::Signer;
 use tw_number::U256;
 use tw_proto::Ethereum::Proto;
 
+use Proto::mod_SigningInput::OneOfuser_operation_oneof as UserOp;
+
 // https://testnet.bscscan.com/tx/0x43fc13dfdf06bbb09da8ce070953753764f1e43782d0c8b621946d8b45749419
 #[test]
 fn test_barz_transfer_account_deployed() {
@@ -43,7 +45,7 @@ fn test_barz_transfer_account_deployed() {
         transaction: Some(Proto::Transaction {
             transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
         }),
-        user_operation: Some(user_op),
+        user_operation_oneof: UserOp::user_operation(user_op),
         ..Proto::SigningInput::default()
     };
 
@@ -96,7 +98,7 @@ fn test_barz_transfer_account_not_deployed() {
         transaction: Some(Proto::Transaction {
             transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
         }),
-        user_operation: Some(user_op),
+        user_operation_oneof: UserOp::user_operation(user_op),
         ..Proto::SigningInput::default()
     };
 
@@ -173,7 +175,7 @@ fn test_barz_batched_account_deployed() {
                 Proto::mod_Transaction::Batch { calls },
             ),
         }),
-        user_operation: Some(user_op),
+        user_operation_oneof: UserOp::user_operation(user_op),
         ..Proto::SigningInput::default()
     };
 
@@ -190,3 +192,97 @@ fn test_barz_batched_account_deployed() {
         "84d0464f5a2b191e06295443970ecdcd2d18f565d0d52b5a79443192153770ab"
     );
 }
+
+fn user_operation_v0_7_input(
+    nonce: u64,
+    verification_gas_limit: u64,
+    user_op: Proto::UserOperationV0_7<'static>,
+) -> Proto::SigningInput<'static> {
+    let private_key =
+        hex::decode("0x3c90badc15c4d35733769093d3733501e92e7f16e101df284cee9a310d36c483").unwrap();
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0x23_86f2_6fc1_0000),
+        data: Cow::default(),
+    };
+    let user_op = Proto::UserOperationV0_7 {
+        entry_point: "0x0000000071727De22E5E9d8BAf0edAc6f37da032".into(),
+        sender: "0xb16Db98B365B1f89191996942612B14F1Da4Bd5f".into(),
+        pre_verification_gas: U256::encode_be_compact(0xb708),
+        verification_gas_limit: U256::encode_be_compact(verification_gas_limit),
+        ..user_op
+    };
+
+    Proto::SigningInput {
+        chain_id: U256::encode_be_compact(97),
+        nonce: U256::encode_be_compact(nonce),
+        tx_mode: Proto::TransactionMode::UserOp,
+        gas_limit: U256::encode_be_compact(0x186A0),
+        max_fee_per_gas: U256::encode_be_compact(0x1_a339_c9e9),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(0x1_a339_c9e9),
+        to_address: "0x61061fCAE11fD5461535e134EfF67A98CFFF44E9".into(),
+        private_key: private_key.into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        user_operation_oneof: UserOp::user_operation_v0_7(user_op),
+        ..Proto::SigningInput::default()
+    }
+}
+
+#[test]
+fn test_barz_transfer_account_deployed_v0_7() {
+    let input = user_operation_v0_7_input(2, 0x186a0, Proto::UserOperationV0_7::default());
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    let expected = r#"{"callData":"0xb61d27f600000000000000000000000061061fcae11fd5461535e134eff67a98cfff44e9000000000000000000000000000000000000000000000000002386f26fc1000000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000","callGasLimit":"100000","maxFeePerGas":"7033440745","maxPriorityFeePerGas":"7033440745","nonce":"2","preVerificationGas":"46856","sender":"0xb16Db98B365B1f89191996942612B14F1Da4Bd5f","signature":"0x2725549f624f0cf7f78c96e1f20d92b8926246e8ee8bdd8d38312ee0f2451417150368739f69049f849f0098dd401505d7368bb3b47b309f13d42d3fcd8e04541c","verificationGasLimit":"100000"}"#;
+    let actual = String::from_utf8(output.encoded.to_vec()).unwrap();
+    assert_eq!(actual, expected);
+
+    assert_eq!(
+        hex::encode(output.pre_hash, false),
+        "eb139097200394126d3c9a8623f199aa735d360ad78254b4278419f3501d99be"
+    );
+}
+
+#[test]
+fn test_barz_transfer_account_not_deployed_with_paymaster_v0_7() {
+    let user_op = Proto::UserOperationV0_7 {
+        factory: "0xf471789937856d80e589f5996cf8b0511ddd9de4".into(),
+        factory_data: hex::decode("0x5fbfb9cf000000000000000000000000b16db98b365b1f89191996942612b14f1da4bd5f0000000000000000000000000000000000000000000000000000000000000000").unwrap().into(),
+        paymaster: "0xA0Cb889707d426A7A386870A03bc70d1b0697598".into(),
+        paymaster_verification_gas_limit: U256::encode_be_compact(0x186a0),
+        paymaster_post_op_gas_limit: U256::encode_be_compact(0x7530),
+        paymaster_data: hex::decode("0xdeadbeef").unwrap().into(),
+        ..Proto::UserOperationV0_7::default()
+    };
+    let input = user_operation_v0_7_input(0, 0x1e8480, user_op);
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    let expected = r#"{"callData":"0xb61d27f600000000000000000000000061061fcae11fd5461535e134eff67a98cfff44e9000000000000000000000000000000000000000000000000002386f26fc1000000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000","callGasLimit":"100000","factory":"0xf471789937856D80e589F5996cf8b0511DDD9de4","factoryData":"0x5fbfb9cf000000000000000000000000b16db98b365b1f89191996942612b14f1da4bd5f0000000000000000000000000000000000000000000000000000000000000000","maxFeePerGas":"7033440745","maxPriorityFeePerGas":"7033440745","nonce":"0","paymaster":"0xa0Cb889707d426A7A386870A03bc70d1b0697598","paymasterData":"0xdeadbeef","paymasterPostOpGasLimit":"30000","paymasterVerificationGasLimit":"100000","preVerificationGas":"46856","sender":"0xb16Db98B365B1f89191996942612B14F1Da4Bd5f","signature":"0xc3ea257b1c81f536b6a221403c3f8e90dfd16d5437f79911ab9d9e88cd1905342b72a03b21ceea6121fabfd36f435d2edb30ad092c110ff1f8c72e6981f7385a1c","verificationGasLimit":"2000000"}"#;
+    let actual = String::from_utf8(output.encoded.to_vec()).unwrap();
+    assert_eq!(actual, expected);
+
+    assert_eq!(
+        hex::encode(output.pre_hash, false),
+        "ed9eeda0c5e6c95ad411a6e2aa713b955571c525c7630709faafbdd31f8039a5"
+    );
+}
+
+#[test]
+fn test_barz_user_operation_v0_7_gas_overflow() {
+    let mut input = user_operation_v0_7_input(2, 0x186a0, Proto::UserOperationV0_7::default());
+    // 2^128 does not fit into the `accountGasLimits` half.
+    let mut gas_limit = vec![0; 17];
+    gas_limit[0] = 1;
+    input.gas_limit = gas_limit.into();
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+}
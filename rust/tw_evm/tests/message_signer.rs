@@ -312,3 +312,185 @@ fn test_message_signer_sign_verify_eip712_different_bytes() {
         signature: "48dc667cd8a53beb58ea6b1745f98c21b12e1a57587ce28bae07689dba3600d40cef2685dc8a68028d38f3e63289891868ecdf05e8affc275fee3001e51d6c581c",
     });
 }
+
+const ERC6492_FACTORY: &str = "0xf471789937856D80e589F5996cf8b0511DDD9de4";
+const ERC6492_FACTORY_CALLDATA: &str = "5fbfb9cf000000000000000000000000b16db98b365b1f89191996942612b14f1da4bd5f0000000000000000000000000000000000000000000000000000000000000000";
+/// Counterfactual address of the smart contract account.
+const ERC6492_SIGNER: &str = "0x4bbeEB066eD09B7AEd07bF39EEe0460DFa261520";
+/// There is no canonical `UniversalSigValidator` deployment, so any address can be used in tests.
+const ERC6492_VALIDATOR: &str = "0x0000000000000000000000000000000000006492";
+const ERC6492_SIGNATURE: &str = "000000000000000000000000f471789937856d80e589f5996cf8b0511ddd9de4000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000e000000000000000000000000000000000000000000000000000000000000000445fbfb9cf000000000000000000000000b16db98b365b1f89191996942612b14f1da4bd5f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000041934696764b38ec41cbabbb074aa6ba9d0a072e9392887fcdd70ce90a619d4b8641628a805cd1c1e727092bcb76db1d46061429617fa5aeb74fc5326afcd1d08f1c000000000000000000000000000000000000000000000000000000000000006492649264926492649264926492649264926492649264926492649264926492";
+
+#[test]
+fn test_message_signer_sign_erc6492() {
+    let coin = TestCoinContext::default();
+
+    let private_key = "4646464646464646464646464646464646464646464646464646464646464646";
+    let signing_input = Proto::MessageSigningInput {
+        private_key: private_key.decode_hex().unwrap().into(),
+        message: "Foo".into(),
+        message_type: Proto::MessageType::MessageType_legacy,
+        erc6492: Some(Proto::Erc6492Wrapper {
+            factory: ERC6492_FACTORY.into(),
+            factory_calldata: ERC6492_FACTORY_CALLDATA.decode_hex().unwrap().into(),
+        }),
+        ..Proto::MessageSigningInput::default()
+    };
+
+    let output = EthMessageSigner.sign_message(&coin, signing_input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+    assert_eq!(output.signature, ERC6492_SIGNATURE);
+
+    // ERC-6492 signature can't be verified offline.
+    let public_key = secp256k1::PrivateKey::try_from(private_key)
+        .unwrap()
+        .public();
+    let verifying_input = Proto::MessageVerifyingInput {
+        message: "Foo".into(),
+        public_key: public_key.compressed().to_vec().into(),
+        signature: ERC6492_SIGNATURE.into(),
+    };
+    assert!(!EthMessageSigner.verify_message(&coin, verifying_input));
+}
+
+#[test]
+fn test_message_signer_prepare_contract_signature_verification_erc6492() {
+    let input = Proto::ContractSignatureVerifyingInput {
+        message: "Foo".into(),
+        signature: ERC6492_SIGNATURE.into(),
+        signer: ERC6492_SIGNER.into(),
+        validator: ERC6492_VALIDATOR.into(),
+    };
+
+    let output = EthMessageSigner::prepare_contract_signature_verification(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    assert_eq!(
+        output.message_hash.to_hex(),
+        "0af844076e792f9685560b2e597967da7403b00a5339b5801ea251ddde375f8a"
+    );
+    assert_eq!(output.signature.to_hex(), "934696764b38ec41cbabbb074aa6ba9d0a072e9392887fcdd70ce90a619d4b8641628a805cd1c1e727092bcb76db1d46061429617fa5aeb74fc5326afcd1d08f1c");
+
+    let erc6492 = output.erc6492.unwrap();
+    assert_eq!(erc6492.factory, ERC6492_FACTORY);
+    assert_eq!(erc6492.factory_calldata.to_hex(), ERC6492_FACTORY_CALLDATA);
+
+    // `isValidSig(signer, hash, wrapped_signature)`
+    assert_eq!(output.call_type, Proto::ContractSignatureCallType::ERC6492);
+    assert_eq!(output.call_to, ERC6492_VALIDATOR);
+    assert_eq!(output.call_data.to_hex(), "98ef1ed80000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa2615200af844076e792f9685560b2e597967da7403b00a5339b5801ea251ddde375f8a00000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000180000000000000000000000000f471789937856d80e589f5996cf8b0511ddd9de4000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000e000000000000000000000000000000000000000000000000000000000000000445fbfb9cf000000000000000000000000b16db98b365b1f89191996942612b14f1da4bd5f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000041934696764b38ec41cbabbb074aa6ba9d0a072e9392887fcdd70ce90a619d4b8641628a805cd1c1e727092bcb76db1d46061429617fa5aeb74fc5326afcd1d08f1c000000000000000000000000000000000000000000000000000000000000006492649264926492649264926492649264926492649264926492649264926492");
+}
+
+#[test]
+fn test_message_signer_prepare_contract_signature_verification_deployed() {
+    let signature = "934696764b38ec41cbabbb074aa6ba9d0a072e9392887fcdd70ce90a619d4b8641628a805cd1c1e727092bcb76db1d46061429617fa5aeb74fc5326afcd1d08f1c";
+    let input = Proto::ContractSignatureVerifyingInput {
+        message: "Foo".into(),
+        signature: signature.into(),
+        signer: ERC6492_SIGNER.into(),
+        // Not needed to verify an unwrapped signature.
+        validator: "".into(),
+    };
+
+    let output = EthMessageSigner::prepare_contract_signature_verification(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert_eq!(output.signature.to_hex(), signature);
+    assert!(output.erc6492.is_none());
+    // `isValidSignature(hash, signature)`
+    assert_eq!(output.call_type, Proto::ContractSignatureCallType::ERC1271);
+    assert_eq!(output.call_to, ERC6492_SIGNER);
+    assert_eq!(output.call_data.to_hex(), "1626ba7e0af844076e792f9685560b2e597967da7403b00a5339b5801ea251ddde375f8a00000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000041934696764b38ec41cbabbb074aa6ba9d0a072e9392887fcdd70ce90a619d4b8641628a805cd1c1e727092bcb76db1d46061429617fa5aeb74fc5326afcd1d08f1c00000000000000000000000000000000000000000000000000000000000000");
+}
+
+#[test]
+fn test_message_signer_prepare_contract_signature_verification_invalid() {
+    // The magic suffix is present, but the wrapped data is truncated.
+    let input = Proto::ContractSignatureVerifyingInput {
+        message: "Foo".into(),
+        signature: "000000000000000000000000f471789937856d80e589f5996cf8b0511ddd9de46492649264926492649264926492649264926492649264926492649264926492".into(),
+        signer: ERC6492_SIGNER.into(),
+        validator: ERC6492_VALIDATOR.into(),
+    };
+    let output = EthMessageSigner::prepare_contract_signature_verification(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+    assert!(!output.error_message.is_empty());
+
+    // The signer address must be specified.
+    let input = Proto::ContractSignatureVerifyingInput {
+        message: "Foo".into(),
+        signature: ERC6492_SIGNATURE.into(),
+        signer: "".into(),
+        validator: ERC6492_VALIDATOR.into(),
+    };
+    let output = EthMessageSigner::prepare_contract_signature_verification(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_address);
+    assert!(!output.error_message.is_empty());
+
+    // The validator address must be specified to verify a wrapped signature.
+    let input = Proto::ContractSignatureVerifyingInput {
+        message: "Foo".into(),
+        signature: ERC6492_SIGNATURE.into(),
+        signer: ERC6492_SIGNER.into(),
+        validator: "".into(),
+    };
+    let output = EthMessageSigner::prepare_contract_signature_verification(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_address);
+    assert!(!output.error_message.is_empty());
+}
+
+#[test]
+fn test_message_signer_decode_contract_signature_result() {
+    let decode = |call_type, call_result: &str| {
+        let input = Proto::ContractSignatureResultInput {
+            call_type,
+            call_result: call_result.decode_hex().unwrap().into(),
+        };
+        EthMessageSigner::decode_contract_signature_result(input)
+    };
+
+    // `isValidSignature` returns the magic value `bytes4`.
+    let output = decode(
+        Proto::ContractSignatureCallType::ERC1271,
+        "1626ba7e00000000000000000000000000000000000000000000000000000000",
+    );
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.valid);
+
+    let output = decode(
+        Proto::ContractSignatureCallType::ERC1271,
+        "ffffffff00000000000000000000000000000000000000000000000000000000",
+    );
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(!output.valid);
+
+    // `isValidSig` returns `bool`.
+    let output = decode(
+        Proto::ContractSignatureCallType::ERC6492,
+        "0000000000000000000000000000000000000000000000000000000000000001",
+    );
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.valid);
+
+    let output = decode(
+        Proto::ContractSignatureCallType::ERC6492,
+        "0000000000000000000000000000000000000000000000000000000000000000",
+    );
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(!output.valid);
+
+    // No data is returned if the signer is not a deployed contract.
+    let output = decode(Proto::ContractSignatureCallType::ERC1271, "");
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(!output.valid);
+
+    let output = decode(Proto::ContractSignatureCallType::ERC6492, "");
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(!output.valid);
+
+    // Malformed data is an error.
+    let output = decode(Proto::ContractSignatureCallType::ERC6492, "01");
+    assert_ne!(output.error, SigningErrorType::OK);
+    assert!(!output.error_message.is_empty());
+}
@@ -0,0 +1,235 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use std::borrow::Cow;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::modules::message_signer::MessageSigner;
+use tw_coin_entry::test_utils::test_context::TestCoinContext;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_evm::modules::message_signer::EthMessageSigner;
+use tw_number::U256;
+use tw_proto::Ethereum::Proto;
+use Proto::mod_Permit::OneOfpermit_oneof as PermitType;
+
+const PRIVATE_KEY: &str = "4646464646464646464646464646464646464646464646464646464646464646";
+const OWNER: &str = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+const DAI: &str = "0x6B175474E89094C44Da98b954EedeAC495271d0F";
+const ONE_INCH_ROUTER: &str = "0x1111111254EEB25477B68fb85Ed929f73A960582";
+const UNIVERSAL_ROUTER: &str = "0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD";
+const DEADLINE: u64 = 1_700_000_000;
+
+fn u256(value: u64) -> Cow<'static, [u8]> {
+    U256::encode_be_compact(value)
+}
+
+fn permit_input(permit: PermitType<'static>) -> Proto::MessageSigningInput<'static> {
+    Proto::MessageSigningInput {
+        private_key: PRIVATE_KEY.decode_hex().unwrap().into(),
+        message_type: Proto::MessageType::MessageType_typed,
+        chain_id: Some(Proto::MaybeChainId { chain_id: 1 }),
+        permit: Some(Proto::Permit {
+            permit_oneof: permit,
+        }),
+        ..Proto::MessageSigningInput::default()
+    }
+}
+
+fn usdc_permit() -> PermitType<'static> {
+    PermitType::erc2612(Proto::ERC2612Permit {
+        domain: Some(Proto::PermitDomain {
+            name: "USD Coin".into(),
+            version: "2".into(),
+            verifying_contract: USDC.into(),
+        }),
+        owner: OWNER.into(),
+        spender: ONE_INCH_ROUTER.into(),
+        value: u256(1_000_000),
+        nonce: u256(0),
+        deadline: u256(DEADLINE),
+    })
+}
+
+fn permit2_details(
+    token: &'static str,
+    amount: Vec<u8>,
+    nonce: u64,
+) -> Proto::Permit2Details<'static> {
+    Proto::Permit2Details {
+        token: token.into(),
+        amount: amount.into(),
+        expiration: DEADLINE,
+        nonce,
+    }
+}
+
+#[track_caller]
+fn assert_preimage_hash(permit: PermitType<'static>, expected: &str) {
+    let coin = TestCoinContext::default();
+    let output = EthMessageSigner.message_preimage_hashes(&coin, permit_input(permit));
+    assert_eq!(
+        output.error,
+        SigningErrorType::OK,
+        "{}",
+        output.error_message
+    );
+    assert_eq!(output.data_hash.to_hex(), expected);
+}
+
+#[track_caller]
+fn assert_sign_error(input: Proto::MessageSigningInput<'static>, expected: SigningErrorType) {
+    let coin = TestCoinContext::default();
+    let output = EthMessageSigner.sign_message(&coin, input);
+    assert_eq!(output.error, expected);
+    assert!(!output.error_message.is_empty());
+}
+
+#[test]
+fn test_sign_erc2612_permit() {
+    assert_preimage_hash(
+        usdc_permit(),
+        "5c0dfb1e6069919a5de7a9e17f1eed8dd3d3871ea94a3e61f89394cdc4452505",
+    );
+
+    let coin = TestCoinContext::default();
+    let output = EthMessageSigner.sign_message(&coin, permit_input(usdc_permit()));
+    assert_eq!(
+        output.error,
+        SigningErrorType::OK,
+        "{}",
+        output.error_message
+    );
+    assert_eq!(output.signature, "c1691ea7fb21a61f570bc559d43582d995600d855c9368ba5e09c4bf575f6cec1d28208c0eece09f4a61cfb44b276cb9a91ca8df423b648236aff0bf947124241b");
+}
+
+#[test]
+fn test_dai_permit_preimage_hash() {
+    let permit = PermitType::dai(Proto::DaiPermit {
+        domain: Some(Proto::PermitDomain {
+            name: "Dai Stablecoin".into(),
+            version: "1".into(),
+            verifying_contract: DAI.into(),
+        }),
+        holder: OWNER.into(),
+        spender: ONE_INCH_ROUTER.into(),
+        nonce: u256(0),
+        expiry: u256(0),
+        allowed: true,
+    });
+    assert_preimage_hash(
+        permit,
+        "f6a2e2c7832d25ef4f0fc1ce3cf82d09360525ac285f0439b27bc0146b7cb0c6",
+    );
+}
+
+#[test]
+fn test_sign_permit2_single() {
+    // Max `uint160` allowance.
+    let max_amount = "ffffffffffffffffffffffffffffffffffffffff"
+        .decode_hex()
+        .unwrap();
+    let permit = || {
+        PermitType::permit2_single(Proto::Permit2Single {
+            details: Some(permit2_details(USDC, max_amount.clone(), 0)),
+            spender: UNIVERSAL_ROUTER.into(),
+            sig_deadline: u256(DEADLINE),
+            ..Proto::Permit2Single::default()
+        })
+    };
+
+    assert_preimage_hash(
+        permit(),
+        "e185f8267b5ee2928c949c556f93059dd0c6f32db74a4199cce69c544452a0bb",
+    );
+
+    let coin = TestCoinContext::default();
+    let output = EthMessageSigner.sign_message(&coin, permit_input(permit()));
+    assert_eq!(
+        output.error,
+        SigningErrorType::OK,
+        "{}",
+        output.error_message
+    );
+    assert_eq!(output.signature, "4b8e83aa72ad22bafc460663e352afdbc3876ffcf765af7ae7770cdc7548bcf218fb55b1a64bfbf9d300604047023a29094b291645fbe257f478ce4a115860331c");
+}
+
+#[test]
+fn test_permit2_batch_preimage_hash() {
+    let permit = PermitType::permit2_batch(Proto::Permit2Batch {
+        details: vec![
+            permit2_details(USDC, u256(1_000_000).to_vec(), 1),
+            permit2_details(DAI, U256::from(10u64.pow(18)).to_big_endian_compact(), 2),
+        ],
+        spender: UNIVERSAL_ROUTER.into(),
+        sig_deadline: u256(DEADLINE),
+        // Canonical Permit2 address set explicitly.
+        permit2_contract: "0x000000000022D473030F116dDEE9F6B43aC78BA3".into(),
+    });
+    assert_preimage_hash(
+        permit,
+        "7c946cc0bf2b18e8c87afc8afdf699ae2aba73cfb380c61f52403d476816df88",
+    );
+}
+
+#[test]
+fn test_permit2_transfer_from_preimage_hash() {
+    let permit = PermitType::permit2_transfer_from(Proto::Permit2TransferFrom {
+        token: USDC.into(),
+        amount: u256(1_000_000),
+        spender: UNIVERSAL_ROUTER.into(),
+        nonce: u256(42),
+        deadline: u256(DEADLINE),
+        ..Proto::Permit2TransferFrom::default()
+    });
+    assert_preimage_hash(
+        permit,
+        "4d211fec2f08f0463390ae0ea8946361c769f272f104946f74d512f960cc12e9",
+    );
+}
+
+#[test]
+fn test_permit_errors() {
+    // Permit must be signed as a typed message.
+    let input = Proto::MessageSigningInput {
+        message_type: Proto::MessageType::MessageType_legacy,
+        ..permit_input(usdc_permit())
+    };
+    assert_sign_error(input, SigningErrorType::Error_invalid_params);
+
+    // Chain ID is required.
+    let input = Proto::MessageSigningInput {
+        chain_id: None,
+        ..permit_input(usdc_permit())
+    };
+    assert_sign_error(input, SigningErrorType::Error_invalid_params);
+
+    // Permit2 amount does not fit `uint160`.
+    let too_big_amount = "010000000000000000000000000000000000000000"
+        .decode_hex()
+        .unwrap();
+    let permit = PermitType::permit2_single(Proto::Permit2Single {
+        details: Some(permit2_details(USDC, too_big_amount, 0)),
+        spender: UNIVERSAL_ROUTER.into(),
+        sig_deadline: u256(DEADLINE),
+        ..Proto::Permit2Single::default()
+    });
+    assert_sign_error(permit_input(permit), SigningErrorType::Error_invalid_params);
+
+    // Permit2 nonce does not fit `uint48`.
+    let permit = PermitType::permit2_single(Proto::Permit2Single {
+        details: Some(permit2_details(USDC, u256(1).to_vec(), 1 << 48)),
+        spender: UNIVERSAL_ROUTER.into(),
+        sig_deadline: u256(DEADLINE),
+        ..Proto::Permit2Single::default()
+    });
+    assert_sign_error(permit_input(permit), SigningErrorType::Error_invalid_params);
+
+    // Domain is required.
+    let permit = PermitType::erc2612(Proto::ERC2612Permit {
+        owner: OWNER.into(),
+        spender: ONE_INCH_ROUTER.into(),
+        ..Proto::ERC2612Permit::default()
+    });
+    assert_sign_error(permit_input(permit), SigningErrorType::Error_invalid_params);
+}
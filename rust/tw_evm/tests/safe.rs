@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use std::str::FromStr;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_evm::abi::prebuild::safe::{MultiSendArgs, Safe, SafeMultiSend, SafeOperation};
+use tw_evm::address::Address;
+use tw_evm::message::safe::signature::{encode_signatures, SafeSignature};
+use tw_evm::message::safe::{SafeTx, SafeVersion};
+use tw_evm::message::EthMessage;
+use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
+use tw_number::U256;
+
+const SAFE: &str = "0x8b2ed2c7eE3Dd1ec1F0DbBb26C8D0B7C5E2CC52f";
+const RECIPIENT: &str = "0x4bbeEB066eD09B7AEd07bF39EEe0460DFa261520";
+const DAI: &str = "0x6B175474E89094C44Da98b954EedeAC495271d0F";
+const MULTI_SEND_CALL_ONLY: &str = "0x40A2aCCbd92BCA938b02010E17A5b8929b49130D";
+/// `transfer(0x4bbeEB066eD09B7AEd07bF39EEe0460DFa261520, 10)`
+const TRANSFER_DATA: &str = "a9059cbb0000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa261520000000000000000000000000000000000000000000000000000000000000000a";
+
+fn transfer_safe_tx(version: SafeVersion) -> SafeTx {
+    SafeTx {
+        safe: Address::from(SAFE),
+        chain_id: U256::from(1u64),
+        version,
+        to: Address::from(RECIPIENT),
+        value: U256::from(100_000_000_000_000_000u64),
+        data: TRANSFER_DATA.decode_hex().unwrap(),
+        operation: SafeOperation::Call,
+        safe_tx_gas: U256::from(50_000u64),
+        base_gas: U256::from(21_000u64),
+        gas_price: U256::zero(),
+        gas_token: Address::default(),
+        refund_receiver: Address::default(),
+        nonce: U256::from(7u64),
+    }
+}
+
+fn private_key(key: &str) -> secp256k1::PrivateKey {
+    secp256k1::PrivateKey::try_from(key).unwrap()
+}
+
+#[test]
+fn test_safe_version_from_str() {
+    assert_eq!(SafeVersion::from_str("0.1.0").unwrap(), SafeVersion::V0_1_0);
+    assert_eq!(SafeVersion::from_str("1.1.1").unwrap(), SafeVersion::V1_0_0);
+    assert_eq!(SafeVersion::from_str("1.2.0").unwrap(), SafeVersion::V1_0_0);
+    assert_eq!(
+        SafeVersion::from_str("1.3.0+L2").unwrap(),
+        SafeVersion::V1_3_0
+    );
+    assert_eq!(SafeVersion::from_str("1.4.1").unwrap(), SafeVersion::V1_3_0);
+    SafeVersion::from_str("latest").unwrap_err();
+}
+
+#[test]
+fn test_safe_tx_hash() {
+    let hash_v1_3 = transfer_safe_tx(SafeVersion::V1_3_0).hash().unwrap();
+    assert_eq!(
+        hash_v1_3.to_hex(),
+        "104164b78bc0b18f230664f772be166ca3c19c7482e5d738dc80529eb78ffb00"
+    );
+
+    // The domain doesn't contain `chainId`.
+    let hash_v1_0 = transfer_safe_tx(SafeVersion::V1_0_0).hash().unwrap();
+    assert_eq!(
+        hash_v1_0.to_hex(),
+        "f4bf057cd68004cd0e58b10ce26a71140e34c4824dba989c9d1d6e684813493f"
+    );
+
+    // `SafeTx` contains `dataGas` instead of `baseGas`.
+    let hash_v0_1 = transfer_safe_tx(SafeVersion::V0_1_0).hash().unwrap();
+    assert_eq!(
+        hash_v0_1.to_hex(),
+        "9a108e1dc8bfeb5ae09485728c5d15f09ac6959f586690ee6bef2de44ae16295"
+    );
+}
+
+#[test]
+fn test_safe_exec_transaction() {
+    let safe_tx = transfer_safe_tx(SafeVersion::V1_3_0);
+    let safe_tx_hash = safe_tx.hash().unwrap();
+
+    let eth_sign = SafeSignature::sign_eth_sign(
+        &private_key("4646464646464646464646464646464646464646464646464646464646464646"),
+        safe_tx_hash,
+    )
+    .unwrap();
+    assert_eq!(
+        eth_sign.owner(),
+        Address::from("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F")
+    );
+    assert_eq!(eth_sign.to_bytes().to_hex(), "894800c12834ba183a7efc7c20923b3dd5238b33e511fb84d1e1d4bf4f20def225f6b190fa660b9f5a809b85d6aecbc373d7a725f2e0d46a55e9d0e14a27b5f320");
+
+    let ecdsa = SafeSignature::sign_ecdsa(
+        &private_key("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"),
+        safe_tx_hash,
+    )
+    .unwrap();
+    assert_eq!(
+        ecdsa.owner(),
+        Address::from("0x2c7536E3605D9C16a7a3D7b1898e529396a65c23")
+    );
+    assert_eq!(ecdsa.to_bytes().to_hex(), "3f1d844e3557af03ae54137a3d91d5a8345540d1322e14f5e024d65c8f33a0273102b2873ce3211f53402262fea900452bbb4f4171ee9c65fd0acda357f1cf641c");
+
+    let pre_validated = SafeSignature::pre_validated(Address::from(RECIPIENT));
+    assert_eq!(pre_validated.to_bytes().to_hex(), "0000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa261520000000000000000000000000000000000000000000000000000000000000000001");
+
+    // Signatures must be sorted by the owner addresses.
+    let signatures = encode_signatures([eth_sign, pre_validated, ecdsa]).unwrap();
+    assert_eq!(signatures.to_hex(), "3f1d844e3557af03ae54137a3d91d5a8345540d1322e14f5e024d65c8f33a0273102b2873ce3211f53402262fea900452bbb4f4171ee9c65fd0acda357f1cf641c0000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa261520000000000000000000000000000000000000000000000000000000000000000001894800c12834ba183a7efc7c20923b3dd5238b33e511fb84d1e1d4bf4f20def225f6b190fa660b9f5a809b85d6aecbc373d7a725f2e0d46a55e9d0e14a27b5f320");
+
+    let call_data =
+        Safe::encode_exec_transaction(safe_tx.exec_transaction_args(signatures)).unwrap();
+    assert_eq!(call_data.to_hex(), "6a7612020000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa261520000000000000000000000000000000000000000000000000016345785d8a000000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c350000000000000000000000000000000000000000000000000000000000000520800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001c00000000000000000000000000000000000000000000000000000000000000044a9059cbb0000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa261520000000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c33f1d844e3557af03ae54137a3d91d5a8345540d1322e14f5e024d65c8f33a0273102b2873ce3211f53402262fea900452bbb4f4171ee9c65fd0acda357f1cf641c0000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa261520000000000000000000000000000000000000000000000000000000000000000001894800c12834ba183a7efc7c20923b3dd5238b33e511fb84d1e1d4bf4f20def225f6b190fa660b9f5a809b85d6aecbc373d7a725f2e0d46a55e9d0e14a27b5f3200000000000000000000000000000000000000000000000000000000000");
+
+    let approve_hash = Safe::encode_approve_hash(safe_tx_hash).unwrap();
+    assert_eq!(
+        approve_hash.to_hex(),
+        "d4d9bdcd104164b78bc0b18f230664f772be166ca3c19c7482e5d738dc80529eb78ffb00"
+    );
+}
+
+#[test]
+fn test_safe_duplicate_signatures() {
+    let owner = Address::from(RECIPIENT);
+    encode_signatures([
+        SafeSignature::pre_validated(owner),
+        SafeSignature::pre_validated(owner),
+    ])
+    .unwrap_err();
+}
+
+#[test]
+fn test_safe_multi_send() {
+    let calls = [
+        MultiSendArgs {
+            operation: SafeOperation::Call,
+            to: Address::from(RECIPIENT),
+            value: U256::from(1_000_000_000_000_000_000u64),
+            data: Vec::default(),
+        },
+        MultiSendArgs {
+            operation: SafeOperation::Call,
+            to: Address::from(DAI),
+            value: U256::zero(),
+            data: TRANSFER_DATA.decode_hex().unwrap(),
+        },
+    ];
+    let multi_send_data = SafeMultiSend::encode_multi_send(calls).unwrap();
+    assert_eq!(multi_send_data.to_hex(), "8d80ff0a000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000ee004bbeeb066ed09b7aed07bf39eee0460dfa2615200000000000000000000000000000000000000000000000000de0b6b3a76400000000000000000000000000000000000000000000000000000000000000000000006b175474e89094c44da98b954eedeac495271d0f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000044a9059cbb0000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa261520000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000");
+
+    let safe_tx = SafeTx {
+        safe: Address::from(SAFE),
+        chain_id: U256::from(137u64),
+        version: SafeVersion::V1_3_0,
+        to: Address::from(MULTI_SEND_CALL_ONLY),
+        value: U256::zero(),
+        data: multi_send_data,
+        operation: SafeOperation::DelegateCall,
+        safe_tx_gas: U256::zero(),
+        base_gas: U256::zero(),
+        gas_price: U256::zero(),
+        gas_token: Address::default(),
+        refund_receiver: Address::default(),
+        nonce: U256::zero(),
+    };
+    assert_eq!(
+        safe_tx.hash().unwrap(),
+        H256::from("f096ffc2da99721952c35e7f19a7c74f4b6ebbdd92280fd566b4b201d6070a15")
+    );
+}
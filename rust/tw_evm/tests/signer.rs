@@ -7,6 +7,7 @@ use tw_coin_entry::error::prelude::*;
 use tw_encoding::hex::{self, ToHex};
 use tw_evm::evm_context::StandardEvmContext;
 use tw_evm::modules::signer::Signer;
+use tw_hash::sha2::sha256;
 use tw_number::U256;
 use tw_proto::Ethereum::Proto;
 use tw_proto::Ethereum::Proto::TransactionMode;
@@ -643,3 +644,313 @@ fn test_sign_transaction_non_typed_erc1155_transfer() {
     let expected_data = "f242432a000000000000000000000000718046867b5b1782379a14ea4fc0c9b724da94fc0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000000000000023c47ee50000000000000000000000000000000000000000000000001bc16d674ec8000000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000040102030400000000000000000000000000000000000000000000000000000000";
     assert_eq!(hex::encode(output.data, false), expected_data);
 }
+
+#[test]
+fn test_sign_transaction_eip7702_self_authorization() {
+    let private_key = parse_hex("4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904");
+
+    // The authorization is signed by the same account, so its nonce is `tx.nonce + 1`.
+    let authorization = Proto::Authorization {
+        chain_id: U256::encode_be_compact(1),
+        address: "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B".into(),
+        nonce: U256::encode_be_compact(1),
+        signature: Cow::default(),
+    };
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(0),
+        tx_mode: TransactionMode::SetCode,
+        gas_limit: U256::encode_be_compact(100_000),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(2_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(3_000_000_000),
+        to_address: "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        authorization_list: vec![authorization],
+        private_key,
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    let expected = "04f8c90180847735940084b2d05e00830186a094b9f5771c27664bf2282d98e09d7f50cec7cb01a78080c0f85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0101a06e68a9eb48d83104756e83bcf868b9d8f8fa21097026ecf9035ae2be4314f697a0731e64a7ac248a0eaff0c35096e44e69539944449f533af8948ca87cdd72c92201a0048634c8c88bb1b6b97fbf90eb823bd045003eb182c78b8abe5ca4ed001063c1a0129ecad8f9a2d25bc698ccab91b1af089a8260c29d88aa2d342d9487cc34b3db";
+    assert_eq!(hex::encode(output.encoded, false), expected);
+    assert_eq!(output.v.to_hex(), "01");
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "d750c6b71f1e01cc06157bff18a3f881f38d8f9c6db2184cd9405f0adeb635c7"
+    );
+}
+
+#[test]
+fn test_sign_transaction_eip7702_external_authorization() {
+    let private_key = parse_hex("4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904");
+
+    // Authorization signed by "0xaa9d131e33158be2cb1603b61fd17ad4f9e178ce" for any chain.
+    let authorization = Proto::Authorization {
+        chain_id: Cow::default(),
+        address: "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B".into(),
+        nonce: U256::encode_be_compact(5),
+        signature: parse_hex("f93e7add14f86d8ff1a99280d97ac7f893c15153bf3a4c43e0a5419b7ee7eeb86c081293c6ff00d6a60019ead6316c247589181bfd1a0ca6aae4829a8549f07201"),
+    };
+
+    let contract_generic = Proto::mod_Transaction::ContractGeneric {
+        amount: U256::encode_be_compact(0),
+        data: parse_hex("a9059cbb"),
+    };
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(3),
+        tx_mode: TransactionMode::SetCode,
+        gas_limit: U256::encode_be_compact(100_000),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(2_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(3_000_000_000),
+        to_address: "0xaa9d131e33158be2cb1603b61fd17ad4f9e178ce".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::contract_generic(
+                contract_generic,
+            ),
+        }),
+        authorization_list: vec![authorization],
+        private_key,
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    let expected = "04f8cd0103847735940084b2d05e00830186a094aa9d131e33158be2cb1603b61fd17ad4f9e178ce8084a9059cbbc0f85cf85a809463c0c19a282a1b52b07dd5a65b58948a07dae32b0501a0f93e7add14f86d8ff1a99280d97ac7f893c15153bf3a4c43e0a5419b7ee7eeb8a06c081293c6ff00d6a60019ead6316c247589181bfd1a0ca6aae4829a8549f07201a010b0d6aca53190664b569120d58151a1c8b07a585062a444438f38d63ad291caa007d45ad1af7902186024b6f81919363a41607fcb9fd4f25731a565d7c242d0ae";
+    assert_eq!(hex::encode(output.encoded, false), expected);
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "15f4e41acef1e649aebf32094e442d08dae3b0c5849e302c2f726ecf7c96ee76"
+    );
+}
+
+#[test]
+fn test_sign_transaction_eip7702_no_authorizations() {
+    let private_key = parse_hex("4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904");
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        tx_mode: TransactionMode::SetCode,
+        gas_limit: U256::encode_be_compact(100_000),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(2_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(3_000_000_000),
+        to_address: "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        private_key,
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+    assert!(!output.error_message.is_empty());
+}
+
+fn blob_transaction_input(
+    blob_versioned_hashes: Vec<Cow<'static, [u8]>>,
+    blob_sidecar: Option<Proto::BlobSidecar<'static>>,
+) -> Proto::SigningInput<'static> {
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+
+    Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(2),
+        tx_mode: TransactionMode::Blob,
+        gas_limit: U256::encode_be_compact(21_000),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(50_000_000_000),
+        max_fee_per_blob_gas: U256::encode_be_compact(3_000_000_000),
+        to_address: "0x6b175474e89094c44da98b954eedeac495271d0f".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        blob_versioned_hashes,
+        blob_sidecar,
+        private_key: parse_hex("4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904"),
+        ..Proto::SigningInput::default()
+    }
+}
+
+/// Sidecar of a single zero blob. KZG commitment and proofs of the zero blob are the point at infinity.
+fn zero_blob_sidecar(
+    version: Proto::BlobSidecarVersion,
+    proofs_count: usize,
+) -> Proto::BlobSidecar<'static> {
+    let mut point_at_infinity = vec![0; 48];
+    point_at_infinity[0] = 0xc0;
+
+    Proto::BlobSidecar {
+        blobs: vec![vec![0; 131_072].into()],
+        commitments: vec![point_at_infinity.clone().into()],
+        proofs: vec![point_at_infinity.into(); proofs_count],
+        version,
+    }
+}
+
+const ZERO_BLOB_VERSIONED_HASH: &str =
+    "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014";
+const SIGNED_BLOB_TRANSACTION: &str = "03f8920102843b9aca00850ba43b7400825208946b175474e89094c44da98b954eedeac495271d0f8080c084b2d05e00e1a0010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c44401401a035737198eeb70b4d876bbf8d996384a81631e84849b0b8354f92debd84ba2a1ba0604c015a93cd243baa0c82a91799fa83098c32b0d887f470b2b411db718b1fab";
+
+#[test]
+fn test_sign_transaction_eip4844() {
+    let input = blob_transaction_input(vec![parse_hex(ZERO_BLOB_VERSIONED_HASH)], None);
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    assert_eq!(hex::encode(output.encoded, false), SIGNED_BLOB_TRANSACTION);
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "031e58c8f284529ab1f0cd0d5c789995f9364e5372b11b3b15fcc466f4b5c7a7"
+    );
+    // No blobs specified, so the network encoding is not available.
+    assert!(output.encoded_network.is_empty());
+}
+
+#[test]
+fn test_sign_transaction_eip4844_with_sidecar() {
+    // Blob versioned hashes are computed from the KZG commitments.
+    let input = blob_transaction_input(
+        Vec::default(),
+        Some(zero_blob_sidecar(Proto::BlobSidecarVersion::BlobProofs, 1)),
+    );
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    assert_eq!(hex::encode(&output.encoded, false), SIGNED_BLOB_TRANSACTION);
+
+    // 0x03 || rlp([tx_payload_body, blobs, commitments, proofs])
+    assert_eq!(output.encoded_network.len(), 131_333);
+    assert_eq!(
+        hex::encode(&output.encoded_network[..12], false),
+        "03fa020100f8920102843b9a"
+    );
+    assert_eq!(
+        sha256(&output.encoded_network).to_hex(),
+        "676f55afec7d26ec075c6396dc2dbfa676c989e7376f11e26e65527f4bdcefba"
+    );
+}
+
+#[test]
+fn test_sign_transaction_eip4844_with_cell_proofs_sidecar() {
+    let sidecar = zero_blob_sidecar(Proto::BlobSidecarVersion::CellProofs, 128);
+    let input = blob_transaction_input(Vec::default(), Some(sidecar));
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    assert_eq!(hex::encode(&output.encoded, false), SIGNED_BLOB_TRANSACTION);
+
+    // 0x03 || rlp([tx_payload_body, wrapper_version, blobs, commitments, cell_proofs])
+    assert_eq!(output.encoded_network.len(), 137_559);
+    assert_eq!(
+        hex::encode(&output.encoded_network[..12], false),
+        "03fa021952f8920102843b9a"
+    );
+    assert_eq!(
+        sha256(&output.encoded_network).to_hex(),
+        "bffbadc806e3f7683715f9fc04a26f5dc3db32b6762ce7bd1dc6cc061d296960"
+    );
+}
+
+#[test]
+fn test_sign_transaction_eip4844_cell_proofs_count_mismatch() {
+    // EIP-7594 requires 128 cell proofs per blob.
+    let sidecar = zero_blob_sidecar(Proto::BlobSidecarVersion::CellProofs, 1);
+    let input = blob_transaction_input(Vec::default(), Some(sidecar));
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+    assert!(!output.error_message.is_empty());
+}
+
+#[test]
+fn test_sign_transaction_eip4844_hashes_mismatch_sidecar() {
+    let invalid_hash = "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444015";
+    let input = blob_transaction_input(
+        vec![parse_hex(invalid_hash)],
+        Some(zero_blob_sidecar(Proto::BlobSidecarVersion::BlobProofs, 1)),
+    );
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+    assert!(!output.error_message.is_empty());
+}
+
+#[test]
+fn test_sign_transaction_eip4844_no_blobs() {
+    let input = blob_transaction_input(Vec::default(), None);
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+    assert!(!output.error_message.is_empty());
+}
+
+#[test]
+fn test_sign_transaction_eip2930_native_transfer_with_access_list() {
+    let private_key = parse_hex("4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904");
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(543_210_987_654_321),
+        data: Cow::default(),
+    };
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(6),
+        tx_mode: TransactionMode::AccessList,
+        gas_price: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(30_000),
+        to_address: "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        access_list: vec![Proto::Access {
+            address: "0xdAC17F958D2ee523a2206206994597C13D831ec7".into(),
+            stored_keys: vec![parse_hex(
+                "76c8f33bcdf467e4f1313522c10a40512a867cdcd34f2b898232ad4669200764",
+            )],
+        }],
+        private_key,
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    let expected = "01f8a601068504a817c80082753094b9f5771c27664bf2282d98e09d7f50cec7cb01a78701ee0c29f50cb180f838f794dac17f958d2ee523a2206206994597c13d831ec7e1a076c8f33bcdf467e4f1313522c10a40512a867cdcd34f2b898232ad466920076401a0c50fe6525fcd346d7f885fdd0300a28797b97377b67e028780d327604d625bbda04d85aa5f32219818d995e5c433c4e6c452a35ccbb2dd2e69dc30b20e931e55c7";
+    assert_eq!(hex::encode(output.encoded, false), expected);
+    assert_eq!(output.v.to_hex(), "01");
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "8badb94305a85dc16deb95b0f7567c2668cc45c3961271926e5fb40f10d4c928"
+    );
+}
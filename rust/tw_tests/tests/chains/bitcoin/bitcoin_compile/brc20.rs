@@ -44,11 +44,13 @@ fn test_bitcoin_compile_brc20_transfer_commit() {
     let out_0 = Proto::Output {
         value: brc20_inscribe_amount,
         to_recipient: output::brc20_inscribe(my_pubkey.to_vec(), "oadf", "20"),
+        cash_token: None,
     };
 
     let explicit_change_out = Proto::Output {
         value: change_amount,
         to_recipient: output::p2wpkh(my_pubkey.to_vec()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -127,6 +129,7 @@ fn test_bitcoin_compile_brc20_transfer_reveal() {
         value: DUST,
         // Reveal to my address.
         to_recipient: output::p2wpkh(my_pubkey.to_vec()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
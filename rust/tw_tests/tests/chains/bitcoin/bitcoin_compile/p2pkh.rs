@@ -40,6 +40,7 @@ fn test_bitcoin_compile_p2pkh() {
     let out1 = Proto::Output {
         value: ONE_BTC * 50 - MINER_FEE,
         to_recipient: output::p2pkh(bob_pubkey.clone()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
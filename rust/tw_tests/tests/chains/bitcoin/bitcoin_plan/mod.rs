@@ -4,6 +4,8 @@
 
 mod plan_exact;
 mod plan_exact_error;
+mod plan_fee_bump;
 mod plan_max;
 mod plan_max_error;
 mod plan_psbt;
+mod plan_satisfaction_weight;
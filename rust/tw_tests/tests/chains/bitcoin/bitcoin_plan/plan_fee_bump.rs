@@ -0,0 +1,243 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    btc_info, input, output, plan, TransactionOneof, DUST, SIGHASH_ALL,
+};
+use tw_any_coin::test_utils::plan_utils::AnyPlannerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::DecodeHex;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::BitcoinV2::Proto::mod_FeeBump::mod_ChildPaysForParent::OneOfdust_policy as CpfpDustPolicy;
+use tw_proto::BitcoinV2::Proto::mod_FeeBump::mod_ReplaceByFee::{
+    OneOfchange as RbfChange, OneOfdust_policy as RbfDustPolicy,
+};
+use tw_proto::BitcoinV2::Proto::mod_FeeBump::OneOfvariant as FeeBumpType;
+use tw_proto::Common::Proto::SigningError;
+
+const MY_PUBKEY: &str = "03a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c";
+
+/// The original transaction spends `1f62c18bfc5f8293a2b7b061587c427bf830fb224289f9a806e6ad48de6a4c7d:0` (20_000 sats)
+/// and sends 10_000 sats to a recipient and 9_000 sats back to the sender, with 1_000 sats fee and 141 vsize.
+const RBF_ORIGINAL_TX: &str = "020000000001017d4c6ade48ade606a8f9894222fb30f87b427c5861b0b7a293825ffc8bc1621f0000000000fdffffff0210270000000000001600145360df8231ac5965147c9d90ca930a2aafb05232282300000000000016001460d7ee599766db323fb1916c7f9e5d818aaf8c1b024730111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111112103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c00000000";
+const RBF_ORIGINAL_UTXO: &str = "1f62c18bfc5f8293a2b7b061587c427bf830fb224289f9a806e6ad48de6a4c7d";
+
+/// The parent transaction sends 20_000 sats to the sender with 82 vsize.
+const CPFP_PARENT_TX: &str = "02000000018d0e94ed369601d13f32bd653071844dfe7139508d2e71d9c105d1faa58230b30000000000ffffffff01204e00000000000016001460d7ee599766db323fb1916c7f9e5d818aaf8c1b00000000";
+const CPFP_PARENT_TXID: &str = "4de90469a435ae3bd3483e2ef607fd57dcfcf130eb0fde7f82b029be7d6b2e4a";
+
+fn p2wpkh_utxo(txid: &str, vout: u32, value: i64) -> Proto::Input<'static> {
+    Proto::Input {
+        out_point: input::out_point(txid, vout),
+        value,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2wpkh(MY_PUBKEY.decode_hex().unwrap()),
+        ..Default::default()
+    }
+}
+
+fn rbf_input(rbf: Proto::mod_FeeBump::ReplaceByFee<'static>) -> Proto::SigningInput<'static> {
+    Proto::SigningInput {
+        chain_info: btc_info(),
+        transaction: TransactionOneof::fee_bump(Proto::FeeBump {
+            variant: FeeBumpType::replace_by_fee(rbf),
+        }),
+        ..Default::default()
+    }
+}
+
+fn cpfp_input(
+    cpfp: Proto::mod_FeeBump::ChildPaysForParent<'static>,
+) -> Proto::SigningInput<'static> {
+    Proto::SigningInput {
+        chain_info: btc_info(),
+        transaction: TransactionOneof::fee_bump(Proto::FeeBump {
+            variant: FeeBumpType::child_pays_for_parent(cpfp),
+        }),
+        ..Default::default()
+    }
+}
+
+fn rbf_with_change(fee_per_vb: i64) -> Proto::mod_FeeBump::ReplaceByFee<'static> {
+    Proto::mod_FeeBump::ReplaceByFee {
+        original_tx: RBF_ORIGINAL_TX.decode_hex().unwrap().into(),
+        inputs: vec![p2wpkh_utxo(RBF_ORIGINAL_UTXO, 0, 20_000)],
+        fee_per_vb,
+        change: RbfChange::change_output_index(1),
+        dust_policy: RbfDustPolicy::fixed_dust_threshold(DUST),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_plan_rbf_reduce_change() {
+    let input = rbf_input(rbf_with_change(10));
+
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![20_000],
+            outputs: vec![10_000, 8_590],
+            vsize_estimate: 141,
+            // vsize * fee_rate
+            fee_estimate: 1_410,
+            change: 8_590,
+        });
+}
+
+#[test]
+fn test_plan_rbf_additional_inputs() {
+    let mut rbf = rbf_with_change(100);
+    rbf.additional_inputs = vec![
+        p2wpkh_utxo(RBF_ORIGINAL_UTXO, 1, 15_000),
+        // Should not be selected.
+        p2wpkh_utxo(RBF_ORIGINAL_UTXO, 2, 30_000),
+    ];
+    let input = rbf_input(rbf);
+
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![20_000, 15_000],
+            outputs: vec![10_000, 4_100],
+            vsize_estimate: 209,
+            // vsize * fee_rate
+            fee_estimate: 20_900,
+            change: 4_100,
+        });
+}
+
+/// The `5d6bf53576a54be4d92cd8abf58d28ecc9ea7956eaf970d24d6bfcb9fcfe9855` transaction
+/// pays 7_097 sats fee with 178 vsize, and does not signal replaceability.
+const NON_RBF_ORIGINAL_TX: &str = "010000000001027d4c6ade48ade606a8f9894222fb30f87b427c5861b0b7a293825ffc8bc1621f0100000000ffffffff8d0e94ed369601d13f32bd653071844dfe7139508d2e71d9c105d1faa58230b30100000000ffffffff01836d0000000000001600145360df8231ac5965147c9d90ca930a2aafb0523202483045022100f95f9ac5d39f4b47dcd8c86daaaeac86374258d9960f922333ba0d5fdaa15b7e0220761794672dc9fbd71398d608f72f5d21a0f6c1306c6b700ad0d82f747c221062012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c02483045022100eb6ba0dcc64af61b2186b7efdab1ff03784d585ee03437f9a53875e93429db080220015a268d308436d3564b83ceaed90bc7272ca164016298ea855d1936568002a7012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c00000000";
+
+/// Replace the [`NON_RBF_ORIGINAL_TX`] transaction, considering its only output is the change.
+#[test]
+fn test_plan_rbf_incremental_relay_fee() {
+    let rbf = Proto::mod_FeeBump::ReplaceByFee {
+        original_tx: NON_RBF_ORIGINAL_TX.decode_hex().unwrap().into(),
+        inputs: vec![
+            p2wpkh_utxo(
+                "b33082a5fad105c1d9712e8d503971fe4d84713065bd323fd1019636ed940e8d",
+                1,
+                30_269,
+            ),
+            p2wpkh_utxo(
+                "1f62c18bfc5f8293a2b7b061587c427bf830fb224289f9a806e6ad48de6a4c7d",
+                1,
+                4_863,
+            ),
+        ],
+        // 40 sat/vB is higher than the original fee rate, but `40 * 178 = 7_120` is less than `7_097 + 178`.
+        fee_per_vb: 40,
+        change: RbfChange::change_output_index(0),
+        // The original transaction does not signal replaceability.
+        allow_full_rbf: true,
+        dust_policy: RbfDustPolicy::fixed_dust_threshold(DUST),
+        ..Default::default()
+    };
+    let input = rbf_input(rbf);
+
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![30_269, 4_863],
+            outputs: vec![27_834],
+            vsize_estimate: 178,
+            // The fee rate is raised to 41 sat/vB.
+            fee_estimate: 7_298,
+            change: 0,
+        });
+}
+
+#[test]
+fn test_plan_rbf_errors() {
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+
+    // The fee rate is not higher than the original one.
+    let plan = planner.plan(CoinType::Bitcoin, rbf_input(rbf_with_change(7)));
+    assert_eq!(plan.error, SigningError::Error_wrong_fee);
+
+    // The original UTXO is not provided.
+    let mut rbf = rbf_with_change(10);
+    rbf.inputs = vec![p2wpkh_utxo(RBF_ORIGINAL_UTXO, 1, 20_000)];
+    let plan = planner.plan(CoinType::Bitcoin, rbf_input(rbf));
+    assert_eq!(plan.error, SigningError::Error_missing_input_utxos);
+
+    // The change output index is out of bounds.
+    let mut rbf = rbf_with_change(10);
+    rbf.change = RbfChange::change_output_index(2);
+    let plan = planner.plan(CoinType::Bitcoin, rbf_input(rbf));
+    assert_eq!(plan.error, SigningError::Error_invalid_params);
+
+    // Not enough funds to pay the higher fee.
+    let plan = planner.plan(CoinType::Bitcoin, rbf_input(rbf_with_change(100)));
+    assert_eq!(plan.error, SigningError::Error_not_enough_utxos);
+
+    // The original transaction does not signal replaceability, and full-RBF is not allowed.
+    let rbf = Proto::mod_FeeBump::ReplaceByFee {
+        original_tx: NON_RBF_ORIGINAL_TX.decode_hex().unwrap().into(),
+        inputs: vec![
+            p2wpkh_utxo(
+                "b33082a5fad105c1d9712e8d503971fe4d84713065bd323fd1019636ed940e8d",
+                1,
+                30_269,
+            ),
+            p2wpkh_utxo(RBF_ORIGINAL_UTXO, 1, 4_863),
+        ],
+        fee_per_vb: 50,
+        change: RbfChange::change_output_index(0),
+        ..Default::default()
+    };
+    let plan = planner.plan(CoinType::Bitcoin, rbf_input(rbf));
+    assert_eq!(plan.error, SigningError::Error_invalid_params);
+}
+
+fn cpfp(parent_value: i64) -> Proto::mod_FeeBump::ChildPaysForParent<'static> {
+    Proto::mod_FeeBump::ChildPaysForParent {
+        parent_tx: CPFP_PARENT_TX.decode_hex().unwrap().into(),
+        parent_fee: 82,
+        parent_output: Some(p2wpkh_utxo(CPFP_PARENT_TXID, 0, parent_value)),
+        child_output: Some(Proto::Output {
+            value: 0,
+            to_recipient: output::p2wpkh(MY_PUBKEY.decode_hex().unwrap()),
+            cash_token: None,
+        }),
+        fee_per_vb: 20,
+        dust_policy: CpfpDustPolicy::fixed_dust_threshold(DUST),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_plan_cpfp() {
+    let input = cpfp_input(cpfp(20_000));
+
+    // The package fee is `(82 + 110) * 20 = 3_840`, so the child should pay at least `3_840 - 82 = 3_758`.
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![20_000],
+            outputs: vec![16_150],
+            vsize_estimate: 110,
+            // The child fee rate is raised to 35 sat/vB.
+            fee_estimate: 3_850,
+            change: 0,
+        });
+}
+
+#[test]
+fn test_plan_cpfp_errors() {
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+
+    // The parent output value does not match.
+    let plan = planner.plan(CoinType::Bitcoin, cpfp_input(cpfp(19_000)));
+    assert_eq!(plan.error, SigningError::Error_invalid_utxo_amount);
+
+    // The parent output does not reference the parent transaction.
+    let mut request = cpfp(20_000);
+    request.parent_output = Some(p2wpkh_utxo(RBF_ORIGINAL_UTXO, 0, 20_000));
+    let plan = planner.plan(CoinType::Bitcoin, cpfp_input(request));
+    assert_eq!(plan.error, SigningError::Error_invalid_utxo);
+}
@@ -34,11 +34,14 @@ fn test_bitcoin_plan_psbt_thorchain_swap_witness() {
         sighash_type: 1,
         sequence: input::sequence(u32::MAX),
         claiming_script: input::receiver_address("bc1qkyu3n8k8jmekl3pwvdl59k5w8enjp25akz2r3z"),
+        cash_token: None,
+        satisfaction_weight: None,
     };
 
     let out_0 = Proto::Output {
         value: 60_000,
         to_recipient: output::to_address("bc1q7g48qdshqd000aysws74pun2uzxrp598gcfum0"),
+        cash_token: None,
     };
     let out_1 = Proto::Output {
         value: 0,
@@ -48,10 +51,12 @@ fn test_bitcoin_plan_psbt_thorchain_swap_witness() {
                 .unwrap()
                 .into()
         ),
+        cash_token: None,
     };
     let out_2 = Proto::Output {
         value: 4_670,
         to_recipient: output::to_address("bc1qkyu3n8k8jmekl3pwvdl59k5w8enjp25akz2r3z"),
+        cash_token: None,
     };
 
     let expected = Proto::TransactionPlan {
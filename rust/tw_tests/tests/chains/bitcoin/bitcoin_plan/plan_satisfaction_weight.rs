@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    btc_info, dust_threshold, input, output, plan, TransactionOneof, DUST, SIGHASH_ALL,
+};
+use tw_any_coin::test_utils::plan_utils::AnyPlannerHelper;
+use tw_any_coin::test_utils::sign_utils::AnySignerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::DecodeHex;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::Common::Proto::SigningError;
+
+const ALICE_PUBKEY: &str = "037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf";
+const DUMMY_TXID: &str = "06953f715201d085d0607097bc7571fcebe92bd4aa30038bd480a72357b28796";
+/// P2WSH scriptPubkey of an arbitrary 2-of-3 multisig.
+const P2WSH_SCRIPT_PUBKEY: &str =
+    "0020e5c7c00d174631d2d1e365d6bf0ca7f8c7ab2ed2a5a1f3a4e8a5d4bd6ac5b2b8";
+/// `OP_TRUE` scriptPubkey.
+const NON_STANDARD_SCRIPT_PUBKEY: &str = "51";
+
+/// Witness of a 2-of-3 P2WSH multisig:
+/// number of items (1) + empty item (1) + 2 signatures (2 * 74) + redeem script (1 + 105).
+const MULTISIG_2_OF_3_WITNESS_SIZE: u32 = 256;
+
+fn multisig_2_of_3_weight() -> Option<Proto::mod_Input::SatisfactionWeight> {
+    Some(Proto::mod_Input::SatisfactionWeight {
+        script_sig_size: 0,
+        max_witness_size: MULTISIG_2_OF_3_WITNESS_SIZE,
+    })
+}
+
+fn custom_script_input(
+    script_pubkey: &str,
+    vout: u32,
+    value: i64,
+    satisfaction_weight: Option<Proto::mod_Input::SatisfactionWeight>,
+) -> Proto::Input<'static> {
+    Proto::Input {
+        out_point: input::out_point(DUMMY_TXID, vout),
+        value,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::custom_script(script_pubkey.decode_hex().unwrap()),
+        satisfaction_weight,
+        ..Proto::Input::default()
+    }
+}
+
+fn make_signing_input(
+    inputs: Vec<Proto::Input<'static>>,
+    output_value: i64,
+    fee_per_vb: i64,
+) -> Proto::SigningInput<'static> {
+    let alice_pubkey = ALICE_PUBKEY.decode_hex().unwrap();
+
+    let out_0 = Proto::Output {
+        value: output_value,
+        to_recipient: output::p2wpkh(alice_pubkey.clone()),
+        cash_token: None,
+    };
+    let change_output = Proto::Output {
+        // Put any value here as the change amount will be overwritten.
+        value: 1234,
+        to_recipient: output::p2wpkh(alice_pubkey),
+        cash_token: None,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        inputs,
+        outputs: vec![out_0],
+        change_output: Some(change_output),
+        input_selector: Proto::InputSelector::UseAll,
+        fee_per_vb,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    Proto::SigningInput {
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Proto::SigningInput::default()
+    }
+}
+
+#[test]
+fn test_plan_p2wsh_input_with_satisfaction_weight() {
+    let p2wsh_input = custom_script_input(P2WSH_SCRIPT_PUBKEY, 0, 50_000, multisig_2_of_3_weight());
+    let p2pkh_input = Proto::Input {
+        out_point: input::out_point(DUMMY_TXID, 1),
+        value: 30_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2pkh(ALICE_PUBKEY.decode_hex().unwrap()),
+        ..Proto::Input::default()
+    };
+
+    let input = make_signing_input(vec![p2wsh_input, p2pkh_input], 60_000, 10);
+
+    // Base size: 4 (version) + 1 + 41 (P2WSH input) + 149 (P2PKH input) + 1 + 2 * 31 (P2WPKH outputs) + 4 (locktime) = 262.
+    // Witness size: 2 (marker and flag) + 256 (P2WSH witness) + 1 (empty P2PKH witness) = 259.
+    // Weight: 262 * 4 + 259 = 1307. vsize: ceil(1307 / 4) = 327.
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![50_000, 30_000],
+            outputs: vec![60_000, 16_730],
+            vsize_estimate: 327,
+            // vsize * fee_rate
+            fee_estimate: 3_270,
+            change: 16_730,
+        });
+
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+    let plan = planner.plan(CoinType::Bitcoin, input);
+    // The P2PKH signature can be 2 bytes shorter, that gives 260 bytes of the base size.
+    // Weight: 260 * 4 + 259 = 1299. vsize: ceil(1299 / 4) = 325.
+    assert_eq!(plan.min_fee_estimate, 3_250);
+    assert_eq!(plan.max_fee_estimate, 3_270);
+}
+
+#[test]
+fn test_plan_non_standard_input_with_satisfaction_weight() {
+    let op_true_weight = Proto::mod_Input::SatisfactionWeight {
+        script_sig_size: 0,
+        max_witness_size: 0,
+    };
+    let op_true_input =
+        custom_script_input(NON_STANDARD_SCRIPT_PUBKEY, 0, 20_000, Some(op_true_weight));
+
+    let input = make_signing_input(vec![op_true_input], 9_000, 2);
+
+    // There are no witnesses in the transaction.
+    // Size: 4 (version) + 1 + 41 (input) + 1 + 2 * 31 (P2WPKH outputs) + 4 (locktime) = 113.
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![20_000],
+            outputs: vec![9_000, 10_774],
+            vsize_estimate: 113,
+            // vsize * fee_rate
+            fee_estimate: 226,
+            change: 10_774,
+        });
+
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+    let plan = planner.plan(CoinType::Bitcoin, input);
+    assert_eq!(plan.min_fee_estimate, 226);
+    assert_eq!(plan.max_fee_estimate, 226);
+}
+
+#[test]
+fn test_plan_p2wsh_input_without_satisfaction_weight() {
+    let p2wsh_input = custom_script_input(P2WSH_SCRIPT_PUBKEY, 0, 50_000, None);
+    let input = make_signing_input(vec![p2wsh_input], 40_000, 10);
+
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+    let plan = planner.plan(CoinType::Bitcoin, input);
+    assert_eq!(plan.error, SigningError::Error_not_supported);
+}
+
+#[test]
+fn test_sign_p2wsh_input_with_satisfaction_weight_unsupported() {
+    let p2wsh_input = custom_script_input(P2WSH_SCRIPT_PUBKEY, 0, 50_000, multisig_2_of_3_weight());
+    let input = make_signing_input(vec![p2wsh_input], 40_000, 10);
+
+    // The UTXO can be planned, but cannot be signed.
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, input);
+    assert_eq!(output.error, SigningError::Error_not_supported);
+}
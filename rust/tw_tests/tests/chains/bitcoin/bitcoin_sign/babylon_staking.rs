@@ -74,6 +74,7 @@ fn test_bitcoin_babylon_stake() {
             covenant_committee_public_keys: covenant_committees(),
             covenant_quorum: 2,
         }),
+        cash_token: None,
     };
 
     let out1 = Proto::Output {
@@ -84,11 +85,13 @@ fn test_bitcoin_babylon_stake() {
             FINALITY_PROVIDER_BABYLON_FOUND_0,
             STAKING_TIME,
         ),
+        cash_token: None,
     };
 
     let change_output = Proto::Output {
         value: 0,
         to_recipient: output::p2tr_key_path(PUBLIC_KEY_1.decode_hex().unwrap()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -179,6 +182,7 @@ fn test_bitcoin_babylon_unbond() {
             covenant_committee_public_keys: covenant_committees(),
             covenant_quorum: 2,
         }),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -251,6 +255,7 @@ fn test_bitcoin_babylon_withdraw_unbonding_tx() {
     let explicit_max_out = Proto::Output {
         value: 26_841,
         to_recipient: output::p2tr_key_path(PUBLIC_KEY_1.decode_hex().unwrap()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -312,12 +317,15 @@ fn test_bitcoin_babylon_withdraw_staking_tx() {
             covenant_committee_public_keys: covenant_committees(),
             covenant_quorum: 2,
         }),
+        cash_token: None,
+        satisfaction_weight: None,
     };
 
     // Regular P2TR transfer.
     let max_out = Proto::Output {
         value: 0,
         to_recipient: output::p2tr_key_path(PUBLIC_KEY_1.decode_hex().unwrap()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -354,3 +362,82 @@ fn test_bitcoin_babylon_withdraw_staking_tx() {
             fee: 146,
         });
 }
+
+#[test]
+fn test_bitcoin_babylon_presign_staking_slashing_tx() {
+    const STAKING_TIME: u32 = 1000;
+    const STAKING_AMOUNT: i64 = 30_000;
+    /// 10% of the staking amount.
+    const SLASHING_AMOUNT: i64 = 3000;
+    const SLASHING_FEE: i64 = 1000;
+    /// `OP_RETURN "babylon"`
+    const SLASHING_SCRIPT_PUBKEY: &str = "6a07626162796c6f6e";
+
+    let txid0 = "0942545a1e324c713933350bd234e708b4f6092406dc45a97a61ce627a095e1b";
+    let utxo0 = Proto::Input {
+        out_point: input::out_point(txid0, 0),
+        value: STAKING_AMOUNT,
+        sighash_type: SIGHASH_ALL,
+        sequence: None,
+        // Neither finality provider nor covenant signatures are required to pre-sign the Slashing transaction.
+        claiming_script: babylon::input::staking_slashing_path(
+            BabylonProto::StakingInfo {
+                staker_public_key: PUBLIC_KEY_1.decode_hex().unwrap().into(),
+                finality_provider_public_key: FINALITY_PROVIDER_BABYLON_FOUND_0
+                    .decode_hex()
+                    .unwrap()
+                    .into(),
+                staking_time: STAKING_TIME,
+                covenant_committee_public_keys: covenant_committees(),
+                covenant_quorum: 2,
+            },
+            None,
+            babylon::input::PubkeySignatures::default(),
+        ),
+        ..Default::default()
+    };
+
+    let slashing_out = Proto::Output {
+        value: SLASHING_AMOUNT,
+        to_recipient: output::custom_script(SLASHING_SCRIPT_PUBKEY.decode_hex().unwrap()),
+        cash_token: None,
+    };
+    let change_out = Proto::Output {
+        value: STAKING_AMOUNT - SLASHING_AMOUNT - SLASHING_FEE,
+        to_recipient: output::p2tr_key_path(PUBLIC_KEY_1.decode_hex().unwrap()),
+        cash_token: None,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        lock_time: 0,
+        inputs: vec![utxo0],
+        outputs: vec![slashing_out, change_out],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![PRIVATE_KEY_1.decode_hex().unwrap().into()],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        dangerous_use_fixed_schnorr_rng: true,
+        ..Default::default()
+    };
+
+    // Witness contains empty covenant and finality provider signatures, the staker's signature,
+    // the slashing script and the control block.
+    let txid = "8503aacc48adb1960df6fb22aa55d0377f37165de0c65b75825ca4fa9d3d2382";
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "020000000001011b5e097a62ce617aa945dc062409f6b408e734d20b353339714c321e5a5442090000000000ffffffff02b80b000000000000096a07626162796c6f6e90650000000000002251205d1b83f2e2991c2d80226a54d89255768a77905d63d0d5f51d18476143f90a8e070000000040fef92cd6a47818989524c02f5c29b288a847f459bd8f601a4f0d710392eb8bbe7e8c0c822d0774f8c270f5115c6434bee96f262f2d79bf9d3368f84891ce7073ac209789cdd12bc90bbd73445718f8a709956eb3cce362716a3425610abb75ea1132ad2003d5a0bb72d71993e435d6c5a70e2aa4db500a62cfaae33c56050deefee64ec0ad2017921cf156ccb4e73d428f996ed11b245313e37e27c978ac4d2cc21eca4672e4ac2049766ccd9e3cd94343e2040474a77fb37cdfd30530d05f9f1e96ae1e2102c86eba2076d1ae01f8fb6bf30108731c884cddcf57ef6eef2d9d9559e130894e0e40c62cba529c41c050929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac073b849453a7e0064d7909a90e5e62589385fe82187f7841bc0e3e732a50f5e7e00000000",
+            txid,
+            inputs: vec![STAKING_AMOUNT],
+            outputs: vec![SLASHING_AMOUNT, STAKING_AMOUNT - SLASHING_AMOUNT - SLASHING_FEE],
+            vsize: 190,
+            weight: 759,
+            fee: SLASHING_FEE,
+        });
+}
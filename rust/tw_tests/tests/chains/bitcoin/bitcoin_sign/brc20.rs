@@ -26,12 +26,14 @@ fn test_bitcoin_sign_brc20_commit() {
     let out1 = Proto::Output {
         value: 7_000,
         to_recipient: output::brc20_inscribe(alice_pubkey.to_vec(), "oadf", "20"),
+        cash_token: None,
     };
 
     // Change/return transaction.
     let out2 = Proto::Output {
         value: 16_400,
         to_recipient: output::p2wpkh(alice_pubkey.to_vec()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -83,6 +85,7 @@ fn test_bitcoin_sign_brc20_reveal() {
     let out1 = Proto::Output {
         value: DUST,
         to_recipient: output::p2wpkh(alice_pubkey.to_vec()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -157,11 +160,13 @@ fn test_bitcoin_sign_brc20_transfer() {
     let out1 = Proto::Output {
         value: DUST,
         to_recipient: output::to_address(bob_address),
+        cash_token: None,
     };
 
     let change_output = Proto::Output {
         value: 13_400,
         to_recipient: output::p2wpkh(alice_pubkey.to_vec()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -230,10 +235,12 @@ fn test_bitcoin_sign_brc20_reveal_with_extra_p2tr_input() {
     let out1 = Proto::Output {
         value: 546,
         to_recipient: output::to_address(my_address),
+        cash_token: None,
     };
     let change_output = Proto::Output {
         value: 0,
         to_recipient: output::to_address(my_address),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -0,0 +1,206 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    btc_info, dust_threshold, input, output, sign, TransactionOneof, DUST, SIGHASH_ALL,
+};
+use tw_any_coin::test_utils::sign_utils::AnySignerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::ToHex;
+use tw_keypair::ecdsa;
+use tw_misc::traits::ToBytesZeroizing;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::Common::Proto::SigningError;
+
+const MY_PRIVATE_KEY: &str = "9ea2172511ed73ae0096be8e593c3b75631700edaf729f1abbae607314a20e35";
+
+/// Signs a transaction spending a single UTXO whose claiming script is derived from the `descriptor`.
+fn sign_descriptor_input(descriptor: &str) -> Proto::SigningOutput<'static> {
+    let my_private_key = ecdsa::secp256k1::PrivateKey::try_from(MY_PRIVATE_KEY).unwrap();
+
+    let utxo_0 = Proto::Input {
+        out_point: input::out_point(
+            "b33082a5fad105c1d9712e8d503971fe4d84713065bd323fd1019636ed940e8d",
+            1,
+        ),
+        value: 50_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::descriptor(descriptor, 0),
+        ..Default::default()
+    };
+
+    let out_0 = Proto::Output {
+        value: 49_000,
+        to_recipient: output::to_address("bc1q2dsdlq3343vk29runkgv4yc292hmq53jedfjmp"),
+        cash_token: None,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![utxo_0],
+        outputs: vec![out_0],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![my_private_key.to_zeroizing_vec().to_vec().into()],
+        chain_info: btc_info(),
+        dangerous_use_fixed_schnorr_rng: true,
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, signing);
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    output
+}
+
+/// The same transaction as in `test_bitcoin_sign_p2wpkh_input_different_builders`,
+/// but the UTXOs claiming scripts are derived from an output descriptor.
+#[test]
+fn test_bitcoin_sign_p2wpkh_input_descriptor() {
+    let my_private_key = ecdsa::secp256k1::PrivateKey::try_from(MY_PRIVATE_KEY).unwrap();
+    let my_descriptor =
+        "wpkh(03a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c)#3n6qryvt";
+
+    let utxo_hash_0 = "b33082a5fad105c1d9712e8d503971fe4d84713065bd323fd1019636ed940e8d";
+    let utxo_0 = Proto::Input {
+        out_point: input::out_point(utxo_hash_0, 1),
+        value: 30_269,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::descriptor(my_descriptor, 0),
+        ..Default::default()
+    };
+
+    let utxo_hash_1 = "1f62c18bfc5f8293a2b7b061587c427bf830fb224289f9a806e6ad48de6a4c7d";
+    let utxo_1 = Proto::Input {
+        out_point: input::out_point(utxo_hash_1, 1),
+        value: 4_863,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::descriptor(my_descriptor, 0),
+        ..Default::default()
+    };
+
+    let out_0 = Proto::Output {
+        value: 28_035,
+        to_recipient: output::to_address("bc1q2dsdlq3343vk29runkgv4yc292hmq53jedfjmp"),
+        cash_token: None,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V1,
+        inputs: vec![utxo_0, utxo_1],
+        outputs: vec![out_0],
+        change_output: None,
+        input_selector: Proto::InputSelector::SelectAscending,
+        dust_policy: dust_threshold(DUST),
+        fee_per_vb: 33,
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![my_private_key.to_zeroizing_vec().to_vec().into()],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    // Successfully broadcasted: https://mempool.space/tx/5d6bf53576a54be4d92cd8abf58d28ecc9ea7956eaf970d24d6bfcb9fcfe9855
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "010000000001027d4c6ade48ade606a8f9894222fb30f87b427c5861b0b7a293825ffc8bc1621f0100000000ffffffff8d0e94ed369601d13f32bd653071844dfe7139508d2e71d9c105d1faa58230b30100000000ffffffff01836d0000000000001600145360df8231ac5965147c9d90ca930a2aafb0523202483045022100f95f9ac5d39f4b47dcd8c86daaaeac86374258d9960f922333ba0d5fdaa15b7e0220761794672dc9fbd71398d608f72f5d21a0f6c1306c6b700ad0d82f747c221062012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c02483045022100eb6ba0dcc64af61b2186b7efdab1ff03784d585ee03437f9a53875e93429db080220015a268d308436d3564b83ceaed90bc7272ca164016298ea855d1936568002a7012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c00000000",
+            txid: "5d6bf53576a54be4d92cd8abf58d28ecc9ea7956eaf970d24d6bfcb9fcfe9855",
+            inputs: vec![4_863, 30_269],
+            outputs: vec![28_035],
+            vsize: 178,
+            weight: 710,
+            fee: 7_097,
+        });
+}
+
+#[test]
+fn test_bitcoin_sign_sh_wpkh_input_descriptor() {
+    let output = sign_descriptor_input(
+        "sh(wpkh(03a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c))",
+    );
+    // The scriptSig pushes the P2WPKH redeem script, while the signature is published as a witness.
+    assert_eq!(output.encoded.to_hex(), "020000000001018d0e94ed369601d13f32bd653071844dfe7139508d2e71d9c105d1faa58230b3010000001716001460d7ee599766db323fb1916c7f9e5d818aaf8c1bffffffff0168bf0000000000001600145360df8231ac5965147c9d90ca930a2aafb052320247304402203ab79b0c5c6c920e0911c3260e65eab6eb4eee0257053eb4fc8efeb647779ccd0220770a46176f47760d2311a708eb93a3294c193d8a7e9e256a36462c0d48af8fda012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c00000000");
+    assert_eq!(
+        output.txid.to_hex(),
+        "74bec38b27002389150a4c0f1399422418ecae313f4a834580d0ba4a610794f4"
+    );
+}
+
+#[test]
+fn test_bitcoin_sign_pkh_uncompressed_input_descriptor() {
+    let output = sign_descriptor_input("pkh(04a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c1bfbf0435c6036fb5521d4835a388a3e44481f067aa6c017762a4b9a4c3b9e11)");
+    // The scriptSig contains the uncompressed public key the UTXO is locked with.
+    assert_eq!(output.encoded.to_hex(), "02000000018d0e94ed369601d13f32bd653071844dfe7139508d2e71d9c105d1faa58230b3010000008a4730440220490590fcd46866a7ebb43150420c7fcc63307fb425590ddd11a36044a118219f022049900356d6a2e5e5379b9d80fbe08a9fbf6523dbc9d3f6a8c7182a5bf045b6a2014104a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c1bfbf0435c6036fb5521d4835a388a3e44481f067aa6c017762a4b9a4c3b9e11ffffffff0168bf0000000000001600145360df8231ac5965147c9d90ca930a2aafb0523200000000");
+    assert_eq!(
+        output.txid.to_hex(),
+        "615c41baafe6cd8eae398ee8fec75d46be012e43d644a7e00ada7cc0198bc19b"
+    );
+}
+
+#[test]
+fn test_bitcoin_sign_tr_script_tree_input_descriptor() {
+    // The UTXO is spent via the key path tweaked with the `pk()` leaf hash.
+    let output = sign_descriptor_input("tr(03a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c,pk(6666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536))");
+    assert_eq!(output.encoded.to_hex(), "020000000001018d0e94ed369601d13f32bd653071844dfe7139508d2e71d9c105d1faa58230b30100000000ffffffff0168bf0000000000001600145360df8231ac5965147c9d90ca930a2aafb05232014037fc7ca5e08f01b52e76de94790d55df1d30860f9db7100fe324df1290230841bec225e8e942847129e3b6177a06a7734d2029a64c90b3ef0abe90a03cae1b8700000000");
+    assert_eq!(
+        output.txid.to_hex(),
+        "2c33a13ee7cb487e828ae45310274b5cf9afdf666e48e71433985ae90e5937cb"
+    );
+}
+
+/// `wsh(multi())` UTXO requires signatures of several parties, so it cannot be signed.
+#[test]
+fn test_bitcoin_sign_descriptor_unsupported_input() {
+    let utxo_0 = Proto::Input {
+        out_point: input::out_point(
+            "b33082a5fad105c1d9712e8d503971fe4d84713065bd323fd1019636ed940e8d",
+            1,
+        ),
+        value: 30_269,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::descriptor(
+            "wsh(multi(1,03a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c))",
+            0,
+        ),
+        ..Default::default()
+    };
+
+    let out_0 = Proto::Output {
+        value: 28_035,
+        to_recipient: output::descriptor(
+            "wpkh(03a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c)",
+            0,
+        ),
+        cash_token: None,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V1,
+        inputs: vec![utxo_0],
+        outputs: vec![out_0],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        fee_per_vb: 1,
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, signing);
+    assert_eq!(output.error, SigningError::Error_not_supported);
+}
@@ -4,6 +4,7 @@
 
 mod babylon_staking;
 mod brc20;
+mod descriptor;
 mod op_return;
 mod ordinal_nft;
 mod p2pkh;
@@ -13,5 +14,8 @@ mod p2tr_script_path;
 mod p2wpkh;
 mod p2wsh;
 mod psbt;
+mod runes;
 mod send_to_address;
 mod sighash_single;
+mod silent_payment;
+mod timelock;
@@ -38,16 +38,19 @@ fn test_bitcoin_deposit_to_zetachain() {
     let out_0 = Proto::Output {
         value: 10_000,
         to_recipient: output::to_address(zeta_tss_address),
+        cash_token: None,
     };
     // OP_RETURN must be the second output after the actual amount and before the change.
     let out_1 = Proto::Output {
         value: 0,
         // Pass my ZetaChain EVM address bytes (20) as the OP_RETURN.
         to_recipient: output::op_return(my_evm_zetachain_address.decode_hex().unwrap()),
+        cash_token: None,
     };
     let explicit_change_output = Proto::Output {
         value: 7_420,
         to_recipient: output::to_address(my_address),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
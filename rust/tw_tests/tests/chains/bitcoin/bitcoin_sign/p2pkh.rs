@@ -53,6 +53,7 @@ fn test_bitcoin_sign_input_p2pkh(utxo_owner: P2PKHClaimingScriptType) {
     let out1 = Proto::Output {
         value: ONE_BTC * 50 - MINER_FEE,
         to_recipient: output::p2pkh(bob_pubkey.clone()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -55,6 +55,7 @@ fn test_bitcoin_sign_input_p2pkh_output_p2tr_key_path(send_to: P2TRRecipientType
     let out1 = Proto::Output {
         value: ONE_BTC * 50 - MINER_FEE,
         to_recipient,
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -141,6 +142,7 @@ fn test_bitcoin_sign_output_p2tr(utxo_owner: P2TRClaimingScriptType) {
     let out1 = Proto::Output {
         value: ONE_BTC * 50 - MINER_FEE - MINER_FEE,
         to_recipient: output::p2tr_key_path(alice_pubkey.to_vec()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -210,6 +212,7 @@ fn test_bitcoin_sign_input_p2tr_key_path_with_change_output_a9c63d() {
     let out0 = Proto::Output {
         value: 3_000,
         to_recipient: output::to_address(SEND_TO),
+        cash_token: None,
     };
     // Send the change amount back to the same P2TR address.
     // The correct amount will be calculated for us.
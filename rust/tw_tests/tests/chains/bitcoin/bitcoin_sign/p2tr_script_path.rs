@@ -1,5 +1,6 @@
 use crate::chains::common::bitcoin::{
-    btc_info, dust_threshold, input, output, sign, TransactionOneof, DUST, SIGHASH_ALL,
+    btc_info, dust_threshold, input, output, sign, TransactionOneof, WitnessItemType, DUST,
+    SIGHASH_ALL,
 };
 use tw_coin_registry::coin_type::CoinType;
 use tw_encoding::hex::DecodeHex;
@@ -39,12 +40,14 @@ fn test_bitcoin_sign_output_p2tr_custom_script_path() {
     let out1 = Proto::Output {
         value: 7_000,
         to_recipient: output::p2tr_script_path(alice_pubkey.to_vec(), merkle_root.to_vec()),
+        cash_token: None,
     };
 
     // Change/return transaction.
     let out2 = Proto::Output {
         value: 16_400,
         to_recipient: output::p2wpkh(alice_pubkey.to_vec()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -78,73 +81,78 @@ fn test_bitcoin_sign_output_p2tr_custom_script_path() {
         });
 }
 
-// TODO uncomment when input P2TR script path
-// #[test]
-// fn test_bitcoin_sign_input_p2tr_custom_script_path() {
-//     // https://www.blockchain.com/explorer/transactions/btc/797d17d47ae66e598341f9dfdea020b04d4017dcf9cc33f0e51f7a6082171fb1
-//     let txid: Vec<u8> = hex("797d17d47ae66e598341f9dfdea020b04d4017dcf9cc33f0e51f7a6082171fb1")
-//         .into_iter()
-//         .rev()
-//         .collect();
-//
-//     // Prepare the BRC20 payload and control block.
-//     let payload = &transfer.script;
-//     let control_block = transfer
-//         .spend_info
-//         .control_block(&(payload.to_owned(), LeafVersion::TapScript))
-//         .unwrap();
-//
-//     // Provide the payload and control block directly to the builder.
-//     let tx1 = Proto::Input {
-//         txid: txid.as_slice().into(),
-//         vout: 0,
-//         value: 7_000,
-//         sighash_type: UtxoProto::SighashType::UseDefault,
-//         to_recipient: ProtoInputRecipient::builder(Proto::mod_Input::InputBuilder {
-//             variant: ProtoInputBuilder::p2tr_script_path(
-//                 Proto::mod_Input::InputTaprootScriptPath {
-//                     one_prevout: false,
-//                     payload: payload.to_vec().into(),
-//                     control_block: control_block.serialize().into(),
-//                 },
-//             ),
-//         }),
-//         ..Default::default()
-//     };
-//
-//     let out1 = Proto::Output {
-//         value: 546,
-//         to_recipient: ProtoOutputRecipient::builder(Proto::mod_Output::OutputBuilder {
-//             variant: ProtoOutputBuilder::p2wpkh(Proto::ToPublicKeyOrHash {
-//                 to_address: ProtoPubkeyOrHash::pubkey(alice_pubkey.as_slice().into()),
-//             }),
-//         }),
-//     };
-//
-//     let signing = Proto::SigningInput {
-//         private_key: alice_private_key.as_slice().into(),
-//         inputs: vec![tx1],
-//         outputs: vec![out1],
-//         input_selector: UtxoProto::InputSelector::UseAll,
-//         disable_change_output: true,
-//         // We enable deterministic Schnorr signatures here
-//         dangerous_use_fixed_schnorr_rng: true,
-//         ..Default::default()
-//     };
-//
-//     let signed = BitcoinEntry.sign(&coin, signing);
-//     assert_eq!(signed.error, Proto::Error::OK);
-//
-//     // https://www.blockchain.com/explorer/transactions/btc/7046dc2689a27e143ea2ad1039710885147e9485ab6453fa7e87464aa7dd3eca
-//     assert_eq!(
-//         signed.txid,
-//         hex("7046dc2689a27e143ea2ad1039710885147e9485ab6453fa7e87464aa7dd3eca")
-//     );
-//
-//     let encoded = tw_encoding::hex::encode(signed.encoded, false);
-//     let transaction = signed.transaction.unwrap();
-//
-//     assert_eq!(encoded, "02000000000101b11f1782607a1fe5f033ccf9dc17404db020a0dedff94183596ee67ad4177d790000000000ffffffff012202000000000000160014e311b8d6ddff856ce8e9a4e03bc6d4fe5050a83d03406a35548b8fa4620028e021a944c1d3dc6e947243a7bfc901bf63fefae0d2460efa149a6440cab51966aa4f09faef2d1e5efcba23ab4ca6e669da598022dbcfe35b0063036f7264010118746578742f706c61696e3b636861727365743d7574662d3800377b2270223a226272632d3230222c226f70223a227472616e73666572222c227469636b223a226f616466222c22616d74223a223230227d6821c00f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb00000000");
-//     assert_eq!(transaction.inputs.len(), 1);
-//     assert_eq!(transaction.outputs.len(), 1);
-// }
+/// A test for the generic P2TR script-tree input builder. This test reconstructs
+/// the BRC20 reveal transaction by spending the single inscription leaf.
+#[test]
+fn test_bitcoin_sign_input_p2tr_script_tree() {
+    let alice_private_key = schnorr::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public();
+
+    // Build the BRC20 transfer outside the library, and provide the inscription script as the only leaf.
+    let ticker = Brc20Ticker::new("oadf".to_string()).unwrap();
+    let amount = "20".to_string();
+    let inscribe_to = alice_pubkey.compressed();
+    let transfer = BRC20TransferInscription::new(&inscribe_to, &ticker, &amount).unwrap();
+
+    let tree = Proto::TaprootScriptTree {
+        leaves: vec![Proto::TaprootLeaf {
+            script: transfer.taproot_program().to_vec().into(),
+            depth: 0,
+            ..Default::default()
+        }],
+        huffman: false,
+    };
+
+    // Spend `797d17d47ae66e598341f9dfdea020b04d4017dcf9cc33f0e51f7a6082171fb1` commit output.
+    let txid = "797d17d47ae66e598341f9dfdea020b04d4017dcf9cc33f0e51f7a6082171fb1";
+    let tx1 = Proto::Input {
+        out_point: input::out_point(txid, 0),
+        value: 7_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2tr_script_tree(
+            alice_pubkey.to_vec(),
+            tree,
+            0,
+            alice_pubkey.to_vec(),
+            vec![WitnessItemType::signature(true)],
+        ),
+        ..Default::default()
+    };
+
+    let out1 = Proto::Output {
+        value: DUST,
+        to_recipient: output::p2wpkh(alice_pubkey.to_vec()),
+        cash_token: None,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![ALICE_PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: btc_info(),
+        // We enable deterministic Schnorr signatures here
+        dangerous_use_fixed_schnorr_rng: true,
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    // https://www.blockchain.com/explorer/transactions/btc/7046dc2689a27e143ea2ad1039710885147e9485ab6453fa7e87464aa7dd3eca
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "02000000000101b11f1782607a1fe5f033ccf9dc17404db020a0dedff94183596ee67ad4177d790000000000ffffffff012202000000000000160014e311b8d6ddff856ce8e9a4e03bc6d4fe5050a83d03406a35548b8fa4620028e021a944c1d3dc6e947243a7bfc901bf63fefae0d2460efa149a6440cab51966aa4f09faef2d1e5efcba23ab4ca6e669da598022dbcfe35b0063036f7264010118746578742f706c61696e3b636861727365743d7574662d3800377b2270223a226272632d3230222c226f70223a227472616e73666572222c227469636b223a226f616466222c22616d74223a223230227d6821c00f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb00000000",
+            txid: "7046dc2689a27e143ea2ad1039710885147e9485ab6453fa7e87464aa7dd3eca",
+            inputs: vec![7_000],
+            outputs: vec![DUST],
+            vsize: 131,
+            weight: 522,
+            fee: 7_000 - DUST,
+        });
+}
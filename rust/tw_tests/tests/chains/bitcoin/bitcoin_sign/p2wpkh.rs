@@ -53,6 +53,7 @@ fn test_bitcoin_sign_p2wpkh_input_different_builders() {
     let out_0 = Proto::Output {
         value: 28_035,
         to_recipient: output::to_address("bc1q2dsdlq3343vk29runkgv4yc292hmq53jedfjmp"),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    btc_info, dust_threshold, input, output, sign, TransactionOneof, DUST, SIGHASH_ALL,
+};
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::DecodeHex;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::BitcoinV2::Proto::mod_Runestone::OneOfpointer as PointerType;
+
+const MY_PRIVATE_KEY: &str = "428d66be0b5a620f126a00fa67637222ce3dc9badfe5c605189520760810cfac";
+const MY_PUBKEY: &str = "0369cdaf80b4a5fdad91e9face90e848225512884ec2e3ed572ca11dc68e750547";
+const BOB_PUBKEY: &str = "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
+
+const RUNE_NAME: &str = "WALLET•CORE•RUNES";
+
+fn p2wpkh_utxo(txid: &str, vout: u32, value: i64) -> Proto::Input<'static> {
+    Proto::Input {
+        out_point: input::out_point(txid, vout),
+        value,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2wpkh(MY_PUBKEY.decode_hex().unwrap()),
+        ..Default::default()
+    }
+}
+
+fn signing_input(
+    inputs: Vec<Proto::Input<'static>>,
+    outputs: Vec<Proto::Output<'static>>,
+) -> Proto::SigningInput<'static> {
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs,
+        outputs,
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    Proto::SigningInput {
+        private_keys: vec![MY_PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: btc_info(),
+        // We enable deterministic Schnorr signatures here
+        dangerous_use_fixed_schnorr_rng: true,
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_bitcoin_sign_runes_transfer() {
+    // Transfer 1000 units of the `840000:3` rune to Bob, and the rest to the change output.
+    let runestone = Proto::Runestone {
+        edicts: vec![Proto::RuneEdict {
+            id: Some(Proto::RuneId {
+                block: 840_000,
+                tx: 3,
+            }),
+            amount: "1000".into(),
+            output: 1,
+        }],
+        ..Default::default()
+    };
+
+    let signing = signing_input(
+        vec![p2wpkh_utxo(
+            "2b871b6c1112ad0a777f6db1f7a7709154c4d9af8e771ba4eca148915f830e9d",
+            2,
+            7_420,
+        )],
+        vec![
+            Proto::Output {
+                value: 0,
+                to_recipient: output::runestone(runestone),
+                cash_token: None,
+            },
+            Proto::Output {
+                value: DUST,
+                to_recipient: output::p2wpkh(BOB_PUBKEY.decode_hex().unwrap()),
+                cash_token: None,
+            },
+            Proto::Output {
+                value: 6_000,
+                to_recipient: output::p2wpkh(MY_PUBKEY.decode_hex().unwrap()),
+                cash_token: None,
+            },
+        ],
+    );
+
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "020000000001019d0e835f9148a1eca41b778eafd9c4549170a7f7b16d7f770aad12116c1b872b0200000000ffffffff0300000000000000000b6a5d0800c0a23303e80701220200000000000016001406afd46bcdfd22ef94ac122aa11f241244a37ecc7017000000000000160014540371330ae036602f2a715adaa044ac0856312c02483045022100a8c005cc00a81e70522928441f7c0c40039aa0bb7dd780af1a7272640367e03502207193da6836eac44d20c1314639bd576d80f8e0c6bd72809557133fd9ad8e5ee901210369cdaf80b4a5fdad91e9face90e848225512884ec2e3ed572ca11dc68e75054700000000",
+            txid: "b3faa197c6303b3510c33a6760a8d6742b5ed413389573ad97c83dcdebb3c036",
+            inputs: vec![7_420],
+            outputs: vec![0, DUST, 6_000],
+            vsize: 161,
+            weight: 642,
+            // sum(inputs) - sum(outputs)
+            fee: 874,
+        });
+}
+
+#[test]
+fn test_bitcoin_sign_rune_etching_commit() {
+    // Spend the change output of `test_bitcoin_sign_runes_transfer`.
+    let signing = signing_input(
+        vec![p2wpkh_utxo(
+            "b3faa197c6303b3510c33a6760a8d6742b5ed413389573ad97c83dcdebb3c036",
+            2,
+            6_000,
+        )],
+        vec![Proto::Output {
+            value: 5_000,
+            to_recipient: output::rune_etching_commit(MY_PUBKEY.decode_hex().unwrap(), RUNE_NAME),
+            cash_token: None,
+        }],
+    );
+
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "0200000000010136c0b3ebcd3dc897ad73953813d45e2b74d6a860673ac310353b30c697a1fab30200000000ffffffff018813000000000000225120861e0e236eb65d7d537436f89d8457cf17b4757a29569f0427ee4ff7d9261abb0247304402203a517a9f63ad68b9c069c357fb8e0336639d6507bae45cf5382207da1d1ed60e02204ffec9cad7f10942929165a28c80c8c253085d9e8b4d1a9dfe5ebcd6c93df92e01210369cdaf80b4a5fdad91e9face90e848225512884ec2e3ed572ca11dc68e75054700000000",
+            txid: "060437bb67bd0bbfadd49200a7636b7fe85764d76fade3cb9b4c6dcbe1a0039c",
+            inputs: vec![6_000],
+            outputs: vec![5_000],
+            vsize: 122,
+            weight: 485,
+            fee: 1_000,
+        });
+}
+
+#[test]
+fn test_bitcoin_sign_rune_etching_reveal() {
+    // Spend the commitment output of `test_bitcoin_sign_rune_etching_commit`.
+    // Please note the commit transaction must have at least 6 confirmations.
+    let commitment = Proto::Input {
+        out_point: input::out_point(
+            "060437bb67bd0bbfadd49200a7636b7fe85764d76fade3cb9b4c6dcbe1a0039c",
+            0,
+        ),
+        value: 5_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::rune_etching_reveal(MY_PUBKEY.decode_hex().unwrap(), RUNE_NAME),
+        ..Default::default()
+    };
+
+    let runestone = Proto::Runestone {
+        etching: Some(Proto::RuneEtching {
+            rune: RUNE_NAME.into(),
+            divisibility: 2,
+            symbol: "ᚱ".into(),
+            premine: "1000000".into(),
+            terms: Some(Proto::RuneTerms {
+                amount: "100".into(),
+                cap: "1000000".into(),
+                // Minting is open for about a year after the etching.
+                offset_end: 52_560,
+                ..Default::default()
+            }),
+            turbo: false,
+        }),
+        // The premine goes to the second output.
+        pointer: PointerType::pointer_output(1),
+        ..Default::default()
+    };
+
+    let signing = signing_input(
+        vec![commitment],
+        vec![
+            Proto::Output {
+                value: 0,
+                to_recipient: output::runestone(runestone),
+                cash_token: None,
+            },
+            Proto::Output {
+                value: DUST,
+                to_recipient: output::p2wpkh(MY_PUBKEY.decode_hex().unwrap()),
+                cash_token: None,
+            },
+        ],
+    );
+
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "020000000001019c03a0e1cb6d4c9bcbe3ad6fd76457e87f6b63a70092d4adbf0bbd67bb3704060000000000ffffffff020000000000000000296a5d2602030494b3eac9dd9e98eaa1a101010203a00405b12d06c0843d0a6408c0843d12d09a0316012202000000000000160014540371330ae036602f2a715adaa044ac0856312c0340844175f48a9c00d4fdb6fdde8b6e6e10caae6ba125fc47566c1be27571b078eb3dc3858dfaa0128d80dfa5df21bbd26eb0b23d563ad1b564dc901242a7f1ef082f2069cdaf80b4a5fdad91e9face90e848225512884ec2e3ed572ca11dc68e750547ac00630994993ad9f560d4a1506821c069cdaf80b4a5fdad91e9face90e848225512884ec2e3ed572ca11dc68e75054700000000",
+            txid: "82a4e4c573cde8a7452ac1a11abfb7603b555eb70d3a555ea554faadda77ec3b",
+            inputs: vec![5_000],
+            outputs: vec![0, DUST],
+            vsize: 170,
+            weight: 678,
+            fee: 5_000 - DUST,
+        });
+}
@@ -44,6 +44,7 @@ fn test_bitcoin_send_to_p2sh_address() {
     let out_0 = Proto::Output {
         value: 1_000,
         to_recipient: output::to_address(&p2sh_address.to_string()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -99,6 +100,7 @@ fn test_bitcoin_send_to_p2pkh_address() {
     let out1 = Proto::Output {
         value: 1_000,
         to_recipient: output::to_address(bob_address),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -160,6 +162,7 @@ fn test_bitcoin_send_to_p2wsh_address() {
     let out_0 = Proto::Output {
         value: 1_000,
         to_recipient: output::to_address(&p2wsh_address.to_string()),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -213,6 +216,7 @@ fn test_bitcoin_send_to_p2wpkh_address() {
     let out1 = Proto::Output {
         value: 1_000,
         to_recipient: output::to_address(bob_address),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -266,6 +270,7 @@ fn test_bitcoin_send_to_p2tr_key_path_address() {
     let out1 = Proto::Output {
         value: 1_000,
         to_recipient: output::to_address(bob_address),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
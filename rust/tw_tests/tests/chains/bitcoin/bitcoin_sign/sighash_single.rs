@@ -45,10 +45,12 @@ fn test_bitcoin_sign_sighash_single() {
     let out_0 = Proto::Output {
         value: 335_790_000,
         to_recipient: output::to_address("1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx"),
+        cash_token: None,
     };
     let explicit_change_output = Proto::Output {
         value: 84_209_739,
         to_recipient: output::to_address("1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU"),
+        cash_token: None,
     };
 
     let builder = Proto::TransactionBuilder {
@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    btc_info, dust_threshold, input, output, sign, timelock_script, TransactionOneof, DUST,
+    SIGHASH_ALL,
+};
+use tw_any_coin::test_utils::sign_utils::{AnySignerHelper, PreImageHelper};
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::Common::Proto::SigningError;
+use Proto::mod_Timelock::OneOfvariant as TimelockType;
+
+const MY_PRIVATE_KEY: &str = "428d66be0b5a620f126a00fa67637222ce3dc9badfe5c605189520760810cfac";
+const MY_PUBKEY: &str = "0369cdaf80b4a5fdad91e9face90e848225512884ec2e3ed572ca11dc68e750547";
+
+/// Silent payment address from the BIP-352 test vectors.
+const SILENT_PAYMENT_ADDRESS: &str = "sp1qqgste7k9hx0qftg6qmwlkqtwuy6cycyavzmzj85c6qdfhjdpdjtdgqjuexzk6murw56suy3e0rd2cgqvycxttddwsvgxe2usfpxumr70xc9pkqwv";
+
+fn tx_builder(outputs: Vec<Proto::Output<'static>>) -> Proto::TransactionBuilder<'static> {
+    let p2wpkh = Proto::Input {
+        out_point: input::out_point(
+            "b3faa197c6303b3510c33a6760a8d6742b5ed413389573ad97c83dcdebb3c036",
+            2,
+        ),
+        value: 6_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2wpkh(MY_PUBKEY.decode_hex().unwrap()),
+        ..Default::default()
+    };
+    let p2tr = Proto::Input {
+        out_point: input::out_point(
+            "82a4e4c573cde8a7452ac1a11abfb7603b555eb70d3a555ea554faadda77ec3b",
+            1,
+        ),
+        value: 9_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2tr_key_path(MY_PUBKEY.decode_hex().unwrap()),
+        ..Default::default()
+    };
+
+    Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![p2wpkh, p2tr],
+        outputs,
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    }
+}
+
+fn silent_payment_outputs() -> Vec<Proto::Output<'static>> {
+    vec![
+        Proto::Output {
+            value: 10_000,
+            to_recipient: output::to_address(SILENT_PAYMENT_ADDRESS),
+            cash_token: None,
+        },
+        Proto::Output {
+            value: 4_000,
+            to_recipient: output::p2wpkh(MY_PUBKEY.decode_hex().unwrap()),
+            cash_token: None,
+        },
+    ]
+}
+
+#[test]
+fn test_bitcoin_sign_silent_payment() {
+    let signing = Proto::SigningInput {
+        private_keys: vec![MY_PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: btc_info(),
+        // We enable deterministic Schnorr signatures here
+        dangerous_use_fixed_schnorr_rng: true,
+        transaction: TransactionOneof::builder(tx_builder(silent_payment_outputs())),
+        ..Default::default()
+    };
+
+    // The first output is P2TR with the `f716a586d429d00848b874e18554c608ba1cbe16d168f7dfca41ec73b1302f9f` output key,
+    // derived from both P2WPKH and P2TR inputs.
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "0200000000010236c0b3ebcd3dc897ad73953813d45e2b74d6a860673ac310353b30c697a1fab30200000000ffffffff3bec77daadfa54a55e553a0db75e553b60b7bf1aa1c12a45a7e8cd73c5e4a4820100000000ffffffff021027000000000000225120f716a586d429d00848b874e18554c608ba1cbe16d168f7dfca41ec73b1302f9fa00f000000000000160014540371330ae036602f2a715adaa044ac0856312c0247304402203d414844c4f512f78907d6d94455010cfdfd3e26ea02eca6577f2a440649c2ac022037ef6f59f351584f8a6a60f65305e7a19e7380710e53ac8e35e5323f1768a48701210369cdaf80b4a5fdad91e9face90e848225512884ec2e3ed572ca11dc68e7505470140b53b3bce95b6fe39a7c0c59e29179bb0e6c2497387062047cc4ee60b0f304f0f7ffa2d7c8cb4c7639f7f96e783669e2f2b084bafecbdc35b09bcab923443878600000000",
+            txid: "aafe4919448e28e928098e5c81ccd72416b3a7ad410860cf86ea222a49521fca",
+            inputs: vec![6_000, 9_000],
+            outputs: vec![10_000, 4_000],
+            vsize: 210,
+            weight: 839,
+            // sum(inputs) - sum(outputs)
+            fee: 1_000,
+        });
+}
+
+#[test]
+fn test_bitcoin_sign_silent_payment_mixed_inputs() {
+    let p2sh_p2wpkh = Proto::Input {
+        out_point: input::out_point(
+            "b3faa197c6303b3510c33a6760a8d6742b5ed413389573ad97c83dcdebb3c036",
+            2,
+        ),
+        value: 6_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::descriptor(&format!("sh(wpkh({MY_PUBKEY}))"), 0),
+        ..Default::default()
+    };
+    // P2WSH input is not eligible, but its outpoint is the smallest one.
+    let p2wsh = Proto::Input {
+        out_point: input::out_point(
+            "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16",
+            0,
+        ),
+        value: 5_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2wsh_timelock(timelock_script(
+            MY_PUBKEY.decode_hex().unwrap(),
+            TimelockType::relative(144),
+            Vec::default(),
+        )),
+        ..Default::default()
+    };
+    let p2tr = Proto::Input {
+        out_point: input::out_point(
+            "82a4e4c573cde8a7452ac1a11abfb7603b555eb70d3a555ea554faadda77ec3b",
+            1,
+        ),
+        value: 9_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2tr_key_path(MY_PUBKEY.decode_hex().unwrap()),
+        ..Default::default()
+    };
+
+    let outputs = vec![
+        Proto::Output {
+            value: 10_000,
+            to_recipient: output::to_address(SILENT_PAYMENT_ADDRESS),
+            cash_token: None,
+        },
+        Proto::Output {
+            value: 9_000,
+            to_recipient: output::p2wpkh(MY_PUBKEY.decode_hex().unwrap()),
+            cash_token: None,
+        },
+    ];
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![p2sh_p2wpkh, p2wsh, p2tr],
+        outputs,
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![MY_PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: btc_info(),
+        dangerous_use_fixed_schnorr_rng: true,
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, signing);
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+
+    // The first output is P2TR with the `3e6ea5bc53c265e362696da6777c5b70740162721b662d34be63b5a901afc4f6` output key,
+    // derived from P2SH-P2WPKH and P2TR inputs, and the smallest outpoint of the P2WSH input.
+    assert_eq!(output.encoded.to_hex(), "0200000000010336c0b3ebcd3dc897ad73953813d45e2b74d6a860673ac310353b30c697a1fab30200000017160014540371330ae036602f2a715adaa044ac0856312cffffffff169e1e83e930853391bc6f35f605c6754cfead57cf8387639d3b4096c54f18f40000000000900000003bec77daadfa54a55e553a0db75e553b60b7bf1aa1c12a45a7e8cd73c5e4a4820100000000ffffffff0210270000000000002251203e6ea5bc53c265e362696da6777c5b70740162721b662d34be63b5a901afc4f62823000000000000160014540371330ae036602f2a715adaa044ac0856312c02483045022100f7255f9986d32734ec59a4dad228382978e7455328a05a976c06a89860f7ce6302207ced61640f96440df950369ec8d4e533021617c9acbda86e58e1de662548852201210369cdaf80b4a5fdad91e9face90e848225512884ec2e3ed572ca11dc68e75054702473044022061e6a4a4d2e1cbb12e9a411841d2979c4d6b532cb7f6ce3d9741513cbe31c2d402206e37401a52ff1e4a4b73b12f41d5d194d7b619533ab5414bec9b340ab9875de20128029000b275210369cdaf80b4a5fdad91e9face90e848225512884ec2e3ed572ca11dc68e750547ac0140a9ccd1c1852a08162cb305388839faaefd3edfc3d21e2550e0d3b5621b9b03ce9e4db1468223ce8632c25131973f02a027adfd928384a494900ca316c84a50fb00000000");
+    assert_eq!(
+        output.txid.to_hex(),
+        "92cf253fd54e45b15be1e6f87533a26a0e97c5ef5d67465784e0c74167d1c43d"
+    );
+}
+
+#[test]
+fn test_bitcoin_sign_silent_payment_errors() {
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+
+    // Invalid silent payment address checksum.
+    let invalid_address = SILENT_PAYMENT_ADDRESS.replace("pkqwv", "pkqwq");
+    let outputs = vec![Proto::Output {
+        value: 10_000,
+        to_recipient: output::to_address(&invalid_address),
+        cash_token: None,
+    }];
+    let signing = Proto::SigningInput {
+        private_keys: vec![MY_PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(tx_builder(outputs)),
+        ..Default::default()
+    };
+    let output = signer.sign(CoinType::Bitcoin, signing);
+    assert_eq!(output.error, SigningError::Error_invalid_address);
+}
+
+#[test]
+fn test_bitcoin_preimage_silent_payment_not_supported() {
+    // Silent payment outputs cannot be derived without private keys.
+    let signing = Proto::SigningInput {
+        public_keys: vec![MY_PUBKEY.decode_hex().unwrap().into()],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(tx_builder(silent_payment_outputs())),
+        ..Default::default()
+    };
+
+    let mut pre_imager = PreImageHelper::<Proto::PreSigningOutput>::default();
+    let output = pre_imager.pre_image_hashes(CoinType::Bitcoin, &signing);
+    assert_eq!(output.error, SigningError::Error_not_supported);
+}
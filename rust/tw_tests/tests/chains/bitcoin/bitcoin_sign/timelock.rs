@@ -0,0 +1,282 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    btc_info, dust_threshold, input, output, timelock_script, ClaimingScriptType, TransactionOneof,
+    DUST, SIGHASH_ALL,
+};
+use tw_any_coin::test_utils::sign_utils::AnySignerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_keypair::{ecdsa, schnorr};
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::BitcoinV2::Proto::mod_SigningOutput::OneOftransaction as ProtobufTransaction;
+use tw_proto::Common::Proto::SigningError;
+use tw_utxo::script::timelock::SEQUENCE_ENABLE_LOCKTIME;
+use Proto::mod_Timelock::OneOfvariant as TimelockType;
+
+const ALICE_PRIVATE_KEY: &str = "56429688a1a6b00b90ccd22a0de0a376b6569d8684022ae92229a28478bfb657";
+const TXID: &str = "c01007bb55bde4e70278e1154c34db72f34a833687d3f37443bd5c49137ee5fe";
+
+const CLTV_HEIGHT: u32 = 800_000;
+const CSV_BLOCKS: u32 = 144;
+
+/// `800000 OP_CHECKLOCKTIMEVERIFY OP_DROP <alice_pubkey> OP_CHECKSIG`
+const CLTV_WITNESS_SCRIPT: &str =
+    "0300350cb17521036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536ac";
+/// `144 OP_CHECKSEQUENCEVERIFY OP_DROP <alice_pubkey> OP_CHECKSIG`
+const CSV_WITNESS_SCRIPT: &str =
+    "029000b27521036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536ac";
+/// `144 OP_CHECKSEQUENCEVERIFY OP_DROP <alice_xonly_pubkey> OP_CHECKSIG`
+const CSV_LEAF_SCRIPT: &str =
+    "029000b275206666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536ac";
+
+fn make_signing_input(
+    claiming_script: ClaimingScriptType<'static>,
+    version: Proto::TransactionVersion,
+    lock_time: u32,
+) -> Proto::SigningInput<'static> {
+    let alice_private_key = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public();
+
+    let tx1 = Proto::Input {
+        out_point: input::out_point(TXID, 0),
+        value: 50_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script,
+        ..Default::default()
+    };
+
+    let out1 = Proto::Output {
+        value: 49_000,
+        to_recipient: output::p2wpkh(alice_pubkey.compressed().to_vec()),
+        cash_token: None,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version,
+        lock_time,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    Proto::SigningInput {
+        private_keys: vec![ALICE_PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: btc_info(),
+        dangerous_use_fixed_schnorr_rng: true,
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_bitcoin_sign_output_p2wsh_timelock() {
+    let alice_private_key = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public().compressed();
+
+    let script = timelock_script(
+        alice_pubkey.to_vec(),
+        TimelockType::absolute(CLTV_HEIGHT),
+        Vec::default(),
+    );
+    let mut input = make_signing_input(
+        input::p2wpkh(alice_pubkey.to_vec()),
+        Proto::TransactionVersion::V2,
+        0,
+    );
+    if let TransactionOneof::builder(ref mut builder) = input.transaction {
+        builder.outputs[0].to_recipient = output::p2wsh_timelock(script);
+    }
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, input);
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+
+    // Creating a timelocked output doesn't require a transaction locktime.
+    assert_eq!(output.encoded.to_hex(), "02000000000101fee57e13495cbd4374f3d38736834af372db344c15e17802e7e4bd55bb0710c00000000000ffffffff0168bf000000000000220020aa2f622b22adab4daee258060e0867029b7e20e0a56e227ec740392cfb89e99e02483045022100baed21c937a8b9f747cb87399f0a073ef7bb88e48627e8b13b537f9edc29717a0220317136c0ed31989ec88782a8022b33fb7ba2b72c7481607e611434175c71fc320121036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953600000000");
+    assert_eq!(
+        output.txid.to_hex(),
+        "a55f7aee48436b9ae87a8e812605025a2698f5cebc1db21f8d52ee53e73b100b"
+    );
+
+    let ProtobufTransaction::bitcoin(tx) = output.transaction else {
+        panic!("Expected a Bitcoin transaction");
+    };
+    // `OP_0 <sha256(CLTV_WITNESS_SCRIPT)>`
+    assert_eq!(
+        tx.outputs[0].script_pubkey.to_hex(),
+        "0020aa2f622b22adab4daee258060e0867029b7e20e0a56e227ec740392cfb89e99e"
+    );
+}
+
+#[test]
+fn test_bitcoin_sign_input_p2wsh_cltv() {
+    let alice_private_key = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public().compressed();
+
+    let script = timelock_script(
+        alice_pubkey.to_vec(),
+        TimelockType::absolute(CLTV_HEIGHT),
+        Vec::default(),
+    );
+    let input = make_signing_input(
+        input::p2wsh_timelock(script),
+        Proto::TransactionVersion::UseDefault,
+        0,
+    );
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, input);
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+
+    assert_eq!(output.encoded.to_hex(), "01000000000101fee57e13495cbd4374f3d38736834af372db344c15e17802e7e4bd55bb0710c00000000000feffffff0168bf000000000000160014e4c1ea86373d554b8f4efff2cfb0001ea19124d2024830450221008e1cd9e80653d2e4254cd2eae112d617a9bd411ecd9c5943912032c701f5ce6802207a257a871b528ff4b5a4b33d41c04d0a250ddf9216c58214ceb45e86d9485b6b01290300350cb17521036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536ac00350c00");
+    assert_eq!(
+        output.txid.to_hex(),
+        "60a1103470fc905df7fac9574aa7a130dbf507b18a51bbde9af463f8056cdfc2"
+    );
+
+    let ProtobufTransaction::bitcoin(tx) = output.transaction else {
+        panic!("Expected a Bitcoin transaction");
+    };
+    assert_eq!(tx.version, 1);
+    // The locktime is set to the CLTV height automatically.
+    assert_eq!(tx.lock_time, CLTV_HEIGHT);
+    assert_eq!(tx.inputs[0].sequence, SEQUENCE_ENABLE_LOCKTIME);
+
+    // Witness: [signature, witness script].
+    let witness = &tx.inputs[0].witness_items;
+    assert_eq!(witness.len(), 2);
+    assert_eq!(witness[1].to_hex(), CLTV_WITNESS_SCRIPT);
+}
+
+#[test]
+fn test_bitcoin_sign_input_p2wsh_csv() {
+    let alice_private_key = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public().compressed();
+
+    let script = timelock_script(
+        alice_pubkey.to_vec(),
+        TimelockType::relative(CSV_BLOCKS),
+        Vec::default(),
+    );
+    let input = make_signing_input(
+        input::p2wsh_timelock(script),
+        Proto::TransactionVersion::UseDefault,
+        0,
+    );
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, input);
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+
+    assert_eq!(output.encoded.to_hex(), "02000000000101fee57e13495cbd4374f3d38736834af372db344c15e17802e7e4bd55bb0710c00000000000900000000168bf000000000000160014e4c1ea86373d554b8f4efff2cfb0001ea19124d20248304502210099301183a21e41a37623aabe3aad37d60d0d30764bfe318740b0c4686915e14d02207079702d0f80bc2f0bfeab3ada07602d7cef4dfdafa7c264f489b6fac2321c360128029000b27521036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536ac00000000");
+    assert_eq!(
+        output.txid.to_hex(),
+        "9238b8d00ab94699140508476aac395e801acc3196ba3ae58ad80d41b15f78cb"
+    );
+
+    let ProtobufTransaction::bitcoin(tx) = output.transaction else {
+        panic!("Expected a Bitcoin transaction");
+    };
+    // Version 2 is required to enable relative timelocks.
+    assert_eq!(tx.version, 2);
+    assert_eq!(tx.lock_time, 0);
+    assert_eq!(tx.inputs[0].sequence, CSV_BLOCKS);
+
+    // Witness: [signature, witness script].
+    let witness = &tx.inputs[0].witness_items;
+    assert_eq!(witness.len(), 2);
+    assert_eq!(witness[1].to_hex(), CSV_WITNESS_SCRIPT);
+}
+
+#[test]
+fn test_bitcoin_sign_input_p2tr_csv() {
+    let alice_private_key = schnorr::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public();
+
+    // Use the unspendable internal key, so the output can be spent via the timelock leaf only.
+    let script = timelock_script(
+        alice_pubkey.compressed().to_vec(),
+        TimelockType::relative(CSV_BLOCKS),
+        Vec::default(),
+    );
+    let input = make_signing_input(
+        input::p2tr_timelock(script),
+        Proto::TransactionVersion::UseDefault,
+        0,
+    );
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, input);
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+
+    assert_eq!(output.encoded.to_hex(), "02000000000101fee57e13495cbd4374f3d38736834af372db344c15e17802e7e4bd55bb0710c00000000000900000000168bf000000000000160014e4c1ea86373d554b8f4efff2cfb0001ea19124d20340c25fdf47a717cc5f7bd91a10b8c2730b8be5752593ff4877d28a79b1f8a31f1de9f14dcc049ec3f1e9e351648baed204379837ce2796367b1b5c272f591621a627029000b275206666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536ac21c050929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac000000000");
+    // The same as the P2WSH spending transaction ID, as witnesses are not included.
+    assert_eq!(
+        output.txid.to_hex(),
+        "9238b8d00ab94699140508476aac395e801acc3196ba3ae58ad80d41b15f78cb"
+    );
+
+    let ProtobufTransaction::bitcoin(tx) = output.transaction else {
+        panic!("Expected a Bitcoin transaction");
+    };
+    assert_eq!(tx.version, 2);
+    assert_eq!(tx.inputs[0].sequence, CSV_BLOCKS);
+
+    // Witness: [signature, leaf script, control block].
+    let witness = &tx.inputs[0].witness_items;
+    assert_eq!(witness.len(), 3);
+    assert_eq!(witness[0].len(), 64);
+    assert_eq!(witness[1].to_hex(), CSV_LEAF_SCRIPT);
+    // The only leaf has no merkle path, so the control block consists of the control byte and the internal key.
+    assert_eq!(
+        witness[2].to_hex(),
+        "c050929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0"
+    );
+}
+
+#[test]
+fn test_bitcoin_sign_input_p2wsh_cltv_locktime_not_reached() {
+    let alice_private_key = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public().compressed();
+
+    let script = timelock_script(
+        alice_pubkey.to_vec(),
+        TimelockType::absolute(CLTV_HEIGHT),
+        Vec::default(),
+    );
+    let input = make_signing_input(
+        input::p2wsh_timelock(script),
+        Proto::TransactionVersion::UseDefault,
+        CLTV_HEIGHT - 1,
+    );
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, input);
+    assert_eq!(output.error, SigningError::Error_invalid_params);
+}
+
+#[test]
+fn test_bitcoin_sign_input_p2wsh_csv_version_1() {
+    let alice_private_key = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public().compressed();
+
+    let script = timelock_script(
+        alice_pubkey.to_vec(),
+        TimelockType::relative(CSV_BLOCKS),
+        Vec::default(),
+    );
+    let input = make_signing_input(
+        input::p2wsh_timelock(script),
+        Proto::TransactionVersion::V1,
+        0,
+    );
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, input);
+    assert_eq!(output.error, SigningError::Error_invalid_params);
+}
@@ -2,11 +2,15 @@
 //
 // Copyright © 2017 Trust Wallet.
 
-use crate::chains::bitcoincash::test_cases::transfer_96ee20;
-use crate::chains::common::bitcoin::{btc_info, plan, sign, TransactionOneof};
+use crate::chains::bitcoincash::test_cases::{cash_token_transfer, transfer_96ee20};
+use crate::chains::common::bitcoin::{btc_info, output, plan, sign, TransactionOneof};
+use tw_any_coin::test_utils::plan_utils::AnyPlannerHelper;
+use tw_any_coin::test_utils::sign_utils::AnySignerHelper;
 use tw_coin_registry::coin_type::CoinType;
 use tw_encoding::hex::DecodeHex;
 use tw_proto::BitcoinV2::Proto;
+use tw_proto::BitcoinV2::Proto::CashTokenCapability;
+use tw_proto::Common::Proto::SigningError;
 
 /// Original test: https://github.com/trustwallet/wallet-core/blob/cd5a27481d2181e63362cb57e2b2160506cce163/tests/chains/BitcoinCash/TWBitcoinCashTests.cpp#L122-L164
 /// *Note* that if we enable change output, current selection algorithm will calculate
@@ -44,3 +48,233 @@ fn test_bitcoincash_sign_input_p2pkh_from_to_address() {
             fee: 226,
         });
 }
+
+#[test]
+fn test_bitcoincash_sign_cash_token_transfer() {
+    let builder = cash_token_transfer::transaction_builder(cash_token_transfer::TO_ADDRESS);
+    let signing = Proto::SigningInput {
+        private_keys: vec![cash_token_transfer::PRIVATE_KEY
+            .decode_hex()
+            .unwrap()
+            .into()],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    // The UTXO holding tokens is selected first even though the selector is `SelectAscending`,
+    // and the UTXO holding tokens of another category is not spent.
+    plan::BitcoinPlanHelper::new(&signing)
+        .coin(CoinType::BitcoinCash)
+        .plan(plan::Expected {
+            inputs: vec![1000, 5151],
+            outputs: vec![800, 4901],
+            vsize_estimate: 450,
+            fee_estimate: 450,
+            change: 4901,
+        });
+
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::BitcoinCash)
+        .sign(sign::Expected {
+            encoded: cash_token_transfer::ENCODED_TX,
+            txid: cash_token_transfer::TX_ID,
+            inputs: vec![1000, 5151],
+            outputs: vec![800, 4901],
+            vsize: 448,
+            weight: 1792,
+            fee: 450,
+        });
+}
+
+#[test]
+fn test_bitcoincash_sign_cash_token_to_regular_address_error() {
+    // Regular (not token-aware) address of the same receiver.
+    let builder = cash_token_transfer::transaction_builder(
+        "bitcoincash:qz0q3xmg38sr94rw8wg45vujah7kzma3cskxymnw06",
+    );
+    let signing = Proto::SigningInput {
+        private_keys: vec![cash_token_transfer::PRIVATE_KEY
+            .decode_hex()
+            .unwrap()
+            .into()],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::BitcoinCash, signing);
+    assert_eq!(output.error, SigningError::Error_invalid_address);
+}
+
+#[test]
+fn test_bitcoincash_sign_cash_token_insufficient_tokens() {
+    let mut builder = cash_token_transfer::transaction_builder(cash_token_transfer::TO_ADDRESS);
+    builder.outputs[0].cash_token =
+        cash_token_transfer::fungible_token(cash_token_transfer::CATEGORY, 1001);
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![cash_token_transfer::PRIVATE_KEY
+            .decode_hex()
+            .unwrap()
+            .into()],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::BitcoinCash, signing);
+    assert_eq!(output.error, SigningError::Error_not_enough_utxos);
+}
+
+#[test]
+fn test_bitcoincash_sign_cash_token_change_to_regular_address_error() {
+    let mut builder = cash_token_transfer::transaction_builder(cash_token_transfer::TO_ADDRESS);
+    // The remaining tokens cannot be sent to a regular (not token-aware) change address.
+    builder.change_output = Some(Proto::Output {
+        value: 0,
+        to_recipient: output::to_address(cash_token_transfer::SENDER_ADDRESS),
+        cash_token: None,
+    });
+
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+    let plan = planner.plan(
+        CoinType::BitcoinCash,
+        cash_token_signing_input(builder.clone()),
+    );
+    assert_eq!(plan.error, SigningError::Error_invalid_address);
+
+    // No tokens remain, so the change output can be a regular address.
+    builder.outputs[0].cash_token =
+        cash_token_transfer::fungible_token(cash_token_transfer::CATEGORY, 1000);
+    let plan = planner.plan(CoinType::BitcoinCash, cash_token_signing_input(builder));
+    assert_eq!(plan.error, SigningError::OK);
+}
+
+#[test]
+fn test_bitcoincash_sign_cash_token_nft_commitment_too_long() {
+    let mut builder = cash_token_transfer::transaction_builder(cash_token_transfer::TO_ADDRESS);
+    builder.inputs[0].cash_token = cash_token_transfer::nft_token(
+        cash_token_transfer::CATEGORY,
+        CashTokenCapability::Mutable,
+        &[],
+        1000,
+    );
+    builder.outputs[0].cash_token = cash_token_transfer::nft_token(
+        cash_token_transfer::CATEGORY,
+        CashTokenCapability::Immutable,
+        &[0xcc; 41],
+        400,
+    );
+
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+    let plan = planner.plan(CoinType::BitcoinCash, cash_token_signing_input(builder));
+    assert_eq!(plan.error, SigningError::Error_invalid_params);
+}
+
+#[test]
+fn test_bitcoincash_sign_cash_token_nft_without_input_nft() {
+    // The UTXO holds fungible tokens of the category only.
+    let mut builder = cash_token_transfer::transaction_builder(cash_token_transfer::TO_ADDRESS);
+    builder.outputs[0].cash_token = cash_token_transfer::nft_token(
+        cash_token_transfer::CATEGORY,
+        CashTokenCapability::Immutable,
+        &[0xcc],
+        400,
+    );
+
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+    let plan = planner.plan(CoinType::BitcoinCash, cash_token_signing_input(builder));
+    assert_eq!(plan.error, SigningError::Error_not_enough_utxos);
+
+    // The UTXO holds an NFT of another category.
+    let mut builder = cash_token_transfer::transaction_builder(cash_token_transfer::TO_ADDRESS);
+    builder.inputs[2].cash_token = cash_token_transfer::nft_token(
+        cash_token_transfer::OTHER_CATEGORY,
+        CashTokenCapability::Minting,
+        &[],
+        0,
+    );
+    builder.outputs[0].cash_token = cash_token_transfer::nft_token(
+        cash_token_transfer::CATEGORY,
+        CashTokenCapability::Immutable,
+        &[0xcc],
+        400,
+    );
+    let plan = planner.plan(CoinType::BitcoinCash, cash_token_signing_input(builder));
+    assert_eq!(plan.error, SigningError::Error_not_enough_utxos);
+}
+
+#[test]
+fn test_bitcoincash_sign_cash_token_nft_capabilities() {
+    #[track_caller]
+    fn test_impl(
+        input_capability: CashTokenCapability,
+        output_capability: CashTokenCapability,
+        output_commitment: &[u8],
+        expected_error: SigningError,
+    ) {
+        let mut builder = cash_token_transfer::transaction_builder(cash_token_transfer::TO_ADDRESS);
+        builder.inputs[0].cash_token = cash_token_transfer::nft_token(
+            cash_token_transfer::CATEGORY,
+            input_capability,
+            &[0xaa],
+            1000,
+        );
+        builder.outputs[0].cash_token = cash_token_transfer::nft_token(
+            cash_token_transfer::CATEGORY,
+            output_capability,
+            output_commitment,
+            400,
+        );
+
+        let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+        let plan = planner.plan(CoinType::BitcoinCash, cash_token_signing_input(builder));
+        assert_eq!(plan.error, expected_error);
+    }
+
+    use CashTokenCapability::{Immutable, Minting, Mutable};
+
+    // Immutable NFT can be sent with the same commitment only.
+    test_impl(Immutable, Immutable, &[0xaa], SigningError::OK);
+    test_impl(
+        Immutable,
+        Immutable,
+        &[0xbb],
+        SigningError::Error_invalid_params,
+    );
+    test_impl(
+        Immutable,
+        Mutable,
+        &[0xaa],
+        SigningError::Error_invalid_params,
+    );
+    // Mutable NFT can change the commitment and downgrade the capability.
+    test_impl(Mutable, Mutable, &[0xbb], SigningError::OK);
+    test_impl(Mutable, Immutable, &[0xbb], SigningError::OK);
+    test_impl(
+        Mutable,
+        Minting,
+        &[0xaa],
+        SigningError::Error_invalid_params,
+    );
+    // Minting NFT can create any NFTs.
+    test_impl(Minting, Immutable, &[0xbb], SigningError::OK);
+    test_impl(Minting, Minting, &[0xbb], SigningError::OK);
+}
+
+fn cash_token_signing_input(
+    builder: Proto::TransactionBuilder<'static>,
+) -> Proto::SigningInput<'static> {
+    Proto::SigningInput {
+        private_keys: vec![cash_token_transfer::PRIVATE_KEY
+            .decode_hex()
+            .unwrap()
+            .into()],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    }
+}
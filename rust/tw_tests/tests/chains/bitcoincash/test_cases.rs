@@ -3,6 +3,7 @@
 // Copyright © 2017 Trust Wallet.
 
 use crate::chains::bitcoincash::BCH_SIGHASH_FORK;
+use crate::chains::common::bitcoin::input::reverse_txid;
 use crate::chains::common::bitcoin::{dust_threshold, input, output, DUST, SIGHASH_ALL};
 use tw_proto::BitcoinV2::Proto;
 
@@ -42,6 +43,7 @@ pub(super) mod transfer_96ee20 {
             value: 600,
             // Legacy address.
             to_recipient: output::to_address("1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx"),
+            cash_token: None,
         };
         let explicit_change_out = Proto::Output {
             value: 4325,
@@ -49,6 +51,7 @@ pub(super) mod transfer_96ee20 {
             to_recipient: output::to_address(
                 "bitcoincash:qz0q3xmg38sr94rw8wg45vujah7kzma3cskxymnw06",
             ),
+            cash_token: None,
         };
 
         Proto::TransactionBuilder {
@@ -63,3 +66,114 @@ pub(super) mod transfer_96ee20 {
         }
     }
 }
+
+/// Sends CashTokens to a token-aware address and returns the remaining tokens to the change output.
+pub(super) mod cash_token_transfer {
+    use super::*;
+
+    pub const PRIVATE_KEY: &str =
+        "7fdafb9db5bc501f2096e7d13d331dc7a75d9594af3d251313ba8b6200f4e384";
+    /// Token-aware address of the receiver.
+    pub const TO_ADDRESS: &str = "bitcoincash:zz0q3xmg38sr94rw8wg45vujah7kzma3cs3vh9agsf";
+    pub const SENDER_ADDRESS: &str = "bitcoincash:qzhlrcrcne07x94h99thved2pgzdtv8ccujjy73xya";
+    /// Token-aware address of the sender.
+    pub const CHANGE_ADDRESS: &str = "bitcoincash:zzhlrcrcne07x94h99thved2pgzdtv8ccu4chqlqmw";
+    pub const CATEGORY: &str = "fad1247e2bf0b59929bb6274afb6975e9009af2906d52a8da2efc70d2ae67e5e";
+    pub const OTHER_CATEGORY: &str =
+        "21a6593406dfa0a93d031499997b9e7e4c5d0ebde878664e716dbab5e82ec337";
+    pub const ENCODED_TX: &str = concat!(
+        "01000000",
+        "02",
+            "f7a2d9f2ffcf3d870e57105bb913e9d6100058e77b3adb03d89e56edcccaa414", "01000000", "6b483045022100db7058cb2b11beeb323f624822585b19ab83524fba09ae95c1161d6e78d5086402204f41e694472c997af1664b2f333b6851572e086ddf93f080e8d487985d1be3b94121038eab72ec78e639d02758e7860cdec018b49498c307791f785aa3019622f4ea5b", "ffffffff",
+            "e28c2b955293159898e34c6840d99bf4d390e2ee1c6f606939f18ee1e2000d05", "02000000", "6b483045022100cb1bc7a70553ed1751fa875d2b7e0829e2f70adb23b730a54062017e36ab91e90220675340c46b7e29a72345a0d3103becc5a81c38d6eb08996bc48556dcf328b52d4121038eab72ec78e639d02758e7860cdec018b49498c307791f785aa3019622f4ea5b", "ffffffff",
+        "02",
+            "2003000000000000", "3eef5e7ee62a0dc7efa28d2ad50629af09905e97b6af7462bb2999b5f02b7e24d1fa10fd900176a9149e089b6889e032d46e3b915a3392edfd616fb1c488ac",
+            "2513000000000000", "3eef5e7ee62a0dc7efa28d2ad50629af09905e97b6af7462bb2999b5f02b7e24d1fa10fd580276a914aff1e0789e5fe316b729577665aa0a04d5b0f8c788ac",
+        "00000000"
+    );
+    pub const TX_ID: &str = "528246198655240d04de64ac1a31cc7225283a5fe30a91fd326fbffbeeb12ea2";
+
+    pub fn fungible_token(category: &str, amount: u64) -> Option<Proto::CashToken<'static>> {
+        Some(Proto::CashToken {
+            category: reverse_txid(category).into(),
+            nft: None,
+            amount,
+        })
+    }
+
+    pub fn nft_token(
+        category: &str,
+        capability: Proto::CashTokenCapability,
+        commitment: &[u8],
+        amount: u64,
+    ) -> Option<Proto::CashToken<'static>> {
+        Some(Proto::CashToken {
+            category: reverse_txid(category).into(),
+            nft: Some(Proto::CashTokenNft {
+                capability,
+                commitment: commitment.to_vec().into(),
+            }),
+            amount,
+        })
+    }
+
+    pub fn transaction_builder(to_address: &str) -> Proto::TransactionBuilder<'static> {
+        // UTXO holding 1000 fungible tokens of `CATEGORY`.
+        let token_utxo = Proto::Input {
+            out_point: input::out_point(
+                "14a4cacced569ed803db3a7be7580010d6e913b95b10570e873dcffff2d9a2f7",
+                1,
+            ),
+            value: 1000,
+            sighash_type: SIGHASH_ALL | BCH_SIGHASH_FORK,
+            claiming_script: input::receiver_address(SENDER_ADDRESS),
+            cash_token: fungible_token(CATEGORY, 1000),
+            ..Default::default()
+        };
+        let bch_utxo = Proto::Input {
+            out_point: input::out_point(
+                "050d00e2e18ef13969606f1ceee290d3f49bd940684ce39898159352952b8ce2",
+                2,
+            ),
+            value: 5151,
+            sighash_type: SIGHASH_ALL | BCH_SIGHASH_FORK,
+            claiming_script: input::receiver_address(SENDER_ADDRESS),
+            ..Default::default()
+        };
+        // UTXO holding tokens of another category must not be spent.
+        let other_token_utxo = Proto::Input {
+            out_point: input::out_point(
+                "9c62e9bcbb0c7a0b6ee0aa5b3b4d2d1b7a4b53c8ce6e55a0f5d3a3e1b4b3e2d1",
+                0,
+            ),
+            value: 800,
+            sighash_type: SIGHASH_ALL | BCH_SIGHASH_FORK,
+            claiming_script: input::receiver_address(SENDER_ADDRESS),
+            cash_token: fungible_token(OTHER_CATEGORY, 50),
+            ..Default::default()
+        };
+
+        let out_1 = Proto::Output {
+            value: 800,
+            to_recipient: output::to_address(to_address),
+            cash_token: fungible_token(CATEGORY, 400),
+        };
+        // The remaining 600 tokens are sent to the change output.
+        let change_out = Proto::Output {
+            value: 0,
+            to_recipient: output::to_address(CHANGE_ADDRESS),
+            cash_token: None,
+        };
+
+        Proto::TransactionBuilder {
+            version: Proto::TransactionVersion::V1,
+            inputs: vec![token_utxo, bch_utxo, other_token_utxo],
+            outputs: vec![out_1],
+            change_output: Some(change_out),
+            input_selector: Proto::InputSelector::SelectAscending,
+            fee_per_vb: 1,
+            dust_policy: dust_threshold(DUST),
+            ..Default::default()
+        }
+    }
+}
@@ -52,6 +52,20 @@ pub mod input {
         ))
     }
 
+    pub fn staking_slashing_path(
+        params: BabylonProto::StakingInfo<'static>,
+        finality_provider_signature: Option<BabylonProto::PublicKeySignature<'static>>,
+        covenant_signatures: PubkeySignatures,
+    ) -> ClaimingScriptType<'static> {
+        claiming_script_builder(InputBuilderType::babylon_staking_slashing_path(
+            BabylonInput::StakingSlashingPath {
+                params: Some(params),
+                finality_provider_signature,
+                covenant_committee_signatures: covenant_signatures.sigs,
+            },
+        ))
+    }
+
     pub fn unbonding_timelock_path(
         params: BabylonProto::StakingInfo<'static>,
     ) -> ClaimingScriptType<'static> {
@@ -7,6 +7,7 @@
 #![allow(dead_code)]
 
 use tw_encoding::hex::DecodeHex;
+use tw_memory::Data;
 
 pub mod babylon;
 pub mod compile;
@@ -33,6 +34,7 @@ pub const BITCOIN_HRP: &str = "bc";
 pub type Amount = i64;
 
 pub use tw_proto::BitcoinV2::Proto::mod_Input::mod_InputBuilder::OneOfvariant as InputBuilderType;
+pub use tw_proto::BitcoinV2::Proto::mod_Input::mod_TaprootWitnessItem::OneOfvariant as WitnessItemType;
 pub use tw_proto::BitcoinV2::Proto::mod_Input::{
     InputBuilder, OneOfclaiming_script as ClaimingScriptType,
 };
@@ -65,6 +67,18 @@ pub fn transaction_psbt(hex: &str) -> TransactionOneof {
     })
 }
 
+pub fn timelock_script(
+    public_key: Data,
+    timelock: Proto::mod_Timelock::OneOfvariant,
+    internal_key: Data,
+) -> Proto::TimelockScript<'static> {
+    Proto::TimelockScript {
+        public_key: public_key.into(),
+        timelock: Some(Proto::Timelock { variant: timelock }),
+        internal_key: internal_key.into(),
+    }
+}
+
 pub mod input {
     use super::*;
     use tw_encoding::hex::DecodeHex;
@@ -129,6 +143,44 @@ pub mod input {
         ))
     }
 
+    pub fn p2tr_script_tree(
+        internal_key: Data,
+        tree: Proto::TaprootScriptTree<'static>,
+        leaf_index: u32,
+        spender: Data,
+        witness_items: Vec<WitnessItemType<'static>>,
+    ) -> ClaimingScriptType<'static> {
+        claiming_script_builder(InputBuilderType::p2tr_script_tree(
+            Proto::mod_Input::InputTaprootScriptTree {
+                internal_key: internal_key.into(),
+                tree: Some(tree),
+                leaf_index,
+                spender_public_key: spender.into(),
+                witness_items: witness_items
+                    .into_iter()
+                    .map(|variant| Proto::mod_Input::TaprootWitnessItem { variant })
+                    .collect(),
+            },
+        ))
+    }
+
+    pub fn p2wsh_timelock(script: Proto::TimelockScript<'static>) -> ClaimingScriptType<'static> {
+        claiming_script_builder(InputBuilderType::p2wsh_timelock(script))
+    }
+
+    pub fn p2tr_timelock(script: Proto::TimelockScript<'static>) -> ClaimingScriptType<'static> {
+        claiming_script_builder(InputBuilderType::p2tr_timelock(script))
+    }
+
+    pub fn rune_etching_reveal(public_key: Data, rune: &str) -> ClaimingScriptType<'static> {
+        claiming_script_builder(InputBuilderType::rune_etching_reveal(
+            Proto::mod_Input::InputRuneCommitment {
+                public_key: public_key.into(),
+                rune: rune.to_string().into(),
+            },
+        ))
+    }
+
     pub fn receiver_address(addr: &str) -> ClaimingScriptType<'static> {
         ClaimingScriptType::receiver_address(addr.to_string().into())
     }
@@ -136,6 +188,13 @@ pub mod input {
     pub fn custom_script(script: Data) -> ClaimingScriptType<'static> {
         ClaimingScriptType::script_data(script.into())
     }
+
+    pub fn descriptor(descriptor: &str, index: u32) -> ClaimingScriptType<'static> {
+        ClaimingScriptType::descriptor(Proto::Descriptor {
+            descriptor: descriptor.to_string().into(),
+            index,
+        })
+    }
 }
 
 pub mod output {
@@ -209,6 +268,18 @@ pub mod output {
         }))
     }
 
+    pub fn p2tr_script_tree(
+        internal_key: Data,
+        tree: Proto::TaprootScriptTree<'static>,
+    ) -> RecipientType<'static> {
+        receiver_builder(OutputBuilderType::p2tr_script_tree(
+            Proto::mod_Output::OutputTaprootScriptTree {
+                internal_key: internal_key.into(),
+                tree: Some(tree),
+            },
+        ))
+    }
+
     pub fn brc20_inscribe(
         inscribe_to: Data,
         ticker: &str,
@@ -223,6 +294,27 @@ pub mod output {
         ))
     }
 
+    pub fn runestone(runestone: Proto::Runestone<'static>) -> RecipientType<'static> {
+        receiver_builder(OutputBuilderType::runestone(runestone))
+    }
+
+    pub fn p2wsh_timelock(script: Proto::TimelockScript<'static>) -> RecipientType<'static> {
+        receiver_builder(OutputBuilderType::p2wsh_timelock(script))
+    }
+
+    pub fn p2tr_timelock(script: Proto::TimelockScript<'static>) -> RecipientType<'static> {
+        receiver_builder(OutputBuilderType::p2tr_timelock(script))
+    }
+
+    pub fn rune_etching_commit(public_key: Data, rune: &str) -> RecipientType<'static> {
+        receiver_builder(OutputBuilderType::rune_etching_commit(
+            Proto::mod_Output::OutputRuneCommitment {
+                public_key: public_key.into(),
+                rune: rune.to_string().into(),
+            },
+        ))
+    }
+
     pub fn to_address(addr: &str) -> RecipientType<'static> {
         RecipientType::to_address(addr.to_string().into())
     }
@@ -234,4 +326,11 @@ pub mod output {
     pub fn custom_script(data: Data) -> RecipientType<'static> {
         RecipientType::custom_script_pubkey(data.into())
     }
+
+    pub fn descriptor(descriptor: &str, index: u32) -> RecipientType<'static> {
+        RecipientType::descriptor(Proto::Descriptor {
+            descriptor: descriptor.to_string().into(),
+            index,
+        })
+    }
 }
@@ -111,6 +111,7 @@ pub fn make_planning_input(args: PlanArgs) -> Proto::SigningInput<'static> {
         .map(|output_amount| Proto::Output {
             value: *output_amount,
             to_recipient: output::p2pkh(bob_pubkey.clone()),
+            cash_token: None,
         })
         .collect();
 
@@ -119,6 +120,7 @@ pub fn make_planning_input(args: PlanArgs) -> Proto::SigningInput<'static> {
             // Put any value here as the change amount will be overwritten.
             value: 1234,
             to_recipient: output::p2pkh(alice_pubkey),
+            cash_token: None,
         })
     } else {
         None
@@ -129,6 +131,7 @@ pub fn make_planning_input(args: PlanArgs) -> Proto::SigningInput<'static> {
             // Put any value here as the max amount will be overwritten.
             value: 4321,
             to_recipient: output::p2pkh(bob_pubkey),
+            cash_token: None,
         })
     } else {
         None
@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    dust_threshold, input, output, plan, RecipientType, TransactionOneof, SIGHASH_ALL,
+};
+use tw_any_coin::test_utils::plan_utils::AnyPlannerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::DecodeHex;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::Common::Proto::SigningError;
+
+const ALICE_PUBKEY: &str = "037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf";
+const DUMMY_TXID: &str = "06953f715201d085d0607097bc7571fcebe92bd4aa30038bd480a72357b28796";
+const ONE_DOGE: i64 = 100_000_000;
+
+fn make_signing_input(
+    to_recipient: RecipientType<'static>,
+    output_value: i64,
+    fee_per_vb: i64,
+    dust: i64,
+) -> Proto::SigningInput<'static> {
+    let alice_pubkey = ALICE_PUBKEY.decode_hex().unwrap();
+
+    let utxo = Proto::Input {
+        out_point: input::out_point(DUMMY_TXID, 0),
+        value: 10 * ONE_DOGE,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2pkh(alice_pubkey.clone()),
+        ..Proto::Input::default()
+    };
+    let out_0 = Proto::Output {
+        value: output_value,
+        to_recipient,
+        cash_token: None,
+    };
+    let change_output = Proto::Output {
+        value: 0,
+        to_recipient: output::p2pkh(alice_pubkey),
+        cash_token: None,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        inputs: vec![utxo],
+        outputs: vec![out_0],
+        change_output: Some(change_output),
+        input_selector: Proto::InputSelector::SelectInOrder,
+        fee_per_vb,
+        dust_policy: dust_threshold(dust),
+        ..Default::default()
+    };
+
+    Proto::SigningInput {
+        transaction: TransactionOneof::builder(builder),
+        ..Proto::SigningInput::default()
+    }
+}
+
+fn plan_error(input: Proto::SigningInput<'static>) -> SigningError {
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+    planner.plan(CoinType::Dogecoin, input).error
+}
+
+#[test]
+fn test_dogecoin_plan_p2pkh() {
+    let alice_pubkey = ALICE_PUBKEY.decode_hex().unwrap();
+    let input = make_signing_input(output::p2pkh(alice_pubkey), 5 * ONE_DOGE, 1_000, 100_000);
+
+    // Size: 4 (version) + 1 + 149 (P2PKH input) + 1 + 2 * 34 (P2PKH outputs) + 4 (locktime) = 227.
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Dogecoin)
+        .plan(plan::Expected {
+            inputs: vec![10 * ONE_DOGE],
+            outputs: vec![5 * ONE_DOGE, 499_773_000],
+            vsize_estimate: 227,
+            // vsize * fee_rate
+            fee_estimate: 227_000,
+            change: 499_773_000,
+        });
+}
+
+#[test]
+fn test_dogecoin_plan_fee_rate_too_low() {
+    let alice_pubkey = ALICE_PUBKEY.decode_hex().unwrap();
+    // 0.0001 DOGE per kB is less than the min relay fee.
+    let input = make_signing_input(output::p2pkh(alice_pubkey), 5 * ONE_DOGE, 10, 100_000);
+    assert_eq!(plan_error(input), SigningError::Error_wrong_fee);
+}
+
+#[test]
+fn test_dogecoin_plan_soft_dust_output() {
+    let alice_pubkey = ALICE_PUBKEY.decode_hex().unwrap();
+    // 0.005 DOGE is less than the soft dust limit, even though the given dust threshold is lower.
+    let input = make_signing_input(output::p2pkh(alice_pubkey), 500_000, 1_000, 100_000);
+    assert_eq!(plan_error(input), SigningError::Error_dust_amount_requested);
+}
+
+#[test]
+fn test_dogecoin_plan_segwit_output_unsupported() {
+    let alice_pubkey = ALICE_PUBKEY.decode_hex().unwrap();
+    let input = make_signing_input(output::p2wpkh(alice_pubkey), 5 * ONE_DOGE, 1_000, 100_000);
+    assert_eq!(plan_error(input), SigningError::Error_script_output);
+}
+
+#[test]
+fn test_dogecoin_plan_taproot_output_unsupported() {
+    let alice_pubkey = ALICE_PUBKEY.decode_hex().unwrap();
+    let input = make_signing_input(
+        output::p2tr_key_path(alice_pubkey),
+        5 * ONE_DOGE,
+        1_000,
+        100_000,
+    );
+    assert_eq!(plan_error(input), SigningError::Error_script_output);
+}
@@ -148,6 +148,7 @@ fn test_ethereum_abi_encode_function() {
             named_token("", TokenEnum::number_uint(number_n::<256>(69))),
             named_token("", TokenEnum::boolean(true)),
         ],
+        ..AbiProto::FunctionEncodingInput::default()
     };
 
     let input_data = TWDataHelper::create(serialize(&input).unwrap());
@@ -166,6 +167,144 @@ fn test_ethereum_abi_encode_function() {
     assert_eq!(output.encoded.to_hex(), "72ed38b600000000000000000000000000000000000000000000000000000000000000450000000000000000000000000000000000000000000000000000000000000001");
 }
 
+#[test]
+fn test_ethereum_abi_encode_function_human_readable() {
+    let input = AbiProto::FunctionEncodingInput {
+        tokens: vec![
+            named_token("", TokenEnum::number_uint(number_n::<256>(69))),
+            named_token("", TokenEnum::boolean(true)),
+        ],
+        human_readable_abi: "function baz(uint256 x, bool y) external returns (bool)".into(),
+        ..AbiProto::FunctionEncodingInput::default()
+    };
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_ethereum_abi_encode_function(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_ethereum_abi_encode_function returned nullptr");
+
+    let output: AbiProto::FunctionEncodingOutput = deserialize(&output_data)
+        .expect("!tw_ethereum_abi_encode_function returned an invalid output");
+
+    assert_eq!(output.error, AbiErrorKind::OK);
+    assert!(output.error_message.is_empty());
+    assert_eq!(output.function_type, "baz(uint256,bool)");
+    assert_eq!(output.encoded.to_hex(), "72ed38b600000000000000000000000000000000000000000000000000000000000000450000000000000000000000000000000000000000000000000000000000000001");
+}
+
+#[test]
+fn test_ethereum_abi_encode_function_human_readable_tuples() {
+    let uint256 = || ParamTypeEnum::number_uint(Proto::NumberNType { bits: 256 });
+    let uint256_array = |values: &[u64]| {
+        TokenEnum::array(Proto::ArrayParam {
+            element_type: Some(Proto::ParamType { param: uint256() }),
+            elements: values
+                .iter()
+                .map(|value| named_token("", TokenEnum::number_uint(number_n::<256>(*value))))
+                .collect(),
+        })
+    };
+
+    // `(address maker, uint256[] amounts)`
+    let order_type = ParamTypeEnum::tuple(Proto::TupleType {
+        params: vec![
+            param("maker", ParamTypeEnum::address(Proto::AddressType {})),
+            param(
+                "amounts",
+                ParamTypeEnum::array(Box::new(Proto::ArrayType {
+                    element_type: Some(Box::new(Proto::ParamType { param: uint256() })),
+                })),
+            ),
+        ],
+    });
+    let order = named_token(
+        "",
+        TokenEnum::tuple(Proto::TupleParam {
+            params: vec![
+                named_token(
+                    "maker",
+                    TokenEnum::address("0x5322B34c88Ed0691971Bf52A7047448f0F4eFC84".into()),
+                ),
+                named_token("amounts", uint256_array(&[1, 2])),
+            ],
+        }),
+    );
+    // Component names don't have to match the fragment.
+    let extra = named_token(
+        "extra",
+        TokenEnum::tuple(Proto::TupleParam {
+            params: vec![
+                named_token(
+                    "account",
+                    TokenEnum::address("0x4bbeEB066eD09B7AEd07bF39EEe0460DFa261520".into()),
+                ),
+                named_token("flag", TokenEnum::boolean(true)),
+            ],
+        }),
+    );
+
+    let input = AbiProto::FunctionEncodingInput {
+        tokens: vec![
+            named_token(
+                "orders",
+                TokenEnum::array(Proto::ArrayParam {
+                    element_type: Some(Proto::ParamType { param: order_type }),
+                    elements: vec![order],
+                }),
+            ),
+            extra,
+        ],
+        human_readable_abi:
+            "function fill((address maker, uint256[] amounts)[] orders, (address owner, bool enabled) extra)"
+                .into(),
+        ..AbiProto::FunctionEncodingInput::default()
+    };
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_ethereum_abi_encode_function(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_ethereum_abi_encode_function returned nullptr");
+
+    let output: AbiProto::FunctionEncodingOutput = deserialize(&output_data)
+        .expect("!tw_ethereum_abi_encode_function returned an invalid output");
+
+    assert_eq!(output.error, AbiErrorKind::OK, "{}", output.error_message);
+    assert_eq!(
+        output.function_type,
+        "fill((address,uint256[])[],(address,bool))"
+    );
+    assert_eq!(output.encoded.to_hex(), "d3bcac1f00000000000000000000000000000000000000000000000000000000000000600000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa2615200000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000200000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002");
+}
+
+#[test]
+fn test_ethereum_abi_encode_function_human_readable_mismatch() {
+    let input = AbiProto::FunctionEncodingInput {
+        tokens: vec![named_token("", TokenEnum::boolean(true))],
+        human_readable_abi: "function baz(uint256 x, bool y)".into(),
+        ..AbiProto::FunctionEncodingInput::default()
+    };
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_ethereum_abi_encode_function(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_ethereum_abi_encode_function returned nullptr");
+
+    let output: AbiProto::FunctionEncodingOutput = deserialize(&output_data)
+        .expect("!tw_ethereum_abi_encode_function returned an invalid output");
+
+    assert_eq!(output.error, AbiErrorKind::Error_abi_mismatch);
+    assert!(!output.error_message.is_empty());
+}
+
 #[test]
 fn test_ethereum_abi_decode_value() {
     let input = AbiProto::ValueDecodingInput {
@@ -290,3 +429,22 @@ fn test_ethereum_get_function_signature_complex() {
         "f((uint256,uint256[],(uint256,uint256)[]),(uint256,uint256),uint256)"
     );
 }
+
+#[test]
+fn test_ethereum_get_function_signature_human_readable() {
+    // The same function as in `test_ethereum_get_function_signature_complex`.
+    let abi = "function f((uint256 a, uint256[] b, (uint256 x, uint256 y)[] c) s, (uint256 x, uint256 y) t, uint256 a) public";
+
+    let abi_string = TWStringHelper::create(abi);
+
+    let actual = TWStringHelper::wrap(unsafe {
+        tw_ethereum_abi_get_function_signature(CoinType::Ethereum as u32, abi_string.ptr())
+    })
+    .to_string()
+    .expect("!tw_ethereum_abi_get_function_signature returned nullptr");
+
+    assert_eq!(
+        actual,
+        "f((uint256,uint256[],(uint256,uint256)[]),(uint256,uint256),uint256)"
+    );
+}
@@ -83,6 +83,73 @@ fn test_transaction_compiler_eth() {
     assert_eq!(output.encoded.to_hex(), expected_encoded);
 }
 
+#[test]
+fn test_transaction_compiler_eth_eip2930() {
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(1_000_000_000_000_000_000),
+        data: Cow::default(),
+    };
+    let input = Proto::SigningInput {
+        nonce: U256::encode_be_compact(0),
+        chain_id: U256::encode_be_compact(1),
+        tx_mode: Proto::TransactionMode::AccessList,
+        gas_price: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(21_000),
+        to_address: "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        ..Proto::SigningInput::default()
+    };
+
+    // Step 1: Obtain preimage hash
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let preimage_data = TWDataHelper::wrap(unsafe {
+        tw_transaction_compiler_pre_image_hashes(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_transaction_compiler_pre_image_hashes returned nullptr");
+
+    let preimage: CompilerProto::PreSigningOutput =
+        deserialize(&preimage_data).expect("Coin entry returned an invalid output");
+
+    assert_eq!(preimage.error, SigningErrorType::OK);
+    assert!(preimage.error_message.is_empty());
+    assert_eq!(
+        preimage.data_hash.to_hex(),
+        "8e77a335d8a828d5fe0efe574b5858dd8a32f5c95c7c071154d28634715f758c"
+    );
+
+    // Step 2: Compile transaction info
+
+    // Simulate signature, normally obtained from signature server
+    let signature = "58f91d15057d2d3e589c1b5754d69fd30b792eacbc82f03b55d01db98d0d06e910512e5d5fb078ce93b27a336bf9c1cfa26b373bf56603f2087eceed952644b201".decode_hex().unwrap();
+    let public_key = "0463ade8ebc212b85e7e4278dc3dcb4f9cc18aab912ef5d302b5d1940e772e9e1a9213522efddad487bbd5dd7907e8e776f918e9a5e4cb51893724e9fe76792a4f".decode_hex().unwrap();
+
+    let signatures = TWDataVectorHelper::create([signature]);
+    let public_keys = TWDataVectorHelper::create([public_key]);
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_transaction_compiler_compile(
+            CoinType::Ethereum as u32,
+            input_data.ptr(),
+            signatures.ptr(),
+            public_keys.ptr(),
+        )
+    })
+    .to_vec()
+    .expect("!tw_transaction_compiler_compile returned nullptr");
+
+    let output: Proto::SigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+    let expected_encoded = "01f86e01808504a817c80082520894b9f5771c27664bf2282d98e09d7f50cec7cb01a7880de0b6b3a764000080c001a058f91d15057d2d3e589c1b5754d69fd30b792eacbc82f03b55d01db98d0d06e9a010512e5d5fb078ce93b27a336bf9c1cfa26b373bf56603f2087eceed952644b2";
+    assert_eq!(output.encoded.to_hex(), expected_encoded);
+}
+
 #[test]
 fn test_transaction_compiler_plan_not_supported() {
     let transfer = Proto::mod_Transaction::Transfer {
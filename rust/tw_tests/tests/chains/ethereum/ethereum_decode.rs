@@ -0,0 +1,348 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use std::borrow::Cow;
+use tw_any_coin::test_utils::sign_utils::AnySignerHelper;
+use tw_any_coin::test_utils::transaction_decode_utils::TransactionDecoderHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_number::U256;
+use tw_proto::Common::Proto::SigningError;
+use tw_proto::Ethereum::Proto;
+use tw_proto::EthereumAbi::Proto as AbiProto;
+
+use AbiProto::mod_Token::OneOftoken as TokenEnum;
+use Proto::mod_Transaction::OneOftransaction_oneof as TransactionType;
+
+const SIGNED_BLOB_TRANSACTION: &str = "03f8920102843b9aca00850ba43b7400825208946b175474e89094c44da98b954eedeac495271d0f8080c084b2d05e00e1a0010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c44401401a035737198eeb70b4d876bbf8d996384a81631e84849b0b8354f92debd84ba2a1ba0604c015a93cd243baa0c82a91799fa83098c32b0d887f470b2b411db718b1fab";
+const BLOB_TRANSACTION_PRE_HASH: &str =
+    "031e58c8f284529ab1f0cd0d5c789995f9364e5372b11b3b15fcc466f4b5c7a7";
+
+fn transfer_amount(transaction: &Proto::Transaction) -> U256 {
+    match transaction.transaction_oneof {
+        TransactionType::transfer(ref transfer) => {
+            U256::from_big_endian_slice(&transfer.amount).unwrap()
+        },
+        _ => panic!("Expected a native transfer"),
+    }
+}
+
+#[test]
+fn test_ethereum_decode_legacy_erc20_transfer() {
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "f8aa808509c7652400830130b9946b175474e89094c44da98b954eedeac495271d0f80b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec8000025a0724c62ad4fbf47346b02de06e603e013f26f26b56fdc0be7ba3d6273401d98cea0032131cae15da7ddcda66963e8bef51ca0d9962bfef0547d3f02597a4a58c931".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::OK);
+    assert!(output.error_message.is_empty());
+
+    assert_eq!(output.sender, "0xAa9d131E33158bE2Cb1603b61fD17aD4f9E178ce");
+    assert_eq!(output.v.to_hex(), "25");
+    assert_eq!(
+        output.r.to_hex(),
+        "724c62ad4fbf47346b02de06e603e013f26f26b56fdc0be7ba3d6273401d98ce"
+    );
+    assert_eq!(
+        output.s.to_hex(),
+        "032131cae15da7ddcda66963e8bef51ca0d9962bfef0547d3f02597a4a58c931"
+    );
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "3a3fc6df8815e15874cc8d6c65f88ea0643b375e5b22726269d187035a5cb486"
+    );
+
+    let input = output.transaction.unwrap();
+    assert_eq!(input.tx_mode, Proto::TransactionMode::Legacy);
+    assert_eq!(input.chain_id.to_hex(), "01");
+    assert!(input.nonce.is_empty());
+    assert_eq!(input.gas_price.to_hex(), "09c7652400");
+    assert_eq!(input.gas_limit.to_hex(), "0130b9");
+    assert_eq!(
+        input.to_address,
+        "0x6B175474E89094C44Da98b954EedeAC495271d0F"
+    );
+    assert!(input.private_key.is_empty());
+    match input.transaction.unwrap().transaction_oneof {
+        TransactionType::contract_generic(call) => {
+            assert!(call.amount.is_empty());
+            assert_eq!(call.data.to_hex(), "a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000");
+        },
+        _ => panic!("Expected a contract call"),
+    }
+
+    let contract_call = output.contract_call.unwrap();
+    assert_eq!(contract_call.standard, Proto::ContractStandard::ERC20);
+    assert_eq!(
+        contract_call.function_signature,
+        "transfer(address,uint256)"
+    );
+    let expected_params = vec![
+        AbiProto::Token {
+            name: "to".into(),
+            token: TokenEnum::address("0x5322B34c88Ed0691971Bf52A7047448f0F4eFC84".into()),
+        },
+        AbiProto::Token {
+            name: "amount".into(),
+            token: TokenEnum::number_uint(AbiProto::NumberNParam {
+                bits: 256,
+                value: U256::encode_be_compact(2_000_000_000_000_000_000),
+            }),
+        },
+    ];
+    assert_eq!(contract_call.params, expected_params);
+}
+
+/// https://eips.ethereum.org/EIPS/eip-155#example
+#[test]
+fn test_ethereum_decode_eip155_transfer() {
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(output.sender, "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+    assert_eq!(output.v.to_hex(), "25");
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+    );
+    // Not a contract call.
+    assert_eq!(output.contract_call, None);
+
+    let input = output.transaction.unwrap();
+    assert_eq!(input.chain_id.to_hex(), "01");
+    assert_eq!(input.nonce.to_hex(), "09");
+    assert_eq!(
+        input.to_address,
+        "0x3535353535353535353535353535353535353535"
+    );
+    assert_eq!(
+        transfer_amount(&input.transaction.unwrap()),
+        U256::from(1_000_000_000_000_000_000_u64)
+    );
+}
+
+#[test]
+fn test_ethereum_decode_unsigned_eip155_transfer() {
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::OK);
+    assert!(output.sender.is_empty());
+    assert!(output.v.is_empty());
+    assert!(output.r.is_empty());
+    assert!(output.s.is_empty());
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+    );
+
+    let input = output.transaction.unwrap();
+    assert_eq!(input.chain_id.to_hex(), "01");
+    assert_eq!(input.gas_price.to_hex(), "04a817c800");
+}
+
+#[test]
+fn test_ethereum_decode_ambiguous_contract_call() {
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    // `approve(0x5322B34c88Ed0691971Bf52A7047448f0F4eFC84, 1000000000000000000)`
+    let output = decoder.decode(CoinType::Ethereum, "f869808504a817c80082ea60946b175474e89094c44da98b954eedeac495271d0f80b844095ea7b30000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000000de0b6b3a7640000018080".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::OK);
+
+    // `approve` selector is the same for ERC-20 and ERC-721.
+    let contract_call = output.contract_call.unwrap();
+    assert_eq!(
+        contract_call.standard,
+        Proto::ContractStandard::UnknownStandard
+    );
+    assert_eq!(contract_call.function_signature, "approve(address,uint256)");
+    let expected_params = vec![
+        AbiProto::Token {
+            name: "".into(),
+            token: TokenEnum::address("0x5322B34c88Ed0691971Bf52A7047448f0F4eFC84".into()),
+        },
+        AbiProto::Token {
+            name: "".into(),
+            token: TokenEnum::number_uint(AbiProto::NumberNParam {
+                bits: 256,
+                value: U256::encode_be_compact(1_000_000_000_000_000_000),
+            }),
+        },
+    ];
+    assert_eq!(contract_call.params, expected_params);
+}
+
+#[test]
+fn test_ethereum_decode_eip2930() {
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "01f86e01808504a817c80082520894b9f5771c27664bf2282d98e09d7f50cec7cb01a7880de0b6b3a764000080c001a058f91d15057d2d3e589c1b5754d69fd30b792eacbc82f03b55d01db98d0d06e9a010512e5d5fb078ce93b27a336bf9c1cfa26b373bf56603f2087eceed952644b2".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(output.sender, "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7");
+    assert_eq!(output.v.to_hex(), "01");
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "8e77a335d8a828d5fe0efe574b5858dd8a32f5c95c7c071154d28634715f758c"
+    );
+
+    let input = output.transaction.unwrap();
+    assert_eq!(input.tx_mode, Proto::TransactionMode::AccessList);
+    assert_eq!(input.chain_id.to_hex(), "01");
+    assert_eq!(input.gas_price.to_hex(), "04a817c800");
+    assert_eq!(input.gas_limit.to_hex(), "5208");
+    assert!(input.access_list.is_empty());
+}
+
+#[test]
+fn test_ethereum_decode_eip1559() {
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "02f8710306847735940084b2d05e0082526c94b9f5771c27664bf2282d98e09d7f50cec7cb01a78701ee0c29f50cb180c080a092c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c64a06487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e58".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(output.sender, "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7");
+    assert_eq!(output.v.to_hex(), "00");
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "6468eb103d51c9a683b51818fdb73390151c9973831d2cfb4e9587ad54273155"
+    );
+
+    let input = output.transaction.unwrap();
+    assert_eq!(input.tx_mode, Proto::TransactionMode::Enveloped);
+    assert_eq!(input.chain_id.to_hex(), "03");
+    assert_eq!(input.nonce.to_hex(), "06");
+    assert_eq!(input.max_inclusion_fee_per_gas.to_hex(), "77359400");
+    assert_eq!(input.max_fee_per_gas.to_hex(), "b2d05e00");
+    assert_eq!(input.gas_limit.to_hex(), "526c");
+    assert_eq!(
+        transfer_amount(&input.transaction.unwrap()),
+        U256::from(543_210_987_654_321_u64)
+    );
+}
+
+#[test]
+fn test_ethereum_decode_eip7702() {
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "04f8c90180847735940084b2d05e00830186a094b9f5771c27664bf2282d98e09d7f50cec7cb01a78080c0f85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0101a06e68a9eb48d83104756e83bcf868b9d8f8fa21097026ecf9035ae2be4314f697a0731e64a7ac248a0eaff0c35096e44e69539944449f533af8948ca87cdd72c92201a0048634c8c88bb1b6b97fbf90eb823bd045003eb182c78b8abe5ca4ed001063c1a0129ecad8f9a2d25bc698ccab91b1af089a8260c29d88aa2d342d9487cc34b3db".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(output.sender, "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7");
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "d750c6b71f1e01cc06157bff18a3f881f38d8f9c6db2184cd9405f0adeb635c7"
+    );
+
+    let input = output.transaction.unwrap();
+    assert_eq!(input.tx_mode, Proto::TransactionMode::SetCode);
+    assert_eq!(input.gas_limit.to_hex(), "0186a0");
+
+    let expected_authorization = Proto::Authorization {
+        chain_id: U256::encode_be_compact(1),
+        address: "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B".into(),
+        nonce: U256::encode_be_compact(1),
+        signature: "6e68a9eb48d83104756e83bcf868b9d8f8fa21097026ecf9035ae2be4314f697731e64a7ac248a0eaff0c35096e44e69539944449f533af8948ca87cdd72c92201".decode_hex().unwrap().into(),
+    };
+    assert_eq!(input.authorization_list, vec![expected_authorization]);
+}
+
+#[test]
+fn test_ethereum_decode_eip4844() {
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(
+        CoinType::Ethereum,
+        SIGNED_BLOB_TRANSACTION.decode_hex().unwrap(),
+    );
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(output.sender, "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7");
+    assert_eq!(output.pre_hash.to_hex(), BLOB_TRANSACTION_PRE_HASH);
+
+    let input = output.transaction.unwrap();
+    assert_eq!(input.tx_mode, Proto::TransactionMode::Blob);
+    assert_eq!(input.max_fee_per_blob_gas.to_hex(), "b2d05e00");
+    assert_eq!(input.blob_versioned_hashes.len(), 1);
+    assert_eq!(
+        input.blob_versioned_hashes[0].to_hex(),
+        "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+    );
+    assert_eq!(input.blob_sidecar, None);
+}
+
+/// Signs a blob transaction of a single zero blob and decodes its network form.
+fn decode_blob_transaction_network_form(
+    version: Proto::BlobSidecarVersion,
+    proofs_count: usize,
+) -> Proto::BlobSidecar<'static> {
+    // KZG commitment and proofs of the zero blob are the point at infinity.
+    let mut point_at_infinity = vec![0; 48];
+    point_at_infinity[0] = 0xc0;
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(2),
+        tx_mode: Proto::TransactionMode::Blob,
+        gas_limit: U256::encode_be_compact(21_000),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(50_000_000_000),
+        max_fee_per_blob_gas: U256::encode_be_compact(3_000_000_000),
+        to_address: "0x6b175474e89094c44da98b954eedeac495271d0f".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: TransactionType::transfer(Proto::mod_Transaction::Transfer {
+                amount: U256::encode_be_compact(0),
+                data: Cow::default(),
+            }),
+        }),
+        blob_sidecar: Some(Proto::BlobSidecar {
+            blobs: vec![vec![0; 131_072].into()],
+            commitments: vec![point_at_infinity.clone().into()],
+            proofs: vec![point_at_infinity.into(); proofs_count],
+            version,
+        }),
+        private_key: "4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904"
+            .decode_hex()
+            .unwrap()
+            .into(),
+        ..Proto::SigningInput::default()
+    };
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let signed = signer.sign(CoinType::Ethereum, input);
+    assert_eq!(signed.error, SigningError::OK);
+
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, signed.encoded_network.to_vec());
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(output.sender, "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7");
+    assert_eq!(output.pre_hash.to_hex(), BLOB_TRANSACTION_PRE_HASH);
+
+    output.transaction.unwrap().blob_sidecar.unwrap()
+}
+
+#[test]
+fn test_ethereum_decode_eip4844_network_form() {
+    let sidecar = decode_blob_transaction_network_form(Proto::BlobSidecarVersion::BlobProofs, 1);
+    assert_eq!(sidecar.version, Proto::BlobSidecarVersion::BlobProofs);
+    assert_eq!(sidecar.blobs.len(), 1);
+    assert_eq!(sidecar.blobs[0].len(), 131_072);
+    assert_eq!(sidecar.commitments.len(), 1);
+    assert_eq!(sidecar.proofs.len(), 1);
+}
+
+#[test]
+fn test_ethereum_decode_eip7594_network_form() {
+    let sidecar = decode_blob_transaction_network_form(Proto::BlobSidecarVersion::CellProofs, 128);
+    assert_eq!(sidecar.version, Proto::BlobSidecarVersion::CellProofs);
+    assert_eq!(sidecar.blobs.len(), 1);
+    assert_eq!(sidecar.commitments.len(), 1);
+    assert_eq!(sidecar.proofs.len(), 128);
+}
+
+#[test]
+fn test_ethereum_decode_unsupported_type() {
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "05c0".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::Error_not_supported);
+    assert!(output.transaction.is_none());
+}
+
+#[test]
+fn test_ethereum_decode_invalid() {
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(
+        CoinType::Ethereum,
+        "f86c0b8504a817c800".decode_hex().unwrap(),
+    );
+    assert_eq!(output.error, SigningError::Error_input_parse);
+    assert!(!output.error_message.is_empty());
+}
@@ -10,6 +10,10 @@ use tw_coin_registry::coin_type::CoinType;
 use tw_encoding::hex::{DecodeHex, ToHex};
 use tw_memory::test_utils::tw_data_helper::TWDataHelper;
 use tw_proto::{deserialize, serialize, Ethereum, TxCompiler};
+use wallet_core_rs::ffi::ethereum::message_signer::{
+    tw_ethereum_message_signer_decode_contract_signature_result,
+    tw_ethereum_message_signer_prepare_contract_signature_verification,
+};
 
 #[test]
 fn test_tw_message_signer_sign() {
@@ -21,6 +25,7 @@ fn test_tw_message_signer_sign() {
         message: "Foo".into(),
         chain_id: None,
         message_type: Ethereum::Proto::MessageType::MessageType_legacy,
+        ..Ethereum::Proto::MessageSigningInput::default()
     };
 
     let input_data = TWDataHelper::create(serialize(&input).unwrap());
@@ -72,6 +77,7 @@ fn test_tw_message_signer_pre_image_hashes() {
         message: "Foo".into(),
         chain_id: None,
         message_type: Ethereum::Proto::MessageType::MessageType_legacy,
+        ..Ethereum::Proto::MessageSigningInput::default()
     };
 
     let input_data = TWDataHelper::create(serialize(&input).unwrap());
@@ -89,3 +95,58 @@ fn test_tw_message_signer_pre_image_hashes() {
         "0af844076e792f9685560b2e597967da7403b00a5339b5801ea251ddde375f8a"
     );
 }
+
+#[test]
+fn test_tw_ethereum_message_signer_prepare_contract_signature_verification() {
+    let input = Ethereum::Proto::ContractSignatureVerifyingInput {
+        message: "Foo".into(),
+        signature: "21a779d499957e7fd39392d49a079679009e60e492d9654a148829be43d2490736ec72bc4a5644047d979c3cf4ebe2c1c514044cf436b063cb89fc6676be71101b".into(),
+        signer: "0x4bbeEB066eD09B7AEd07bF39EEe0460DFa261520".into(),
+        validator: "".into(),
+    };
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let output = TWDataHelper::wrap(unsafe {
+        tw_ethereum_message_signer_prepare_contract_signature_verification(
+            CoinType::Ethereum as u32,
+            input_data.ptr(),
+        )
+    })
+    .to_vec()
+    .expect("!tw_ethereum_message_signer_prepare_contract_signature_verification returned nullptr");
+
+    let output: Ethereum::Proto::ContractSignatureVerifyingOutput = deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.erc6492.is_none());
+    assert_eq!(output.call_data.to_hex(), "1626ba7e0af844076e792f9685560b2e597967da7403b00a5339b5801ea251ddde375f8a0000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000004121a779d499957e7fd39392d49a079679009e60e492d9654a148829be43d2490736ec72bc4a5644047d979c3cf4ebe2c1c514044cf436b063cb89fc6676be71101b00000000000000000000000000000000000000000000000000000000000000");
+    assert_eq!(
+        output.call_type,
+        Ethereum::Proto::ContractSignatureCallType::ERC1271
+    );
+    assert_eq!(output.call_to, "0x4bbeEB066eD09B7AEd07bF39EEe0460DFa261520");
+}
+
+#[test]
+fn test_tw_ethereum_message_signer_decode_contract_signature_result() {
+    let input = Ethereum::Proto::ContractSignatureResultInput {
+        call_type: Ethereum::Proto::ContractSignatureCallType::ERC1271,
+        call_result: "1626ba7e00000000000000000000000000000000000000000000000000000000"
+            .decode_hex()
+            .unwrap()
+            .into(),
+    };
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let output = TWDataHelper::wrap(unsafe {
+        tw_ethereum_message_signer_decode_contract_signature_result(
+            CoinType::Ethereum as u32,
+            input_data.ptr(),
+        )
+    })
+    .to_vec()
+    .expect("!tw_ethereum_message_signer_decode_contract_signature_result returned nullptr");
+
+    let output: Ethereum::Proto::ContractSignatureResultOutput = deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.valid);
+}
@@ -11,3 +11,14 @@ fn test_ethereum_transaction_util_calc_tx_hash() {
         "0x199a7829fc5149e49b452c2cab76d8fa5a9682fee6e4891b8acb697ac142513e"
     );
 }
+
+#[test]
+fn test_ethereum_transaction_util_calc_tx_hash_eip2930() {
+    let encoded_tx = "01f86e01808504a817c80082520894b9f5771c27664bf2282d98e09d7f50cec7cb01a7880de0b6b3a764000080c001a058f91d15057d2d3e589c1b5754d69fd30b792eacbc82f03b55d01db98d0d06e9a010512e5d5fb078ce93b27a336bf9c1cfa26b373bf56603f2087eceed952644b2";
+    let tx_hash = TransactionUtilHelper::calc_tx_hash(CoinType::Ethereum, encoded_tx);
+
+    assert_eq!(
+        tx_hash,
+        "0xdb3edd179d8dc1c6961323268fcc9d7704c041c911a4de52375d822066fc01e5"
+    );
+}
@@ -5,6 +5,7 @@
 mod ethereum_abi;
 mod ethereum_address;
 mod ethereum_compile;
+mod ethereum_decode;
 mod ethereum_message_sign;
 mod ethereum_rlp;
 mod ethereum_sign;
@@ -33,6 +33,7 @@ fn test_komodo_compile() {
     let out1 = Proto::Output {
         value: 892_984_972,
         to_recipient: output::to_address(TO_ADDRESS),
+        cash_token: None,
     };
 
     let extra_data = ZcashProto::TransactionBuilderExtraData {
@@ -34,6 +34,7 @@ fn test_komodo_sign_sapling_era() {
     let out1 = Proto::Output {
         value: 488_000,
         to_recipient: output::to_address(TO_ADDRESS),
+        cash_token: None,
     };
 
     let extra_data = ZcashProto::TransactionBuilderExtraData {
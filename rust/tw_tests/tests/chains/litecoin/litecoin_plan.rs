@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    dust_threshold, input, output, plan, RecipientType, TransactionOneof, DUST, SIGHASH_ALL,
+};
+use tw_any_coin::test_utils::plan_utils::AnyPlannerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::DecodeHex;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::Common::Proto::SigningError;
+
+const ALICE_PUBKEY: &str = "037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf";
+const DUMMY_TXID: &str = "06953f715201d085d0607097bc7571fcebe92bd4aa30038bd480a72357b28796";
+/// MWEB peg-in scriptPubkey: witness version 9 with a 32-byte program.
+const MWEB_PEGIN_SCRIPT_PUBKEY: &str =
+    "5920e5c7c00d174631d2d1e365d6bf0ca7f8c7ab2ed2a5a1f3a4e8a5d4bd6ac5b2b8";
+
+fn make_signing_input(
+    to_recipient: RecipientType<'static>,
+    output_value: i64,
+) -> Proto::SigningInput<'static> {
+    let alice_pubkey = ALICE_PUBKEY.decode_hex().unwrap();
+
+    let utxo = Proto::Input {
+        out_point: input::out_point(DUMMY_TXID, 0),
+        value: 100_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2wpkh(alice_pubkey.clone()),
+        ..Proto::Input::default()
+    };
+    let out_0 = Proto::Output {
+        value: output_value,
+        to_recipient,
+        cash_token: None,
+    };
+    let change_output = Proto::Output {
+        value: 0,
+        to_recipient: output::p2wpkh(alice_pubkey),
+        cash_token: None,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        inputs: vec![utxo],
+        outputs: vec![out_0],
+        change_output: Some(change_output),
+        input_selector: Proto::InputSelector::SelectInOrder,
+        fee_per_vb: 10,
+        // Bitcoin dust threshold is raised to the Litecoin one.
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    Proto::SigningInput {
+        transaction: TransactionOneof::builder(builder),
+        ..Proto::SigningInput::default()
+    }
+}
+
+fn plan_error(input: Proto::SigningInput<'static>) -> SigningError {
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+    planner.plan(CoinType::Litecoin, input).error
+}
+
+#[test]
+fn test_litecoin_plan_p2wpkh() {
+    let alice_pubkey = ALICE_PUBKEY.decode_hex().unwrap();
+    let input = make_signing_input(output::p2wpkh(alice_pubkey), 50_000);
+
+    // Base size: 4 (version) + 1 + 41 (P2WPKH input) + 1 + 2 * 31 (P2WPKH outputs) + 4 (locktime) = 113.
+    // Witness size: 2 (marker and flag) + 109 (P2WPKH witness) = 111.
+    // Weight: 113 * 4 + 111 = 563. vsize: ceil(563 / 4) = 141.
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Litecoin)
+        .plan(plan::Expected {
+            inputs: vec![100_000],
+            outputs: vec![50_000, 48_590],
+            vsize_estimate: 141,
+            // vsize * fee_rate
+            fee_estimate: 1_410,
+            change: 48_590,
+        });
+}
+
+#[test]
+fn test_litecoin_plan_dust_output() {
+    let alice_pubkey = ALICE_PUBKEY.decode_hex().unwrap();
+    // 5000 litoshi is not dust in Bitcoin, but it is in Litecoin.
+    let input = make_signing_input(output::p2wpkh(alice_pubkey), 5_000);
+    assert_eq!(plan_error(input), SigningError::Error_dust_amount_requested);
+}
+
+#[test]
+fn test_litecoin_plan_mweb_pegin_unsupported() {
+    let script_pubkey = MWEB_PEGIN_SCRIPT_PUBKEY.decode_hex().unwrap();
+    let input = make_signing_input(output::custom_script(script_pubkey), 50_000);
+    assert_eq!(plan_error(input), SigningError::Error_script_output);
+}
@@ -8,6 +8,7 @@ mod bitcoin;
 mod bitcoincash;
 mod common;
 mod cosmos;
+mod dogecoin;
 mod dydx;
 mod ecash;
 mod ethereum;
@@ -15,6 +16,7 @@ mod greenfield;
 mod internet_computer;
 mod komodo;
 mod kusama;
+mod litecoin;
 mod native_evmos;
 mod native_injective;
 mod pactus;
@@ -10,7 +10,6 @@ use tw_keypair::ed25519::{sha512::PublicKey, Signature};
 use tw_keypair::traits::VerifyingKeyTrait;
 use tw_proto::Common::Proto::SigningError;
 use tw_proto::Polkadot::Proto::{self, SigningInput};
-use tw_proto::TxCompiler::Proto::{self as CompilerProto, PreSigningOutput};
 
 mod polkadot_address;
 mod polkadot_compile;
@@ -35,7 +34,7 @@ pub fn helper_sign(coin: CoinType, input: SigningInput<'_>) -> String {
 }
 
 pub fn helper_encode(coin: CoinType, input: &SigningInput<'_>) -> String {
-    let mut pre_imager = PreImageHelper::<PreSigningOutput>::default();
+    let mut pre_imager = PreImageHelper::<Proto::PreSigningOutput>::default();
     let preimage_output = pre_imager.pre_image_hashes(coin, input);
 
     assert_eq!(preimage_output.error, SigningError::OK);
@@ -66,7 +65,7 @@ pub fn helper_encode_and_compile(
     ed25519: bool,
 ) -> (String, String) {
     // Step 1: Obtain preimage hash
-    let mut pre_imager = PreImageHelper::<CompilerProto::PreSigningOutput>::default();
+    let mut pre_imager = PreImageHelper::<Proto::PreSigningOutput>::default();
     let preimage_output = pre_imager.pre_image_hashes(coin, &input);
 
     assert_eq!(preimage_output.error, SigningError::OK);
@@ -82,7 +81,7 @@ pub fn helper_encode_and_compile(
     if !ed25519 {
         let signature = Signature::try_from(signature_bytes.as_slice()).unwrap();
         let public = PublicKey::try_from(public_key.as_slice()).unwrap();
-        assert!(public.verify(signature, preimage_output.data.into()));
+        assert!(public.verify(signature, preimage_output.data_hash.into()));
     }
 
     // Compile transaction info
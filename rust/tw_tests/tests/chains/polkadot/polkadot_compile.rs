@@ -2,21 +2,34 @@
 //
 // Copyright © 2017 Trust Wallet.
 
-use crate::chains::polkadot::{balance_call, helper_encode_and_compile, GENESIS_HASH};
+use crate::chains::polkadot::{
+    balance_call, helper_encode_and_compile, helper_sign, GENESIS_HASH, PRIVATE_KEY, PRIVATE_KEY_2,
+};
+use tw_any_coin::test_utils::sign_utils::{CompilerHelper, PreImageHelper};
 use tw_coin_registry::coin_type::CoinType;
-use tw_encoding::hex::DecodeHex;
-use tw_proto::Polkadot::Proto::{self, mod_Balance::Transfer};
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_hash::blake2::blake2_b;
+use tw_keypair::ed25519::sha512::{KeyPair, PrivateKey};
+use tw_keypair::traits::{KeyPairTrait, SigningKeyTrait};
+use tw_proto::Common::Proto::SigningError;
+use tw_proto::Polkadot::Proto::{
+    self,
+    mod_Balance::{BatchTransfer, Transfer},
+};
 
-#[test]
-fn test_polkadot_compile_transfer() {
-    // Step 1: Prepare input.
+const TRANSFER_PUBLIC_KEY: &str =
+    "d84accbb64934815506288fafbfc7d275e64aa4e3cd9c5392db6e83b13256bf3";
+const TRANSFER_SIGNATURE: &str = "fb43727477caaa12542b9060856816d42eedef6ebf2e98e4f8dff4355fe384751925833c4a26b2fed1707aebe655cb3317504a61ee59697c086f7baa6ca06a09";
+const TRANSFER_PREIMAGE: &str = "0500be4c21aa92dcba057e9b719ce1de970f774f064c09b13a3ea3009affb8cb5ec707000cdc0f219dfe0000190000000500000091b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c340cee3c3b7f8422f4c512e9ebebdeeff1c28e81cc678ee4864d945d641e05f9b";
+
+fn transfer_input() -> Proto::SigningInput<'static> {
     let block_hash = "40cee3c3b7f8422f4c512e9ebebdeeff1c28e81cc678ee4864d945d641e05f9b"
         .decode_hex()
         .unwrap();
     let genesis_hash = GENESIS_HASH.decode_hex().unwrap();
     let value = "210fdc0c00".decode_hex().unwrap();
 
-    let input = Proto::SigningInput {
+    Proto::SigningInput {
         network: 0,
         nonce: 0,
         block_hash: block_hash.into(),
@@ -33,21 +46,150 @@ fn test_polkadot_compile_transfer() {
             ..Default::default()
         })),
         ..Default::default()
-    };
+    }
+}
+
+#[test]
+fn test_polkadot_compile_transfer() {
+    // Step 1: Prepare input.
+    let input = transfer_input();
 
     // Simulate signature, normally obtained from signature server
-    let signature = "fb43727477caaa12542b9060856816d42eedef6ebf2e98e4f8dff4355fe384751925833c4a26b2fed1707aebe655cb3317504a61ee59697c086f7baa6ca06a09";
-    let public_key = "d84accbb64934815506288fafbfc7d275e64aa4e3cd9c5392db6e83b13256bf3";
+    let (preimage, signed) = helper_encode_and_compile(
+        CoinType::Polkadot,
+        input,
+        TRANSFER_SIGNATURE,
+        TRANSFER_PUBLIC_KEY,
+        true,
+    );
+
+    assert_eq!(preimage, TRANSFER_PREIMAGE);
+    assert_eq!(
+        signed,
+        "390284d84accbb64934815506288fafbfc7d275e64aa4e3cd9c5392db6e83b13256bf300fb43727477caaa12542b9060856816d42eedef6ebf2e98e4f8dff4355fe384751925833c4a26b2fed1707aebe655cb3317504a61ee59697c086f7baa6ca06a099dfe00000500be4c21aa92dcba057e9b719ce1de970f774f064c09b13a3ea3009affb8cb5ec707000cdc0f21"
+    );
+}
+
+#[test]
+fn test_polkadot_preimage_hashes_public_key_only() {
+    let input = Proto::SigningInput {
+        public_key: TRANSFER_PUBLIC_KEY.decode_hex().unwrap().into(),
+        ..transfer_input()
+    };
 
-    let (preimage, signed) =
-        helper_encode_and_compile(CoinType::Polkadot, input, signature, public_key, true);
+    let mut pre_imager = PreImageHelper::<Proto::PreSigningOutput>::default();
+    let output = pre_imager.pre_image_hashes(CoinType::Polkadot, &input);
 
+    assert_eq!(output.error, SigningError::OK);
+    // The pre-image is short enough to be signed as is.
+    assert!(!output.is_hashed);
+    assert_eq!(output.data_hash.to_hex(), TRANSFER_PREIMAGE);
+    assert_eq!(output.data.to_hex(), TRANSFER_PREIMAGE);
+    assert_eq!(output.signature_scheme, Proto::SignatureScheme::ED25519);
     assert_eq!(
-        preimage,
-        "0500be4c21aa92dcba057e9b719ce1de970f774f064c09b13a3ea3009affb8cb5ec707000cdc0f219dfe0000190000000500000091b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c340cee3c3b7f8422f4c512e9ebebdeeff1c28e81cc678ee4864d945d641e05f9b"
+        output.signer_address,
+        "15tbZfoWgw19G9A1ckxeJQK56MVX5QMwwTKRmRWi31GtupHe"
     );
+}
+
+#[test]
+fn test_polkadot_preimage_hashes_and_compile_large_payload() {
+    let private_key = PRIVATE_KEY.decode_hex().unwrap();
+    let keypair = KeyPair::try_from(private_key.as_slice()).unwrap();
+
+    let transfer = Transfer {
+        to_address: "15JWiQUmczAFU3hrZrD2gDyuJdL2BbFaX9yngivb1UWiBJWA".into(),
+        value: "02540be400".decode_hex().unwrap().into(),
+        ..Default::default()
+    };
+    let input = Proto::SigningInput {
+        public_key: keypair.public().as_slice().to_vec().into(),
+        message_oneof: balance_call(Proto::mod_Balance::OneOfmessage_oneof::batch_transfer(
+            BatchTransfer {
+                transfers: vec![transfer; 8],
+                ..Default::default()
+            },
+        )),
+        ..transfer_input()
+    };
+
+    let mut pre_imager = PreImageHelper::<Proto::PreSigningOutput>::default();
+    let output = pre_imager.pre_image_hashes(CoinType::Polkadot, &input);
+
+    assert_eq!(output.error, SigningError::OK);
+    // The pre-image is larger than 256 bytes, so its Blake2b-256 hash must be signed.
+    assert!(output.is_hashed);
+    assert!(output.data.len() > 256);
     assert_eq!(
-        signed,
-        "390284d84accbb64934815506288fafbfc7d275e64aa4e3cd9c5392db6e83b13256bf300fb43727477caaa12542b9060856816d42eedef6ebf2e98e4f8dff4355fe384751925833c4a26b2fed1707aebe655cb3317504a61ee59697c086f7baa6ca06a099dfe00000500be4c21aa92dcba057e9b719ce1de970f774f064c09b13a3ea3009affb8cb5ec707000cdc0f21"
+        output.data_hash.to_vec(),
+        blake2_b(&output.data, 32).unwrap()
+    );
+
+    // Simulate an external signer that signs the digest.
+    let signature = keypair.sign(output.data_hash.to_vec()).unwrap();
+
+    let mut compiler = CompilerHelper::<Proto::SigningOutput>::default();
+    let compiled = compiler.compile(
+        CoinType::Polkadot,
+        &input,
+        vec![signature.to_bytes().to_vec()],
+        vec![keypair.public().as_slice().to_vec()],
+    );
+    assert_eq!(compiled.error, SigningError::OK);
+
+    let signed = helper_sign(
+        CoinType::Polkadot,
+        Proto::SigningInput {
+            private_key: private_key.into(),
+            ..input
+        },
     );
+    assert_eq!(compiled.encoded.to_hex(), signed);
+}
+
+#[test]
+fn test_polkadot_compile_invalid_signature() {
+    let input = transfer_input();
+
+    let mut signature = TRANSFER_SIGNATURE.decode_hex().unwrap();
+    signature[0] ^= 1;
+    let public_key = TRANSFER_PUBLIC_KEY.decode_hex().unwrap();
+
+    let mut compiler = CompilerHelper::<Proto::SigningOutput>::default();
+    let output = compiler.compile(
+        CoinType::Polkadot,
+        &input,
+        vec![signature],
+        vec![public_key],
+    );
+
+    assert_eq!(output.error, SigningError::Error_signing);
+    assert!(output.encoded.is_empty());
+}
+
+#[test]
+fn test_polkadot_compile_public_key_mismatch() {
+    let other_public_key = PrivateKey::try_from(PRIVATE_KEY_2)
+        .unwrap()
+        .public()
+        .as_slice()
+        .to_vec();
+    let input = Proto::SigningInput {
+        public_key: other_public_key.into(),
+        ..transfer_input()
+    };
+
+    let signature = TRANSFER_SIGNATURE.decode_hex().unwrap();
+    let public_key = TRANSFER_PUBLIC_KEY.decode_hex().unwrap();
+
+    let mut compiler = CompilerHelper::<Proto::SigningOutput>::default();
+    let output = compiler.compile(
+        CoinType::Polkadot,
+        &input,
+        vec![signature],
+        vec![public_key],
+    );
+
+    assert_eq!(output.error, SigningError::Error_invalid_params);
+    assert!(output.encoded.is_empty());
 }
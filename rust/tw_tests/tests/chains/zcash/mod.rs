@@ -15,6 +15,8 @@ mod zcash_sign;
 pub const SAPLING_BRANCH_ID: &[u8] = &[0xbb, 0x09, 0xb8, 0x76];
 /// See https://github.com/zcash/zips/blob/master/zips/zip-0206.rst#blossom-deployment BRANCH_ID section
 pub const BLOSSOM_BRANCH_ID: &[u8] = &[0x60, 0x0e, 0xb4, 0x2b];
+/// See https://zips.z.cash/zip-0252#nu5-deployment CONSENSUS_BRANCH_ID section
+pub const NU5_BRANCH_ID: &[u8] = &[0xb4, 0xd0, 0xd6, 0xc2];
 /// See https://github.com/zcash/zips/blob/main/zips/zip-0253.md#nu6-deployment CONSENSUS_BRANCH_ID section
 pub const NU6_BRANCH_ID: &[u8] = &[0x55, 0x10, 0xe7, 0xc8];
 pub const ZCASH_P2PKH_PREFIX: u8 = 184;
@@ -34,6 +34,15 @@ fn test_zcash_address_is_valid() {
     test_address_valid(CoinType::Zcash, "t1Wg9uPPAfwhBWeRjtDPa5ZHNzyBx9rJVKY");
     test_address_valid(CoinType::Zcash, "t1TKCtCETHPrAdA6eY1fdhhnTkTmb371oPt");
     test_address_valid(CoinType::Zcash, "t1cWhcXydPYTG1pgHMsZ6JEPsWGxVMdJ5t6");
+    // TEX addresses.
+    test_address_valid(
+        CoinType::Zcash,
+        "tex1s2rt77ggv6q989lr49rkgzmh5slsksa9khdgte",
+    );
+    test_address_valid(
+        CoinType::Zcash,
+        "tex133h52v2h397w9ek7gyln98vetlsd37gzcddkaa",
+    );
 }
 
 #[test]
@@ -54,6 +63,16 @@ fn test_zcash_address_invalid() {
     test_address_invalid(CoinType::Zcash, "TJRyWwFs9wTFGZg3JbrVriFbNfCug5tDeC");
     // Invalid T-prefix
     test_address_invalid(CoinType::Zcash, "2NRbuP5YfzRNEa1RibT5kXay1VgvQHnydZY1");
+    // TEX address with Bech32 checksum instead of Bech32m.
+    test_address_invalid(
+        CoinType::Zcash,
+        "tex133h52v2h397w9ek7gyln98vetlsd37gzd3a6cl",
+    );
+    // Testnet TEX address.
+    test_address_invalid(
+        CoinType::Zcash,
+        "textest133h52v2h397w9ek7gyln98vetlsd37gzprjqcx",
+    );
 }
 
 #[test]
@@ -63,4 +82,9 @@ fn test_zcash_address_get_data() {
         "t1Wg9uPPAfwhBWeRjtDPa5ZHNzyBx9rJVKY",
         "8c6f453157897ce2e6de413f329d995fe0d8f902",
     );
+    test_address_get_data(
+        CoinType::Zcash,
+        "tex133h52v2h397w9ek7gyln98vetlsd37gzcddkaa",
+        "8c6f453157897ce2e6de413f329d995fe0d8f902",
+    );
 }
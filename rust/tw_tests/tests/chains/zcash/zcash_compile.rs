@@ -4,9 +4,11 @@
 
 use crate::chains::common::bitcoin::{
     compile, dust_threshold, input, output, preimage, TransactionOneof, DUST, SIGHASH_ALL,
-    SIGHASH_ANYONE_CAN_PAY,
+    SIGHASH_ANYONE_CAN_PAY, SIGHASH_NONE, SIGHASH_SINGLE,
+};
+use crate::chains::zcash::{
+    zcash_extra_data, zec_info, BLOSSOM_BRANCH_ID, NU5_BRANCH_ID, SAPLING_BRANCH_ID,
 };
-use crate::chains::zcash::{zcash_extra_data, zec_info, BLOSSOM_BRANCH_ID, SAPLING_BRANCH_ID};
 use tw_coin_registry::coin_type::CoinType;
 use tw_encoding::hex::DecodeHex;
 use tw_misc::traits::ToBytesVec;
@@ -40,6 +42,7 @@ fn test_zcash_pre_hash_different_sighash_types() {
         let out1 = Proto::Output {
             value: 40_000_000,
             to_recipient: output::custom_script(script_out1),
+            cash_token: None,
         };
 
         let script_out2 = "76a9145453e4698f02a38abdaa521cd1ff2dee6fac187188ac"
@@ -48,6 +51,7 @@ fn test_zcash_pre_hash_different_sighash_types() {
         let out2 = Proto::Output {
             value: 9_999_755,
             to_recipient: output::custom_script(script_out2),
+            cash_token: None,
         };
 
         let extra_data = ZcashProto::TransactionBuilderExtraData {
@@ -98,6 +102,98 @@ fn test_zcash_pre_hash_different_sighash_types() {
     // );
 }
 
+/// Same transaction as in [`test_zcash_pre_hash_different_sighash_types`], but in the NU5 era.
+/// Version 5 transaction sighash is computed according to ZIP-244.
+#[test]
+fn test_zcash_pre_hash_v5_different_sighash_types() {
+    const PUBLIC_KEY: &str = "0365ffea3efa3908918a8b8627724af852fc9b86d7375b103ab0543cf418bcaa7f";
+
+    fn test_impl(sighash_type: u32, expected_sighash: &str) {
+        // Create transaction with P2PKH as input and output.
+        let txid = "d9042195d9a1b65b2f1f79d68ceb1a5ea6459c9651a6ad4dc1f465824785c6a8";
+        let spend_script = "76a914507173527b4c3318a2aecd793bf1cfed705950cf88ac"
+            .decode_hex()
+            .unwrap();
+        let utxo = Proto::Input {
+            out_point: input::out_point(txid, 1),
+            value: 50_000_000,
+            sighash_type,
+            sequence: input::sequence(0xFFFFFFFE),
+            claiming_script: input::custom_script(spend_script),
+            ..Default::default()
+        };
+
+        let script_out1 = "76a9148132712c3ff19f3a151234616777420a6d7ef22688ac"
+            .decode_hex()
+            .unwrap();
+        let out1 = Proto::Output {
+            value: 40_000_000,
+            to_recipient: output::custom_script(script_out1),
+            cash_token: None,
+        };
+
+        let script_out2 = "76a9145453e4698f02a38abdaa521cd1ff2dee6fac187188ac"
+            .decode_hex()
+            .unwrap();
+        let out2 = Proto::Output {
+            value: 9_999_755,
+            to_recipient: output::custom_script(script_out2),
+            cash_token: None,
+        };
+
+        let extra_data = ZcashProto::TransactionBuilderExtraData {
+            branch_id: NU5_BRANCH_ID.into(),
+            zip_0317: false,
+            expiry_height: 307_272,
+        };
+
+        let builder = Proto::TransactionBuilder {
+            version: Proto::TransactionVersion::UseDefault,
+            lock_time: 307_241,
+            inputs: vec![utxo],
+            outputs: vec![out1, out2],
+            input_selector: Proto::InputSelector::UseAll,
+            dust_policy: dust_threshold(DUST),
+            chain_specific: zcash_extra_data(extra_data),
+            ..Default::default()
+        };
+
+        let signing = Proto::SigningInput {
+            public_keys: vec![PUBLIC_KEY.decode_hex().unwrap().into()],
+            chain_info: zec_info(),
+            transaction: TransactionOneof::builder(builder),
+            ..Default::default()
+        };
+
+        let expected_sighash = expected_sighash.decode_hex().unwrap().to_vec();
+        preimage::BitcoinPreImageHelper::new(&signing)
+            .coin(CoinType::Zcash)
+            .add_expected_legacy(PUBLIC_KEY.decode_hex().unwrap(), expected_sighash)
+            .pre_image();
+    }
+
+    test_impl(
+        SIGHASH_ALL,
+        "4f84a5da06d5de516df61d9456db59fabd7fe4a4d8af3cae787b7adc8667e06c",
+    );
+    test_impl(
+        SIGHASH_NONE,
+        "a9ba55c88a440b0969071efb8e34aebf15ac1fb95cea840e290400d6b4e57be4",
+    );
+    test_impl(
+        SIGHASH_SINGLE,
+        "71dc2d9cf2b8603a520297e9bf68a7c22431fc1fc9ed094ce2c1f01a429841c9",
+    );
+    test_impl(
+        SIGHASH_ANYONE_CAN_PAY | SIGHASH_ALL,
+        "26c97e318d20c7f69440aa4f4a7eba383a225f6ece17b49528ef42b0f4ac08ec",
+    );
+    test_impl(
+        SIGHASH_ANYONE_CAN_PAY | SIGHASH_SINGLE,
+        "8fdb828c06b5f85980a0892566d9a68ad8a4617d76e8774e8c87ea0a0c048bfe",
+    );
+}
+
 /// Original test:
 /// https://github.com/trustwallet/wallet-core/blob/cd5a27481d2181e63362cb57e2b2160506cce163/tests/chains/Zcash/TransactionCompilerTests.cpp#L25
 ///
@@ -121,6 +217,7 @@ fn test_zcash_compile_with_signatures() {
     let out1 = Proto::Output {
         value: 17_615,
         to_recipient: output::to_address(TO_ADDRESS),
+        cash_token: None,
     };
 
     let extra_data = ZcashProto::TransactionBuilderExtraData {
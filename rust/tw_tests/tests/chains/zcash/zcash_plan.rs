@@ -48,6 +48,7 @@ fn test_zcash_plan_eip_0317_many_utxo() {
     let out1 = Proto::Output {
         value: 0,
         to_recipient: output::to_address(TO_ADDRESS),
+        cash_token: None,
     };
 
     let extra_data = ZcashProto::TransactionBuilderExtraData {
@@ -80,7 +81,8 @@ fn test_zcash_plan_eip_0317_many_utxo() {
         .plan(plan::Expected {
             inputs: input_amounts,
             outputs: vec![output_amount],
-            vsize_estimate: 2447,
+            // Version 5 transaction is used since NU5.
+            vsize_estimate: 2443,
             fee_estimate: expected_fee,
             // Change output has been omitted.
             change: 0,
@@ -6,7 +6,7 @@ use crate::chains::common::bitcoin::{
     dust_threshold, input, output, plan, sign, transaction_psbt, TransactionOneof, DUST,
     SIGHASH_ALL,
 };
-use crate::chains::zcash::{zcash_extra_data, zec_info, SAPLING_BRANCH_ID};
+use crate::chains::zcash::{zcash_extra_data, zec_info, NU6_BRANCH_ID, SAPLING_BRANCH_ID};
 use tw_any_coin::test_utils::sign_utils::AnySignerHelper;
 use tw_coin_registry::coin_type::CoinType;
 use tw_encoding::hex::DecodeHex;
@@ -35,6 +35,7 @@ fn test_zcash_sign_sapling_era() {
     let out1 = Proto::Output {
         value: 488_000,
         to_recipient: output::to_address(TO_ADDRESS),
+        cash_token: None,
     };
 
     let extra_data = ZcashProto::TransactionBuilderExtraData {
@@ -87,6 +88,205 @@ fn test_zcash_sign_sapling_era() {
         });
 }
 
+/// Version 5 transaction is built and signed according to ZIP-225 and ZIP-244 since NU5.
+#[test]
+fn test_zcash_sign_v5_nu6_era() {
+    const PRIVATE_KEY: &str = "a9684f5bebd0e1208aae2e02bc9e9163bd1965ad23d8538644e1df8b99b99559";
+    const SENDER_ADDRESS: &str = "t1gWVE2uyrET2CxSmCaBiKzmWxQdHhnvMSz";
+    const TO_ADDRESS: &str = "t1QahNjDdibyE4EdYkawUSKBBcVTSqv64CS";
+
+    // Create transaction with two P2PKH inputs and one P2PKH output.
+    let tx1 = Proto::Input {
+        out_point: input::out_point(
+            "3a19dd44032dfed61bfca5ba5751aab8a107b30609cbd5d70dc5ef09885b6853",
+            0,
+        ),
+        value: 494_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::receiver_address(SENDER_ADDRESS),
+        ..Default::default()
+    };
+    let tx2 = Proto::Input {
+        out_point: input::out_point(
+            "ec9033381c1cc53ada837ef9981c03ead1c7c41700ff3a954389cfaddc949256",
+            0,
+        ),
+        value: 488_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::receiver_address(SENDER_ADDRESS),
+        ..Default::default()
+    };
+
+    let out1 = Proto::Output {
+        value: 970_000,
+        to_recipient: output::to_address(TO_ADDRESS),
+        cash_token: None,
+    };
+
+    let extra_data = ZcashProto::TransactionBuilderExtraData {
+        branch_id: NU6_BRANCH_ID.into(),
+        zip_0317: false,
+        expiry_height: 3_100_000,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::UseDefault,
+        inputs: vec![tx1, tx2],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        chain_specific: zcash_extra_data(extra_data),
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: zec_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    plan::BitcoinPlanHelper::new(&signing)
+        .coin(CoinType::Zcash)
+        .plan(plan::Expected {
+            inputs: vec![494_000, 488_000],
+            outputs: vec![970_000],
+            // Estimated size is 3 bytes greater than the final transaction.
+            // That's because the final DER.1 serialized signatures become more compact.
+            vsize_estimate: 357,
+            fee_estimate: 12_000,
+            // Change output has been omitted.
+            change: 0,
+        });
+
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Zcash)
+        .sign(sign::Expected {
+            encoded: "050000800a27a7265510e7c800000000604d2f000253685b8809efc50dd7d5cb0906b307a1b8aa5157baa5fc1bd6fe2d0344dd193a000000006a47304402202ce530e5ba23419483b9231ed38603c3c0cf3305bb34920fccfd0ba5670614200220031ac23547b7887cbfe4977606ab6a6334b4d2ce86a22830a28b5218a53f78c6012103b243171fae5516d1dc15f9178cfcc5fdc67b0a883055c117b01ba8af29b953f6ffffffff569294dcadcf8943953aff0017c4c7d1ea031c98f97e83da3ac51c1c383390ec000000006b483045022100eea9efc930a2a9d97ec776de0046b0bb274f52f2b770e97bc905afaec31c02ea022073220e07566ce5b9ef109a10fea34bd75f862d1d9f77e3bd995059dedd3743b0012103b243171fae5516d1dc15f9178cfcc5fdc67b0a883055c117b01ba8af29b953f6ffffffff0110cd0e00000000001976a91449964a736f3713d64283fd0018626ba50091c7e988ac000000",
+            // ZIP-244 transaction ID doesn't depend on the signatures.
+            txid: "066a8d28c0e7b6a9ccd73d777951870cbe36311c00030bcf9c997fddc1639634",
+            inputs: vec![494_000, 488_000],
+            outputs: vec![970_000],
+            // `vsize` is different from the estimated value due to the signatures der serialization.
+            vsize: 354,
+            weight: 354 * 4,
+            fee: 12_000,
+        });
+}
+
+/// Same as [`test_zcash_sign_sapling_era`], but the recipient is given as a TEX address.
+#[test]
+fn test_zcash_sign_to_tex_address() {
+    const PRIVATE_KEY: &str = "a9684f5bebd0e1208aae2e02bc9e9163bd1965ad23d8538644e1df8b99b99559";
+    const SENDER_ADDRESS: &str = "t1gWVE2uyrET2CxSmCaBiKzmWxQdHhnvMSz";
+    // Corresponds to `t1QahNjDdibyE4EdYkawUSKBBcVTSqv64CS`.
+    const TO_ADDRESS: &str = "tex1fxty5um0xufavs5rl5qpscnt55qfr3lfk9n3ex";
+
+    let tx1 = Proto::Input {
+        out_point: input::out_point(
+            "3a19dd44032dfed61bfca5ba5751aab8a107b30609cbd5d70dc5ef09885b6853",
+            0,
+        ),
+        value: 494_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::receiver_address(SENDER_ADDRESS),
+        ..Default::default()
+    };
+
+    let out1 = Proto::Output {
+        value: 488_000,
+        to_recipient: output::to_address(TO_ADDRESS),
+        cash_token: None,
+    };
+
+    let extra_data = ZcashProto::TransactionBuilderExtraData {
+        branch_id: SAPLING_BRANCH_ID.into(),
+        zip_0317: false,
+        expiry_height: 0,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::UseDefault,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        chain_specific: zcash_extra_data(extra_data),
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: zec_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Zcash)
+        .sign(sign::Expected {
+            encoded: "0400008085202f890153685b8809efc50dd7d5cb0906b307a1b8aa5157baa5fc1bd6fe2d0344dd193a000000006b483045022100ca0be9f37a4975432a52bb65b25e483f6f93d577955290bb7fb0060a93bfc92002203e0627dff004d3c72a957dc9f8e4e0e696e69d125e4d8e275d119001924d3b48012103b243171fae5516d1dc15f9178cfcc5fdc67b0a883055c117b01ba8af29b953f6ffffffff0140720700000000001976a91449964a736f3713d64283fd0018626ba50091c7e988ac00000000000000000000000000000000000000",
+            txid: "ec9033381c1cc53ada837ef9981c03ead1c7c41700ff3a954389cfaddc949256",
+            inputs: vec![494_000],
+            outputs: vec![488_000],
+            vsize: 211,
+            weight: 211 * 4,
+            fee: 6_000,
+        });
+}
+
+/// ZIP-320: a transaction paying to a TEX address must spend transparent P2PKH inputs only.
+#[test]
+fn test_zcash_sign_to_tex_address_non_p2pkh_input() {
+    const PRIVATE_KEY: &str = "a9684f5bebd0e1208aae2e02bc9e9163bd1965ad23d8538644e1df8b99b99559";
+    const PUBLIC_KEY: &str = "03b243171fae5516d1dc15f9178cfcc5fdc67b0a883055c117b01ba8af29b953f6";
+    const TO_ADDRESS: &str = "tex1fxty5um0xufavs5rl5qpscnt55qfr3lfk9n3ex";
+
+    let tx1 = Proto::Input {
+        out_point: input::out_point(
+            "3a19dd44032dfed61bfca5ba5751aab8a107b30609cbd5d70dc5ef09885b6853",
+            0,
+        ),
+        value: 494_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2pk(PUBLIC_KEY.decode_hex().unwrap()),
+        ..Default::default()
+    };
+
+    let out1 = Proto::Output {
+        value: 488_000,
+        to_recipient: output::to_address(TO_ADDRESS),
+        cash_token: None,
+    };
+
+    let extra_data = ZcashProto::TransactionBuilderExtraData {
+        branch_id: SAPLING_BRANCH_ID.into(),
+        zip_0317: false,
+        expiry_height: 0,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::UseDefault,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        chain_specific: zcash_extra_data(extra_data),
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: zec_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Zcash, signing);
+    assert_eq!(output.error, SigningError::Error_invalid_utxo);
+}
+
 #[test]
 fn test_zcash_sign_psbt_not_supported() {
     const DUMMY_PRIV: &str = "a9684f5bebd0e1208aae2e02bc9e9163bd1965ad23d8538644e1df8b99b99559";
@@ -102,3 +302,55 @@ fn test_zcash_sign_psbt_not_supported() {
     let output = signer.sign(CoinType::Zcash, signing);
     assert_eq!(output.error, SigningError::Error_not_supported);
 }
+
+#[test]
+fn test_zcash_sign_unknown_branch_id() {
+    const PRIVATE_KEY: &str = "a9684f5bebd0e1208aae2e02bc9e9163bd1965ad23d8538644e1df8b99b99559";
+    const SENDER_ADDRESS: &str = "t1gWVE2uyrET2CxSmCaBiKzmWxQdHhnvMSz";
+    const TO_ADDRESS: &str = "t1QahNjDdibyE4EdYkawUSKBBcVTSqv64CS";
+
+    let tx1 = Proto::Input {
+        out_point: input::out_point(
+            "3a19dd44032dfed61bfca5ba5751aab8a107b30609cbd5d70dc5ef09885b6853",
+            0,
+        ),
+        value: 494_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::receiver_address(SENDER_ADDRESS),
+        ..Default::default()
+    };
+
+    let out1 = Proto::Output {
+        value: 488_000,
+        to_recipient: output::to_address(TO_ADDRESS),
+        cash_token: None,
+    };
+
+    // The transaction format cannot be chosen for an unknown consensus branch.
+    let extra_data = ZcashProto::TransactionBuilderExtraData {
+        branch_id: vec![0x01, 0x02, 0x03, 0x04].into(),
+        zip_0317: false,
+        expiry_height: 0,
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::UseDefault,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        chain_specific: zcash_extra_data(extra_data),
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: zec_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Zcash, signing);
+    assert_eq!(output.error, SigningError::Error_invalid_params);
+}
@@ -132,3 +132,23 @@ pub unsafe extern "C" fn tw_ethereum_abi_decode_value(
         .map(|data| TWData::from(data).into_ptr())
         .unwrap_or_else(|_| std::ptr::null_mut())
 }
+
+/// Decode an event log according to the given contract ABI json.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input The serialized data of `TW.EthereumAbi.Proto.EventLogDecodingInput`.
+/// \return serialized `EthereumAbi::Proto::EventLogDecodingOutput`.
+#[no_mangle]
+pub unsafe extern "C" fn tw_ethereum_abi_decode_event_log(
+    coin: u32,
+    input: *const TWData,
+) -> *mut TWData {
+    let coin = try_or_else!(CoinType::try_from(coin), std::ptr::null_mut);
+    let input_data = try_or_else!(TWData::from_ptr_as_ref(input), std::ptr::null_mut);
+    let evm_dispatcher = try_or_else!(evm_dispatcher(coin), std::ptr::null_mut);
+
+    evm_dispatcher
+        .decode_abi_event_log(input_data.as_slice())
+        .map(|data| TWData::from(data).into_ptr())
+        .unwrap_or_else(|_| std::ptr::null_mut())
+}
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+#![allow(clippy::missing_safety_doc)]
+
+use tw_coin_registry::coin_type::CoinType;
+use tw_coin_registry::dispatcher::evm_dispatcher;
+use tw_memory::ffi::tw_data::TWData;
+use tw_memory::ffi::RawPtrTrait;
+use tw_misc::try_or_else;
+
+/// Prepares the call to verify a smart contract account signature (ERC-1271).
+/// The signature can be wrapped according to ERC-6492.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input The serialized data of `TW.Ethereum.Proto.ContractSignatureVerifyingInput`.
+/// \return serialized `Ethereum::Proto::ContractSignatureVerifyingOutput`.
+#[no_mangle]
+pub unsafe extern "C" fn tw_ethereum_message_signer_prepare_contract_signature_verification(
+    coin: u32,
+    input: *const TWData,
+) -> *mut TWData {
+    let coin = try_or_else!(CoinType::try_from(coin), std::ptr::null_mut);
+    let input_data = try_or_else!(TWData::from_ptr_as_ref(input), std::ptr::null_mut);
+    let evm_dispatcher = try_or_else!(evm_dispatcher(coin), std::ptr::null_mut);
+
+    evm_dispatcher
+        .prepare_contract_signature_verification(input_data.as_slice())
+        .map(|data| TWData::from(data).into_ptr())
+        .unwrap_or_else(|_| std::ptr::null_mut())
+}
+
+/// Decodes the result of the smart contract account signature verification call.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input The serialized data of `TW.Ethereum.Proto.ContractSignatureResultInput`.
+/// \return serialized `Ethereum::Proto::ContractSignatureResultOutput`.
+#[no_mangle]
+pub unsafe extern "C" fn tw_ethereum_message_signer_decode_contract_signature_result(
+    coin: u32,
+    input: *const TWData,
+) -> *mut TWData {
+    let coin = try_or_else!(CoinType::try_from(coin), std::ptr::null_mut);
+    let input_data = try_or_else!(TWData::from_ptr_as_ref(input), std::ptr::null_mut);
+    let evm_dispatcher = try_or_else!(evm_dispatcher(coin), std::ptr::null_mut);
+
+    evm_dispatcher
+        .decode_contract_signature_result(input_data.as_slice())
+        .map(|data| TWData::from(data).into_ptr())
+        .unwrap_or_else(|_| std::ptr::null_mut())
+}
@@ -69,6 +69,8 @@
 #include "BitcoinCash/Entry.h"
 #include "Pactus/Entry.h"
 #include "Komodo/Entry.h"
+#include "Dogecoin/Entry.h"
+#include "Litecoin/Entry.h"
 // end_of_coin_includes_marker_do_not_modify
 
 using namespace TW;
@@ -131,6 +133,8 @@ NativeInjective::Entry NativeInjectiveDP;
 BitcoinCash::Entry BitcoinCashDP;
 Pactus::Entry PactusDP;
 Komodo::Entry KomodoDP;
+Dogecoin::Entry DogecoinDP;
+Litecoin::Entry LitecoinDP;
 // end_of_coin_dipatcher_declarations_marker_do_not_modify
 
 CoinEntry* coinDispatcher(TWCoinType coinType) {
@@ -195,6 +199,8 @@ CoinEntry* coinDispatcher(TWCoinType coinType) {
         case TWBlockchainBitcoinCash: entry = &BitcoinCashDP; break;
         case TWBlockchainPactus: entry = &PactusDP; break;
         case TWBlockchainKomodo: entry = &KomodoDP; break;
+        case TWBlockchainDogecoin: entry = &DogecoinDP; break;
+        case TWBlockchainLitecoin: entry = &LitecoinDP; break;
         // end_of_coin_dipatcher_switch_marker_do_not_modify
 
         default: entry = nullptr; break;
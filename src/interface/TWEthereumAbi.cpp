@@ -44,6 +44,10 @@ TWData* _Nonnull TWEthereumAbiEncodeFunction(enum TWCoinType coin, TWData* _Nonn
     return ethereumAbiForwardToRust(Rust::tw_ethereum_abi_encode_function, coin, input);
 }
 
+TWData* _Nonnull TWEthereumAbiDecodeEventLog(enum TWCoinType coin, TWData* _Nonnull input) {
+    return ethereumAbiForwardToRust(Rust::tw_ethereum_abi_decode_event_log, coin, input);
+}
+
 TWData* _Nonnull TWEthereumAbiEncode(struct TWEthereumAbiFunction* _Nonnull func_in) {
     assert(func_in != nullptr);
     Data encodedData;
@@ -5,6 +5,7 @@
 #include <TrustWalletCore/TWEthereumMessageSigner.h>
 
 #include "Ethereum/MessageSigner.h"
+#include "rust/Wrapper.h"
 
 namespace TW::internal {
 
@@ -51,3 +52,23 @@ TWString* _Nonnull TWEthereumMessageSignerSignMessageEip155(const struct TWPriva
 bool TWEthereumMessageSignerVerifyMessage(const struct TWPublicKey* _Nonnull publicKey, TWString* _Nonnull message, TWString* _Nonnull signature) {
     return TW::Ethereum::MessageSigner::verifyMessage(publicKey->impl, TWStringUTF8Bytes(message), TWStringUTF8Bytes(signature));
 }
+
+TWData* _Nonnull TWEthereumMessageSignerPrepareContractSignatureVerification(enum TWCoinType coin, TWData* _Nonnull input) {
+    const TW::Data& inputData = *(reinterpret_cast<const TW::Data*>(input));
+
+    const TW::Rust::TWDataWrapper dataInPtr(inputData);
+    TW::Rust::TWDataWrapper dataOutPtr = TW::Rust::tw_ethereum_message_signer_prepare_contract_signature_verification(static_cast<uint32_t>(coin), dataInPtr.get());
+
+    auto dataOut = dataOutPtr.toDataOrDefault();
+    return TWDataCreateWithBytes(dataOut.data(), dataOut.size());
+}
+
+TWData* _Nonnull TWEthereumMessageSignerDecodeContractSignatureResult(enum TWCoinType coin, TWData* _Nonnull input) {
+    const TW::Data& inputData = *(reinterpret_cast<const TW::Data*>(input));
+
+    const TW::Rust::TWDataWrapper dataInPtr(inputData);
+    TW::Rust::TWDataWrapper dataOutPtr = TW::Rust::tw_ethereum_message_signer_decode_contract_signature_result(static_cast<uint32_t>(coin), dataInPtr.get());
+
+    auto dataOut = dataOutPtr.toDataOrDefault();
+    return TWDataCreateWithBytes(dataOut.data(), dataOut.size());
+}
@@ -21,7 +21,7 @@ TEST(TWDogecoinCoinType, TWCoinType) {
     auto name = WRAPS(TWCoinTypeConfigurationGetName(TWCoinTypeDogecoin));
 
     ASSERT_EQ(TWCoinTypeConfigurationGetDecimals(TWCoinTypeDogecoin), 8);
-    ASSERT_EQ(TWBlockchainBitcoin, TWCoinTypeBlockchain(TWCoinTypeDogecoin));
+    ASSERT_EQ(TWBlockchainDogecoin, TWCoinTypeBlockchain(TWCoinTypeDogecoin));
     ASSERT_EQ(0x16, TWCoinTypeP2shPrefix(TWCoinTypeDogecoin));
     ASSERT_EQ(0x0, TWCoinTypeStaticPrefix(TWCoinTypeDogecoin));
     assertStringsEqual(symbol, "DOGE");
@@ -8,6 +8,7 @@
 #include <Ethereum/MessageSigner.h>
 #include <HexCoding.h>
 #include <PrivateKey.h>
+#include "proto/Ethereum.pb.h"
 
 #include <fstream>
 #include <gtest/gtest.h>
@@ -359,4 +360,31 @@ namespace TW::Ethereum {
         auto signature = Ethereum::MessageSigner::signTypedData(ethKey, msg, MessageType::Eip155, chainId);
         ASSERT_EQ(signature, "248b45acf2920a9cef00d3b469a875482b5f0e8ce16f6290212d395aaec7f3be0645d6a5cb6fcdfdca9ecefbadd4e77dae656124094ecc984c5fcb9cb4384b05e3");
     }
+
+    TEST(EthereumMessageSigner, DecodeContractSignatureResult) {
+        auto decode = [](Proto::ContractSignatureCallType callType, const std::string& callResult) {
+            Proto::ContractSignatureResultInput input;
+            input.set_call_type(callType);
+            const auto callResultData = parse_hex(callResult);
+            input.set_call_result(callResultData.data(), callResultData.size());
+
+            const auto inputData = input.SerializeAsString();
+            const auto inputTWData = WRAPD(TWDataCreateWithBytes((const uint8_t*)inputData.data(), inputData.size()));
+            const auto outputTWData = WRAPD(TWEthereumMessageSignerDecodeContractSignatureResult(TWCoinTypeEthereum, inputTWData.get()));
+
+            Proto::ContractSignatureResultOutput output;
+            output.ParseFromArray(TWDataBytes(outputTWData.get()), static_cast<int>(TWDataSize(outputTWData.get())));
+            EXPECT_EQ(output.error(), Common::Proto::OK);
+            return output.valid();
+        };
+
+        // `isValidSignature` returns the magic value.
+        EXPECT_TRUE(decode(Proto::ERC1271, "1626ba7e00000000000000000000000000000000000000000000000000000000"));
+        EXPECT_FALSE(decode(Proto::ERC1271, "ffffffff00000000000000000000000000000000000000000000000000000000"));
+        // `isValidSig` returns a boolean.
+        EXPECT_TRUE(decode(Proto::ERC6492, "0000000000000000000000000000000000000000000000000000000000000001"));
+        EXPECT_FALSE(decode(Proto::ERC6492, "0000000000000000000000000000000000000000000000000000000000000000"));
+        // No data is returned if there is no contract at the called address.
+        EXPECT_FALSE(decode(Proto::ERC6492, ""));
+    }
 }